@@ -0,0 +1,53 @@
+//! Building the dashboard tree when every verse of the Bible has been read, the most labels
+//! and colors the tree ever has to work out
+
+use std::collections::{BTreeSet, HashSet};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use bible_reading_progress::widgets::gradient::ColorGradient;
+use bible_reading_progress::widgets::tree_builder::DashboardTree;
+use bible_reading_progress::{get_bible_structure, BookFilter, ReadingProgress};
+
+fn build_tree_items(c: &mut Criterion) {
+    let bible = get_bible_structure();
+    let mut progress = ReadingProgress::new();
+    for (book, chapters) in bible.ot.iter().chain(&bible.nt) {
+        for (idx, &last) in chapters.iter().enumerate() {
+            progress.record_reading(book, idx as u32 + 1, 1, last);
+        }
+    }
+    let gradient = ColorGradient::default();
+    let all_books: HashSet<String> = bible.ot.keys().chain(bible.nt.keys()).cloned().collect();
+
+    c.bench_function("summarize a fully read Bible", |b| {
+        b.iter(|| DashboardTree::new(bible, &progress, &gradient))
+    });
+
+    let tree = DashboardTree::new(bible, &progress, &gradient);
+    c.bench_function("tree items, collapsed", |b| {
+        b.iter(|| {
+            black_box(tree.items(
+                bible,
+                BookFilter::All,
+                &HashSet::new(),
+                &BTreeSet::new(),
+                None,
+            ))
+        })
+    });
+    c.bench_function("tree items, every book expanded", |b| {
+        b.iter(|| {
+            black_box(tree.items(
+                bible,
+                BookFilter::All,
+                &all_books,
+                &BTreeSet::new(),
+                Some(60),
+            ))
+        })
+    });
+}
+
+criterion_group!(benches, build_tree_items);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+//! Loading and saving a progress file with a long reading history
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use bible_reading_progress::storage::{load_progress_from, save_progress_with, FileStorage};
+use bible_reading_progress::{get_bible_structure, ReadingProgress};
+use chrono::{Duration, NaiveDate};
+
+const EVENTS: usize = 10_000;
+
+/// Every chapter of the Bible in order, over and over, a few chapters a day
+fn long_history() -> ReadingProgress {
+    let bible = get_bible_structure();
+    let chapters: Vec<(&String, u32, u32)> = bible
+        .ot
+        .iter()
+        .chain(&bible.nt)
+        .flat_map(|(book, verses)| {
+            verses
+                .iter()
+                .enumerate()
+                .map(move |(idx, &last)| (book, idx as u32 + 1, last))
+        })
+        .collect();
+    let first_day = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
+    let mut progress = ReadingProgress::new();
+    for (i, (book, chapter, last)) in chapters.iter().cycle().take(EVENTS).enumerate() {
+        let day = first_day + Duration::days(i as i64 / 3);
+        progress.record_reading_on(book, *chapter, 1, *last, 1, day);
+    }
+    progress
+}
+
+fn load_and_save(c: &mut Criterion) {
+    let progress = long_history();
+    let path = std::env::temp_dir().join(format!("brp-bench-{}.yaml", std::process::id()));
+
+    c.bench_function("save 10k events", |b| {
+        b.iter(|| save_progress_with(&FileStorage, &progress, &path).unwrap())
+    });
+    save_progress_with(&FileStorage, &progress, &path).unwrap();
+    c.bench_function("load 10k events", |b| {
+        b.iter(|| load_progress_from(&FileStorage, &path).unwrap())
+    });
+    std::fs::remove_file(&path).unwrap();
+}
+
+criterion_group!(benches, load_and_save);
+criterion_main!(benches);
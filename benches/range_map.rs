@@ -0,0 +1,47 @@
+//! `RangeMap::insert_with`, which every recorded reading goes through
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use bible_reading_progress::RangeMap;
+
+/// Overlapping ranges spread over a 2,000-verse book, like re-reading passages of varying
+/// length
+fn ranges(count: u32) -> Vec<std::ops::Range<u32>> {
+    (0..count)
+        .map(|i| {
+            let start = (i * 37) % 2000;
+            start..start + 1 + (i * 13) % 60
+        })
+        .collect()
+}
+
+fn insert_with(c: &mut Criterion) {
+    let ranges = ranges(1000);
+    c.bench_function("insert_with 1000 overlapping ranges", |b| {
+        b.iter(|| {
+            let mut map = RangeMap::new();
+            for range in &ranges {
+                map.insert_with(range.clone(), 1u32, |old, new| old + new);
+            }
+            black_box(map)
+        })
+    });
+
+    let mut filled = RangeMap::new();
+    for range in &ranges {
+        filled.insert_with(range.clone(), 1u32, |old, new| old + new);
+    }
+    c.bench_function("insert_with across a fragmented map", |b| {
+        b.iter_batched(
+            || filled.clone(),
+            |mut map| {
+                map.insert_with(0..2000, 1, |old, new| old + new);
+                map
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, insert_with);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+//! Any input either fails to parse or gives a passage that formats back to itself:
+//! `cargo +nightly fuzz run parse_reference`
+
+#![no_main]
+
+use bible_reading_progress::{get_bible_structure, parse_passage, parse_reference};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let bible = get_bible_structure();
+    let _ = parse_reference(bible, input);
+    if let Ok(passage) = parse_passage(bible, input) {
+        let formatted = passage.to_string();
+        assert_eq!(
+            parse_passage(bible, &formatted),
+            Ok(passage),
+            "{}",
+            formatted
+        );
+    }
+});
@@ -0,0 +1,171 @@
+use chrono::NaiveDate;
+
+use crate::bible_structure::{BibleStructure, BookFilter, Genre};
+use crate::coverage::{book_min_read_count, book_percent_read, percent_read};
+use crate::progress::ReadingProgress;
+use crate::stats::longest_streak;
+
+/// A badge earned by reaching a reading milestone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    FirstReading,
+    FirstBook,
+    Gospels,
+    NewTestament,
+    OldTestament,
+    WholeBible,
+    PsalmsTwice,
+    Streak7,
+    Streak30,
+    Streak100,
+}
+
+impl Achievement {
+    /// Every achievement, in the order they are listed
+    pub const ALL: [Achievement; 10] = [
+        Achievement::FirstReading,
+        Achievement::FirstBook,
+        Achievement::Gospels,
+        Achievement::NewTestament,
+        Achievement::OldTestament,
+        Achievement::WholeBible,
+        Achievement::PsalmsTwice,
+        Achievement::Streak7,
+        Achievement::Streak30,
+        Achievement::Streak100,
+    ];
+
+    /// Key the achievement is stored under in the progress file
+    pub fn id(self) -> &'static str {
+        match self {
+            Achievement::FirstReading => "first_reading",
+            Achievement::FirstBook => "first_book",
+            Achievement::Gospels => "gospels",
+            Achievement::NewTestament => "new_testament",
+            Achievement::OldTestament => "old_testament",
+            Achievement::WholeBible => "whole_bible",
+            Achievement::PsalmsTwice => "psalms_twice",
+            Achievement::Streak7 => "streak_7",
+            Achievement::Streak30 => "streak_30",
+            Achievement::Streak100 => "streak_100",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::FirstReading => "First Steps",
+            Achievement::FirstBook => "Cover to Cover",
+            Achievement::Gospels => "Good News",
+            Achievement::NewTestament => "New Covenant",
+            Achievement::OldTestament => "Law and Prophets",
+            Achievement::WholeBible => "Genesis to Revelation",
+            Achievement::PsalmsTwice => "Songs Twice Sung",
+            Achievement::Streak7 => "One Week",
+            Achievement::Streak30 => "One Month",
+            Achievement::Streak100 => "Hundred Days",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::FirstReading => "Record your first reading",
+            Achievement::FirstBook => "Read every verse of a book",
+            Achievement::Gospels => "Read all four Gospels",
+            Achievement::NewTestament => "Read the whole New Testament",
+            Achievement::OldTestament => "Read the whole Old Testament",
+            Achievement::WholeBible => "Read the whole Bible",
+            Achievement::PsalmsTwice => "Read every psalm twice",
+            Achievement::Streak7 => "Read on 7 days in a row",
+            Achievement::Streak30 => "Read on 30 days in a row",
+            Achievement::Streak100 => "Read on 100 days in a row",
+        }
+    }
+
+    /// True if `progress` meets this achievement's milestone
+    fn is_earned(self, bible: &BibleStructure, progress: &ReadingProgress) -> bool {
+        let complete = |filter| percent_read(bible, progress, filter) >= 100.0;
+        match self {
+            Achievement::FirstReading => !progress.history.is_empty(),
+            Achievement::FirstBook => progress
+                .books
+                .keys()
+                .any(|book| book_percent_read(bible, progress, book) >= 100.0),
+            Achievement::Gospels => complete(BookFilter::Genre(Genre::Gospels)),
+            Achievement::NewTestament => complete(BookFilter::NewTestament),
+            Achievement::OldTestament => complete(BookFilter::OldTestament),
+            Achievement::WholeBible => complete(BookFilter::All),
+            Achievement::PsalmsTwice => book_min_read_count(bible, progress, "Psalms") >= 2,
+            Achievement::Streak7 => longest_streak(&progress.history) >= 7,
+            Achievement::Streak30 => longest_streak(&progress.history) >= 30,
+            Achievement::Streak100 => longest_streak(&progress.history) >= 100,
+        }
+    }
+}
+
+/// Date `achievement` was unlocked, if it has been
+pub fn unlocked_on(progress: &ReadingProgress, achievement: Achievement) -> Option<NaiveDate> {
+    progress.achievements.get(achievement.id()).copied()
+}
+
+/// Unlock every achievement `progress` has newly earned, dated `today`, and return them.
+/// Achievements stay unlocked even if the progress that earned them is later overwritten.
+pub fn unlock_new(
+    bible: &BibleStructure,
+    progress: &mut ReadingProgress,
+    today: NaiveDate,
+) -> Vec<Achievement> {
+    let newly_earned: Vec<Achievement> = Achievement::ALL
+        .into_iter()
+        .filter(|achievement| !progress.achievements.contains_key(achievement.id()))
+        .filter(|achievement| achievement.is_earned(bible, progress))
+        .collect();
+    for achievement in &newly_earned {
+        progress
+            .achievements
+            .insert(achievement.id().to_string(), today);
+    }
+    newly_earned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+    }
+
+    #[test]
+    fn unlocks_each_achievement_once() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        assert!(unlock_new(bible, &mut progress, today()).is_empty());
+
+        progress.record_reading("Jude", 1, 1, 25);
+        assert_eq!(
+            unlock_new(bible, &mut progress, today()),
+            vec![Achievement::FirstReading, Achievement::FirstBook]
+        );
+        assert_eq!(
+            unlocked_on(&progress, Achievement::FirstBook),
+            Some(today())
+        );
+        assert!(unlock_new(bible, &mut progress, today()).is_empty());
+    }
+
+    #[test]
+    fn psalms_twice_needs_every_verse_read_twice() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        for (chapter_idx, &verses) in bible.ot["Psalms"].iter().enumerate() {
+            progress.record_reading("Psalms", chapter_idx as u32 + 1, 1, verses);
+        }
+        assert!(!Achievement::PsalmsTwice.is_earned(bible, &progress));
+
+        for (chapter_idx, &verses) in bible.ot["Psalms"].iter().enumerate() {
+            progress.record_reading("Psalms", chapter_idx as u32 + 1, 1, verses);
+        }
+        assert!(Achievement::PsalmsTwice.is_earned(bible, &progress));
+    }
+}
@@ -0,0 +1,388 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+
+use crate::achievements::unlock_new;
+use crate::bible_structure::BibleStructure;
+use crate::book_name::BookName;
+use crate::config::Config;
+use crate::hook::HookPayload;
+use crate::memorize::MemoryCard;
+use crate::merge::{merge_progress, Resolution};
+use crate::milestones::{Milestone, MilestoneLevels};
+use crate::plans::{CatchUp, PlanState};
+use crate::plugins::Plugins;
+use crate::progress::{ReadingEvent, ReadingProgress};
+use crate::stats::{current_streak, session_summary};
+
+/// Something the user did, for [`AppCore::dispatch`]
+#[derive(Debug, Clone)]
+pub enum Action {
+    /// The progress after a form recorded readings into a copy of it, e.g. the entry form or
+    /// the import form
+    Record(Box<ReadingProgress>),
+    /// Read each of the chapters in full today
+    ReadChapters(Vec<(String, u32)>),
+    /// Set every verse of each chapter to `count` reads, dated today
+    SetReadCount {
+        chapters: Vec<(String, u32)>,
+        count: u32,
+    },
+    /// Start memorizing a passage
+    Memorize(MemoryCard),
+    /// Schedule the card at this index of the memorization list
+    GradeCard {
+        card: usize,
+        grade: u8,
+    },
+    /// Record for the next campaign, or for none after the last one
+    SwitchCampaign,
+    SaveJournal {
+        date: NaiveDate,
+        text: String,
+    },
+    StartPlan(PlanState),
+    /// Reschedule the active plan's missed readings
+    CatchUp(CatchUp),
+    /// Merge another device's progress, with the side chosen for each conflicting book
+    Merge {
+        theirs: Box<ReadingProgress>,
+        resolutions: HashMap<String, Resolution>,
+    },
+    /// The date changed, e.g. at midnight
+    NewDay(NaiveDate),
+    Quit,
+}
+
+/// Something the interface has to do after an action, in order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    /// Write the progress to the progress file
+    Save,
+    /// Tell the plugins, including the post-save hook, about these readings
+    RunPlugins(HookPayload),
+    /// Tell the user something, e.g. in a toast
+    Notify { title: String, lines: Vec<String> },
+    /// Milestones the readings reached
+    Celebrate(Vec<Milestone>),
+    /// Books whose records changed, so views showing them can update
+    BooksChanged(HashSet<BookName>),
+    /// The progress changed too much to update views book by book
+    ProgressReplaced,
+    /// Ask before quitting, showing what was recorded this session
+    ConfirmQuit(String),
+    /// Save the progress, then stop
+    SaveAndExit,
+}
+
+fn notify(title: &str, line: String) -> Effect {
+    Effect::Notify {
+        title: title.to_string(),
+        lines: vec![line],
+    }
+}
+
+/// The app's state and what each action does to it, without a terminal: actions go in and the
+/// effects the interface should carry out come out, so flows can be tested deterministically
+pub struct AppCore<'a> {
+    bible: &'a BibleStructure,
+    progress: ReadingProgress,
+    plans_dir: PathBuf,
+    has_plugins: bool,
+    today: NaiveDate,
+    /// How far the reading had come at the last save, to tell which milestones a save reaches
+    milestone_levels: MilestoneLevels,
+    /// Length of the reading history when the app started; later events were recorded this
+    /// session
+    session_start: usize,
+    /// Length of the reading history when the plugins were last told about readings; later
+    /// events haven't been sent to them yet
+    hooked_events: usize,
+}
+
+impl<'a> AppCore<'a> {
+    pub fn new(
+        bible: &'a BibleStructure,
+        mut progress: ReadingProgress,
+        config: &Config,
+        today: NaiveDate,
+    ) -> Self {
+        progress.same_day_readings = config.same_day_readings;
+        Self {
+            bible,
+            plans_dir: config.plans_dir.clone(),
+            has_plugins: !Plugins::from_config(config).is_empty(),
+            today,
+            milestone_levels: MilestoneLevels::new(bible, &progress, today),
+            session_start: progress.history.len(),
+            hooked_events: progress.history.len(),
+            progress,
+        }
+    }
+
+    pub fn progress(&self) -> &ReadingProgress {
+        &self.progress
+    }
+
+    /// Take up the plans directory, plugins and same-day policy of a reloaded config
+    pub fn reconfigure(&mut self, config: &Config) {
+        self.plans_dir = config.plans_dir.clone();
+        self.has_plugins = !Plugins::from_config(config).is_empty();
+        self.progress.same_day_readings = config.same_day_readings;
+    }
+
+    /// Fill in the reading history of progress that was loaded without it. Call it before
+    /// dispatching any action, as the history is taken to be all from earlier sessions.
+    pub fn load_history(&mut self, history: Vec<ReadingEvent>) {
+        self.progress.history = history;
+        self.session_start = self.progress.history.len();
+        self.hooked_events = self.progress.history.len();
+        self.milestone_levels = MilestoneLevels::new(self.bible, &self.progress, self.today);
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.today
+    }
+
+    /// What was recorded since the app started, if anything
+    pub fn session_summary(&self) -> Option<String> {
+        let session = self.progress.history.get(self.session_start..)?;
+        session_summary(session, current_streak(&self.progress.history, self.today))
+    }
+
+    pub fn dispatch(&mut self, action: Action) -> Vec<Effect> {
+        match action {
+            Action::Record(progress) => {
+                self.progress = *progress;
+                self.finish_reading()
+            }
+            Action::ReadChapters(chapters) => {
+                let recorded = self.progress.history.len();
+                for (book, chapter, verses) in self.chapter_verses(&chapters) {
+                    self.progress
+                        .record_new_reading(&book, chapter, 1, verses, 1, self.today);
+                }
+                if self.progress.history.len() == recorded {
+                    return vec![notify(
+                        "Recorded",
+                        "Already read today, so not counted again".to_string(),
+                    )];
+                }
+                self.finish_reading()
+            }
+            Action::SetReadCount { chapters, count } => {
+                for (book, chapter, verses) in self.chapter_verses(&chapters) {
+                    self.progress.record_reading_overwrite(
+                        &book,
+                        chapter,
+                        1,
+                        verses,
+                        count,
+                        Some(self.today),
+                    );
+                }
+                self.finish_reading()
+            }
+            Action::Memorize(card) => {
+                let reference = card.reference();
+                if self
+                    .progress
+                    .memorization
+                    .iter()
+                    .any(|existing| existing.same_passage(&card))
+                {
+                    return vec![notify(
+                        "Memorize",
+                        format!("Already memorizing {}", reference),
+                    )];
+                }
+                self.progress.memorization.push(card);
+                vec![
+                    Effect::Save,
+                    notify(
+                        "Memorize",
+                        format!("{} is due for review today (R)", reference),
+                    ),
+                ]
+            }
+            Action::GradeCard { card, grade } => match self.progress.memorization.get_mut(card) {
+                Some(card) => {
+                    card.grade(grade, self.today);
+                    vec![Effect::Save]
+                }
+                None => Vec::new(),
+            },
+            Action::SwitchCampaign => {
+                if self.progress.campaigns.is_empty() {
+                    return vec![notify(
+                        "Campaigns",
+                        "Start one with `brp campaign start <NAME>`".to_string(),
+                    )];
+                }
+                self.progress.active_campaign = self.progress.next_campaign();
+                let message = match &self.progress.active_campaign {
+                    Some(name) => format!("Recording for {}", name),
+                    None => "Showing lifetime totals".to_string(),
+                };
+                vec![
+                    Effect::Save,
+                    Effect::ProgressReplaced,
+                    notify("Campaigns", message),
+                ]
+            }
+            Action::SaveJournal { date, text } => {
+                self.progress.set_journal_entry(date, &text);
+                vec![Effect::Save]
+            }
+            Action::StartPlan(state) => {
+                self.progress.plan = Some(state);
+                vec![Effect::Save]
+            }
+            Action::CatchUp(strategy) => {
+                let Some(state) = self.progress.plan.as_mut() else {
+                    return Vec::new();
+                };
+                match state.plan.load(self.bible, &self.plans_dir) {
+                    Ok(plan) => {
+                        state.catch_up(&plan, &self.progress.history, self.today, strategy);
+                        vec![Effect::Save, notify("Rescheduled", strategy.description())]
+                    }
+                    Err(e) => vec![notify("Catch up", e)],
+                }
+            }
+            Action::Merge {
+                theirs,
+                resolutions,
+            } => {
+                self.progress = merge_progress(&self.progress, &theirs, &resolutions);
+                // Readings merged in weren't recorded this session, and the other device ran
+                // its own plugins for them
+                self.session_start = self.progress.history.len();
+                self.hooked_events = self.progress.history.len();
+                self.milestone_levels =
+                    MilestoneLevels::new(self.bible, &self.progress, self.today);
+                vec![
+                    Effect::Save,
+                    Effect::ProgressReplaced,
+                    notify("Merged", format!("{} books resolved", resolutions.len())),
+                ]
+            }
+            Action::NewDay(today) => {
+                self.today = today;
+                Vec::new()
+            }
+            Action::Quit => match self.session_summary() {
+                Some(summary) => vec![Effect::ConfirmQuit(summary)],
+                None => vec![Effect::SaveAndExit],
+            },
+        }
+    }
+
+    /// Each chapter that exists with its number of verses
+    fn chapter_verses(&self, chapters: &[(String, u32)]) -> Vec<(String, u32, u32)> {
+        chapters
+            .iter()
+            .filter_map(|(book, chapter)| {
+                let verses = self
+                    .bible
+                    .ot
+                    .get(book)
+                    .or_else(|| self.bible.nt.get(book))?
+                    .get((*chapter as usize).checked_sub(1)?)?;
+                Some((book.clone(), *chapter, *verses))
+            })
+            .collect()
+    }
+
+    /// Unlock any achievements earned by the readings just added, record the milestones they
+    /// reached, save, and send them to the plugins
+    fn finish_reading(&mut self) -> Vec<Effect> {
+        let unlocked = unlock_new(self.bible, &mut self.progress, self.today);
+        let levels = MilestoneLevels::new(self.bible, &self.progress, self.today);
+        let reached = levels.reached_since(self.bible, &self.milestone_levels, self.today);
+        self.milestone_levels = levels;
+        self.progress.milestones.extend(reached.iter().cloned());
+
+        let mut effects = vec![Effect::Save];
+        if self.has_plugins && self.progress.history.len() > self.hooked_events {
+            effects.push(Effect::RunPlugins(HookPayload::new(
+                self.bible,
+                &self.progress,
+                self.hooked_events,
+                self.today,
+            )));
+            self.hooked_events = self.progress.history.len();
+        }
+        if !unlocked.is_empty() {
+            effects.push(Effect::Notify {
+                title: "Achievement unlocked".to_string(),
+                lines: unlocked
+                    .iter()
+                    .map(|achievement| {
+                        format!("★ {} — {}", achievement.name(), achievement.description())
+                    })
+                    .collect(),
+            });
+        }
+        if !reached.is_empty() {
+            effects.push(Effect::Celebrate(reached));
+        }
+        let changed_books = self.progress.take_changed_books();
+        if !changed_books.is_empty() {
+            effects.push(Effect::BooksChanged(changed_books));
+        }
+        effects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn record_john_3_then_quit() {
+        let bible = get_bible_structure();
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let mut config = Config::default();
+        config.post_save_hook.command = Some("true".to_string());
+        let mut core = AppCore::new(bible, ReadingProgress::new(), &config, today);
+        assert_eq!(core.dispatch(Action::Quit), vec![Effect::SaveAndExit]);
+
+        let mut draft = core.progress().clone();
+        draft.record_reading_on("John", 3, 1, 36, 1, today);
+        let effects = core.dispatch(Action::Record(Box::new(draft)));
+        assert_eq!(effects[0], Effect::Save);
+        let Effect::RunPlugins(payload) = &effects[1] else {
+            panic!("expected the plugins to run, got {:?}", effects[1]);
+        };
+        assert_eq!(payload.chapters, 1);
+        assert!(
+            matches!(&effects[2], Effect::Notify { title, .. } if title == "Achievement unlocked")
+        );
+        assert_eq!(
+            effects.last(),
+            Some(&Effect::BooksChanged(HashSet::from([BookName::new(
+                "John"
+            )])))
+        );
+
+        // Jude has one chapter, so reading it reaches a milestone; the plugins only get Jude
+        let effects = core.dispatch(Action::ReadChapters(vec![("Jude".to_string(), 1)]));
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            Effect::RunPlugins(payload) if payload.recorded.len() == 1
+        )));
+        assert!(effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::Celebrate(reached) if reached.len() == 1)));
+
+        assert_eq!(
+            core.dispatch(Action::Quit),
+            vec![Effect::ConfirmQuit(
+                "Recorded: John 3, Jude 1 · streak 1 day".to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use serde::Serialize;
+
+use crate::bible_structure::BibleStructure;
+use crate::coverage::chapter_read_count;
+use crate::progress::ReadingProgress;
+use crate::reference::parse_reference;
+use crate::utils::get_all_books;
+
+/// Average narration time of a verse. Unabridged audio Bibles run about 75 hours for the
+/// 31,102 verses, so a chapter's length is estimated from its number of verses.
+pub const SECONDS_PER_VERSE: f64 = 8.7;
+
+/// Approximate length of a chapter's audio, in seconds
+pub fn chapter_seconds(bible: &BibleStructure, book: &str, chapter: u32) -> Option<u32> {
+    let verses = bible
+        .ot
+        .get(book)
+        .or_else(|| bible.nt.get(book))?
+        .get((chapter as usize).checked_sub(1)?)?;
+    Some((*verses as f64 * SECONDS_PER_VERSE).round() as u32)
+}
+
+/// A listening session described by its length and where it started, e.g. "35 min from Mark 4"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listening {
+    pub minutes: u32,
+    pub book: String,
+    pub chapter: u32,
+    pub verse: u32,
+}
+
+/// Parse "35 min from Mark 4", "35 minutes starting at Mark 4" or "listened 35m from mk 4".
+/// None if the input doesn't describe a listening session, so it can be parsed as a passage
+/// instead; an error if it does but the reference or the length is invalid.
+pub fn parse_listening(bible: &BibleStructure, input: &str) -> Option<Result<Listening, String>> {
+    let lower = input.trim().to_lowercase();
+    let rest = lower
+        .strip_prefix("listened")
+        .unwrap_or(&lower)
+        .trim_start();
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    if digits == 0 {
+        return None;
+    }
+    let (minutes, rest) = rest.split_at(digits);
+    let rest = rest.trim_start();
+    let rest = ["minutes", "minute", "mins", "min", "m"]
+        .iter()
+        .find_map(|unit| rest.strip_prefix(unit))?;
+    let reference = ["starting at", "starting with", "from"]
+        .iter()
+        .find_map(|word| rest.trim_start().strip_prefix(word))?
+        .trim();
+
+    Some((|| {
+        let minutes: u32 = minutes
+            .parse()
+            .map_err(|_| format!("Invalid number of minutes: {}", minutes))?;
+        if minutes == 0 {
+            return Err("Listened for 0 minutes".to_string());
+        }
+        let start = parse_reference(bible, reference)?;
+        Ok(Listening {
+            minutes,
+            book: start.book,
+            chapter: start.chapter.unwrap_or(1),
+            verse: start.verse.unwrap_or(1),
+        })
+    })())
+}
+
+impl Listening {
+    /// The verses covered, in canonical order, as (book, chapter, verse_start, verse_end). The
+    /// rest of the starting chapter and the chapters after it are covered while the time lasts,
+    /// the last one only as far as it fits; listening stops at the end of Revelation.
+    pub fn ranges(&self, bible: &BibleStructure) -> Vec<(String, u32, u32, u32)> {
+        let books = get_all_books(bible);
+        let Some(first_book) = books.iter().position(|book| *book == self.book) else {
+            return Vec::new();
+        };
+        let mut verses_left = (self.minutes as f64 * 60.0 / SECONDS_PER_VERSE).round() as u32;
+        let mut ranges = Vec::new();
+        let (mut chapter, mut verse) = (self.chapter, self.verse);
+        for book in &books[first_book..] {
+            let Some(chapters) = bible.ot.get(book).or_else(|| bible.nt.get(book)) else {
+                continue;
+            };
+            while verses_left > 0 {
+                let Some(&verses) = chapter
+                    .checked_sub(1)
+                    .and_then(|i| chapters.get(i as usize))
+                else {
+                    break;
+                };
+                if verse <= verses {
+                    let end = verses.min(verse + verses_left - 1);
+                    ranges.push((book.clone(), chapter, verse, end));
+                    verses_left -= end - verse + 1;
+                }
+                chapter += 1;
+                verse = 1;
+            }
+            chapter = 1;
+        }
+        ranges
+    }
+}
+
+/// `audio_file_pattern` unless the config sets another
+pub const DEFAULT_AUDIO_FILE_PATTERN: &str = "{book}_{chapter}.mp3";
+
+/// Name of a chapter's audio file: `pattern` with `{book}`, `{book_number}` (1 to 66) and
+/// `{chapter}` filled in. Numbers can be padded with zeros, as in `{chapter:03}`.
+pub fn audio_file_name(
+    pattern: &str,
+    book: &str,
+    book_number: usize,
+    chapter: u32,
+) -> Result<String, String> {
+    let mut name = String::new();
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        name.push_str(&rest[..open]);
+        let close = rest[open..]
+            .find('}')
+            .map(|close| open + close)
+            .ok_or_else(|| format!("Unclosed {{ in the audio file pattern \"{}\"", pattern))?;
+        let placeholder = &rest[open + 1..close];
+        let (key, width) = match placeholder.split_once(":0") {
+            Some((key, width)) => (
+                key,
+                width
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid padding in {{{}}}", placeholder))?,
+            ),
+            None => (placeholder, 0),
+        };
+        match key {
+            "book" => name.push_str(book),
+            "book_number" => name.push_str(&format!("{:0width$}", book_number)),
+            "chapter" => name.push_str(&format!("{:0width$}", chapter)),
+            _ => {
+                return Err(format!(
+                    "Unknown {{{}}} in the audio file pattern; use {{book}}, {{book_number}} or {{chapter}}",
+                    key
+                ))
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+    name.push_str(rest);
+    Ok(name)
+}
+
+/// A chapter of an audio queue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueuedChapter {
+    pub book: String,
+    pub chapter: u32,
+    /// Audio file, named by the pattern
+    pub file: String,
+    /// Approximate length
+    pub seconds: u32,
+}
+
+/// The next `count` chapters with unread verses, in canonical order from the chapter after the
+/// one read last, wrapping around from Revelation to Genesis, with their audio files named by
+/// `pattern`
+pub fn audio_queue(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    pattern: &str,
+    count: usize,
+) -> Result<Vec<QueuedChapter>, String> {
+    let books = get_all_books(bible);
+    let chapters: Vec<(usize, &str, u32)> = books
+        .iter()
+        .enumerate()
+        .flat_map(|(index, book)| {
+            let chapter_count = bible
+                .ot
+                .get(book)
+                .or_else(|| bible.nt.get(book))
+                .map_or(0, Vec::len);
+            (1..=chapter_count as u32).map(move |chapter| (index + 1, book.as_str(), chapter))
+        })
+        .collect();
+    let start = progress
+        .history
+        .last()
+        .and_then(|last| {
+            chapters
+                .iter()
+                .position(|&(_, book, chapter)| last.book == book && chapter == last.chapter)
+        })
+        .map_or(0, |last| last + 1);
+
+    (0..chapters.len())
+        .map(|offset| chapters[(start + offset) % chapters.len()])
+        .filter(|&(_, book, chapter)| chapter_read_count(bible, progress, book, chapter).0 == 0)
+        .take(count)
+        .map(|(book_number, book, chapter)| {
+            Ok(QueuedChapter {
+                book: book.to_string(),
+                chapter,
+                file: audio_file_name(pattern, book, book_number, chapter)?,
+                seconds: chapter_seconds(bible, book, chapter).unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// The queue as an extended M3U playlist, which most audio players open
+pub fn queue_to_m3u(queue: &[QueuedChapter]) -> String {
+    let mut m3u = "#EXTM3U\n".to_string();
+    for chapter in queue {
+        m3u.push_str(&format!(
+            "#EXTINF:{},{} {}\n{}\n",
+            chapter.seconds, chapter.book, chapter.chapter, chapter.file
+        ));
+    }
+    m3u
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn listening_time_becomes_chapters() {
+        let bible = get_bible_structure();
+        assert_eq!(chapter_seconds(bible, "Mark", 4), Some(357));
+        assert_eq!(parse_listening(bible, "Mark 4"), None);
+        assert_eq!(parse_listening(bible, "2 Kings 4"), None);
+
+        let listening = parse_listening(bible, "listened 15 minutes starting at mk 15")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            listening,
+            Listening {
+                minutes: 15,
+                book: "Mark".to_string(),
+                chapter: 15,
+                verse: 1,
+            }
+        );
+        // 103 verses: Mark 15 (47) and 16 (20), then 36 verses of Luke 1
+        assert_eq!(
+            listening.ranges(bible),
+            vec![
+                ("Mark".to_string(), 15, 1, 47),
+                ("Mark".to_string(), 16, 1, 20),
+                ("Luke".to_string(), 1, 1, 36),
+            ]
+        );
+
+        assert!(parse_listening(bible, "35 min from Nowhere 4")
+            .unwrap()
+            .is_err());
+        let end = parse_listening(bible, "90m from rev 21").unwrap().unwrap();
+        assert_eq!(end.ranges(bible).len(), 2);
+        let mid_chapter = parse_listening(bible, "1 min from John 3:16")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            mid_chapter.ranges(bible),
+            vec![("John".to_string(), 3, 16, 22)]
+        );
+    }
+
+    #[test]
+    fn queue_continues_with_the_unread_chapters() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Jude", 1, 1, 25);
+        progress.record_reading("Revelation of John", 2, 1, 29);
+        progress.record_reading("Revelation of John", 21, 1, 27);
+
+        let queue = audio_queue(
+            bible,
+            &progress,
+            "{book_number:02}/{book}_{chapter:03}.mp3",
+            3,
+        )
+        .unwrap();
+        let files: Vec<&str> = queue.iter().map(|chapter| chapter.file.as_str()).collect();
+        assert_eq!(
+            files,
+            [
+                "66/Revelation of John_022.mp3",
+                "01/Genesis_001.mp3",
+                "01/Genesis_002.mp3"
+            ]
+        );
+        assert!(queue_to_m3u(&queue).starts_with("#EXTM3U\n#EXTINF:"));
+        assert_eq!(
+            audio_file_name(DEFAULT_AUDIO_FILE_PATTERN, "I Samuel", 9, 3),
+            Ok("I Samuel_3.mp3".to_string())
+        );
+        assert!(audio_file_name("{verse}.mp3", "Jude", 65, 1).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::NaiveDate;
+
+use crate::bible_structure::{BibleStructure, BookFilter};
+use crate::coverage::{book_min_read_count, percent_read};
+use crate::progress::ReadingProgress;
+use crate::stats::current_streak;
+
+/// Approximate width of a character of the badge's 11px Verdana text
+const CHAR_WIDTH: f64 = 6.5;
+/// Padding on each side of the label and of the value
+const PADDING: f64 = 6.0;
+const HEIGHT: u32 = 20;
+
+/// Value of the progress badge, e.g. "64% · NT ×2 · 21-day streak". Testaments are listed once
+/// every verse in them has been read, with the number of times they have been, and the streak
+/// once there is one.
+pub fn badge_value(bible: &BibleStructure, progress: &ReadingProgress, today: NaiveDate) -> String {
+    let mut parts = vec![format!(
+        "{:.0}%",
+        percent_read(bible, progress, BookFilter::All)
+    )];
+    for (name, books) in [("OT", &bible.ot), ("NT", &bible.nt)] {
+        let times = books
+            .keys()
+            .map(|book| book_min_read_count(bible, progress, book))
+            .min()
+            .unwrap_or(0);
+        if times > 0 {
+            parts.push(format!("{} ×{}", name, times));
+        }
+    }
+    let streak = current_streak(&progress.history, today);
+    if streak > 0 {
+        parts.push(format!("{}-day streak", streak));
+    }
+    parts.join(" · ")
+}
+
+/// A flat badge in the style of those on project READMEs, with `label` on gray and `value` on
+/// `color`. Text widths are estimated, since no font is available to measure them.
+pub fn badge_svg(label: &str, value: &str, color: &str) -> String {
+    let text_width =
+        |text: &str| (text.chars().count() as f64 * CHAR_WIDTH + 2.0 * PADDING).round();
+    let label_width = text_width(label);
+    let value_width = text_width(value);
+    let width = label_width + value_width;
+    let (label, value) = (escape_xml(label), escape_xml(value));
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{HEIGHT}" role="img" aria-label="{label}: {value}">
+  <title>{label}: {value}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r"><rect width="{width}" height="{HEIGHT}" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="{HEIGHT}" fill="#555"/>
+    <rect x="{label_width}" width="{value_width}" height="{HEIGHT}" fill="{color}"/>
+    <rect width="{width}" height="{HEIGHT}" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text>
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{value_x}" y="15" fill="#010101" fill-opacity=".3">{value}</text>
+    <text x="{value_x}" y="14">{value}</text>
+  </g>
+</svg>
+"##,
+        label_x = label_width / 2.0,
+        value_x = label_width + value_width / 2.0,
+    )
+}
+
+/// Badge color for how much of the Bible has been read: orange, then yellow, then green
+pub fn badge_color(percent: f64) -> &'static str {
+    match percent {
+        p if p < 25.0 => "#fe7d37",
+        p if p < 75.0 => "#dfb317",
+        _ => "#4c1",
+    }
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn badge_lists_completed_testaments_and_the_streak() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        let today = chrono::Utc::now().date_naive();
+        assert_eq!(badge_value(bible, &progress, today), "0%");
+
+        for _ in 0..2 {
+            for (book, chapters) in &bible.nt {
+                for (idx, &verses) in chapters.iter().enumerate() {
+                    progress.record_reading(book, idx as u32 + 1, 1, verses);
+                }
+            }
+        }
+        // Recorded today, so the streak is one day
+        assert_eq!(
+            badge_value(bible, &progress, today),
+            "26% · NT ×2 · 1-day streak"
+        );
+
+        let svg = badge_svg("Bible", "22% · NT ×2", badge_color(22.0));
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r##"fill="#fe7d37""##));
+        assert!(svg.contains(">22% · NT ×2</text>"));
+        assert_eq!(escape_xml("A & <B>"), "A &amp; &lt;B&gt;");
+    }
+}
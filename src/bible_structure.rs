@@ -9,7 +9,7 @@ pub struct BibleStructure {
     pub nt: IndexMap<String, Vec<u32>>,
 }
 
-const BIBLE_STRUCTURE_STR: &str = include_str!("../bible_structure.json");
+pub(crate) const BIBLE_STRUCTURE_STR: &str = include_str!("../bible_structure.json");
 static BIBLE_STRUCTURE: OnceLock<BibleStructure> = OnceLock::new();
 
 pub fn get_bible_structure() -> &'static BibleStructure {
@@ -19,3 +19,142 @@ pub fn get_bible_structure() -> &'static BibleStructure {
         structure
     })
 }
+
+/// Literary genre of a book, used to filter the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Genre {
+    Law,
+    History,
+    Wisdom,
+    MajorProphets,
+    MinorProphets,
+    Gospels,
+    PaulineEpistles,
+    GeneralEpistles,
+    Apocalyptic,
+}
+
+impl Genre {
+    pub const ALL: [Genre; 9] = [
+        Genre::Law,
+        Genre::History,
+        Genre::Wisdom,
+        Genre::MajorProphets,
+        Genre::MinorProphets,
+        Genre::Gospels,
+        Genre::PaulineEpistles,
+        Genre::GeneralEpistles,
+        Genre::Apocalyptic,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Genre::Law => "Law",
+            Genre::History => "History",
+            Genre::Wisdom => "Wisdom & Poetry",
+            Genre::MajorProphets => "Major Prophets",
+            Genre::MinorProphets => "Minor Prophets",
+            Genre::Gospels => "Gospels",
+            Genre::PaulineEpistles => "Pauline Epistles",
+            Genre::GeneralEpistles => "General Epistles",
+            Genre::Apocalyptic => "Apocalyptic",
+        }
+    }
+
+    /// Genre of a book, by its name in the bible structure
+    pub fn of_book(book: &str) -> Option<Genre> {
+        let genre = match book {
+            "Genesis" | "Exodus" | "Leviticus" | "Numbers" | "Deuteronomy" => Genre::Law,
+            "Joshua" | "Judges" | "Ruth" | "I Samuel" | "II Samuel" | "I Kings" | "II Kings"
+            | "I Chronicles" | "II Chronicles" | "Ezra" | "Nehemiah" | "Esther" | "Acts" => {
+                Genre::History
+            }
+            "Job" | "Psalms" | "Proverbs" | "Ecclesiastes" | "Song of Solomon" => Genre::Wisdom,
+            "Isaiah" | "Jeremiah" | "Lamentations" | "Ezekiel" | "Daniel" => Genre::MajorProphets,
+            "Hosea" | "Joel" | "Amos" | "Obadiah" | "Jonah" | "Micah" | "Nahum" | "Habakkuk"
+            | "Zephaniah" | "Haggai" | "Zechariah" | "Malachi" => Genre::MinorProphets,
+            "Matthew" | "Mark" | "Luke" | "John" => Genre::Gospels,
+            "Romans" | "I Corinthians" | "II Corinthians" | "Galatians" | "Ephesians"
+            | "Philippians" | "Colossians" | "I Thessalonians" | "II Thessalonians"
+            | "I Timothy" | "II Timothy" | "Titus" | "Philemon" => Genre::PaulineEpistles,
+            "Hebrews" | "James" | "I Peter" | "II Peter" | "I John" | "II John" | "III John"
+            | "Jude" => Genre::GeneralEpistles,
+            "Revelation of John" => Genre::Apocalyptic,
+            _ => return None,
+        };
+        Some(genre)
+    }
+}
+
+/// Which books the dashboard shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookFilter {
+    #[default]
+    All,
+    OldTestament,
+    NewTestament,
+    Genre(Genre),
+}
+
+impl BookFilter {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BookFilter::All => "All books",
+            BookFilter::OldTestament => "Old Testament",
+            BookFilter::NewTestament => "New Testament",
+            BookFilter::Genre(genre) => genre.name(),
+        }
+    }
+
+    /// Parse a filter as given on the command line: `all`, `ot`, `nt`, or a genre such as
+    /// `gospels` or `minor_prophets`
+    pub fn parse(input: &str) -> Option<BookFilter> {
+        let input = input.trim().to_lowercase().replace(['-', ' '], "_");
+        match input.as_str() {
+            "all" => Some(BookFilter::All),
+            "ot" | "old_testament" => Some(BookFilter::OldTestament),
+            "nt" | "new_testament" => Some(BookFilter::NewTestament),
+            // Genres by their serialized name
+            genre => serde_yaml::from_str(genre).ok().map(BookFilter::Genre),
+        }
+    }
+
+    /// Whether a book (in the Old Testament if `in_old_testament`) passes the filter
+    pub fn includes(&self, in_old_testament: bool, book: &str) -> bool {
+        match self {
+            BookFilter::All => true,
+            BookFilter::OldTestament => in_old_testament,
+            BookFilter::NewTestament => !in_old_testament,
+            BookFilter::Genre(genre) => Genre::of_book(book) == Some(*genre),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_book_has_a_genre() {
+        let bible = get_bible_structure();
+        for book in bible.ot.keys().chain(bible.nt.keys()) {
+            assert!(Genre::of_book(book).is_some(), "{} has no genre", book);
+        }
+    }
+
+    #[test]
+    fn filters_select_expected_books() {
+        assert_eq!(BookFilter::parse("NT"), Some(BookFilter::NewTestament));
+        assert_eq!(
+            BookFilter::parse("minor prophets"),
+            Some(BookFilter::Genre(Genre::MinorProphets))
+        );
+        assert_eq!(BookFilter::parse("apocrypha"), None);
+        assert!(BookFilter::OldTestament.includes(true, "Genesis"));
+        assert!(!BookFilter::NewTestament.includes(true, "Genesis"));
+        assert!(BookFilter::Genre(Genre::History).includes(false, "Acts"));
+        assert!(!BookFilter::Genre(Genre::Gospels).includes(false, "Acts"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The name of a book, interned: every `BookName` for "Genesis" shares one allocation, so the
+/// thousands of history events, records and tree ids naming a book cost a pointer each, and
+/// cloning one never allocates. Derefs to `str`, and maps keyed by it can be queried with a
+/// `&str`.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BookName(Arc<str>);
+
+/// Every name interned so far: the books of the Bible, plus any unknown names read from files
+static NAMES: OnceLock<Mutex<HashSet<Arc<str>>>> = OnceLock::new();
+
+impl BookName {
+    pub fn new(name: &str) -> Self {
+        let mut names = NAMES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(interned) = names.get(name) {
+            return Self(interned.clone());
+        }
+        let interned: Arc<str> = Arc::from(name);
+        names.insert(interned.clone());
+        Self(interned)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for BookName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for BookName {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for BookName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for BookName {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&String> for BookName {
+    fn from(name: &String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for BookName {
+    fn from(name: String) -> Self {
+        Self::new(&name)
+    }
+}
+
+impl PartialEq<str> for BookName {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for BookName {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for BookName {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl fmt::Display for BookName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Debug for BookName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl Serialize for BookName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for BookName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Self::new(&name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn names_are_shared_and_work_as_str_keys() {
+        let a = BookName::new("Habakkuk");
+        let b: BookName = serde_yaml::from_str("Habakkuk").unwrap();
+        assert!(Arc::ptr_eq(&a.0, &b.0));
+        assert_eq!(a, "Habakkuk");
+        assert_eq!(serde_yaml::to_string(&b).unwrap(), "Habakkuk\n");
+
+        let counts = HashMap::from([(a, 3)]);
+        assert_eq!(counts.get("Habakkuk"), Some(&3));
+    }
+}
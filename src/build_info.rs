@@ -0,0 +1,96 @@
+//! What a build of brp was made from. When two devices disagree about the same progress, this
+//! tells whether they run the same code, with the same features, on the same canon (the
+//! chapters and verse counts of `bible_structure.json`). Printed by `brp --version --verbose`,
+//! and written into exported documents and charts.
+
+use std::fmt;
+
+use chrono::DateTime;
+use serde::Serialize;
+
+use crate::bible_structure::BIBLE_STRUCTURE_STR;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    /// Short hash of the commit built, or "unknown" outside a git checkout
+    pub git_hash: &'static str,
+    /// UTC, e.g. "2025-03-01 14:05"
+    pub build_date: String,
+    /// Cargo features compiled in
+    pub features: Vec<&'static str>,
+    /// FNV-1a hash of the canon the app counts verses against
+    pub canon_checksum: String,
+}
+
+pub fn build_info() -> BuildInfo {
+    let build_date = env!("BRP_BUILD_TIME")
+        .parse()
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .map_or_else(
+            || "unknown".to_string(),
+            |time| time.format("%Y-%m-%d %H:%M").to_string(),
+        );
+    let features = [
+        ("tui", cfg!(feature = "tui")),
+        ("fs", cfg!(feature = "fs")),
+        ("daemon", cfg!(feature = "daemon")),
+        ("wasm", cfg!(feature = "wasm")),
+    ]
+    .into_iter()
+    .filter_map(|(feature, enabled)| enabled.then_some(feature))
+    .collect();
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        git_hash: env!("BRP_GIT_HASH"),
+        build_date,
+        features,
+        canon_checksum: format!("{:016x}", fnv1a(BIBLE_STRUCTURE_STR.as_bytes())),
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl BuildInfo {
+    /// One line, for embedding in exported files, e.g.
+    /// "brp 0.1.0 (3f2a9c1e0b7d, built 2025-03-01 14:05, canon 9b0c…)"
+    pub fn summary(&self) -> String {
+        format!(
+            "brp {} ({}, built {}, canon {})",
+            self.version, self.git_hash, self.build_date, self.canon_checksum
+        )
+    }
+}
+
+/// A line per field, for `brp --version --verbose`
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "brp {}", self.version)?;
+        writeln!(f, "git hash: {}", self.git_hash)?;
+        writeln!(f, "build date: {}", self.build_date)?;
+        writeln!(f, "features: {}", self.features.join(", "))?;
+        write!(f, "canon checksum: {}", self.canon_checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canon_checksum_is_stable() {
+        // Changes only when bible_structure.json does
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        let info = build_info();
+        assert_eq!(info.canon_checksum.len(), 16);
+        assert!(info
+            .summary()
+            .starts_with(&format!("brp {} (", info.version)));
+        assert!(info.to_string().contains("canon checksum: "));
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+
+use crate::badge::escape_xml;
+use crate::bible_structure::{BibleStructure, BookFilter};
+use crate::build_info::build_info;
+use crate::coverage::{chapter_read_count, percent_read};
+use crate::progress::ReadingProgress;
+
+/// Page width, in SVG user units (points when printed)
+const WIDTH: u32 = 612;
+const MARGIN: u32 = 36;
+/// Width of the column of book names
+const LABEL_WIDTH: u32 = 108;
+const BOX: u32 = 16;
+const GAP: u32 = 2;
+/// Vertical space taken by a testament heading
+const HEADING_HEIGHT: u32 = 24;
+/// Title, subtitle and legend above the grid
+const HEADER_HEIGHT: u32 = 70;
+
+/// Fill and text color of a chapter box by how many times it has been read: white when
+/// unread, then darker grays, which print well in black and white
+fn box_colors(times: u32) -> (&'static str, &'static str) {
+    match times {
+        0 => ("#ffffff", "#777777"),
+        1 => ("#c8c8c8", "#000000"),
+        2 => ("#888888", "#ffffff"),
+        _ => ("#333333", "#ffffff"),
+    }
+}
+
+/// Chapters per row of the grid
+fn chapters_per_row() -> u32 {
+    (WIDTH - 2 * MARGIN - LABEL_WIDTH + GAP) / (BOX + GAP)
+}
+
+/// The classic Bible reading chart as an SVG page: every chapter of the books shown by `filter`
+/// as a numbered box, shaded by how many times it has been read, with partly read chapters
+/// marked by a dot
+pub fn chart_svg(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    filter: BookFilter,
+    today: NaiveDate,
+) -> String {
+    let per_row = chapters_per_row();
+    let mut body = String::new();
+    let mut y = MARGIN + HEADER_HEIGHT;
+    for (name, books, in_old_testament) in [
+        ("Old Testament", &bible.ot, true),
+        ("New Testament", &bible.nt, false),
+    ] {
+        let books: Vec<_> = books
+            .iter()
+            .filter(|(book, _)| filter.includes(in_old_testament, book))
+            .collect();
+        if books.is_empty() {
+            continue;
+        }
+        let _ = writeln!(
+            body,
+            r#"  <text x="{MARGIN}" y="{}" font-size="13" font-weight="bold">{name}</text>"#,
+            y + 14
+        );
+        y += HEADING_HEIGHT;
+        for (book, chapters) in books {
+            let _ = writeln!(
+                body,
+                r#"  <text x="{MARGIN}" y="{}" font-size="9">{}</text>"#,
+                y + BOX - 4,
+                escape_xml(book)
+            );
+            for chapter in 1..=chapters.len() as u32 {
+                let column = (chapter - 1) % per_row;
+                let row = (chapter - 1) / per_row;
+                let x = MARGIN + LABEL_WIDTH + column * (BOX + GAP);
+                let box_y = y + row * (BOX + GAP);
+                let (times, started) = chapter_read_count(bible, progress, book, chapter);
+                let (fill, text) = box_colors(times);
+                let _ = writeln!(
+                    body,
+                    r##"  <rect x="{x}" y="{box_y}" width="{BOX}" height="{BOX}" fill="{fill}" stroke="#000" stroke-width="0.5"/>"##
+                );
+                let _ = writeln!(
+                    body,
+                    r#"  <text x="{}" y="{}" font-size="7" text-anchor="middle" fill="{text}">{chapter}</text>"#,
+                    x + BOX / 2,
+                    box_y + BOX / 2 + 3
+                );
+                if times == 0 && started {
+                    let _ = writeln!(
+                        body,
+                        r##"  <circle cx="{}" cy="{}" r="1.5" fill="#000"/>"##,
+                        x + BOX - 3,
+                        box_y + 3
+                    );
+                }
+            }
+            let rows = (chapters.len() as u32).div_ceil(per_row).max(1);
+            y += rows * (BOX + GAP) + GAP;
+        }
+        y += GAP * 4;
+    }
+    let height = y + MARGIN;
+
+    let mut legend = String::new();
+    let legend_y = MARGIN + 44;
+    for (idx, (label, times)) in [("Unread", 0), ("Read once", 1), ("Twice", 2), ("3+", 3)]
+        .into_iter()
+        .enumerate()
+    {
+        let x = MARGIN + idx as u32 * 90;
+        let _ = writeln!(
+            legend,
+            r##"  <rect x="{x}" y="{legend_y}" width="12" height="12" fill="{}" stroke="#000" stroke-width="0.5"/>
+  <text x="{}" y="{}" font-size="9">{label}</text>"##,
+            box_colors(times).0,
+            x + 16,
+            legend_y + 10
+        );
+    }
+    let _ = writeln!(
+        legend,
+        r##"  <circle cx="{}" cy="{}" r="1.5" fill="#000"/>
+  <text x="{}" y="{}" font-size="9">Partly read</text>"##,
+        MARGIN + 4 * 90 + 6,
+        legend_y + 6,
+        MARGIN + 4 * 90 + 16,
+        legend_y + 10
+    );
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="Helvetica,Arial,sans-serif">
+  <!-- {} -->
+  <rect width="{WIDTH}" height="{height}" fill="#fff"/>
+  <text x="{MARGIN}" y="{}" font-size="18" font-weight="bold">Bible Reading Chart</text>
+  <text x="{MARGIN}" y="{}" font-size="10" fill="#444">{} · {:.1}% read · {}</text>
+{legend}{body}</svg>
+"##,
+        escape_xml(&build_info().summary()),
+        MARGIN + 16,
+        MARGIN + 32,
+        escape_xml(filter.name()),
+        percent_read(bible, progress, filter),
+        today.format("%B %-d, %Y"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::{get_bible_structure, Genre};
+
+    #[test]
+    fn chart_shades_chapters_by_read_count() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Mark", 1, 1, 45);
+        progress.record_reading("Mark", 1, 1, 45);
+        progress.record_reading("Mark", 2, 1, 5);
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        let svg = chart_svg(bible, &progress, BookFilter::Genre(Genre::Gospels), today);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Gospels · "));
+        assert!(!svg.contains("Old Testament</text>"));
+        assert!(svg.contains(">John</text>"));
+        // Mark 1 was read twice, Mark 2 only partly
+        assert_eq!(svg.matches(r##"fill="#888888""##).count(), 2);
+        assert_eq!(svg.matches("<circle").count(), 2);
+        // 28 + 16 + 24 + 21 chapters
+        assert_eq!(svg.matches("<rect").count(), 1 + 4 + 89);
+    }
+}
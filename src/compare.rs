@@ -0,0 +1,112 @@
+//! Progress side by side, book by book: yours against a group member's, one campaign against
+//! another, or one year of reading against another.
+
+use std::collections::BTreeSet;
+
+use chrono::Datelike;
+
+use crate::bible_structure::{BibleStructure, BookFilter};
+use crate::coverage::{book_percent_read, percent_read};
+use crate::date_range::DateRange;
+use crate::progress::ReadingProgress;
+use crate::utils::get_all_books;
+
+/// One side of a comparison
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareSide {
+    /// Whose or which readings, e.g. "You", a campaign or a year
+    pub name: String,
+    /// Percentage of the Bible's verses read
+    pub percent_read: f64,
+    /// Percentage of each book's verses read, in canonical order
+    pub percent_by_book: Vec<(String, f64)>,
+}
+
+impl CompareSide {
+    pub fn of(bible: &BibleStructure, name: impl Into<String>, progress: &ReadingProgress) -> Self {
+        Self {
+            name: name.into(),
+            percent_read: percent_read(bible, progress, BookFilter::All),
+            percent_by_book: get_all_books(bible)
+                .into_iter()
+                .map(|book| {
+                    let percent = book_percent_read(bible, progress, &book);
+                    (book, percent)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Everything that can be compared: your lifetime progress, each campaign, each year with a
+/// reading (the latest first), then each of `others`, such as the members of your group
+pub fn compare_sides(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    others: &[(String, ReadingProgress)],
+) -> Vec<CompareSide> {
+    let mut sides = vec![CompareSide::of(bible, "You", progress)];
+    for campaign in &progress.campaigns {
+        let layer = progress.campaign_layer(&campaign.name);
+        sides.push(CompareSide::of(bible, campaign.name.as_str(), &layer));
+    }
+    // Years count every reading, whichever campaign was active
+    let mut lifetime = progress.clone();
+    lifetime.active_campaign = None;
+    let years: BTreeSet<i32> = progress
+        .history
+        .iter()
+        .map(|event| event.date.year())
+        .collect();
+    let ranges = years
+        .iter()
+        .rev()
+        .filter_map(|year| DateRange::parse(&year.to_string()).ok());
+    for range in ranges {
+        sides.push(CompareSide::of(
+            bible,
+            range.to_string(),
+            &lifetime.range_layer(range),
+        ));
+    }
+    for (name, other) in others {
+        sides.push(CompareSide::of(bible, name.as_str(), other));
+    }
+    sides
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn campaigns_years_and_others_are_offered() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        progress.record_reading_overwrite("Jude", 1, 1, 25, 1, Some(date));
+        progress.start_campaign("Gospels", date).unwrap();
+        progress.record_reading("Mark", 1, 1, 45);
+        let mut spouse = ReadingProgress::new();
+        spouse.record_reading("Ruth", 1, 1, 22);
+
+        let sides = compare_sides(bible, &progress, &[("Sam".to_string(), spouse)]);
+        let this_year = chrono::Utc::now().year().to_string();
+        let names: Vec<&str> = sides.iter().map(|side| side.name.as_str()).collect();
+        assert_eq!(names, ["You", "Gospels", this_year.as_str(), "2024", "Sam"]);
+
+        let jude = |side: &CompareSide| {
+            side.percent_by_book
+                .iter()
+                .find(|(book, _)| book == "Jude")
+                .unwrap()
+                .1
+        };
+        assert_eq!(jude(&sides[0]), 100.0);
+        assert_eq!(jude(&sides[1]), 0.0);
+        assert_eq!(jude(&sides[3]), 100.0);
+        assert_eq!(sides[4].percent_by_book.len(), 66);
+    }
+}
@@ -1,17 +1,166 @@
+use chrono::NaiveTime;
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::audio::DEFAULT_AUDIO_FILE_PATTERN;
+use crate::hook::PostSaveHook;
+use crate::i18n::Language;
+use crate::plugins::CommandPlugin;
+use crate::progress::{ReadingSource, SameDayReadings};
+use crate::stats::{Goal, DEFAULT_NEGLECTED_DAYS};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConfigFile {
     /// Path where the reading progress is stored
     /// Can be absolute or relative to the config directory
     pub progress_path: Option<String>,
+    /// Number of chapters to read each day, shown as goal progress in the status bar
+    pub daily_chapter_goal: Option<u32>,
+    /// Minutes to read each day; the Today panel suggests as many chapters as fit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_minutes: Option<u32>,
+    /// Colors used for dashboard passages
+    #[serde(default)]
+    pub color_gradient: GradientConfig,
+    /// Plain-text Bible (one `Book Chapter:Verse Text` line per verse) shown in the passage
+    /// preview. Can be absolute or relative to the config directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scripture_text_path: Option<String>,
+    /// Replace color-only distinctions with text markers and drop colors and styling
+    #[serde(default)]
+    pub accessibility_mode: bool,
+    /// Books listed first when picking a book to record, before the recently read ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_books: Vec<String>,
+    /// Whether new entries default to read or listened
+    #[serde(default, skip_serializing_if = "ReadingSource::is_read")]
+    pub default_source: ReadingSource,
+    /// Translations offered in the entry form; the first is the default
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub translations: Vec<String>,
+    /// Directory of custom reading plan files (`.yaml`, `.yml` or `.csv`). Can be absolute or
+    /// relative to the config directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plans_dir: Option<String>,
+    /// Weekly, monthly and daily targets, and books to finish by a date
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals: Vec<Goal>,
+    /// Days without a reading after which a book is listed as neglected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neglected_days: Option<u32>,
+    /// Time of day (e.g. "20:00") at which `brp daemon` reminds you of today's reading
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reminder_time: Option<NaiveTime>,
+    /// Shared group file listing the progress files of people reading together. Can be
+    /// absolute or relative to the config directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_path: Option<String>,
+    /// Shell command and/or URL told what was recorded each time readings are saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_save_hook: Option<PostSaveHook>,
+    /// External commands told about readings and asked for extra stats
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<CommandPlugin>,
+    /// Language of the interface, e.g. `es`; English by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    /// Name of a chapter's audio file in queues written by `brp queue`, e.g.
+    /// `{book}_{chapter}.mp3`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_file_pattern: Option<String>,
+    /// `once` to count a verse once a day however many times it's recorded that day
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub same_day_readings: Option<SameDayReadings>,
+}
+
+/// What the dashboard color gradient is indexed by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GradientMode {
+    /// Color by the minimum number of times every verse has been read
+    #[default]
+    ReadCount,
+    /// Color by how recently the passage was last read
+    Recency,
+}
+
+/// Configurable color gradient for dashboard passages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GradientConfig {
+    #[serde(default)]
+    pub mode: GradientMode,
+    /// Colors from "least read" to "most read" (names like `blue` or hex like `#ffd700`)
+    #[serde(default = "default_gradient_colors")]
+    pub colors: Vec<String>,
+    /// Day thresholds for recency mode, from oldest to most recent
+    #[serde(default = "default_recency_days")]
+    pub recency_days: Vec<i64>,
+}
+
+impl Default for GradientConfig {
+    fn default() -> Self {
+        Self {
+            mode: GradientMode::default(),
+            colors: default_gradient_colors(),
+            recency_days: default_recency_days(),
+        }
+    }
+}
+
+fn default_gradient_colors() -> Vec<String> {
+    ["gray", "blue", "green", "#ffd700"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_recency_days() -> Vec<i64> {
+    vec![90, 30, 7]
 }
 
 pub struct Config {
     pub progress_path: PathBuf,
+    /// The progress file named in the config file, if one is. Picking another in the settings
+    /// changes this, and `progress_path` only once the app restarts.
+    pub configured_progress_path: Option<PathBuf>,
+    /// Path where dashboard UI state (expanded nodes, selection, filters) is remembered
+    pub ui_state_path: PathBuf,
+    pub daily_chapter_goal: Option<u32>,
+    /// Reading time budget the Today panel's suggestions fit in
+    pub daily_minutes: Option<u32>,
+    pub color_gradient: GradientConfig,
+    /// Bible text file for the passage preview, if one is configured
+    pub scripture_text_path: Option<PathBuf>,
+    /// Screen-reader friendly, colorless rendering
+    pub accessibility_mode: bool,
+    /// Book names (possibly abbreviated) offered first when picking a book to record
+    pub pinned_books: Vec<String>,
+    /// Source new entries start with
+    pub default_source: ReadingSource,
+    /// Translations to cycle through in the entry form, the default first
+    pub translations: Vec<String>,
+    /// Directory searched for custom reading plans
+    pub plans_dir: PathBuf,
+    /// Goals shown in the status bar and the Stats tab
+    pub goals: Vec<Goal>,
+    /// Days without a reading after which a book is listed as neglected
+    pub neglected_days: u32,
+    /// When `brp daemon` sends its reminder
+    pub reminder_time: Option<NaiveTime>,
+    /// Group file compared in the Stats tab
+    pub group_path: Option<PathBuf>,
+    /// Run after readings are saved
+    pub post_save_hook: PostSaveHook,
+    /// External plugins, run after the post-save hook
+    pub plugins: Vec<CommandPlugin>,
+    pub language: Language,
+    /// How `brp queue` names each chapter's audio file
+    pub audio_file_pattern: String,
+    /// Whether verses recorded again on a day they were read count again
+    pub same_day_readings: SameDayReadings,
     config_file_path: PathBuf,
     /// True when progress path was overridden in dev mode (in-repo file)
     progress_path_overridden: bool,
@@ -44,23 +193,49 @@ impl Config {
             default_config
         };
 
+        // Determine which config file was actually used
+        let config_file_path = if config_file_yaml.exists() {
+            config_file_yaml
+        } else if config_file_yml.exists() {
+            config_file_yml
+        } else {
+            config_file_yaml
+        };
+        Self::from_file(&config_dir, config_file, config_file_path)
+    }
+
+    /// Read the config file again, e.g. after it was edited while the app runs. Unlike `load`,
+    /// a file that doesn't parse is an error, rather than falling back to the defaults.
+    pub fn reload(&self) -> Result<Self> {
+        let content = fs::read_to_string(&self.config_file_path)?;
+        let config_file: ConfigFile = serde_yaml::from_str(&content)?;
+        let config_dir = self
+            .config_file_path
+            .parent()
+            .ok_or_else(|| color_eyre::eyre::eyre!("Failed to get config directory"))?;
+        Self::from_file(config_dir, config_file, self.config_file_path.clone())
+    }
+
+    /// When the config file was last changed, to tell when to reload it
+    pub fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.config_file_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn from_file(
+        config_dir: &Path,
+        config_file: ConfigFile,
+        config_file_path: PathBuf,
+    ) -> Result<Self> {
         // Determine progress path
-        let mut progress_path = if let Some(configured_path) = &config_file.progress_path {
-            if let Some(stripped) = configured_path.strip_prefix("~/") {
-                let home = dirs::home_dir()
-                    .ok_or_else(|| color_eyre::eyre::eyre!("Failed to get home directory"))?;
-                home.join(stripped)
-            } else if configured_path == "~" {
-                dirs::home_dir()
-                    .ok_or_else(|| color_eyre::eyre::eyre!("Failed to get home directory"))?
-            } else {
-                let path = PathBuf::from(configured_path);
-                if path.is_absolute() {
-                    path
-                } else {
-                    config_dir.join(configured_path)
-                }
-            }
+        let configured_progress_path = config_file
+            .progress_path
+            .as_deref()
+            .map(|path| resolve_configured_path(config_dir, path))
+            .transpose()?;
+        let mut progress_path = if let Some(configured_path) = &configured_progress_path {
+            configured_path.clone()
         } else {
             // Default: use data directory for progress storage
             if cfg!(debug_assertions) {
@@ -82,24 +257,112 @@ impl Config {
             progress_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("reading_progress.yaml");
         }
 
-        // Determine which config file was actually used
-        let config_file_path = if config_file_yaml.exists() {
-            config_file_yaml
-        } else if config_file_yml.exists() {
-            config_file_yml
-        } else {
-            config_file_yaml
+        let ui_state_path = default_ui_state_path()
+            .ok_or_else(|| color_eyre::eyre::eyre!("Failed to get data directory"))?;
+
+        let scripture_text_path = config_file
+            .scripture_text_path
+            .as_deref()
+            .map(|path| resolve_configured_path(config_dir, path))
+            .transpose()?;
+
+        let group_path = config_file
+            .group_path
+            .as_deref()
+            .map(|path| resolve_configured_path(config_dir, path))
+            .transpose()?;
+
+        let plans_dir = match &config_file.plans_dir {
+            Some(path) => resolve_configured_path(config_dir, path)?,
+            None => default_plans_dir(config_dir),
         };
 
         Ok(Self {
             progress_path,
+            configured_progress_path,
+            ui_state_path,
+            daily_chapter_goal: config_file.daily_chapter_goal,
+            daily_minutes: config_file.daily_minutes,
+            color_gradient: config_file.color_gradient,
+            scripture_text_path,
+            accessibility_mode: config_file.accessibility_mode,
+            pinned_books: config_file.pinned_books,
+            default_source: config_file.default_source,
+            translations: config_file.translations,
+            plans_dir,
+            goals: config_file.goals,
+            neglected_days: config_file.neglected_days.unwrap_or(DEFAULT_NEGLECTED_DAYS),
+            reminder_time: config_file.reminder_time,
+            group_path,
+            post_save_hook: config_file.post_save_hook.unwrap_or_default(),
+            plugins: config_file.plugins,
+            language: config_file.language.unwrap_or_default(),
+            audio_file_pattern: config_file
+                .audio_file_pattern
+                .unwrap_or_else(|| DEFAULT_AUDIO_FILE_PATTERN.to_string()),
+            same_day_readings: config_file.same_day_readings.unwrap_or_default(),
             config_file_path,
             progress_path_overridden,
         })
     }
 }
 
+/// Resolve a path from the config file: `~` expands to the home directory and relative paths
+/// are relative to the config directory
+pub(crate) fn resolve_configured_path(config_dir: &Path, configured_path: &str) -> Result<PathBuf> {
+    if let Some(stripped) = configured_path.strip_prefix("~/") {
+        let home = dirs::home_dir()
+            .ok_or_else(|| color_eyre::eyre::eyre!("Failed to get home directory"))?;
+        Ok(home.join(stripped))
+    } else if configured_path == "~" {
+        dirs::home_dir().ok_or_else(|| color_eyre::eyre::eyre!("Failed to get home directory"))
+    } else {
+        let path = PathBuf::from(configured_path);
+        if path.is_absolute() {
+            Ok(path)
+        } else {
+            Ok(config_dir.join(configured_path))
+        }
+    }
+}
+
 impl Config {
+    /// Write the settings editable in the TUI back to the config file, keeping its other fields
+    pub fn save_settings(&self) -> Result<()> {
+        let mut config_file: ConfigFile = match fs::read_to_string(&self.config_file_path) {
+            Ok(content) => serde_yaml::from_str(&content).unwrap_or_default(),
+            Err(_) => ConfigFile::default(),
+        };
+        config_file.daily_chapter_goal = self.daily_chapter_goal;
+        config_file.daily_minutes = self.daily_minutes;
+        config_file.color_gradient = self.color_gradient.clone();
+        config_file.accessibility_mode = self.accessibility_mode;
+        // Paths are only written once changed, so ones relative to the config directory stay so
+        let config_dir = self.config_file_path.parent().unwrap_or(Path::new("."));
+        let resolve = |path: &Option<String>| {
+            path.as_deref()
+                .and_then(|path| resolve_configured_path(config_dir, path).ok())
+        };
+        if resolve(&config_file.progress_path) != self.configured_progress_path {
+            config_file.progress_path = self
+                .configured_progress_path
+                .as_ref()
+                .map(|path| path.display().to_string());
+        }
+        if resolve(&config_file.plans_dir).unwrap_or_else(|| default_plans_dir(config_dir))
+            != self.plans_dir
+        {
+            config_file.plans_dir = Some(self.plans_dir.display().to_string());
+        }
+
+        if let Some(parent) = self.config_file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_yaml::to_string(&config_file)?;
+        fs::write(&self.config_file_path, content)?;
+        Ok(())
+    }
+
     /// Returns the path to the config file that was loaded
     pub fn config_file_path(&self) -> &PathBuf {
         &self.config_file_path
@@ -136,15 +399,111 @@ impl Default for Config {
                     .join("bible-reading-progress")
                     .join("reading_progress.yaml")
             };
-            let config_file_path = dirs::config_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
-                .join("bible-reading-progress.yaml");
+            let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+            let config_file_path = config_dir.join("bible-reading-progress.yaml");
+            let ui_state_path =
+                default_ui_state_path().unwrap_or_else(|| PathBuf::from("ui_state.yaml"));
             let progress_path_overridden = cfg!(debug_assertions);
             Self {
                 progress_path,
+                configured_progress_path: None,
+                ui_state_path,
+                daily_chapter_goal: None,
+                daily_minutes: None,
+                color_gradient: GradientConfig::default(),
+                scripture_text_path: None,
+                accessibility_mode: false,
+                pinned_books: Vec::new(),
+                default_source: ReadingSource::Read,
+                translations: Vec::new(),
+                plans_dir: default_plans_dir(&config_dir),
+                goals: Vec::new(),
+                neglected_days: DEFAULT_NEGLECTED_DAYS,
+                reminder_time: None,
+                group_path: None,
+                post_save_hook: PostSaveHook::default(),
+                plugins: Vec::new(),
+                language: Language::default(),
+                audio_file_pattern: DEFAULT_AUDIO_FILE_PATTERN.to_string(),
+                same_day_readings: SameDayReadings::default(),
                 config_file_path,
                 progress_path_overridden,
             }
         })
     }
 }
+
+/// Plan files live next to the config file, in `bible-reading-progress/plans`
+fn default_plans_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join("bible-reading-progress").join("plans")
+}
+
+/// UI state is kept out of the progress file's directory, which may be under version control
+fn default_ui_state_path() -> Option<PathBuf> {
+    if cfg!(debug_assertions) {
+        // Debug/dev builds: use in-repo file
+        Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("ui_state.yaml"))
+    } else {
+        let state_dir = dirs::state_dir().or_else(dirs::data_dir)?;
+        Some(
+            state_dir
+                .join("bible-reading-progress")
+                .join("ui_state.yaml"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reload_reads_edits_and_rejects_broken_files() {
+        let dir = std::env::temp_dir().join(format!("brp-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = Config {
+            config_file_path: dir.join("bible-reading-progress.yaml"),
+            ..Config::default()
+        };
+
+        fs::write(
+            &config.config_file_path,
+            "daily_chapter_goal: 4\nplans_dir: mine\n",
+        )
+        .unwrap();
+        let reloaded = config.reload().unwrap();
+        assert_eq!(reloaded.daily_chapter_goal, Some(4));
+        assert_eq!(reloaded.plans_dir, dir.join("mine"));
+        assert!(config.modified().is_some());
+
+        fs::write(&config.config_file_path, "daily_chapter_goal: [").unwrap();
+        assert!(config.reload().is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn saving_settings_writes_only_changed_paths() {
+        let dir = std::env::temp_dir().join(format!("brp-config-paths-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_file_path = dir.join("bible-reading-progress.yaml");
+        fs::write(&config_file_path, "progress_path: mine.yaml\n").unwrap();
+        let mut config = Config {
+            config_file_path,
+            ..Config::default()
+        }
+        .reload()
+        .unwrap();
+        assert_eq!(config.configured_progress_path, Some(dir.join("mine.yaml")));
+
+        config.plans_dir = dir.join("plans");
+        config.save_settings().unwrap();
+        let saved: ConfigFile =
+            serde_yaml::from_str(&fs::read_to_string(config.config_file_path()).unwrap()).unwrap();
+        assert_eq!(saved.progress_path.as_deref(), Some("mine.yaml"));
+        assert_eq!(
+            saved.plans_dir,
+            Some(dir.join("plans").display().to_string())
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,317 @@
+//! How much of the Bible, a book or a chapter has been read, computed from the reading records
+
+use chrono::NaiveDate;
+
+use crate::bible_structure::{BibleStructure, BookFilter};
+use crate::progress::{InsideBookBibleReference, ReadingProgress, ReadingRecord};
+use crate::range_query::RangeMap;
+
+/// The read count of each verse of a chapter (index 0 is verse 1) and the most recent date any
+/// of them was read
+pub fn chapter_verse_counts(
+    chapter: u32,
+    max_verse: u32,
+    book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
+) -> (Vec<u32>, Option<NaiveDate>) {
+    let mut verse_counts = vec![0; max_verse as usize];
+    let mut last_read = None;
+
+    let Some(records) = book_records else {
+        return (verse_counts, last_read);
+    };
+
+    let chapter_start = InsideBookBibleReference { chapter, verse: 1 };
+    let chapter_end_exclusive = InsideBookBibleReference {
+        chapter,
+        verse: max_verse + 1,
+    };
+    for (range, record) in records.range(chapter_start..chapter_end_exclusive) {
+        if range.start.chapter != chapter || range.end.chapter != chapter {
+            continue;
+        }
+        // Verses are numbered from 1, but a hand-edited file can hold a range from verse 0
+        for verse in range.start.verse.max(1)..range.end.verse.min(max_verse + 1) {
+            let count = &mut verse_counts[verse as usize - 1];
+            *count = (*count).max(record.read_count);
+        }
+        if range.end.verse > range.start.verse {
+            last_read = last_read.max(Some(record.last_read));
+        }
+    }
+
+    (verse_counts, last_read)
+}
+
+/// Percentage of verses among the books shown by `filter` that have been read at least once
+pub fn percent_read(bible: &BibleStructure, progress: &ReadingProgress, filter: BookFilter) -> f64 {
+    let (mut verses_read, mut total_verses) = (0, 0);
+    for (books, in_old_testament) in [(&bible.ot, true), (&bible.nt, false)] {
+        for (book, chapters) in books {
+            if !filter.includes(in_old_testament, book) {
+                continue;
+            }
+            let (read, total) = book_verses_read(chapters, progress.books.get(book.as_str()));
+            verses_read += read;
+            total_verses += total;
+        }
+    }
+    percentage(verses_read, total_verses)
+}
+
+/// Percentage of a book's verses that have been read at least once
+pub fn book_percent_read(bible: &BibleStructure, progress: &ReadingProgress, book: &str) -> f64 {
+    let Some(chapters) = bible.ot.get(book).or_else(|| bible.nt.get(book)) else {
+        return 0.0;
+    };
+    let (verses_read, total_verses) = book_verses_read(chapters, progress.books.get(book));
+    percentage(verses_read, total_verses)
+}
+
+/// Where to continue reading `book`: the first chapter with unread verses after the chapter of
+/// the book read most recently, wrapping around to the start of the book. None once every verse
+/// of the book has been read.
+pub fn next_unread_chapter(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    book: &str,
+) -> Option<u32> {
+    let chapters = bible.ot.get(book).or_else(|| bible.nt.get(book))?;
+    let book_records = progress.books.get(book);
+    let last_read = progress
+        .history
+        .iter()
+        .rev()
+        .find(|event| event.book == book)
+        .map_or(0, |event| event.chapter as usize);
+    (0..chapters.len())
+        .map(|offset| (last_read + offset) % chapters.len())
+        .find(|&chapter_idx| {
+            let chapter = (chapter_idx + 1) as u32;
+            let (verse_counts, _) =
+                chapter_verse_counts(chapter, chapters[chapter_idx], book_records);
+            verse_counts.contains(&0)
+        })
+        .map(|chapter_idx| (chapter_idx + 1) as u32)
+}
+
+/// Number of times every verse of a book has been read (the read count of its least-read verse)
+pub fn book_min_read_count(bible: &BibleStructure, progress: &ReadingProgress, book: &str) -> u32 {
+    let Some(chapters) = bible.ot.get(book).or_else(|| bible.nt.get(book)) else {
+        return 0;
+    };
+    let book_records = progress.books.get(book);
+    chapters
+        .iter()
+        .enumerate()
+        .map(|(chapter_idx, &max_verse)| {
+            let (verse_counts, _) =
+                chapter_verse_counts((chapter_idx + 1) as u32, max_verse, book_records);
+            verse_counts.into_iter().min().unwrap_or(0)
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Number of times every verse of a chapter has been read, and whether any of it has been
+pub fn chapter_read_count(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    book: &str,
+    chapter: u32,
+) -> (u32, bool) {
+    let Some(&max_verse) = bible
+        .ot
+        .get(book)
+        .or_else(|| bible.nt.get(book))
+        .and_then(|chapters| chapters.get((chapter as usize).checked_sub(1)?))
+    else {
+        return (0, false);
+    };
+    let (verse_counts, _) = chapter_verse_counts(chapter, max_verse, progress.books.get(book));
+    (
+        verse_counts.iter().copied().min().unwrap_or(0),
+        verse_counts.iter().any(|&count| count > 0),
+    )
+}
+
+/// Consecutive verses of a chapter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerseRun {
+    pub book: String,
+    pub chapter: u32,
+    pub verse_start: u32,
+    /// Inclusive
+    pub verse_end: u32,
+}
+
+/// The lowest read count of any verse among the books shown by `filter` (what holds back the
+/// next full pass through them), and every run of consecutive verses read only that many times,
+/// in canonical order. None if `filter` shows no books.
+pub fn least_read_verses(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    filter: BookFilter,
+) -> Option<(u32, Vec<VerseRun>)> {
+    let mut least: Option<(u32, Vec<VerseRun>)> = None;
+    for (books, in_old_testament) in [(&bible.ot, true), (&bible.nt, false)] {
+        for (book, chapters) in books {
+            if !filter.includes(in_old_testament, book) {
+                continue;
+            }
+            let book_records = progress.books.get(book.as_str());
+            for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
+                let chapter = (chapter_idx + 1) as u32;
+                let (verse_counts, _) = chapter_verse_counts(chapter, max_verse, book_records);
+                let Some(&min) = verse_counts.iter().min() else {
+                    continue;
+                };
+                let runs = match &mut least {
+                    Some((count, runs)) if *count == min => runs,
+                    Some((count, _)) if *count < min => continue,
+                    _ => &mut least.insert((min, Vec::new())).1,
+                };
+                for (verse_idx, &count) in verse_counts.iter().enumerate() {
+                    if count != min {
+                        continue;
+                    }
+                    let verse = (verse_idx + 1) as u32;
+                    match runs.last_mut() {
+                        Some(run)
+                            if run.book == *book
+                                && run.chapter == chapter
+                                && run.verse_end + 1 == verse =>
+                        {
+                            run.verse_end = verse
+                        }
+                        _ => runs.push(VerseRun {
+                            book: book.to_string(),
+                            chapter,
+                            verse_start: verse,
+                            verse_end: verse,
+                        }),
+                    }
+                }
+            }
+        }
+    }
+    least
+}
+
+/// Number of a book's verses read at least once, and its total number of verses
+fn book_verses_read(
+    chapters: &[u32],
+    book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
+) -> (u32, u32) {
+    let (mut verses_read, mut total_verses) = (0, 0);
+    for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
+        let (verse_counts, _) =
+            chapter_verse_counts((chapter_idx + 1) as u32, max_verse, book_records);
+        verses_read += verse_counts.iter().filter(|&&count| count > 0).count() as u32;
+        total_verses += max_verse;
+    }
+    (verses_read, total_verses)
+}
+
+pub(crate) fn percentage(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn book_percent_counts_each_verse_once() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Jude", 1, 1, 25);
+        progress.record_reading("Jude", 1, 1, 5);
+        assert_eq!(book_percent_read(bible, &progress, "Jude"), 100.0);
+        assert_eq!(book_percent_read(bible, &progress, "Genesis"), 0.0);
+        assert_eq!(book_percent_read(bible, &progress, "Not a book"), 0.0);
+    }
+
+    #[test]
+    fn a_range_from_verse_0_counts_from_verse_1() {
+        let bible = get_bible_structure();
+        let progress: ReadingProgress = serde_yaml::from_str(
+            "books:
+  Ruth:
+    map:
+      ? chapter: 1
+        verse: 0
+      : - chapter: 1
+          verse: 4
+        - read_count: 2
+          last_read: 2024-05-01
+",
+        )
+        .unwrap();
+        let (verse_counts, last_read) = chapter_verse_counts(1, 22, progress.books.get("Ruth"));
+        assert_eq!(verse_counts[..4], [2, 2, 2, 0]);
+        assert_eq!(last_read, NaiveDate::from_ymd_opt(2024, 5, 1));
+        assert_eq!(chapter_read_count(bible, &progress, "Ruth", 1), (0, true));
+    }
+
+    #[test]
+    fn next_unread_chapter_continues_after_the_last_read() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        assert_eq!(next_unread_chapter(bible, &progress, "Titus"), Some(1));
+
+        progress.record_reading("Titus", 1, 1, 16);
+        progress.record_reading("Titus", 3, 1, 15);
+        // Chapter 3 was read last, so this wraps around to the unread chapter 2
+        assert_eq!(next_unread_chapter(bible, &progress, "Titus"), Some(2));
+
+        progress.record_reading("Titus", 2, 1, 15);
+        assert_eq!(next_unread_chapter(bible, &progress, "Titus"), None);
+    }
+
+    #[test]
+    fn least_read_verses_are_found_in_runs() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        assert_eq!(
+            least_read_verses(bible, &progress, BookFilter::All).map(|(_, runs)| runs.len()),
+            Some(1189)
+        );
+
+        // The whole Bible three times, but for some of Jude the third time
+        for books in [&bible.ot, &bible.nt] {
+            for (book, chapters) in books {
+                for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
+                    let chapter = (chapter_idx + 1) as u32;
+                    progress.record_reading(book, chapter, 1, max_verse);
+                    progress.record_reading(book, chapter, 1, max_verse);
+                    if book != "Jude" {
+                        progress.record_reading(book, chapter, 1, max_verse);
+                    }
+                }
+            }
+        }
+        progress.record_reading("Jude", 1, 3, 4);
+        progress.record_reading("Jude", 1, 10, 25);
+
+        let (count, runs) = least_read_verses(bible, &progress, BookFilter::All).unwrap();
+        assert_eq!(count, 2);
+        let runs: Vec<String> = runs
+            .iter()
+            .map(|run| {
+                format!(
+                    "{} {}:{}-{}",
+                    run.book, run.chapter, run.verse_start, run.verse_end
+                )
+            })
+            .collect();
+        assert_eq!(runs, ["Jude 1:1-2", "Jude 1:5-9"]);
+
+        let (count, runs) = least_read_verses(bible, &progress, BookFilter::OldTestament).unwrap();
+        assert_eq!((count, runs.len()), (3, 929));
+    }
+}
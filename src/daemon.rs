@@ -0,0 +1,226 @@
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use color_eyre::{eyre::eyre, Result};
+
+use crate::bible_structure::{get_bible_structure, BibleStructure};
+use crate::config::Config;
+use crate::progress::ReadingProgress;
+use crate::stats::chapters_read_on;
+use crate::utils::load_progress;
+
+/// How often the daemon looks at the clock, so it catches up after the computer sleeps
+const POLL_INTERVAL: StdDuration = StdDuration::from_secs(60);
+
+/// Title of the reminder notification
+const TITLE: &str = "Bible reading";
+
+/// Run in the foreground until killed, sending a notification at `reminder_time` each day if
+/// today's reading hasn't been recorded yet. Notifications that can't be sent, and progress
+/// files that can't be read (e.g. while the app is writing one), are passed to `on_error`, and
+/// the daemon carries on.
+pub fn run(config: &Config, on_error: impl Fn(io::Error)) -> Result<()> {
+    let reminder_time = config
+        .reminder_time
+        .ok_or_else(|| eyre!("Set `reminder_time` (e.g. \"20:00\") in the config file"))?;
+    let bible = get_bible_structure();
+
+    let mut next = next_reminder(Local::now(), reminder_time);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let now = Local::now();
+        if now < next {
+            continue;
+        }
+        // Reload each time, as readings are recorded by the app in the meantime. If that
+        // fails, try again at the next poll rather than skipping the day's reminder.
+        let progress = match load_progress(config) {
+            Ok(progress) => progress,
+            Err(e) => {
+                on_error(io::Error::other(format!(
+                    "Couldn't load the progress file: {}",
+                    e
+                )));
+                continue;
+            }
+        };
+        next = next_reminder(now, reminder_time);
+        if let Some(message) = reminder(bible, &progress, &config.plans_dir, reading_dates(now)) {
+            if let Err(e) = notify(TITLE, &message) {
+                on_error(io::Error::other(format!(
+                    "Couldn't send a notification: {}",
+                    e
+                )));
+            }
+        }
+    }
+}
+
+/// The first time after `now` when the clock reads `time`
+fn next_reminder(now: DateTime<Local>, time: NaiveTime) -> DateTime<Local> {
+    let today = now.date_naive().and_time(time);
+    let target = if today > now.naive_local() {
+        today
+    } else {
+        today + Duration::days(1)
+    };
+    // A time skipped by a DST change falls back to an hour later
+    target
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(|| now + Duration::hours(1))
+}
+
+/// The dates of the readings recorded on the local day of `now`, up to `now`. Like the rest of
+/// the app, readings are dated by the UTC day they were recorded on, so the local day can span
+/// two of them: west of UTC, an evening reminder already falls on the next UTC day. The last of
+/// them is the app's "today".
+fn reading_dates<Tz: TimeZone>(now: DateTime<Tz>) -> RangeInclusive<NaiveDate> {
+    let today = now.with_timezone(&Utc).date_naive();
+    let midnight = now
+        .date_naive()
+        .and_time(NaiveTime::MIN)
+        .and_local_timezone(now.timezone())
+        .earliest()
+        .map_or(today, |midnight| midnight.with_timezone(&Utc).date_naive());
+    midnight..=today
+}
+
+/// What is still to read, if anything: the unread chapters of the plan day of the last of
+/// `dates`, or, without a plan, a nudge when nothing has been recorded on any of `dates`
+pub fn reminder(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    plans_dir: &Path,
+    dates: RangeInclusive<NaiveDate>,
+) -> Option<String> {
+    let today = *dates.end();
+    let plan = progress
+        .plan
+        .as_ref()
+        .and_then(|state| Some((state, state.plan.load(bible, plans_dir).ok()?)));
+    if let Some((state, plan)) = plan {
+        let plan = state.schedule(&plan);
+        let completion = plan.completion(state, &progress.history);
+        let day = state.current_day(&plan, today);
+        let unread: Vec<String> = plan.days[day]
+            .iter()
+            .zip(&completion[day])
+            .filter(|(_, &done)| !done)
+            .map(|(item, _)| format!("{} {}", item.book, item.chapter))
+            .collect();
+        return (!unread.is_empty()).then(|| format!("Still to read today: {}", unread.join(", ")));
+    }
+    dates
+        .start()
+        .iter_days()
+        .take_while(|date| date <= dates.end())
+        .all(|date| chapters_read_on(&progress.history, date).is_empty())
+        .then(|| "Nothing has been recorded today yet".to_string())
+}
+
+/// Show a desktop notification with the platform's own tool: `osascript` on macOS and
+/// `notify-send` elsewhere
+fn notify(title: &str, body: &str) -> io::Result<()> {
+    let status = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {:?} with title {:?}",
+            body.replace('"', "'"),
+            title
+        );
+        Command::new("osascript").args(["-e", &script]).status()?
+    } else {
+        Command::new("notify-send").args([title, body]).status()?
+    };
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "the notifier exited with {}",
+            status
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::plans::{PlanId, PlanKind, PlanState};
+    use chrono::FixedOffset;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn reminds_of_unread_plan_chapters() {
+        let bible = get_bible_structure();
+        let plans_dir = Path::new("/nonexistent");
+        let mut progress = ReadingProgress::new();
+        assert_eq!(
+            reminder(bible, &progress, plans_dir, date(3, 1)..=date(3, 1)).as_deref(),
+            Some("Nothing has been recorded today yet")
+        );
+
+        progress.plan = Some(PlanState::new(
+            PlanId::BuiltIn(PlanKind::NewTestament90),
+            date(3, 1),
+        ));
+        progress.record_reading("Matthew", 1, 1, 25);
+        progress.history.last_mut().unwrap().date = date(3, 1);
+        assert_eq!(
+            reminder(bible, &progress, plans_dir, date(3, 1)..=date(3, 1)).as_deref(),
+            Some("Still to read today: Matthew 2, Matthew 3")
+        );
+
+        let noon = date(3, 1)
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap();
+        let evening = NaiveTime::from_hms_opt(20, 0, 0).unwrap();
+        assert_eq!(
+            next_reminder(noon, evening).naive_local(),
+            date(3, 1).and_time(evening)
+        );
+        let night = noon + Duration::hours(9);
+        assert_eq!(
+            next_reminder(night, evening).naive_local(),
+            date(3, 2).and_time(evening)
+        );
+    }
+
+    #[test]
+    fn an_evening_reminder_west_of_utc_counts_that_days_readings() {
+        let bible = get_bible_structure();
+        let plans_dir = Path::new("/nonexistent");
+        // 20:00 on March 1st in California is 04:00 on March 2nd in UTC
+        let california = FixedOffset::west_opt(8 * 3600).unwrap();
+        let evening = california.with_ymd_and_hms(2025, 3, 1, 20, 0, 0).unwrap();
+        assert_eq!(reading_dates(evening), date(3, 1)..=date(3, 2));
+        let morning = FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2025, 3, 2, 7, 0, 0)
+            .unwrap();
+        assert_eq!(reading_dates(morning), date(3, 1)..=date(3, 1));
+
+        // Read at lunchtime, which the app dated March 1st
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        progress.history.last_mut().unwrap().date = date(3, 1);
+        assert_eq!(
+            reminder(bible, &progress, plans_dir, reading_dates(evening)),
+            None
+        );
+        assert_eq!(
+            reminder(bible, &progress, plans_dir, date(3, 2)..=date(3, 2)).as_deref(),
+            Some("Nothing has been recorded today yet")
+        );
+    }
+}
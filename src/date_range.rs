@@ -0,0 +1,119 @@
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Days from `start` to `end`, both included, e.g. a year or the weeks of Lent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    /// Parse a year ("2024"), a month ("2024-03"), a day ("2024-03-05"), or two of these
+    /// separated by ".." or " to ", from the start of the first to the end of the second
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let (first, last) = input
+            .split_once("..")
+            .or_else(|| input.split_once(" to "))
+            .unwrap_or((input, input));
+        let start = period(first)?.start;
+        let end = period(last)?.end;
+        if end < start {
+            return Err(format!("{} is before {}", end, start));
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        (self.start..=self.end).contains(&date)
+    }
+}
+
+/// The days of a year, month or day written as "2024", "2024-03" or "2024-03-05"
+fn period(input: &str) -> Result<DateRange, String> {
+    let input = input.trim();
+    let invalid = || {
+        format!(
+            "expected a year, month or day like 2024, 2024-03 or 2024-03-05, not \"{}\"",
+            input
+        )
+    };
+    let parts: Vec<&str> = input.split('-').collect();
+    let numbers: Vec<u32> = parts
+        .iter()
+        .map(|part| part.parse().map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    let date = |year: u32, month: u32, day: u32| {
+        NaiveDate::from_ymd_opt(year as i32, month, day).ok_or_else(invalid)
+    };
+    let (start, end) = match numbers[..] {
+        [year] if parts[0].len() == 4 => (date(year, 1, 1)?, date(year, 12, 31)?),
+        [year, month] => {
+            let start = date(year, month, 1)?;
+            let next = start
+                .checked_add_months(chrono::Months::new(1))
+                .ok_or_else(invalid)?;
+            (start, next - Duration::days(1))
+        }
+        [year, month, day] => (date(year, month, day)?, date(year, month, day)?),
+        _ => return Err(invalid()),
+    };
+    Ok(DateRange { start, end })
+}
+
+/// As short as it can be written: "2024", "2024-03", "2024-03-05" or
+/// "2025-03-05 to 2025-04-20"
+impl fmt::Display for DateRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let whole_months = self.start.day() == 1 && (self.end + Duration::days(1)).day() == 1;
+        let same_year = self.start.year() == self.end.year();
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else if whole_months && same_year && (self.start.month(), self.end.month()) == (1, 12) {
+            write!(f, "{}", self.start.year())
+        } else if whole_months && same_year && self.start.month() == self.end.month() {
+            write!(f, "{}", self.start.format("%Y-%m"))
+        } else {
+            write!(f, "{} to {}", self.start, self.end)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn years_months_and_days_are_parsed_and_written_back() {
+        let range = DateRange::parse("2024").unwrap();
+        assert_eq!(
+            (range.start, range.end),
+            (day(2024, 1, 1), day(2024, 12, 31))
+        );
+        assert_eq!(range.to_string(), "2024");
+
+        let range = DateRange::parse("2024-02").unwrap();
+        assert_eq!(range.end, day(2024, 2, 29));
+        assert_eq!(range.to_string(), "2024-02");
+
+        let lent = DateRange::parse("2025-03-05..2025-04-19").unwrap();
+        assert!(lent.contains(day(2025, 4, 19)) && !lent.contains(day(2025, 4, 20)));
+        assert_eq!(lent.to_string(), "2025-03-05 to 2025-04-19");
+        assert_eq!(DateRange::parse(&lent.to_string()), Ok(lent));
+        assert_eq!(
+            DateRange::parse("2023 to 2024-06").unwrap().end,
+            day(2024, 6, 30)
+        );
+
+        assert!(DateRange::parse("2024-13").is_err());
+        assert!(DateRange::parse("24").is_err());
+        assert!(DateRange::parse("2025..2024").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! A made-up reader's progress, always the same for a given day: some books read through, some
+//! part way, a streak up to yesterday, a plan under way, a passage being memorized and a
+//! journal. `brp --demo` shows it for screenshots, trying out themes or looking around without
+//! touching any files, and tests can use it as a realistic fixture.
+
+use chrono::{Duration, NaiveDate};
+
+use crate::achievements::unlock_new;
+use crate::bible_structure::BibleStructure;
+use crate::memorize::MemoryCard;
+use crate::plans::{PlanId, PlanKind, PlanState};
+use crate::progress::ReadingProgress;
+
+/// Days the plan has been followed, up to yesterday, so today's reading is still to do
+const PLAN_DAYS: i64 = 20;
+
+pub fn demo_progress(bible: &BibleStructure, today: NaiveDate) -> ReadingProgress {
+    let verses = |book: &str, chapter: u32| -> u32 {
+        bible
+            .ot
+            .get(book)
+            .or_else(|| bible.nt.get(book))
+            .and_then(|verses| verses.get(chapter as usize - 1))
+            .copied()
+            .unwrap_or(1)
+    };
+    let days_ago = |days: i64| today - Duration::days(days);
+    // (date, book, chapter, last verse read)
+    let mut readings: Vec<(NaiveDate, &str, u32, u32)> = Vec::new();
+    let mut read_book = |book: &'static str, chapters: u32, start: NaiveDate, per_day: u32| {
+        for chapter in 1..=chapters {
+            let date = start + Duration::days(((chapter - 1) / per_day) as i64);
+            readings.push((date, book, chapter, verses(book, chapter)));
+        }
+    };
+
+    // Read through two years ago, and Romans twice
+    read_book("Genesis", 50, days_ago(730), 2);
+    read_book("Ruth", 4, days_ago(690), 4);
+    read_book("Jonah", 4, days_ago(560), 4);
+    read_book("Romans", 16, days_ago(400), 1);
+    read_book("Romans", 16, days_ago(180), 2);
+    // Part way through
+    read_book("Psalms", 60, days_ago(150), 1);
+    read_book("John", 14, days_ago(60), 1);
+    for days in [120, 90, 45] {
+        readings.push((days_ago(days), "Psalms", 23, verses("Psalms", 23)));
+    }
+    readings.push((days_ago(46), "John", 15, 8));
+
+    // The New Testament in 90 days, followed every day until yesterday
+    let plan = PlanKind::NewTestament90.build(bible);
+    let plan_start = days_ago(PLAN_DAYS);
+    for (day, items) in plan.days.iter().take(PLAN_DAYS as usize).enumerate() {
+        for item in items {
+            readings.push((
+                plan_start + Duration::days(day as i64),
+                &item.book,
+                item.chapter,
+                verses(&item.book, item.chapter),
+            ));
+        }
+    }
+
+    let mut progress = ReadingProgress::new();
+    // The history is oldest first
+    readings.sort_by_key(|(date, ..)| *date);
+    for (date, book, chapter, verse_end) in readings {
+        progress.record_reading_on(book, chapter, 1, verse_end, 1, date);
+    }
+    progress.plan = Some(PlanState::new(
+        PlanId::BuiltIn(PlanKind::NewTestament90),
+        plan_start,
+    ));
+    progress.memorization.push(MemoryCard::new(
+        "Psalms".to_string(),
+        23,
+        1,
+        6,
+        days_ago(10),
+    ));
+    for days in [5, 3, 2, 1] {
+        progress.record_session(days_ago(days), 15 + days as u32 * 5);
+    }
+    progress.set_journal_entry(
+        days_ago(1),
+        "Matthew 5: the Beatitudes. Blessed are the peacemakers.",
+    );
+    unlock_new(bible, &mut progress, today);
+    progress.take_changed_books();
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::stats::current_streak;
+
+    #[test]
+    fn demo_is_the_same_each_time_and_shows_a_streak() {
+        let bible = get_bible_structure();
+        let today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        let progress = demo_progress(bible, today);
+        let again = demo_progress(bible, today);
+        assert_eq!(progress.history, again.history);
+        assert_eq!(progress.books, again.books);
+        assert_eq!(current_streak(&progress.history, today), PLAN_DAYS as u32);
+        assert!(progress.history.is_sorted_by_key(|event| event.date));
+        assert!(!progress.achievements.is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use color_eyre::Result;
+use crossterm::event::{self, Event};
+
+/// How often `AppEvent::Tick` is sent when nothing else happens
+pub const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Everything the main loop reacts to
+#[derive(Debug, Clone)]
+pub enum AppEvent {
+    /// Periodic tick, for timers, animations and background housekeeping
+    Tick,
+    /// Terminal input (keys, mouse, resize)
+    Terminal(Event),
+    /// A plugin, such as the post-save hook, failed, with why
+    PluginFailed(String),
+    /// A background save failed, with why
+    SaveFailed(String),
+    /// The reading history finished parsing in the background
+    HistoryParsed,
+    /// The plugins' stats lines, run in the background
+    PluginStats(Vec<(String, String)>),
+}
+
+/// Merges terminal input and periodic ticks into a single channel. Other event sources
+/// (e.g. file watchers) can send into the same channel through `sender`.
+pub struct EventHandler {
+    sender: Sender<AppEvent>,
+    receiver: Receiver<AppEvent>,
+}
+
+impl EventHandler {
+    /// Start a background thread that forwards terminal events and emits a tick every `tick_rate`
+    pub fn new(tick_rate: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let input_sender = sender.clone();
+        thread::spawn(move || {
+            let mut last_tick = Instant::now();
+            loop {
+                let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+                let event = match event::poll(timeout) {
+                    Ok(true) => match event::read() {
+                        Ok(event) => Some(AppEvent::Terminal(event)),
+                        Err(_) => None,
+                    },
+                    Ok(false) => None,
+                    // The terminal is gone, nothing more to forward
+                    Err(_) => break,
+                };
+                if let Some(event) = event {
+                    if input_sender.send(event).is_err() {
+                        break;
+                    }
+                }
+                if last_tick.elapsed() >= tick_rate {
+                    if input_sender.send(AppEvent::Tick).is_err() {
+                        break;
+                    }
+                    last_tick = Instant::now();
+                }
+            }
+        });
+
+        Self { sender, receiver }
+    }
+
+    /// A handle for sending additional events into the loop
+    pub fn sender(&self) -> Sender<AppEvent> {
+        self.sender.clone()
+    }
+
+    /// Block until the next event arrives
+    pub fn next(&self) -> Result<AppEvent> {
+        Ok(self.receiver.recv()?)
+    }
+}
@@ -0,0 +1,194 @@
+use std::fs;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::bible_structure::{BibleStructure, BookFilter};
+use crate::config::resolve_configured_path;
+use crate::coverage::percent_read;
+use crate::progress::ReadingProgress;
+use crate::stats::{chapters_per_day, current_streak};
+
+/// A group of people reading together, as written in a shared group file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupFile {
+    /// Shown as the title of the group view; defaults to "Group"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub members: Vec<GroupMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupMember {
+    pub name: String,
+    /// The member's progress file, absolute or relative to the group file
+    pub progress: String,
+}
+
+/// How far a member has got
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberStats {
+    /// Percentage of verses read in the whole Bible, the Old and the New Testament
+    pub percent_read: f64,
+    pub percent_old_testament: f64,
+    pub percent_new_testament: f64,
+    pub streak: u32,
+    /// Chapters read over the last 7 days
+    pub chapters_this_week: u64,
+}
+
+impl MemberStats {
+    pub fn of(bible: &BibleStructure, progress: &ReadingProgress, today: NaiveDate) -> Self {
+        Self {
+            percent_read: percent_read(bible, progress, BookFilter::All),
+            percent_old_testament: percent_read(bible, progress, BookFilter::OldTestament),
+            percent_new_testament: percent_read(bible, progress, BookFilter::NewTestament),
+            streak: current_streak(&progress.history, today),
+            chapters_this_week: chapters_per_day(&progress.history, today, 7).iter().sum(),
+        }
+    }
+}
+
+/// The members of a group with their stats, or why their progress file couldn't be read
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub name: String,
+    pub members: Vec<(String, Result<MemberStats, String>)>,
+}
+
+/// The members of a group with their progress, or why their progress file couldn't be read
+#[derive(Debug, Clone)]
+pub struct GroupProgress {
+    pub name: String,
+    pub members: Vec<(String, Result<ReadingProgress, String>)>,
+}
+
+impl GroupProgress {
+    /// Read the group file at `path` and every member's progress file
+    #[tracing::instrument]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+        let file: GroupFile = serde_yaml::from_str(&content)
+            .map_err(|e| format!("Couldn't parse {}: {}", path.display(), e))?;
+        let group_dir = path.parent().unwrap_or(Path::new("."));
+        let members = file
+            .members
+            .into_iter()
+            .map(|member| (member.name, load_member(group_dir, &member.progress)))
+            .collect();
+        Ok(Self {
+            name: file.name.unwrap_or_else(|| "Group".to_string()),
+            members,
+        })
+    }
+
+    /// The members whose progress could be read
+    pub fn readable(&self) -> Vec<(String, ReadingProgress)> {
+        self.members
+            .iter()
+            .filter_map(|(name, progress)| Some((name.clone(), progress.as_ref().ok()?.clone())))
+            .collect()
+    }
+}
+
+impl Group {
+    /// Read the group file at `path` and every member's progress file
+    pub fn load(bible: &BibleStructure, path: &Path, today: NaiveDate) -> Result<Self, String> {
+        GroupProgress::load(path).map(|group| Self::of(bible, &group, today))
+    }
+
+    /// Each member's stats, as of `today`
+    pub fn of(bible: &BibleStructure, group: &GroupProgress, today: NaiveDate) -> Self {
+        Self {
+            name: group.name.clone(),
+            members: group
+                .members
+                .iter()
+                .map(|(name, progress)| {
+                    let stats = progress
+                        .as_ref()
+                        .map(|progress| MemberStats::of(bible, progress, today))
+                        .map_err(Clone::clone);
+                    (name.clone(), stats)
+                })
+                .collect(),
+        }
+    }
+
+    /// Members sorted by how much of the Bible they have read, then by streak, those whose
+    /// progress couldn't be read last
+    pub fn ranked(&self) -> Vec<&(String, Result<MemberStats, String>)> {
+        let mut members: Vec<_> = self.members.iter().collect();
+        members.sort_by(|(_, a), (_, b)| match (a, b) {
+            (Ok(a), Ok(b)) => b
+                .percent_read
+                .total_cmp(&a.percent_read)
+                .then(b.streak.cmp(&a.streak)),
+            (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+            (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+            (Err(_), Err(_)) => std::cmp::Ordering::Equal,
+        });
+        members
+    }
+
+    /// Combined chapters read by the group over the last 7 days
+    pub fn chapters_this_week(&self) -> u64 {
+        self.members
+            .iter()
+            .filter_map(|(_, stats)| stats.as_ref().ok())
+            .map(|stats| stats.chapters_this_week)
+            .sum()
+    }
+}
+
+fn load_member(group_dir: &Path, progress_path: &str) -> Result<ReadingProgress, String> {
+    let path = resolve_configured_path(group_dir, progress_path).map_err(|e| e.to_string())?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    serde_yaml::from_str(&content).map_err(|e| format!("Couldn't parse {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn members_are_ranked_by_completion() {
+        let bible = get_bible_structure();
+        let today = chrono::Utc::now().date_naive();
+        let dir = std::env::temp_dir().join(format!("brp-group-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut anna = ReadingProgress::new();
+        for (idx, &verses) in bible.nt["Mark"].iter().enumerate() {
+            anna.record_reading("Mark", idx as u32 + 1, 1, verses);
+        }
+        let mut ben = ReadingProgress::new();
+        ben.record_reading("Jude", 1, 1, 25);
+        fs::write(dir.join("anna.yaml"), serde_yaml::to_string(&anna).unwrap()).unwrap();
+        fs::write(dir.join("ben.yaml"), serde_yaml::to_string(&ben).unwrap()).unwrap();
+        let group_path = dir.join("group.yaml");
+        fs::write(
+            &group_path,
+            "name: Home group\nmembers:\n  - {name: Ben, progress: ben.yaml}\n  - {name: Cal, progress: missing.yaml}\n  - {name: Anna, progress: anna.yaml}\n",
+        )
+        .unwrap();
+
+        let group = Group::load(bible, &group_path, today).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(group.name, "Home group");
+        let ranked: Vec<(&str, Option<u32>)> = group
+            .ranked()
+            .into_iter()
+            .map(|(name, stats)| (name.as_str(), stats.as_ref().ok().map(|s| s.streak)))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![("Anna", Some(1)), ("Ben", Some(1)), ("Cal", None)]
+        );
+        assert_eq!(group.chapters_this_week(), 17);
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::bible_structure::{BibleStructure, BookFilter};
+use crate::coverage::percent_read;
+use crate::progress::{ReadingEvent, ReadingProgress};
+use crate::stats::current_streak;
+
+/// Seconds `curl` is given to deliver the payload
+const HTTP_TIMEOUT_SECONDS: u32 = 30;
+
+/// Run after readings are saved, to let other tools (habit trackers, home automation) know
+/// what was recorded. Both may be set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostSaveHook {
+    /// Shell command run with the JSON payload on its standard input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// URL the JSON payload is POSTed to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// What was recorded, sent to the post-save hook as JSON
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HookPayload {
+    /// Readings logged since the hook last ran
+    pub recorded: Vec<ReadingEvent>,
+    /// Distinct chapters among `recorded`, e.g. a data point for a Beeminder goal
+    pub chapters: usize,
+    pub streak: u32,
+    /// Percentage of the Bible's verses read
+    pub percent_read: f64,
+}
+
+impl HookPayload {
+    /// The readings logged since the history had `first_event` entries, with the totals after
+    /// them
+    pub fn new(
+        bible: &BibleStructure,
+        progress: &ReadingProgress,
+        first_event: usize,
+        today: NaiveDate,
+    ) -> Self {
+        let recorded = progress
+            .history
+            .get(first_event..)
+            .unwrap_or_default()
+            .to_vec();
+        let chapters = recorded
+            .iter()
+            .map(|event| (event.book.as_str(), event.chapter))
+            .collect::<HashSet<_>>()
+            .len();
+        Self {
+            chapters,
+            recorded,
+            streak: current_streak(&progress.history, today),
+            percent_read: percent_read(bible, progress, BookFilter::All),
+        }
+    }
+}
+
+impl PostSaveHook {
+    pub fn is_set(&self) -> bool {
+        self.command.is_some() || self.url.is_some()
+    }
+
+    /// Run the command and send the request, waiting for both. The error describes every
+    /// part that failed.
+    pub fn run(&self, payload: &HookPayload) -> Result<(), String> {
+        let json = serde_json::to_string(payload).map_err(|e| e.to_string())?;
+        let mut errors = Vec::new();
+        if let Some(command) = &self.command {
+            if let Err(e) = run_with_input(shell(command), &json) {
+                errors.push(format!("`{}` failed: {}", command, e));
+            }
+        }
+        if let Some(url) = &self.url {
+            if let Err(e) = run_with_input(post(url), &json) {
+                errors.push(format!("POST to {} failed: {}", url, e));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// `command` run by the platform's shell
+pub(crate) fn shell(command: &str) -> Command {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+    shell
+}
+
+/// A `curl` call POSTing its standard input to `url` as JSON, so no HTTP client (and TLS
+/// stack) has to be built in
+fn post(url: &str) -> Command {
+    let mut curl = Command::new("curl");
+    curl.args(["--silent", "--show-error", "--fail", "--max-time"])
+        .arg(HTTP_TIMEOUT_SECONDS.to_string())
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--data-binary", "@-"])
+        .arg(url);
+    curl
+}
+
+/// Run `command` with `input` on its standard input, returning what it printed. A failure
+/// carries its error output.
+pub(crate) fn run_with_input(mut command: Command, input: &str) -> io::Result<String> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that doesn't read the payload may exit before it is written
+        match stdin.write_all(input.as_bytes()) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+            _ => {}
+        }
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(io::Error::other(match stderr.trim() {
+        "" => format!("exited with {}", output.status),
+        message => message.to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn command_receives_what_was_recorded() {
+        let bible = get_bible_structure();
+        let today = chrono::Utc::now().date_naive();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Jude", 1, 1, 25);
+        progress.record_reading("Ruth", 1, 1, 11);
+        progress.record_reading("Ruth", 1, 12, 22);
+
+        let payload = HookPayload::new(bible, &progress, 1, today);
+        assert_eq!(payload.recorded.len(), 2);
+        assert_eq!(payload.chapters, 1);
+        assert_eq!(payload.streak, 1);
+
+        let path = std::env::temp_dir().join(format!("brp-hook-{}.json", std::process::id()));
+        let hook = PostSaveHook {
+            command: Some(format!("cat > '{}'", path.display())),
+            url: None,
+        };
+        hook.run(&payload).unwrap();
+        let sent: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(sent["chapters"], 1);
+        assert_eq!(sent["recorded"][1]["verse_start"], 12);
+
+        let failing = PostSaveHook {
+            command: Some("echo nope >&2; exit 3".to_string()),
+            url: None,
+        };
+        assert_eq!(
+            failing.run(&payload),
+            Err("`echo nope >&2; exit 3` failed: nope".to_string())
+        );
+    }
+}
@@ -0,0 +1,445 @@
+//! Translations of the interface. Text is written in English in the code and passed through
+//! [`tr`] (or [`trf`], for text with values filled in), which looks it up in the catalog of the
+//! language chosen with `language` in the config. Text missing from a catalog stays in English,
+//! so the interface can be translated a screen at a time.
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "es")]
+    Spanish,
+}
+
+/// The language the interface is shown in, as a `Language` discriminant
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// The language's own name for itself
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    /// (English, translation) pairs
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => &[],
+            Language::Spanish => SPANISH,
+        }
+    }
+
+    /// `text` in this language, or as it is if it hasn't been translated
+    pub fn translate(self, text: &str) -> &str {
+        self.catalog()
+            .iter()
+            .find(|(english, _)| *english == text)
+            .map_or(text, |(_, translation)| translation)
+    }
+}
+
+/// Show the interface in `language` from now on
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    let current = LANGUAGE.load(Ordering::Relaxed);
+    Language::ALL
+        .into_iter()
+        .find(|language| *language as u8 == current)
+        .unwrap_or_default()
+}
+
+/// `text` in the interface language
+pub fn tr(text: &str) -> &str {
+    language().translate(text)
+}
+
+/// `template` in the interface language, with each `{}` replaced by the next of `args`
+pub fn trf(template: &str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = tr(template).split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            filled.push_str(&arg.to_string());
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+const SPANISH: &[(&str, &str)] = &[
+    // Tabs
+    ("Dashboard", "Panel"),
+    ("Plans", "Planes"),
+    ("History", "Historial"),
+    ("Stats", "Estadísticas"),
+    ("Settings", "Ajustes"),
+    ("Switch to the previous/next tab", "Ir a la pestaña anterior/siguiente"),
+    ("Switch to a tab by number", "Ir a una pestaña por su número"),
+    // Help
+    (
+        "Help: {} (press any key to close)",
+        "Ayuda: {} (pulsa cualquier tecla para cerrar)",
+    ),
+    ("Merge", "Combinar"),
+    ("Today", "Hoy"),
+    ("Memory Review", "Repaso de memoria"),
+    ("Import", "Importar"),
+    ("Journal", "Diario"),
+    ("Chapter Grid", "Cuadrícula de capítulos"),
+    ("Click", "Clic"),
+    ("Scroll wheel", "Rueda del ratón"),
+    ("Space", "Espacio"),
+    ("Show this help", "Mostrar esta ayuda"),
+    ("Quit", "Salir"),
+    // Status bar
+    ("Streak: ", "Racha: "),
+    ("Today: ", "Hoy: "),
+    ("nothing yet", "nada todavía"),
+    ("1 day", "1 día"),
+    ("{} days", "{} días"),
+    ("Goal: ", "Meta: "),
+    ("{}/{} chapters", "{}/{} capítulos"),
+    ("?: Help", "?: Ayuda"),
+    // Dashboard
+    ("Navigate through passages", "Moverse por los pasajes"),
+    ("Move a page up or down", "Subir o bajar una página"),
+    ("Jump to the first/last passage", "Ir al primer/último pasaje"),
+    (
+        "Expand or collapse the selected passage",
+        "Expandir o contraer el pasaje seleccionado",
+    ),
+    (
+        "Show the selected book as a grid of chapters (expands or collapses other passages)",
+        "Mostrar el libro seleccionado como cuadrícula de capítulos (expande o contrae otros pasajes)",
+    ),
+    (
+        "Mark/unmark the selected chapter (expands or collapses other passages)",
+        "Marcar/desmarcar el capítulo seleccionado (expande o contrae otros pasajes)",
+    ),
+    (
+        "Apply an action to the marked chapters",
+        "Aplicar una acción a los capítulos marcados",
+    ),
+    ("Collapse the selected passage", "Contraer el pasaje seleccionado"),
+    (
+        "Jump to the next/previous chapter with unread verses",
+        "Ir al capítulo siguiente/anterior con versículos sin leer",
+    ),
+    ("Expand the whole tree", "Expandir todo el árbol"),
+    ("Collapse the whole tree", "Contraer todo el árbol"),
+    (
+        "Expand to testament/book/chapter depth",
+        "Expandir hasta testamento/libro/capítulo",
+    ),
+    (
+        "Show only the Old Testament (press again for all books)",
+        "Mostrar solo el Antiguo Testamento (pulsa otra vez para todos los libros)",
+    ),
+    (
+        "Show only the New Testament (press again for all books)",
+        "Mostrar solo el Nuevo Testamento (pulsa otra vez para todos los libros)",
+    ),
+    (
+        "Pick a genre (or testament) to show",
+        "Elegir un género (o testamento) para mostrar",
+    ),
+    (
+        "Go to a reference (e.g. \"ps 23\")",
+        "Ir a una referencia (p. ej. \"ps 23\")",
+    ),
+    (
+        "Show only the readings of a date range (e.g. \"2024\"; empty for all)",
+        "Mostrar solo las lecturas de un periodo (p. ej. \"2024\"; vacío para todas)",
+    ),
+    ("Show the color legend", "Mostrar la leyenda de colores"),
+    (
+        "Read the selected chapter's text",
+        "Leer el texto del capítulo seleccionado",
+    ),
+    (
+        "Show today's suggested readings",
+        "Mostrar las lecturas sugeridas para hoy",
+    ),
+    (
+        "Copy the selected passage's reference",
+        "Copiar la referencia del pasaje seleccionado",
+    ),
+    (
+        "Copy the selected passage's stats line",
+        "Copiar la línea de estadísticas del pasaje seleccionado",
+    ),
+    (
+        "List the selected chapter's last readings",
+        "Ver las últimas lecturas del capítulo seleccionado",
+    ),
+    (
+        "Jump to the least-read verses (press again for the next place)",
+        "Ir a los versículos menos leídos (pulsa otra vez para el siguiente lugar)",
+    ),
+    (
+        "Tick off consecutive chapters: pick where to start, then Enter marks each one read",
+        "Marcar capítulos seguidos: elige dónde empezar y cada Enter marca uno como leído",
+    ),
+    (
+        "Start memorizing the selected chapter or passage",
+        "Empezar a memorizar el capítulo o pasaje seleccionado",
+    ),
+    (
+        "Review the passages you're memorizing",
+        "Repasar los pasajes que estás memorizando",
+    ),
+    (
+        "Switch between lifetime totals and your campaigns (read-throughs)",
+        "Alternar entre los totales de siempre y tus campañas (lecturas completas)",
+    ),
+    ("Record what you read today", "Registrar lo que leíste hoy"),
+    (
+        "Record the last recorded passage again, today",
+        "Registrar otra vez hoy el último pasaje registrado",
+    ),
+    (
+        "Manually add readings (overwrite)",
+        "Añadir lecturas a mano (sobrescribir)",
+    ),
+    (
+        "Import readings from a text file or pasted lines",
+        "Importar lecturas de un archivo de texto o de líneas pegadas",
+    ),
+    (
+        "Select a passage (double-click or click the arrow to expand)",
+        "Seleccionar un pasaje (doble clic o clic en la flecha para expandir)",
+    ),
+    ("Scroll the tree", "Desplazar el árbol"),
+    // Settings
+    ("Select a setting", "Elegir un ajuste"),
+    ("Change the selected setting", "Cambiar el ajuste elegido"),
+    (
+        "Save changes to the config file",
+        "Guardar los cambios en el archivo de configuración",
+    ),
+    ("Discard unsaved changes", "Descartar los cambios sin guardar"),
+    (
+        "Browse for the file or folder of a path setting",
+        "Buscar el archivo o la carpeta de un ajuste de ruta",
+    ),
+    (
+        "Enter: browse | Ctrl+S: save | Esc: discard changes | ?: Help",
+        "Enter: buscar | Ctrl+S: guardar | Esc: descartar cambios | ?: Ayuda",
+    ),
+    ("Progress file", "Archivo de progreso"),
+    ("Plans folder", "Carpeta de planes"),
+    (
+        "{} (used from the next start)",
+        "{} (se usa desde el próximo inicio)",
+    ),
+    ("Settings (unsaved changes)", "Ajustes (cambios sin guardar)"),
+    (
+        "←/→: change | Enter: save | Esc: discard changes | ?: Help",
+        "←/→: cambiar | Enter: guardar | Esc: descartar cambios | ?: Ayuda",
+    ),
+    ("Daily chapter goal", "Meta diaria de capítulos"),
+    ("Daily reading time", "Tiempo de lectura diario"),
+    ("Color passages by", "Colorear pasajes según"),
+    ("Color gradient", "Degradado de colores"),
+    ("Accessibility mode", "Modo de accesibilidad"),
+    ("{} chapters", "{} capítulos"),
+    ("off", "no"),
+    ("on", "sí"),
+    ("read count", "veces leído"),
+    ("recency", "lectura reciente"),
+    ("custom ({})", "personalizado ({})"),
+    // Quitting
+    ("Retry", "Reintentar"),
+    ("Save As…", "Guardar como…"),
+    ("Save settings", "Guardar ajustes"),
+    ("Quit anyway", "Salir de todos modos"),
+    ("Copy and quit", "Copiar y salir"),
+    ("Cancel", "Cancelar"),
+    ("Couldn't save progress", "No se pudo guardar el progreso"),
+    (
+        "Your reading progress could not be saved:\n{}",
+        "No se pudo guardar tu progreso de lectura:\n{}",
+    ),
+    ("Unsaved settings", "Ajustes sin guardar"),
+    (
+        "You changed settings that haven't been saved to the config file.",
+        "Cambiaste ajustes que no se han guardado en el archivo de configuración.",
+    ),
+    ("Session summary", "Resumen de la sesión"),
+    ("Save as: ", "Guardar como: "),
+    (
+        "Enter: save and quit | Esc: back",
+        "Enter: guardar y salir | Esc: volver",
+    ),
+    (
+        "←/→: choose | Enter: confirm | Esc: cancel",
+        "←/→: elegir | Enter: confirmar | Esc: cancelar",
+    ),
+    // Damaged progress files
+    ("Damaged progress file", "Archivo de progreso dañado"),
+    (
+        "Your reading progress couldn't be loaded:",
+        "No se pudo cargar tu progreso de lectura:",
+    ),
+    (
+        "Restore backup: replace it with {}, kept from before your last session. The damaged file is kept as .corrupt.",
+        "Restaurar copia: reemplazarlo por {}, guardada antes de tu última sesión. El archivo dañado se guarda como .corrupt.",
+    ),
+    (
+        "Open read-only: show whatever can be read, and write what was left out next to the file. Nothing is saved.",
+        "Abrir en solo lectura: mostrar lo que se pueda leer y anotar lo que se omitió junto al archivo. No se guarda nada.",
+    ),
+    (
+        "Restore from…: browse for another copy, e.g. from a sync folder or a backup drive.",
+        "Restaurar desde…: buscar otra copia, p. ej. en una carpeta sincronizada o un disco de respaldo.",
+    ),
+    ("Restore backup", "Restaurar copia"),
+    ("Restore from…", "Restaurar desde…"),
+    ("Open read-only", "Abrir en solo lectura"),
+    ("←/→: choose | Enter: confirm", "←/→: elegir | Enter: confirmar"),
+    // File picker
+    ("Pick a File", "Elegir un archivo"),
+    ("Select a file or folder", "Elegir un archivo o una carpeta"),
+    (
+        "Open the selected folder, or pick the selected file",
+        "Abrir la carpeta elegida, o elegir el archivo seleccionado",
+    ),
+    ("Open the selected folder", "Abrir la carpeta elegida"),
+    ("Go up to the parent folder", "Subir a la carpeta superior"),
+    ("Go to your home folder", "Ir a tu carpeta personal"),
+    ("Show or hide hidden files", "Mostrar u ocultar los archivos ocultos"),
+    (
+        "Jump to the next entry starting with the letter",
+        "Saltar a la siguiente entrada que empieza por la letra",
+    ),
+    ("(pick this folder)", "(elegir esta carpeta)"),
+    ("Couldn't open {}", "No se pudo abrir {}"),
+    (
+        "Enter: open/pick | ←: up | ~: home | .: hidden files | Esc: cancel",
+        "Enter: abrir/elegir | ←: subir | ~: inicio | .: archivos ocultos | Esc: cancelar",
+    ),
+    ("Import readings from", "Importar lecturas desde"),
+    ("Restore from", "Restaurar desde"),
+    // Chapter readings
+    (
+        "Readings of {} {} (press any key to close)",
+        "Lecturas de {} {} (pulsa una tecla para cerrar)",
+    ),
+    ("set to {}x", "fijado en {}x"),
+    ("Not read yet", "Aún sin leer"),
+    ("…and {} earlier", "…y {} anteriores"),
+    // Chapter ticker
+    ("Chapter Ticker", "Contador de capítulos"),
+    (
+        "Where to start (e.g. \"gen 12\"; a book alone starts at its next unread chapter)",
+        "Dónde empezar (p. ej. \"gn 12\"; un libro solo empieza en su siguiente capítulo sin leer)",
+    ),
+    (
+        "Start, then mark the next chapter as read today",
+        "Empezar y luego marcar el siguiente capítulo como leído hoy",
+    ),
+    (
+        "Skip ahead or go back a chapter without marking it",
+        "Saltar o volver un capítulo sin marcarlo",
+    ),
+    ("Close the ticker", "Cerrar el contador"),
+    ("Start at: ", "Empezar en: "),
+    ("Next: ", "Siguiente: "),
+    ("End of the Bible", "Fin de la Biblia"),
+    ("1 chapter marked read today", "1 capítulo marcado como leído hoy"),
+    ("{} chapters marked read today", "{} capítulos marcados como leídos hoy"),
+    (
+        "Chapter ticker (Enter: start, Esc: close)",
+        "Contador de capítulos (Enter: empezar, Esc: cerrar)",
+    ),
+    (
+        "Chapter ticker (Enter: mark read, →/←: skip/back, Esc: close)",
+        "Contador de capítulos (Enter: marcar leído, →/←: saltar/volver, Esc: cerrar)",
+    ),
+    // Command palette
+    ("Search every command", "Buscar entre todas las órdenes"),
+    ("Command Palette", "Paleta de órdenes"),
+    (
+        "Filter the commands (letters in order, e.g. \"rec\")",
+        "Filtrar las órdenes (letras en orden, p. ej. \"reg\")",
+    ),
+    ("Select a command", "Elegir una orden"),
+    ("Run the selected command", "Ejecutar la orden elegida"),
+    ("Close the palette", "Cerrar la paleta"),
+    ("Go to {}", "Ir a {}"),
+    (
+        "Command palette (Enter: run, Esc: close)",
+        "Paleta de órdenes (Enter: ejecutar, Esc: cerrar)",
+    ),
+    // Small terminals
+    ("Terminal too small", "Terminal demasiado pequeña"),
+    ("Need {}x{}, have {}x{}", "Se necesita {}x{}, hay {}x{}"),
+    (
+        "Enlarge the window to continue",
+        "Agranda la ventana para continuar",
+    ),
+    // Notifications
+    ("Achievement unlocked", "Logro desbloqueado"),
+    ("Campaigns", "Campañas"),
+    ("Catch up", "Ponerse al día"),
+    ("Clipboard", "Portapapeles"),
+    ("Config not reloaded", "Configuración no recargada"),
+    ("Config reloaded", "Configuración recargada"),
+    ("Copied", "Copiado"),
+    ("Demo", "Demostración"),
+    ("Goals at risk", "Metas en riesgo"),
+    ("Imported", "Importado"),
+    ("Memory review", "Repaso de memoria"),
+    ("Merged", "Combinado"),
+    ("Plugins", "Complementos"),
+    ("Reading list", "Lista de lectura"),
+    ("Read-only", "Solo lectura"),
+    ("Recorded", "Registrado"),
+    ("Repeat", "Repetir"),
+    ("Rescheduled", "Reprogramado"),
+    ("Restored", "Restaurado"),
+    ("Saving failed", "Error al guardar"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn catalogs_keep_the_placeholders_and_fall_back_to_english() {
+        for language in Language::ALL {
+            let mut seen = HashSet::new();
+            for (english, translation) in language.catalog() {
+                assert!(seen.insert(english), "{:?} twice", english);
+                assert_eq!(
+                    english.matches("{}").count(),
+                    translation.matches("{}").count(),
+                    "{:?}",
+                    english
+                );
+            }
+        }
+        assert_eq!(Language::Spanish.translate("History"), "Historial");
+        assert_eq!(
+            Language::Spanish.translate("Not translated"),
+            "Not translated"
+        );
+        assert_eq!(trf("{}/{} chapters", &[&2, &3]), "2/3 chapters");
+    }
+}
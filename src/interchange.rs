@@ -0,0 +1,264 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::bible_structure::BibleStructure;
+use crate::build_info::build_info;
+use crate::progress::{Campaign, ReadingEvent, ReadingProgress, ReadingSource};
+use crate::reference::unambiguous_book;
+use crate::utils::get_all_books;
+
+/// Identifies an interchange document, so other JSON isn't mistaken for one
+pub const FORMAT: &str = "bible-reading-progress";
+/// Version of the interchange schema written by this build. Readers accept documents of this
+/// version or older; fields added within a version are optional and ignored by older readers.
+pub const VERSION: u32 = 1;
+
+/// A versioned JSON document of reading events, for syncing with other Bible-tracking tools.
+/// The schema is documented in the README under "Syncing With Other Apps".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interchange {
+    pub format: String,
+    pub version: u32,
+    /// Day the document was written
+    pub exported: NaiveDate,
+    /// Build of brp that wrote the document, see [`crate::build_info::BuildInfo::summary`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+    /// Oldest first
+    pub readings: Vec<InterchangeReading>,
+}
+
+/// One reading of a verse range within one chapter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterchangeReading {
+    pub date: NaiveDate,
+    /// English book name, e.g. "1 Corinthians"; unambiguous abbreviations are accepted on
+    /// import
+    pub book: String,
+    pub chapter: u32,
+    pub verse_start: u32,
+    /// Inclusive
+    pub verse_end: u32,
+    /// Times the verses were read, or the read count they were set to with `"mode": "set"`
+    #[serde(default = "one")]
+    pub count: u32,
+    #[serde(default)]
+    pub mode: ReadingMode,
+    #[serde(default)]
+    pub source: ReadingSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Bible translation, e.g. "ESV"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    /// Name of the read-through the reading belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub campaign: Option<String>,
+}
+
+/// How a reading changes the verses' read count
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingMode {
+    /// Adds `count` to the read count
+    #[default]
+    Add,
+    /// Sets the read count to `count`
+    Set,
+}
+
+fn one() -> u32 {
+    1
+}
+
+/// What an import added
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub imported: usize,
+    /// Readings already in the history, e.g. from an earlier sync
+    pub duplicates: usize,
+}
+
+impl From<&ReadingEvent> for InterchangeReading {
+    fn from(event: &ReadingEvent) -> Self {
+        Self {
+            date: event.date,
+            book: event.book.to_string(),
+            chapter: event.chapter,
+            verse_start: event.verse_start,
+            verse_end: event.verse_end,
+            count: event.read_count,
+            mode: if event.overwrite {
+                ReadingMode::Set
+            } else {
+                ReadingMode::Add
+            },
+            source: event.source,
+            note: event.note.clone(),
+            tags: event.tags.clone(),
+            translation: event.translation.clone(),
+            campaign: event.campaign.clone(),
+        }
+    }
+}
+
+/// Every reading in the history, as an interchange document
+pub fn export(progress: &ReadingProgress, today: NaiveDate) -> Interchange {
+    Interchange {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        exported: today,
+        generator: Some(build_info().summary()),
+        readings: progress.history.iter().map(Into::into).collect(),
+    }
+}
+
+/// Parse an interchange document, rejecting other JSON and versions newer than this build's
+pub fn parse(json: &str) -> Result<Interchange, String> {
+    let document: Interchange =
+        serde_json::from_str(json).map_err(|e| format!("Not a readings document: {}", e))?;
+    if document.format != FORMAT {
+        return Err(format!(
+            "Unknown format \"{}\", expected \"{}\"",
+            document.format, FORMAT
+        ));
+    }
+    if document.version > VERSION {
+        return Err(format!(
+            "The document is version {}, but this version of brp reads up to version {}",
+            document.version, VERSION
+        ));
+    }
+    Ok(document)
+}
+
+impl InterchangeReading {
+    /// The reading as a history event, with its book and verses checked against `bible`
+    fn to_event(&self, bible: &BibleStructure) -> Result<ReadingEvent, String> {
+        let book = get_all_books(bible)
+            .into_iter()
+            .find(|book| *book == self.book)
+            .or_else(|| unambiguous_book(bible, &self.book))
+            .ok_or_else(|| format!("Unknown book \"{}\"", self.book))?;
+        let verses = bible
+            .ot
+            .get(&book)
+            .or_else(|| bible.nt.get(&book))
+            .and_then(|chapters| chapters.get((self.chapter as usize).checked_sub(1)?))
+            .ok_or_else(|| format!("{} has no chapter {}", book, self.chapter))?;
+        if self.verse_start == 0 || self.verse_start > self.verse_end || self.verse_end > *verses {
+            return Err(format!(
+                "{} {} has no verses {}-{}",
+                book, self.chapter, self.verse_start, self.verse_end
+            ));
+        }
+        Ok(ReadingEvent {
+            date: self.date,
+            book: book.into(),
+            chapter: self.chapter,
+            verse_start: self.verse_start,
+            verse_end: self.verse_end,
+            read_count: self.count,
+            note: self.note.clone(),
+            tags: self.tags.clone(),
+            source: self.source,
+            translation: self.translation.clone(),
+            campaign: self.campaign.clone(),
+            overwrite: self.mode == ReadingMode::Set,
+        })
+    }
+}
+
+/// Record the document's readings that aren't in the history yet, so syncing the same readings
+/// twice doesn't count them twice. Campaigns the readings belong to are created if missing.
+/// Nothing is recorded if any reading is invalid.
+pub fn import(
+    bible: &BibleStructure,
+    progress: &mut ReadingProgress,
+    document: &Interchange,
+) -> Result<ImportSummary, String> {
+    let events = document
+        .readings
+        .iter()
+        .enumerate()
+        .map(|(idx, reading)| {
+            reading
+                .to_event(bible)
+                .map_err(|e| format!("Reading {}: {}", idx + 1, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Each reading already recorded matches one in the document, so a passage read twice a
+    // day is still imported twice into an empty history
+    let mut unmatched: Vec<&ReadingEvent> = progress.history.iter().collect();
+    let mut new_events = Vec::new();
+    for event in events {
+        match unmatched.iter().position(|recorded| **recorded == event) {
+            Some(idx) => {
+                unmatched.swap_remove(idx);
+            }
+            None => new_events.push(event),
+        }
+    }
+
+    let summary = ImportSummary {
+        imported: new_events.len(),
+        duplicates: document.readings.len() - new_events.len(),
+    };
+    for event in new_events {
+        if let Some(name) = &event.campaign {
+            if !progress.campaigns.iter().any(|c| c.name == *name) {
+                progress.campaigns.push(Campaign {
+                    name: name.clone(),
+                    started: event.date,
+                });
+            }
+        }
+        progress.replay(event);
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn export_round_trips_through_import() {
+        let bible = get_bible_structure();
+        let day = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let mut progress = ReadingProgress::new();
+        progress.start_campaign("2025", day).unwrap();
+        progress.record_reading_on("John", 3, 1, 36, 1, day);
+        progress.record_reading_on("John", 3, 1, 36, 1, day);
+        progress.record_reading_overwrite("Ruth", 1, 1, 22, 4, Some(day));
+        progress.annotate_since(0, |event| event.translation = Some("ESV".to_string()));
+
+        let json = serde_json::to_string(&export(&progress, day)).unwrap();
+        let document = parse(&json).unwrap();
+        assert_eq!(document.readings[2].mode, ReadingMode::Set);
+
+        let mut copy = ReadingProgress::new();
+        let summary = import(bible, &mut copy, &document).unwrap();
+        assert_eq!(summary.imported, 3);
+        assert_eq!(copy.history, progress.history);
+        assert_eq!(copy.books, progress.books);
+        assert_eq!(copy.campaigns[0].name, "2025");
+
+        // Syncing again adds nothing
+        let summary = import(bible, &mut copy, &document).unwrap();
+        assert_eq!(summary.imported, 0);
+        assert_eq!(summary.duplicates, 3);
+
+        let newer = json.replace("\"version\":1", "\"version\":2");
+        assert!(parse(&newer).unwrap_err().contains("version 2"));
+        let invalid = json.replace("\"chapter\":3", "\"chapter\":30");
+        assert_eq!(
+            import(bible, &mut copy, &parse(&invalid).unwrap()),
+            Err("Reading 1: John has no chapter 30".to_string())
+        );
+    }
+}
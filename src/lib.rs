@@ -1,6 +1,86 @@
+//! Tracks Bible reading: which verses have been read, how often and when, with statistics,
+//! reading plans and reference parsing built on top. This is the core of the `brp` app, usable
+//! by other tools without its terminal interface.
+//!
+//! ```
+//! use bible_reading_progress::{get_bible_structure, parse_passage, percent_read};
+//! use bible_reading_progress::{BookFilter, ReadingProgress};
+//!
+//! let bible = get_bible_structure();
+//! let mut progress = ReadingProgress::new();
+//! let passage = parse_passage(bible, "Jude").unwrap();
+//! for (chapter, first, last) in passage.chapter_ranges(bible) {
+//!     progress.record_reading(&passage.book, chapter, first, last);
+//! }
+//! assert!(percent_read(bible, &progress, BookFilter::NewTestament) > 0.0);
+//! ```
+//!
+//! Nothing in the library prints; errors are returned to the caller. The `tui` feature, on by
+//! default, adds the terminal app's widgets and the `brp` binary; build with
+//! `default-features = false` for just the library.
+
+pub mod achievements;
+pub mod app_core;
+pub mod audio;
+pub mod badge;
 pub mod bible_structure;
+pub mod book_name;
+pub mod build_info;
+pub mod chart;
+pub mod clipboard;
+pub mod compare;
 pub mod config;
+pub mod coverage;
+#[cfg(feature = "daemon")]
+pub mod daemon;
+pub mod date_range;
+pub mod demo;
+#[cfg(feature = "tui")]
+pub mod events;
+pub mod group;
+pub mod hook;
+pub mod i18n;
+pub mod interchange;
+#[cfg(feature = "tui")]
+pub mod logging;
+pub mod memorize;
+pub mod merge;
+pub mod milestones;
+pub mod overview;
+pub mod pace;
+pub mod plans;
+pub mod plugins;
 pub mod progress;
 pub mod range_query;
+pub mod reference;
+pub mod review;
+pub mod salvage;
+#[cfg(feature = "fs")]
+pub mod save_worker;
+pub mod scripture;
+pub mod stats;
+pub mod storage;
+pub mod today;
+#[cfg(feature = "tui")]
+pub mod ui_state;
 pub mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod weekly_report;
+#[cfg(feature = "tui")]
 pub mod widgets;
+pub mod year_review;
+
+pub use bible_structure::{get_bible_structure, BibleStructure, BookFilter, Genre};
+pub use book_name::BookName;
+pub use coverage::{
+    book_min_read_count, book_percent_read, chapter_read_count, next_unread_chapter, percent_read,
+};
+pub use plans::{Plan, PlanId, PlanItem, PlanKind, PlanState};
+pub use progress::{
+    Campaign, InsideBookBibleReference, ReadingEvent, ReadingProgress, ReadingRecord,
+    ReadingSession, ReadingSource,
+};
+pub use range_query::RangeMap;
+pub use reference::{parse_passage, parse_reference, resolve_book, Passage, Reference};
+pub use stats::{current_streak, longest_streak, neglected_books, reading_days, Goal};
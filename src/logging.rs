@@ -0,0 +1,68 @@
+//! The app's log file, for diagnosing slowness and sync problems users report. The library
+//! records spans around loading and saving, building the dashboard tree, parsing references
+//! and merging; the app writes them to a file, since the terminal belongs to the interface.
+
+use std::path::PathBuf;
+
+use color_eyre::Result;
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Days of logs kept; older files are deleted as the log rotates
+const KEPT_LOG_FILES: usize = 7;
+
+/// `brp.<date>.log` files in the `logs` folder of the data directory
+pub fn log_dir() -> Option<PathBuf> {
+    Some(
+        dirs::data_dir()?
+            .join("bible-reading-progress")
+            .join("logs"),
+    )
+}
+
+/// Most detailed level logged: info and above by default, debug with `--verbose` and trace
+/// with it twice
+pub fn level(verbosity: u8) -> Level {
+    match verbosity {
+        0 => Level::INFO,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// Send everything logged at `level(verbosity)` or above to today's log file. Log lines are
+/// written from a background thread until the returned guard is dropped.
+pub fn init(verbosity: u8) -> Result<WorkerGuard> {
+    let dir = log_dir().ok_or_else(|| color_eyre::eyre::eyre!("Failed to get data directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("brp")
+        .filename_suffix("log")
+        .max_log_files(KEPT_LOG_FILES)
+        .build(dir)?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        // A line as each span closes, with how long it took
+        .with_span_events(FmtSpan::CLOSE)
+        .with_max_level(level(verbosity))
+        .try_init()
+        .map_err(|e| color_eyre::eyre::eyre!(e))?;
+    Ok(guard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbose_raises_the_level() {
+        assert_eq!(level(0), Level::INFO);
+        assert_eq!(level(1), Level::DEBUG);
+        assert_eq!(level(3), Level::TRACE);
+    }
+}
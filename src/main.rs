@@ -1,162 +1,1677 @@
-use clap::Parser;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::thread::{self, JoinHandle};
+use std::time::{Instant, SystemTime};
+
+use chrono::{Datelike, NaiveDate, Utc};
+use clap::{Parser, Subcommand};
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::event::{
+    DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+};
+use crossterm::execute;
 use ratatui::prelude::*;
 
-use bible_reading_progress::bible_structure::get_bible_structure;
+use bible_reading_progress::app_core::{Action, AppCore, Effect};
+use bible_reading_progress::audio::{audio_queue, queue_to_m3u};
+use bible_reading_progress::badge::{badge_color, badge_svg, badge_value};
+use bible_reading_progress::bible_structure::{get_bible_structure, BibleStructure, BookFilter};
+use bible_reading_progress::build_info::build_info;
+use bible_reading_progress::chart::chart_svg;
+use bible_reading_progress::clipboard::copy_to_clipboard;
 use bible_reading_progress::config::Config;
-use bible_reading_progress::progress::ReadingProgress;
-use bible_reading_progress::utils::{load_progress, save_progress};
-use bible_reading_progress::widgets::dashboard::{DashboardAction, DashboardWidget};
-use bible_reading_progress::widgets::manual_add::{ManualAddAction, ManualAddWidget};
-use bible_reading_progress::widgets::record::{RecordAction, RecordWidget};
+use bible_reading_progress::coverage::{least_read_verses, percent_read};
+use bible_reading_progress::date_range::DateRange;
+use bible_reading_progress::demo::demo_progress;
+use bible_reading_progress::events::{AppEvent, EventHandler, TICK_RATE};
+use bible_reading_progress::hook::HookPayload;
+use bible_reading_progress::i18n;
+use bible_reading_progress::interchange;
+use bible_reading_progress::logging;
+use bible_reading_progress::memorize::{due_cards, MemoryCard};
+use bible_reading_progress::merge::{find_conflicts, merge_progress};
+use bible_reading_progress::milestones::Milestone;
+use bible_reading_progress::overview::stats_overview;
+use bible_reading_progress::plans::{import_plan_file, shared_plan_file, today_lines, PlanState};
+use bible_reading_progress::plugins::Plugins;
+use bible_reading_progress::progress::{ReadingEvent, ReadingProgress};
+use bible_reading_progress::review::review_suggestions;
+use bible_reading_progress::save_worker::{backup_path, SaveWorker};
+use bible_reading_progress::scripture::ScriptureText;
+use bible_reading_progress::stats::{
+    current_streak, evaluate_goals, neglected_books, reading_days,
+};
+use bible_reading_progress::storage::UnparsedHistory;
+use bible_reading_progress::today::{balanced_items, today_items, TodayItem};
+use bible_reading_progress::ui_state::UiState;
+use bible_reading_progress::utils::{
+    get_all_books, get_progress_file_path, load_progress, load_progress_lazily, load_ui_state,
+    quick_books, restore_progress_from, salvage_progress, save_progress, save_progress_to,
+    save_ui_state,
+};
+use bible_reading_progress::weekly_report::WeeklyReport;
+use bible_reading_progress::widgets::accessibility::strip_styles;
+use bible_reading_progress::widgets::batch_menu::BatchAction;
+use bible_reading_progress::widgets::chapter_grid;
+use bible_reading_progress::widgets::chapter_readings::ChapterReadings;
+use bible_reading_progress::widgets::chapter_ticker::{self, ChapterTicker, TickerAction};
+use bible_reading_progress::widgets::command_palette::{
+    self, CommandPalette, PaletteAction, PaletteCommand,
+};
+use bible_reading_progress::widgets::dashboard::{self, DashboardAction, DashboardWidget};
+use bible_reading_progress::widgets::entry::{self, EntryAction, EntryWidget, WriteMode};
+use bible_reading_progress::widgets::file_picker::{self, FilePicker, FilePickerAction, PickKind};
+use bible_reading_progress::widgets::help::{render_help, KeyBinding};
+use bible_reading_progress::widgets::history::{self, HistoryAction, HistoryWidget};
+use bible_reading_progress::widgets::import::{self, ImportAction, ImportWidget};
+use bible_reading_progress::widgets::journal;
+use bible_reading_progress::widgets::layout::{is_too_small, render_too_small};
+use bible_reading_progress::widgets::memorize::{
+    self, MemoryReview, MemoryReviewAction, ReviewCard,
+};
+use bible_reading_progress::widgets::merge::{self, MergeAction, MergeWidget};
+use bible_reading_progress::widgets::milestones::render_milestones;
+use bible_reading_progress::widgets::plans::{self, PlansAction, PlansWidget};
+use bible_reading_progress::widgets::quit_dialog::{QuitDialog, QuitDialogAction, QuitReason};
+use bible_reading_progress::widgets::recovery::{RecoveryAction, RecoveryDialog};
+use bible_reading_progress::widgets::settings::{self, SettingsAction, SettingsWidget};
+use bible_reading_progress::widgets::stats::{self, StatsWidget};
+use bible_reading_progress::widgets::tabs::{self, render_tabs, Tab};
+use bible_reading_progress::widgets::text_area;
+use bible_reading_progress::widgets::text_input;
+use bible_reading_progress::widgets::toast::Toast;
+use bible_reading_progress::widgets::today::{self, TodayAction, TodayPanel};
+use bible_reading_progress::widgets::year_review::render_year_review;
+use bible_reading_progress::year_review::YearReview;
 
 #[derive(Parser, Debug)]
 #[command(name = "brp")]
 #[command(about = "Bible Reading Progress Tracker", long_about = None)]
 struct Args {
+    /// Print the version; with --verbose, also the commit, build date, features and canon
+    /// checksum, for comparing the builds on different devices
+    #[arg(short = 'V', long)]
+    version: bool,
     /// Display the loaded configuration and exit
     #[arg(long)]
     show_config: bool,
+    /// Explore the app with made-up progress instead of yours; nothing is saved
+    #[arg(long)]
+    demo: bool,
+    /// Log more detail to the log file; twice for everything
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Reading plan commands
+    Plan {
+        #[command(subcommand)]
+        command: PlanCommand,
+    },
+    /// Print the chapters to read next, as in the Today panel
+    Next {
+        /// List the chapters most due for re-reading instead
+        #[arg(long)]
+        review: bool,
+        /// List a balanced mix instead: one chapter each of Old Testament narrative, Psalms
+        /// & wisdom, and the New Testament, the least read first
+        #[arg(long, conflicts_with = "review")]
+        balanced: bool,
+        /// Number of chapters to list with --review
+        #[arg(short = 'n', long, default_value_t = 5)]
+        count: usize,
+    },
+    /// Stay running and send a desktop notification at `reminder_time` if today's reading
+    /// hasn't been recorded yet
+    #[cfg(feature = "daemon")]
+    Daemon,
+    /// Named read-throughs, each tracking its own coverage
+    Campaign {
+        #[command(subcommand)]
+        command: CampaignCommand,
+    },
+    /// Print your streak and how much of the Bible you have read
+    Stats {
+        /// List the books not read in a while instead, to nudge balanced reading
+        #[arg(long)]
+        neglected: bool,
+        /// Days without a reading after which a book is neglected; defaults to
+        /// `neglected_days` from the config
+        #[arg(long, requires = "neglected")]
+        days: Option<u32>,
+    },
+    /// Print a Markdown summary of a year of reading
+    Review {
+        /// Year to look back on; defaults to last year
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    /// Write a dated Markdown summary of the week, for a cron job to file away or mail
+    Report {
+        /// Summarize the seven days ending today: chapters, streak and plan progress
+        #[arg(long, required = true)]
+        weekly: bool,
+        /// Folder to write the report to, named after the week's last day, instead of
+        /// printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Also mail the report to this address with `sendmail`
+        #[arg(long)]
+        email: Option<String>,
+    },
+    /// Write the next unread chapters as a playlist of audio files named by
+    /// `audio_file_pattern`, for an audio player to play what you have left
+    Queue {
+        /// Number of chapters to queue
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Write a JSON list of the chapters instead of an M3U playlist; the default when the
+        /// output file ends in .json
+        #[arg(long)]
+        json: bool,
+        /// File to write the queue to, instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Write an SVG badge of your progress (e.g. "Bible | 64% · NT ×2 · 21-day streak"), for
+    /// a website or README
+    Badge {
+        /// File to write the badge to, instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Text on the left of the badge
+        #[arg(long, default_value = "Bible")]
+        label: String,
+    },
+    /// Write a printable SVG chart with a box for every chapter, shaded by how many times it
+    /// has been read
+    Chart {
+        /// File to write the chart to, instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Books to include: all, ot, nt, or a genre such as gospels; defaults to the
+        /// dashboard's filter
+        #[arg(long, value_parser = parse_book_filter)]
+        books: Option<BookFilter>,
+    },
+    /// Write every reading as a versioned JSON document, for other Bible-tracking tools
+    Export {
+        /// File to write the readings to, instead of printing them
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Record the readings of a JSON document written by `brp export`, skipping those
+    /// already recorded
+    Import { file: PathBuf },
+    /// Merge another copy of the progress file, such as a sync conflict copy from another
+    /// device, choosing which side wins for books both read differently
+    Merge { file: PathBuf },
+}
+
+fn parse_book_filter(input: &str) -> Result<BookFilter, String> {
+    BookFilter::parse(input).ok_or_else(|| {
+        format!(
+            "expected all, ot, nt or a genre (e.g. gospels), not {}",
+            input
+        )
+    })
 }
 
-enum AppMode {
-    Dashboard(DashboardWidget),
-    Record(RecordWidget),
-    ManualAdd(ManualAddWidget),
+#[derive(Subcommand, Debug)]
+enum CampaignCommand {
+    /// Start a read-through and record new readings for it
+    Start {
+        /// e.g. "2025 canonical read-through"
+        name: String,
+    },
+    /// List the campaigns with how much of the Bible each has covered
+    List,
+    /// Record new readings for another campaign, or for lifetime totals only without a name
+    Switch { name: Option<String> },
+}
+
+#[derive(Subcommand, Debug)]
+enum PlanCommand {
+    /// Print today's chapters of the plan being followed
+    Today,
+    /// Write the plan being followed, as rescheduled, to a plan file dated day by day, for
+    /// others to import
+    Export {
+        /// File to write the plan to, instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Add a plan file someone shared to the plans directory and start following it
+    Import { file: PathBuf },
+}
+
+/// What a path picked with the file picker is for
+enum PickTarget {
+    /// The selected path setting
+    Setting,
+    /// Lines to import
+    ImportFile,
+    /// A copy of the damaged progress file to restore
+    RestoreFrom,
 }
 
 struct App {
     running: bool,
-    mode: AppMode,
+    tab: Tab,
+    dashboard: DashboardWidget,
+    /// Reading entry form, open on top of the dashboard tab
+    entry: Option<EntryWidget>,
+    /// Form for importing readings from text, open on top of the dashboard tab
+    import: Option<ImportWidget>,
+    plans: PlansWidget,
+    history: HistoryWidget,
+    stats: StatsWidget,
+    settings: SettingsWidget,
     bible: &'static bible_reading_progress::bible_structure::BibleStructure,
-    progress: ReadingProgress,
+    /// The progress and what actions do to it; this struct only shows it
+    core: AppCore<'static>,
+    /// Readings of the active campaign or of the dashboard's date range, which the dashboard
+    /// shows instead of the lifetime totals
+    layer: Option<ReadingProgress>,
     config: Config,
+    /// The post-save hook and the plugins from the config
+    plugins: Plugins,
+    /// The plugins' latest stats lines, shown on the Stats tab until they are run again
+    plugin_stats: Vec<(String, String)>,
+    ui_state: UiState,
+    show_help: bool,
+    /// Shown instead of quitting when saving failed or settings are unsaved
+    quit_dialog: Option<QuitDialog>,
+    /// Today's suggested readings, shown over the dashboard
+    today_panel: Option<TodayPanel>,
+    /// Review of the memorized passages that are due, shown over the dashboard
+    memory_review: Option<MemoryReview>,
+    /// Every command, for finding one by name; opened with Ctrl+P
+    command_palette: Option<CommandPalette>,
+    /// Highlights of a year of reading, shown until a key is pressed
+    year_review: Option<YearReview>,
+    /// A chapter's latest readings, shown until a key is pressed
+    chapter_readings: Option<ChapterReadings>,
+    /// Marks consecutive chapters read, one per Enter, shown over the dashboard
+    chapter_ticker: Option<ChapterTicker>,
+    /// Milestones the last save reached, celebrated until a key is pressed
+    milestones: Vec<Milestone>,
+    /// Books another progress file read differently, and that file's progress, shown until
+    /// the user merges or cancels
+    merge: Option<(MergeWidget, ReadingProgress)>,
+    /// Notification for newly unlocked achievements, cleared once it expires
+    toast: Option<Toast>,
+    /// For reporting back from background work, such as the plugins; set once the
+    /// event loop runs
+    event_sender: Option<Sender<AppEvent>>,
+    /// Writes the progress file off the render thread; started with the event loop
+    saver: Option<SaveWorker>,
+    /// The reading history, left unparsed so the dashboard shows right away; parsed on another
+    /// thread once the event loop runs
+    unparsed_history: Option<UnparsedHistory>,
+    /// Parsing the reading history. Input waits for it, so nothing is recorded or saved
+    /// without the history.
+    history_loader: Option<JoinHandle<Result<Vec<ReadingEvent>>>>,
+    /// Showing made-up progress: nothing is saved, and plugins aren't run
+    demo: bool,
+    /// Shown over an empty dashboard when the progress file couldn't be loaded
+    recovery: Option<RecoveryDialog>,
+    /// Browsing for a path, shown over everything else
+    file_picker: Option<(FilePicker, PickTarget)>,
+    /// Nothing is saved and plugins aren't told of readings: the progress is made up, only
+    /// what could be read of a damaged file, or not loaded yet
+    read_only: bool,
+    /// When the config file was changed as of the last (re)load, to reload it once it's edited
+    config_modified: Option<SystemTime>,
 }
 
 impl App {
-    fn new_with_config(config: Config) -> Result<Self> {
+    #[tracing::instrument(skip_all)]
+    fn new_with_config(config: Config, demo: bool) -> Result<Self> {
         let bible = get_bible_structure();
-        let progress = load_progress(&config)?;
-        let dashboard = DashboardWidget::new(bible, &progress);
+        let today = Utc::now().date_naive();
+        let ui_state = if demo {
+            UiState::default()
+        } else {
+            load_ui_state(&config)
+        };
+        let date_range = ui_state.dashboard.date_range;
+        let loaded = if demo {
+            Ok((demo_progress(bible, today), None))
+        } else {
+            load_progress_lazily(&config).and_then(|(mut progress, mut unparsed_history)| {
+                // The dashboard of a campaign or date range is worked out from the history
+                if progress.active_campaign.is_some() || date_range.is_some() {
+                    if let Some(history) = unparsed_history.take() {
+                        progress.history = history.parse()?;
+                    }
+                }
+                Ok((progress, unparsed_history))
+            })
+        };
+        let (progress, unparsed_history, recovery) = match loaded {
+            Ok((progress, unparsed_history)) => (progress, unparsed_history, None),
+            Err(e) => {
+                tracing::error!("progress not loaded: {}", e);
+                let recovery = recovery_dialog(&config, e.to_string());
+                (ReadingProgress::new(), None, Some(recovery))
+            }
+        };
+        let plugins = if demo {
+            Plugins::default()
+        } else {
+            Plugins::from_config(&config)
+        };
+        let layer = dashboard_layer(&progress, date_range);
+        let mut dashboard =
+            DashboardWidget::new(bible, layer.as_ref().unwrap_or(&progress), &config);
+        dashboard.set_filter(ui_state.dashboard.filter);
+        dashboard.restore_ui_state(&ui_state.dashboard);
 
-        Ok(Self {
+        let mut app = Self {
+            milestones: Vec::new(),
+            merge: None,
+            event_sender: None,
+            saver: None,
+            history_loader: None,
+            config_modified: config.modified(),
             running: true,
-            mode: AppMode::Dashboard(dashboard),
+            tab: Tab::Dashboard,
+            dashboard,
+            entry: None,
+            import: None,
+            plans: PlansWidget::new(bible, &progress, &config.plans_dir, today),
+            history: HistoryWidget::new(bible, &progress),
+            // The plugins' lines are added when the Stats tab is opened
+            stats: StatsWidget::new(bible, &progress, &config),
+            settings: SettingsWidget::new(&config),
             bible,
-            progress,
+            core: AppCore::new(bible, progress, &config, today),
+            layer,
             config,
-        })
+            plugins,
+            plugin_stats: Vec::new(),
+            ui_state,
+            show_help: false,
+            quit_dialog: None,
+            today_panel: None,
+            memory_review: None,
+            command_palette: None,
+            chapter_readings: None,
+            chapter_ticker: None,
+            year_review: None,
+            toast: None,
+            unparsed_history,
+            demo,
+            read_only: demo || recovery.is_some(),
+            recovery,
+            file_picker: None,
+        };
+        if app.unparsed_history.is_none() && app.recovery.is_none() {
+            app.greet();
+        }
+        if demo {
+            app.toast = Some(Toast::new(
+                "Demo",
+                vec!["Made-up readings; nothing you do is saved".to_string()],
+            ));
+        }
+        Ok(app)
     }
 
-    fn run(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+    /// Things shown over the dashboard at startup that need the reading history: the year in
+    /// review in January, and goals at risk
+    fn greet(&mut self) {
+        let progress = self.core.progress();
+        let today = self.core.today();
+        // In January, look back on the year that just ended, once
+        let last_year = today.year() - 1;
+        if today.month() == 1
+            && self.ui_state.year_review_shown != Some(last_year)
+            && progress
+                .history
+                .iter()
+                .any(|event| event.date.year() == last_year)
+        {
+            self.ui_state.year_review_shown = Some(last_year);
+            self.year_review = Some(YearReview::new(self.bible, &progress.history, last_year));
+        }
+        let goals_at_risk: Vec<String> =
+            evaluate_goals(self.bible, progress, &self.config.goals, today)
+                .into_iter()
+                .filter_map(|goal| goal.warning)
+                .collect();
+        if !goals_at_risk.is_empty() {
+            self.toast = Some(Toast::new("Goals at risk", goals_at_risk));
+        }
+    }
+
+    /// Wait for the reading history if it's still being parsed, then show it where the
+    /// dashboard uses it (the streak and activity) and greet with it
+    fn finish_loading_history(&mut self) -> Result<()> {
+        let Some(loader) = self.history_loader.take() else {
+            return Ok(());
+        };
+        let history = match loader
+            .join()
+            .map_err(|_| color_eyre::eyre::eyre!("Parsing the reading history panicked"))?
+        {
+            Ok(history) => history,
+            Err(e) => {
+                tracing::error!("reading history not loaded: {}", e);
+                self.read_only = true;
+                self.recovery = Some(recovery_dialog(&self.config, e.to_string()));
+                return Ok(());
+            }
+        };
+        self.core.load_history(history);
+        self.dashboard.refresh_day(self.core.progress());
+        self.greet();
+        Ok(())
+    }
+
+    fn run(&mut self, terminal: &mut Terminal<impl Backend>, started: Instant) -> Result<()> {
+        let events = EventHandler::new(TICK_RATE);
+        self.event_sender = Some(events.sender());
+        if let Some(history) = self.unparsed_history.take() {
+            let sender = events.sender();
+            self.history_loader = Some(thread::spawn(move || {
+                let history = history.parse();
+                let _ = sender.send(AppEvent::HistoryParsed);
+                history
+            }));
+        }
+        if !self.demo {
+            let sender = events.sender();
+            self.saver = Some(SaveWorker::spawn(
+                get_progress_file_path(&self.config),
+                move |e| {
+                    let _ = sender.send(AppEvent::SaveFailed(e));
+                },
+            ));
+        }
+        terminal.draw(|frame| self.render(frame))?;
+        tracing::info!("first frame drawn {:?} after starting", started.elapsed());
         while self.running {
-            terminal.draw(|frame| self.render(frame))?;
-            self.handle_events()?;
+            match events.next()? {
+                AppEvent::Tick => self.tick()?,
+                AppEvent::Terminal(event) => {
+                    self.finish_loading_history()?;
+                    self.handle_event(event)?;
+                }
+                AppEvent::HistoryParsed => self.finish_loading_history()?,
+                AppEvent::PluginStats(lines) => {
+                    self.stats.set_plugin_stats(lines.clone());
+                    self.plugin_stats = lines;
+                }
+                AppEvent::PluginFailed(e) => {
+                    tracing::warn!("plugin failed: {}", e);
+                    self.toast = Some(Toast::new("Plugins", vec![e]));
+                }
+                AppEvent::SaveFailed(e) => {
+                    tracing::error!("save failed: {}", e);
+                    self.toast = Some(Toast::new("Saving failed", vec![e]));
+                }
+            }
+            if self.running {
+                terminal.draw(|frame| self.render(frame))?;
+            }
         }
         Ok(())
     }
 
+    /// Periodic housekeeping between input events
+    fn tick(&mut self) -> Result<()> {
+        // "Today", the streak in the status bar, the activity sparkline and the Today panel
+        // change at midnight
+        let today = Utc::now().date_naive();
+        if today != self.core.today() {
+            self.apply(Action::NewDay(today))?;
+            self.dashboard
+                .refresh_day(self.layer.as_ref().unwrap_or(self.core.progress()));
+            let items = self.today_items();
+            if let Some(today_panel) = self.today_panel.as_mut() {
+                today_panel.set_items(items);
+            }
+            self.plans.refresh(self.bible, self.core.progress(), today);
+        }
+        if self.toast.as_ref().is_some_and(Toast::is_expired) {
+            self.toast = None;
+        }
+        let config_modified = self.config.modified();
+        if config_modified != self.config_modified {
+            self.config_modified = config_modified;
+            self.reload_config();
+        }
+        Ok(())
+    }
+
+    /// Apply the config file after it was edited: colors, goals, pinned books, plans, plugins
+    /// and so on. The progress and UI state files stay where they were until the app restarts.
+    fn reload_config(&mut self) {
+        let mut config = match self.config.reload() {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!("config not reloaded: {}", e);
+                self.toast = Some(Toast::new(
+                    "Config not reloaded",
+                    vec![format!(
+                        "{}: {}",
+                        self.config.config_file_path().display(),
+                        e
+                    )],
+                ));
+                return;
+            }
+        };
+        config.progress_path = self.config.progress_path.clone();
+        config.ui_state_path = self.config.ui_state_path.clone();
+        self.config = config;
+        i18n::set_language(self.config.language);
+        if !self.demo {
+            self.plugins = Plugins::from_config(&self.config);
+        }
+        self.reconfigure();
+        // Keep settings being edited
+        if !self.settings.has_unsaved_changes() {
+            self.settings = SettingsWidget::new(&self.config);
+        }
+        self.rebuild_dashboard();
+        tracing::info!("config reloaded");
+        self.toast = Some(Toast::new(
+            "Config reloaded",
+            vec![self.config.config_file_path().display().to_string()],
+        ));
+    }
+
+    /// Take up the plans directory and plugins of the config, after the settings or an edit
+    /// changed it
+    fn reconfigure(&mut self) {
+        self.core.reconfigure(&self.config);
+        self.plans = PlansWidget::new(
+            self.bible,
+            self.core.progress(),
+            &self.config.plans_dir,
+            self.core.today(),
+        );
+    }
+
+    /// Name of the current screen and the keybindings it supports, for the help overlay
+    fn help(&self) -> (&'static str, Vec<KeyBinding>) {
+        if self.file_picker.is_some() {
+            return ("Pick a File", file_picker::KEYBINDINGS.to_vec());
+        }
+        if self.command_palette.is_some() {
+            return ("Command Palette", command_palette::KEYBINDINGS.to_vec());
+        }
+        if self.merge.is_some() {
+            return ("Merge", merge::KEYBINDINGS.to_vec());
+        }
+        if self.today_panel.is_some() {
+            return ("Today", today::KEYBINDINGS.to_vec());
+        }
+        if self.memory_review.is_some() {
+            return ("Memory Review", memorize::KEYBINDINGS.to_vec());
+        }
+        if self.chapter_ticker.is_some() {
+            return (
+                "Chapter Ticker",
+                [chapter_ticker::KEYBINDINGS, text_input::KEYBINDINGS].concat(),
+            );
+        }
+        if self.import.is_some() {
+            return (
+                "Import",
+                [import::KEYBINDINGS, text_input::KEYBINDINGS].concat(),
+            );
+        }
+        let (name, bindings) = match (&self.entry, self.tab) {
+            (Some(entry), _) => {
+                return (
+                    entry.title(),
+                    [entry::KEYBINDINGS, text_input::KEYBINDINGS].concat(),
+                )
+            }
+            (None, Tab::History) if self.history.is_capturing_input() => {
+                return (
+                    "Journal",
+                    [
+                        journal::KEYBINDINGS,
+                        text_area::KEYBINDINGS,
+                        text_input::KEYBINDINGS,
+                    ]
+                    .concat(),
+                )
+            }
+            (None, Tab::Dashboard) if self.dashboard.is_showing_chapter_grid() => {
+                ("Chapter Grid", chapter_grid::KEYBINDINGS)
+            }
+            (None, Tab::Dashboard) => ("Dashboard", dashboard::KEYBINDINGS),
+            (None, Tab::Plans) => ("Plans", plans::KEYBINDINGS),
+            (None, Tab::History) => ("History", history::KEYBINDINGS),
+            (None, Tab::Stats) => ("Stats", stats::KEYBINDINGS),
+            (None, Tab::Settings) => ("Settings", settings::KEYBINDINGS),
+        };
+        (name, [bindings, tabs::KEYBINDINGS].concat())
+    }
+
     fn render(&mut self, frame: &mut Frame) {
-        match &mut self.mode {
-            AppMode::Dashboard(dashboard) => dashboard.render(frame),
-            AppMode::Record(record) => record.render(frame),
-            AppMode::ManualAdd(manual_add) => manual_add.render(frame),
+        if is_too_small(frame.area()) {
+            render_too_small(frame, frame.area());
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(frame.area());
+        render_tabs(frame, chunks[0], self.tab);
+
+        match (&mut self.entry, self.tab) {
+            _ if self.import.is_some() => {
+                if let Some(import) = self.import.as_mut() {
+                    import.render(frame, chunks[1]);
+                }
+            }
+            (Some(entry), _) => entry.render(frame, chunks[1]),
+            (None, Tab::Dashboard) => self.dashboard.render(frame, chunks[1]),
+            (None, Tab::History) => self.history.render(frame, chunks[1]),
+            (None, Tab::Stats) => self.stats.render(frame, chunks[1]),
+            (None, Tab::Plans) => self.plans.render(frame, chunks[1]),
+            (None, Tab::Settings) => self.settings.render(frame, chunks[1]),
+        }
+
+        if let Some((merge, _)) = self.merge.as_mut() {
+            merge.render(frame);
+        }
+        if let Some(today_panel) = self.today_panel.as_mut() {
+            today_panel.render(frame);
+        }
+        if let Some(memory_review) = &self.memory_review {
+            memory_review.render(frame);
         }
+        if let Some(chapter_ticker) = &self.chapter_ticker {
+            chapter_ticker.render(frame);
+        }
+        if let Some(year_review) = &self.year_review {
+            render_year_review(frame, year_review);
+        }
+        if let Some(chapter_readings) = &self.chapter_readings {
+            chapter_readings.render(frame, self.bible);
+        }
+        if !self.milestones.is_empty() {
+            render_milestones(frame, &self.milestones);
+        }
+        if let Some(command_palette) = self.command_palette.as_mut() {
+            command_palette.render(frame);
+        }
+        if let Some(toast) = &self.toast {
+            toast.render(frame);
+        }
+        if let Some(quit_dialog) = &self.quit_dialog {
+            quit_dialog.render(frame);
+        }
+        if let Some(recovery) = &self.recovery {
+            recovery.render(frame);
+        }
+        if let Some((file_picker, _)) = self.file_picker.as_mut() {
+            file_picker.render(frame);
+        }
+        if self.show_help {
+            let (name, bindings) = self.help();
+            render_help(frame, name, &bindings);
+        }
+        if self.config.accessibility_mode {
+            strip_styles(frame.buffer_mut());
+        }
+    }
+
+    /// True when keys should go straight to the current screen, e.g. while typing into a form
+    fn captures_text_input(&self) -> bool {
+        self.entry.is_some()
+            || self.import.is_some()
+            || self.command_palette.is_some()
+            || self
+                .chapter_ticker
+                .as_ref()
+                .is_some_and(ChapterTicker::is_capturing_input)
+            || (self.tab == Tab::Dashboard && self.dashboard.is_capturing_input())
+            || (self.tab == Tab::History && self.history.is_capturing_input())
     }
 
-    fn handle_events(&mut self) -> Result<()> {
-        match event::read()? {
-            Event::Key(key) if key.kind == KeyEventKind::Press => match &mut self.mode {
-                AppMode::Dashboard(dashboard) => {
-                    let action = dashboard.handle_key(key);
-                    self.handle_dashboard_action(action);
+    fn handle_event(&mut self, event: Event) -> Result<()> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.show_help => {
+                // Any key closes the help overlay
+                self.show_help = false;
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.file_picker.is_some() => {
+                if key.code == KeyCode::Char('?') {
+                    self.show_help = true;
+                } else if let Some((file_picker, _)) = self.file_picker.as_mut() {
+                    let action = file_picker.handle_key(key);
+                    self.handle_file_picker_action(action);
                 }
-                AppMode::Record(record) => {
-                    let action = record.handle_key(key, self.bible)?;
-                    match action {
-                        RecordAction::None => {}
-                        RecordAction::Cancel => {
-                            self.dashboard_mode();
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.recovery.is_some() => {
+                if let Some(recovery) = self.recovery.as_mut() {
+                    let action = recovery.handle_key(key);
+                    self.handle_recovery_action(action);
+                }
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.quit_dialog.is_some() => {
+                if let Some(quit_dialog) = self.quit_dialog.as_mut() {
+                    let action = quit_dialog.handle_key(key);
+                    self.handle_quit_dialog_action(action);
+                }
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.year_review.is_some() => {
+                self.year_review = None;
+            }
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press && self.chapter_readings.is_some() =>
+            {
+                self.chapter_readings = None;
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && !self.milestones.is_empty() => {
+                self.milestones.clear();
+            }
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press
+                    && key.code == KeyCode::Char('?')
+                    && !self.captures_text_input() =>
+            {
+                self.show_help = true;
+            }
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press && self.command_palette.is_some() =>
+            {
+                if let Some(command_palette) = self.command_palette.as_mut() {
+                    match command_palette.handle_key(key) {
+                        PaletteAction::None => {}
+                        PaletteAction::Close => self.command_palette = None,
+                        PaletteAction::Run(command) => self.run_palette_command(command)?,
+                    }
+                }
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.merge.is_some() => {
+                if let Some((merge, _)) = self.merge.as_mut() {
+                    let action = merge.handle_key(key);
+                    self.handle_merge_action(action)?;
+                }
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.today_panel.is_some() => {
+                if let Some(today_panel) = self.today_panel.as_mut() {
+                    let action = today_panel.handle_key(key);
+                    self.handle_today_action(action)?;
+                }
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.memory_review.is_some() => {
+                if let Some(memory_review) = self.memory_review.as_mut() {
+                    let action = memory_review.handle_key(key);
+                    self.handle_memory_review_action(action)?;
+                }
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && self.chapter_ticker.is_some() => {
+                if let Some(chapter_ticker) = self.chapter_ticker.as_mut() {
+                    match chapter_ticker.handle_key(key, self.core.progress()) {
+                        TickerAction::None => {}
+                        TickerAction::Close => self.chapter_ticker = None,
+                        TickerAction::MarkRead { book, chapter } => {
+                            self.apply(Action::ReadChapters(vec![(book, chapter)]))?
                         }
-                        RecordAction::AddReading => {
-                            // Add reading (clears fields), then save and exit
-                            if let Err(e) = record.add_reading(&mut self.progress, self.bible) {
-                                record.error_message = Some(e);
-                            } else {
-                                save_progress(&self.progress, &self.config)?;
-                                self.dashboard_mode();
-                            }
+                    }
+                }
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press && !self.captures_text_input() => {
+                match (key.modifiers, key.code) {
+                    (_, KeyCode::Char('[')) => self.select_tab(self.tab.previous()),
+                    (_, KeyCode::Char(']')) => self.select_tab(self.tab.next()),
+                    (KeyModifiers::CONTROL, KeyCode::Char('p')) => {
+                        self.command_palette = Some(CommandPalette::new());
+                    }
+                    (KeyModifiers::ALT, KeyCode::Char(c)) if c.is_ascii_digit() => {
+                        if let Some(tab) = c.to_digit(10).and_then(Tab::from_number) {
+                            self.select_tab(tab);
                         }
                     }
+                    _ => self.handle_tab_key(key)?,
                 }
-                AppMode::ManualAdd(manual_add) => {
-                    let action = manual_add.handle_key(key, self.bible)?;
-                    match action {
-                        ManualAddAction::None => {}
-                        ManualAddAction::Cancel => {
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press => self.handle_entry_key(key)?,
+            Event::Mouse(mouse)
+                if !self.show_help
+                    && self.file_picker.is_none()
+                    && self.recovery.is_none()
+                    && self.quit_dialog.is_none()
+                    && self.merge.is_none()
+                    && self.today_panel.is_none()
+                    && self.memory_review.is_none()
+                    && self.chapter_ticker.is_none()
+                    && self.command_palette.is_none()
+                    && self.year_review.is_none()
+                    && self.chapter_readings.is_none()
+                    && self.milestones.is_empty()
+                    && self.entry.is_none()
+                    && self.import.is_none()
+                    && self.tab == Tab::Dashboard =>
+            {
+                let action = self.dashboard.handle_mouse(mouse);
+                self.handle_dashboard_action(action)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Close the command palette and do what it was asked to, as if its key was pressed on its
+    /// tab
+    fn run_palette_command(&mut self, command: PaletteCommand) -> Result<()> {
+        self.command_palette = None;
+        if self.tab != command.tab {
+            self.select_tab(command.tab);
+        }
+        match command.key {
+            Some(key) => self.handle_tab_key(key),
+            None => Ok(()),
+        }
+    }
+
+    /// Keys for the current tab when no form is open
+    fn handle_tab_key(&mut self, key: KeyEvent) -> Result<()> {
+        match self.tab {
+            Tab::Dashboard => {
+                let action = self.dashboard.handle_key(key);
+                self.handle_dashboard_action(action)?;
+            }
+            _ if key.code == KeyCode::Char('q') => self.quit()?,
+            Tab::Plans => {
+                let action = self.plans.handle_key(key, self.bible, self.core.today());
+                self.handle_plans_action(action)?;
+            }
+            Tab::History => {
+                let action = self.history.handle_key(key);
+                self.handle_history_action(action)?;
+            }
+            Tab::Stats if key.code == KeyCode::Char('y') => {
+                self.year_review = Some(YearReview::new(
+                    self.bible,
+                    &self.core.progress().history,
+                    self.core.today().year() - 1,
+                ));
+            }
+            Tab::Stats => self.stats.handle_key(key),
+            Tab::Settings => match self.settings.handle_key(key) {
+                SettingsAction::None => {}
+                SettingsAction::Save => self.save_settings(),
+                SettingsAction::PickFile(start) => {
+                    let picker = FilePicker::new("Progress file", PickKind::File, &start);
+                    self.file_picker = Some((picker, PickTarget::Setting));
+                }
+                SettingsAction::PickFolder(start) => {
+                    let picker = FilePicker::new("Plans folder", PickKind::Folder, &start);
+                    self.file_picker = Some((picker, PickTarget::Setting));
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Keys for the dashboard while a form or prompt is capturing text input
+    fn handle_entry_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.tab == Tab::History {
+            let action = self.history.handle_key(key);
+            return self.handle_history_action(action);
+        }
+        if let Some(import) = self.import.as_mut() {
+            match import.handle_key(key, self.bible) {
+                ImportAction::None => {}
+                ImportAction::Cancel => self.dashboard_mode(),
+                ImportAction::PickFile => {
+                    let start = std::env::current_dir().unwrap_or_default();
+                    let picker = FilePicker::new("Import readings from", PickKind::File, &start);
+                    self.file_picker = Some((picker, PickTarget::ImportFile));
+                }
+                ImportAction::Import => {
+                    let mut draft = self.core.progress().clone();
+                    let imported = import.import(&mut draft);
+                    self.toast = Some(Toast::new(
+                        "Imported",
+                        vec![format!(
+                            "{} reading{} recorded",
+                            imported,
+                            if imported == 1 { "" } else { "s" }
+                        )],
+                    ));
+                    self.apply(Action::Record(Box::new(draft)))?;
+                    self.dashboard_mode();
+                }
+            }
+            return Ok(());
+        }
+        match &mut self.entry {
+            None => {
+                let action = self.dashboard.handle_key(key);
+                self.handle_dashboard_action(action)?;
+            }
+            Some(entry) => {
+                let action = entry.handle_key(key, self.bible, self.core.progress())?;
+                match action {
+                    EntryAction::None => {}
+                    EntryAction::Cancel => {
+                        self.dashboard_mode();
+                    }
+                    EntryAction::AddReading => {
+                        // Record the added passages, then save and exit
+                        let mut draft = self.core.progress().clone();
+                        if let Err(e) = entry.add_reading(&mut draft) {
+                            entry.error_message = Some(e);
+                        } else {
+                            self.apply(Action::Record(Box::new(draft)))?;
                             self.dashboard_mode();
                         }
-                        ManualAddAction::AddReading => {
-                            // Add reading (clears fields), then save and exit
-                            if let Err(e) = manual_add.add_reading(&mut self.progress, self.bible) {
-                                manual_add.error_message = Some(e);
-                            } else {
-                                save_progress(&self.progress, &self.config)?;
-                                self.dashboard_mode();
-                            }
-                        }
                     }
                 }
-            },
-            _ => {}
+            }
         }
         Ok(())
     }
 
-    fn handle_dashboard_action(&mut self, action: DashboardAction) {
+    fn handle_dashboard_action(&mut self, action: DashboardAction) -> Result<()> {
         match action {
             DashboardAction::None => {}
-            DashboardAction::Quit => self.quit(),
-            DashboardAction::StartRecord => self.start_record_mode(),
-            DashboardAction::StartManualAdd => self.start_manual_add_mode(),
+            DashboardAction::Quit => self.quit()?,
+            DashboardAction::StartRecord => self.start_entry(WriteMode::Accumulate),
+            DashboardAction::StartManualAdd => self.start_entry(WriteMode::Overwrite),
+            DashboardAction::StartImport => {
+                self.store_dashboard_state();
+                self.import = Some(ImportWidget::new());
+            }
+            DashboardAction::ShowToday => {
+                self.today_panel = Some(TodayPanel::new(
+                    self.today_items(),
+                    self.ui_state.balanced_suggestions,
+                ));
+            }
+            DashboardAction::Batch { action, chapters } => self.apply_batch(action, chapters)?,
+            DashboardAction::RepeatLast => {
+                match entry::last_passage(self.bible, self.core.progress()) {
+                    Some(passage) => {
+                        let mut draft = self.core.progress().clone();
+                        passage.record(&mut draft);
+                        self.toast = Some(Toast::new(
+                            "Recorded",
+                            vec![format!("{} again, today", passage.label)],
+                        ));
+                        self.apply(Action::Record(Box::new(draft)))?;
+                    }
+                    None => {
+                        self.toast = Some(Toast::new(
+                            "Repeat",
+                            vec!["Nothing recorded yet".to_string()],
+                        ))
+                    }
+                }
+            }
+            DashboardAction::Copy(text) => {
+                self.toast = Some(match copy_to_clipboard(&text) {
+                    Ok(()) => Toast::new("Copied", vec![text]),
+                    Err(e) => Toast::new("Clipboard", vec![format!("Couldn't copy: {}", e)]),
+                });
+            }
+            DashboardAction::Memorize {
+                book,
+                chapter,
+                verse_start,
+                verse_end,
+            } => {
+                let card =
+                    MemoryCard::new(book, chapter, verse_start, verse_end, self.core.today());
+                self.apply(Action::Memorize(card))?;
+            }
+            DashboardAction::ReviewMemory => self.start_memory_review(),
+            DashboardAction::DateRangeChanged => {
+                self.refresh_layer();
+                self.rebuild_dashboard();
+            }
+            DashboardAction::ShowChapterReadings { book, chapter } => {
+                self.chapter_readings = Some(ChapterReadings::new(
+                    &self.core.progress().history,
+                    &book,
+                    chapter,
+                ));
+            }
+            DashboardAction::SwitchCampaign => self.apply(Action::SwitchCampaign)?,
+            DashboardAction::StartTicker { start } => {
+                self.chapter_ticker = Some(ChapterTicker::new(self.bible, start));
+            }
+            DashboardAction::FindLeastRead { after } => self.find_least_read(after),
         }
+        Ok(())
     }
 
-    fn start_record_mode(&mut self) {
-        let record = RecordWidget::new(self.bible);
-        self.mode = AppMode::Record(record);
+    /// Select the chapter with the least-read verses of the books shown, the first one after
+    /// `after` (wrapping around), and tell which verses they are and how often they were read
+    fn find_least_read(&mut self, after: Option<(String, u32)>) {
+        let progress = self.layer.as_ref().unwrap_or(self.core.progress());
+        let Some((count, runs)) = least_read_verses(self.bible, progress, self.dashboard.filter)
+        else {
+            return;
+        };
+        // The runs are in canonical order, so those of a chapter are next to each other
+        let mut chapters: Vec<(String, u32, Vec<String>)> = Vec::new();
+        for run in runs {
+            let verses = if run.verse_start == run.verse_end {
+                run.verse_start.to_string()
+            } else {
+                format!("{}-{}", run.verse_start, run.verse_end)
+            };
+            match chapters.last_mut() {
+                Some((book, chapter, runs)) if *book == run.book && *chapter == run.chapter => {
+                    runs.push(verses)
+                }
+                _ => chapters.push((run.book, run.chapter, vec![verses])),
+            }
+        }
+
+        let books = get_all_books(self.bible);
+        let position =
+            |book: &str, chapter: u32| (books.iter().position(|name| name == book), chapter);
+        let index = after
+            .and_then(|(book, chapter)| {
+                let after = position(&book, chapter);
+                chapters
+                    .iter()
+                    .position(|(book, chapter, _)| position(book, *chapter) > after)
+            })
+            .unwrap_or(0);
+        let (book, chapter, verses) = &chapters[index];
+        if let Err(e) = self.dashboard.select_reference(book, Some(*chapter)) {
+            self.toast = Some(Toast::new("Least read", vec![e]));
+            return;
+        }
+        self.toast = Some(Toast::new(
+            "Least read",
+            vec![
+                format!("{} {}:{}", book, chapter, verses.join(", ")),
+                format!(
+                    "Read {} time{}; chapter {} of {} holding back the next pass",
+                    count,
+                    if count == 1 { "" } else { "s" },
+                    index + 1,
+                    chapters.len()
+                ),
+            ],
+        ));
     }
 
-    fn start_manual_add_mode(&mut self) {
-        let manual_add = ManualAddWidget::new(self.bible);
-        self.mode = AppMode::ManualAdd(manual_add);
+    /// Open the review of the memorized passages that are due today, with their text when a
+    /// scripture text file is configured
+    fn start_memory_review(&mut self) {
+        let memorization = &self.core.progress().memorization;
+        let due = due_cards(memorization, self.core.today());
+        if due.is_empty() {
+            let message = if memorization.is_empty() {
+                "Press M on a chapter or passage to start memorizing it"
+            } else {
+                "Nothing is due for review today"
+            };
+            self.toast = Some(Toast::new("Memory review", vec![message.to_string()]));
+            return;
+        }
+        let scripture = self
+            .config
+            .scripture_text_path
+            .as_ref()
+            .and_then(|path| ScriptureText::load(self.bible, path).ok());
+        let cards = due
+            .into_iter()
+            .map(|idx| {
+                let card = &memorization[idx];
+                let text = scripture
+                    .as_ref()
+                    .and_then(|scripture| scripture.chapter(&card.book, card.chapter))
+                    .map(|verses| {
+                        verses
+                            .iter()
+                            .filter(|(verse, _)| {
+                                (card.verse_start..=card.verse_end).contains(verse)
+                            })
+                            .map(|(verse, text)| format!("{} {}", verse, text))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    });
+                ReviewCard {
+                    card: idx,
+                    reference: card.reference(),
+                    text,
+                }
+            })
+            .collect();
+        self.memory_review = Some(MemoryReview::new(cards));
     }
 
+    fn handle_memory_review_action(&mut self, action: MemoryReviewAction) -> Result<()> {
+        match action {
+            MemoryReviewAction::None => {}
+            MemoryReviewAction::Close => self.memory_review = None,
+            MemoryReviewAction::Grade { card, grade } => {
+                self.apply(Action::GradeCard { card, grade })?
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a batch action to the chapters marked in the dashboard
+    fn apply_batch(&mut self, action: BatchAction, chapters: Vec<(String, u32)>) -> Result<()> {
+        match action {
+            BatchAction::Export => self.export_reading_list(&chapters),
+            BatchAction::MarkRead => self.apply(Action::ReadChapters(chapters))?,
+            BatchAction::SetReadCount(count) => {
+                self.apply(Action::SetReadCount { chapters, count })?
+            }
+            BatchAction::ClearMarks => {}
+        }
+        Ok(())
+    }
+
+    /// Write `chapters` to a reading list next to the progress file, one per line
+    fn export_reading_list(&mut self, chapters: &[(String, u32)]) {
+        if self.demo {
+            self.toast = Some(Toast::new(
+                "Reading list",
+                vec!["Not exported in demo mode".to_string()],
+            ));
+            return;
+        }
+        let path = self.config.progress_path.with_file_name("reading_list.txt");
+        let content: String = chapters
+            .iter()
+            .map(|(book, chapter)| format!("{} {}\n", book, chapter))
+            .collect();
+        let lines = match std::fs::write(&path, content) {
+            Ok(()) => vec![format!(
+                "Exported {} chapters to {}",
+                chapters.len(),
+                path.display()
+            )],
+            Err(e) => vec![format!("Couldn't write {}: {}", path.display(), e)],
+        };
+        self.toast = Some(Toast::new("Reading list", lines));
+    }
+
+    fn handle_today_action(&mut self, action: TodayAction) -> Result<()> {
+        match action {
+            TodayAction::None => {}
+            TodayAction::Close => self.today_panel = None,
+            TodayAction::ToggleBalanced => {
+                self.ui_state.balanced_suggestions = !self.ui_state.balanced_suggestions;
+                self.today_panel = Some(TodayPanel::new(
+                    self.today_items(),
+                    self.ui_state.balanced_suggestions,
+                ));
+            }
+            TodayAction::MarkRead { book, chapter } => {
+                self.apply(Action::ReadChapters(vec![(book, chapter)]))?;
+                let items = self.today_items();
+                if let Some(today_panel) = self.today_panel.as_mut() {
+                    today_panel.set_items(items);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_merge_action(&mut self, action: MergeAction) -> Result<()> {
+        match action {
+            MergeAction::None => {}
+            MergeAction::Cancel => self.merge = None,
+            MergeAction::Apply(resolutions) => {
+                if let Some((_, theirs)) = self.merge.take() {
+                    self.apply(Action::Merge {
+                        theirs: Box::new(theirs),
+                        resolutions,
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_history_action(&mut self, action: HistoryAction) -> Result<()> {
+        match action {
+            HistoryAction::None => {}
+            HistoryAction::SaveJournal { date, text } => {
+                self.apply(Action::SaveJournal { date, text })?;
+                self.history.refresh(self.bible, self.core.progress());
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_plans_action(&mut self, action: PlansAction) -> Result<()> {
+        match action {
+            PlansAction::None => {}
+            PlansAction::Start(state) => self.apply(Action::StartPlan(state))?,
+            PlansAction::CatchUp(strategy) => self.apply(Action::CatchUp(strategy))?,
+            PlansAction::MarkRead { book, chapter } => {
+                self.apply(Action::ReadChapters(vec![(book, chapter)]))?
+            }
+        }
+        self.plans
+            .refresh(self.bible, self.core.progress(), self.core.today());
+        Ok(())
+    }
+
+    fn today_items(&self) -> Vec<TodayItem> {
+        if self.ui_state.balanced_suggestions {
+            return balanced_items(self.bible, self.core.progress(), self.core.today());
+        }
+        today_items(
+            self.bible,
+            self.core.progress(),
+            self.core.today(),
+            self.config.daily_chapter_goal,
+            self.config.daily_minutes,
+        )
+    }
+
+    /// Switch tabs, refreshing the destination from the current progress
+    fn select_tab(&mut self, tab: Tab) {
+        match tab {
+            Tab::Plans => self
+                .plans
+                .refresh(self.bible, self.core.progress(), self.core.today()),
+            Tab::History => self.history.refresh(self.bible, self.core.progress()),
+            Tab::Stats => {
+                self.stats = StatsWidget::new(self.bible, self.core.progress(), &self.config)
+                    .with_plugin_stats(self.plugin_stats.clone());
+                self.run_plugin_stats();
+            }
+            Tab::Settings => self.settings = SettingsWidget::new(&self.config),
+            _ => {}
+        }
+        self.tab = tab;
+    }
+
+    /// Apply the edited settings, write them to the config file and rebuild the dashboard
+    fn save_settings(&mut self) {
+        self.settings.apply_to(&mut self.config);
+        self.reconfigure();
+        self.settings.message = Some(if self.demo {
+            Ok("Applied, but not saved in demo mode".to_string())
+        } else {
+            match self.config.save_settings() {
+                Ok(()) => {
+                    // Not an edit to reload
+                    self.config_modified = self.config.modified();
+                    Ok(format!(
+                        "Saved to {}",
+                        self.config.config_file_path().display()
+                    ))
+                }
+                Err(e) => Err(format!("Error saving settings: {}", e)),
+            }
+        });
+        self.rebuild_dashboard();
+    }
+
+    /// Remember the dashboard's expanded nodes and selection
+    fn store_dashboard_state(&mut self) {
+        self.ui_state.dashboard = self.dashboard.ui_state();
+    }
+
+    /// Open the entry form over the dashboard, adding readings or overwriting them
+    fn start_entry(&mut self, write_mode: WriteMode) {
+        self.store_dashboard_state();
+        self.entry = Some(
+            EntryWidget::new(
+                self.bible,
+                self.core.progress(),
+                self.quick_books(),
+                write_mode,
+            )
+            .with_reading_defaults(self.config.default_source, self.config.translations.clone()),
+        );
+    }
+
+    /// Dispatch an action to the core and carry out the effects it asks for
+    fn apply(&mut self, action: Action) -> Result<()> {
+        self.finish_loading_history()?;
+        for effect in self.core.dispatch(action) {
+            match effect {
+                Effect::Save => self.save()?,
+                Effect::RunPlugins(_) if self.read_only => {}
+                Effect::RunPlugins(payload) => self.run_plugins(payload),
+                Effect::Notify { title, lines } => self.toast = Some(Toast::new(&title, lines)),
+                Effect::Celebrate(reached) => self.milestones.extend(reached),
+                Effect::BooksChanged(books) => {
+                    self.refresh_layer();
+                    let progress = self.layer.as_ref().unwrap_or(self.core.progress());
+                    self.dashboard.update_books(progress, &books);
+                }
+                Effect::ProgressReplaced => {
+                    self.refresh_layer();
+                    self.rebuild_dashboard();
+                }
+                Effect::ConfirmQuit(summary) => {
+                    self.quit_dialog = Some(QuitDialog::new(QuitReason::SessionSummary(summary)));
+                }
+                Effect::SaveAndExit => self.save_and_quit(),
+            }
+        }
+        Ok(())
+    }
+
+    /// Send readings to the plugins in the background, so a slow command or server doesn't
+    /// hold up the app
+    fn run_plugins(&mut self, payload: HookPayload) {
+        let plugins = self.plugins.clone();
+        let sender = self.event_sender.clone();
+        thread::spawn(move || {
+            if let (Err(e), Some(sender)) = (plugins.recorded_and_saved(&payload), sender) {
+                // The app may have quit in the meantime
+                let _ = sender.send(AppEvent::PluginFailed(e));
+            }
+        });
+    }
+
+    /// Run the plugins' stats commands in the background, like `run_plugins`, so a slow or hung
+    /// command doesn't freeze the Stats tab; their lines are shown when they arrive
+    fn run_plugin_stats(&mut self) {
+        let Some(sender) = self.event_sender.clone() else {
+            return;
+        };
+        if self.plugins.is_empty() {
+            return;
+        }
+        let plugins = self.plugins.clone();
+        let overview = stats_overview(
+            self.bible,
+            self.core.progress(),
+            self.core.today(),
+            self.config.neglected_days,
+        );
+        thread::spawn(move || {
+            // The app may have quit in the meantime
+            let _ = sender.send(AppEvent::PluginStats(plugins.stats(&overview)));
+        });
+    }
+
+    /// Pinned and recently read books, offered first in the book pickers
+    fn quick_books(&self) -> Vec<String> {
+        quick_books(self.bible, self.core.progress(), &self.config.pinned_books)
+    }
+
+    /// Close the entry and import forms
     fn dashboard_mode(&mut self) {
-        let dashboard = DashboardWidget::new(self.bible, &self.progress);
-        self.mode = AppMode::Dashboard(dashboard);
+        self.entry = None;
+        self.import = None;
     }
 
-    fn quit(&mut self) {
-        // Save before quitting
-        if let Err(e) = save_progress(&self.progress, &self.config) {
-            eprintln!("Error saving progress: {}", e);
+    /// Rebuild the dashboard from scratch (e.g. after the color gradient changed), keeping its
+    /// expanded nodes, selection and filters
+    fn rebuild_dashboard(&mut self) {
+        self.store_dashboard_state();
+        let progress = self.layer.as_ref().unwrap_or(self.core.progress());
+        let mut dashboard = DashboardWidget::new(self.bible, progress, &self.config);
+        dashboard.set_filter(self.ui_state.dashboard.filter);
+        dashboard.restore_ui_state(&self.ui_state.dashboard);
+        self.dashboard = dashboard;
+    }
+
+    fn refresh_layer(&mut self) {
+        self.layer = dashboard_layer(self.core.progress(), self.dashboard.date_range());
+    }
+
+    /// Quit, unless settings are unsaved or the progress can't be saved, in which case the
+    /// quit dialog asks what to do
+    fn quit(&mut self) -> Result<()> {
+        if self.settings.has_unsaved_changes() {
+            self.quit_dialog = Some(QuitDialog::new(QuitReason::UnsavedSettings));
+            return Ok(());
+        }
+        self.apply(Action::Quit)
+    }
+
+    /// Save in the background, or right away before the event loop has started
+    fn save(&mut self) -> Result<()> {
+        match &self.saver {
+            _ if self.read_only => {}
+            Some(saver) => saver.save(self.core.progress().clone()),
+            None => save_progress(self.core.progress(), &self.config)?,
+        }
+        Ok(())
+    }
+
+    fn save_and_quit(&mut self) {
+        let result = match &self.saver {
+            _ if self.read_only => Ok(()),
+            Some(saver) => {
+                saver.save(self.core.progress().clone());
+                saver.flush()
+            }
+            None => save_progress(self.core.progress(), &self.config).map_err(|e| e.to_string()),
+        };
+        match result {
+            Ok(()) => self.exit(),
+            Err(e) => {
+                self.quit_dialog = Some(QuitDialog::new(QuitReason::SaveFailed(e)));
+            }
+        }
+    }
+
+    fn handle_quit_dialog_action(&mut self, action: QuitDialogAction) {
+        let Some(quit_dialog) = self.quit_dialog.take() else {
+            return;
+        };
+        match action {
+            QuitDialogAction::None => self.quit_dialog = Some(quit_dialog),
+            QuitDialogAction::Cancel => {}
+            QuitDialogAction::Retry => self.save_and_quit(),
+            QuitDialogAction::SaveAs(path) => match save_progress_to(self.core.progress(), &path) {
+                Ok(()) => self.exit(),
+                Err(e) => {
+                    self.quit_dialog = Some(QuitDialog::new(QuitReason::SaveFailed(format!(
+                        "{}: {}",
+                        path.display(),
+                        e
+                    ))));
+                }
+            },
+            QuitDialogAction::SaveSettings => {
+                self.save_settings();
+                if matches!(self.settings.message, Some(Err(_))) {
+                    // Show the error on the settings tab instead of quitting
+                    self.tab = Tab::Settings;
+                } else {
+                    self.save_and_quit();
+                }
+            }
+            QuitDialogAction::QuitAnyway => match quit_dialog.reason {
+                // Unsaved settings are discarded, but progress still has to be saved
+                QuitReason::UnsavedSettings | QuitReason::SessionSummary(_) => self.save_and_quit(),
+                QuitReason::SaveFailed(_) => self.exit(),
+            },
+            QuitDialogAction::CopyAndQuit => {
+                if let QuitReason::SessionSummary(summary) = &quit_dialog.reason {
+                    // The clipboard is a convenience, so failing to reach it shouldn't block quitting
+                    let _ = copy_to_clipboard(summary);
+                }
+                self.save_and_quit();
+            }
+        }
+    }
+
+    fn handle_recovery_action(&mut self, action: RecoveryAction) {
+        let Some(mut recovery) = self.recovery.take() else {
+            return;
+        };
+        match action {
+            RecoveryAction::None => self.recovery = Some(recovery),
+            RecoveryAction::Restore(copy) => match restore_progress_from(&self.config, &copy) {
+                Ok(progress) => {
+                    tracing::info!("progress restored from {}", copy.display());
+                    self.read_only = false;
+                    self.replace_progress(progress);
+                    self.toast = Some(Toast::new("Restored", vec![copy.display().to_string()]));
+                }
+                Err(e) => {
+                    recovery.message =
+                        Some(format!("{} couldn't be restored: {}", copy.display(), e));
+                    self.recovery = Some(recovery);
+                }
+            },
+            RecoveryAction::PickCopy => {
+                let start = recovery
+                    .backup
+                    .clone()
+                    .unwrap_or_else(|| get_progress_file_path(&self.config));
+                let picker = FilePicker::new("Restore from", PickKind::File, &start);
+                self.file_picker = Some((picker, PickTarget::RestoreFrom));
+                self.recovery = Some(recovery);
+            }
+            RecoveryAction::Salvage => match salvage_progress(&self.config, &recovery.error) {
+                Ok((salvaged, report)) => {
+                    tracing::warn!(
+                        "opened read-only, {} parts left out",
+                        salvaged.skipped.len()
+                    );
+                    self.replace_progress(salvaged.progress);
+                    self.toast = Some(Toast::new(
+                        "Read-only",
+                        vec![
+                            format!(
+                                "{} parts left out, listed in {}",
+                                salvaged.skipped.len(),
+                                report.display()
+                            ),
+                            "Nothing you do is saved".to_string(),
+                        ],
+                    ));
+                }
+                Err(e) => {
+                    recovery.message = Some(format!("The file couldn't be read: {}", e));
+                    self.recovery = Some(recovery);
+                }
+            },
+            RecoveryAction::Quit => self.exit(),
+        }
+    }
+
+    fn handle_file_picker_action(&mut self, action: FilePickerAction) {
+        match action {
+            FilePickerAction::None => {}
+            FilePickerAction::Cancel => self.file_picker = None,
+            FilePickerAction::Picked(path) => match self.file_picker.take() {
+                Some((_, PickTarget::Setting)) => self.settings.set_selected_path(path),
+                Some((_, PickTarget::ImportFile)) => {
+                    if let Some(import) = self.import.as_mut() {
+                        import.load_file(self.bible, &path);
+                    }
+                }
+                Some((_, PickTarget::RestoreFrom)) => {
+                    self.handle_recovery_action(RecoveryAction::Restore(path));
+                }
+                None => {}
+            },
+        }
+    }
+
+    /// Show `progress` instead of what was loaded at startup, e.g. once a damaged file was
+    /// recovered
+    fn replace_progress(&mut self, progress: ReadingProgress) {
+        let today = self.core.today();
+        self.core = AppCore::new(self.bible, progress, &self.config, today);
+        self.refresh_layer();
+        self.rebuild_dashboard();
+        self.greet();
+    }
+
+    /// Stop the app. UI state is a convenience, so failing to save it shouldn't block quitting
+    fn exit(&mut self) {
+        self.store_dashboard_state();
+        if !self.demo {
+            let _ = save_ui_state(&self.ui_state, &self.config);
         }
         self.running = false;
     }
 }
 
+/// The dialog offering to recover the progress file, which couldn't be loaded because of
+/// `error`
+/// The readings the dashboard shows instead of the lifetime totals: those of the active
+/// campaign, within `date_range` if one is chosen
+fn dashboard_layer(
+    progress: &ReadingProgress,
+    date_range: Option<DateRange>,
+) -> Option<ReadingProgress> {
+    match (date_range, progress.active_campaign.as_deref()) {
+        (Some(range), _) => Some(progress.range_layer(range)),
+        (None, Some(name)) => Some(progress.campaign_layer(name)),
+        (None, None) => None,
+    }
+}
+
+fn recovery_dialog(config: &Config, error: String) -> RecoveryDialog {
+    let backup = backup_path(&get_progress_file_path(config));
+    RecoveryDialog::new(error, backup.exists().then_some(backup))
+}
+
+/// The plugins' extra stats lines for `progress`
+fn plugin_stats(
+    plugins: &Plugins,
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    config: &Config,
+    today: NaiveDate,
+) -> Vec<(String, String)> {
+    if plugins.is_empty() {
+        return Vec::new();
+    }
+    plugins.stats(&stats_overview(
+        bible,
+        progress,
+        today,
+        config.neglected_days,
+    ))
+}
+
 fn main() -> Result<()> {
+    let started = Instant::now();
     color_eyre::install()?;
     let args = Args::parse();
+    if args.version {
+        let info = build_info();
+        if args.verbose > 0 {
+            println!("{}", info);
+        } else {
+            println!("brp {}", info.version);
+        }
+        return Ok(());
+    }
+    // Logs go to a file: the terminal belongs to the interface
+    let _log_guard = match logging::init(args.verbose) {
+        Ok(guard) => Some(guard),
+        Err(e) => {
+            eprintln!("Couldn't open the log file: {}", e);
+            None
+        }
+    };
 
     let config = Config::load()?;
+    i18n::set_language(config.language);
 
     if args.show_config {
         // Display config and exit
         println!("Configuration:");
         println!("  Config file: {}", config.config_file_path().display());
+        println!("  UI state file: {}", config.ui_state_path.display());
         let progress_path = config.progress_path_absolute();
         if config.progress_path_overridden() {
             println!(
@@ -166,12 +1681,354 @@ fn main() -> Result<()> {
         } else {
             println!("  Progress path: {}", progress_path.display());
         }
+        if let Some(path) = &config.scripture_text_path {
+            println!("  Scripture text: {}", path.display());
+        }
+        if let Some(path) = logging::log_dir() {
+            println!("  Log files: {}", path.display());
+        }
+        return Ok(());
+    }
+    if args.demo && args.command.is_some() {
+        return Err(color_eyre::eyre::eyre!(
+            "--demo starts the app; it can't be used with a command"
+        ));
+    }
+
+    #[cfg(feature = "daemon")]
+    if let Some(Command::Daemon) = args.command {
+        if let Some(time) = config.reminder_time {
+            println!("Reminding at {} each day", time.format("%H:%M"));
+        }
+        return bible_reading_progress::daemon::run(&config, |e| eprintln!("{}", e));
+    }
+
+    if let Some(Command::Review { year }) = args.command {
+        let progress = load_progress(&config)?;
+        let year = year.unwrap_or(Utc::now().year() - 1);
+        let review = YearReview::new(get_bible_structure(), &progress.history, year);
+        print!("{}", review.to_markdown());
+        println!("\n<!-- {} -->", build_info().summary());
         return Ok(());
     }
 
+    if let Some(Command::Report { output, email, .. }) = &args.command {
+        let bible = get_bible_structure();
+        let progress = load_progress(&config)?;
+        let plan = match &progress.plan {
+            Some(state) => Some((
+                state
+                    .plan
+                    .load(bible, &config.plans_dir)
+                    .map_err(|e| color_eyre::eyre::eyre!(e))?,
+                state,
+            )),
+            None => None,
+        };
+        let report = WeeklyReport::new(
+            &progress.history,
+            plan.as_ref().map(|(plan, state)| (plan, *state)),
+            Utc::now().date_naive(),
+        );
+        let markdown = format!(
+            "{}\n<!-- {} -->\n",
+            report.to_markdown(),
+            build_info().summary()
+        );
+        match output {
+            Some(dir) => {
+                std::fs::create_dir_all(dir)?;
+                let path = dir.join(report.file_name());
+                std::fs::write(&path, &markdown)?;
+                println!("Wrote {}", path.display());
+            }
+            None => print!("{}", markdown),
+        }
+        if let Some(to) = email {
+            report.send_mail(&markdown, to).map_err(|e| {
+                color_eyre::eyre::eyre!("Couldn't mail the report with sendmail: {}", e)
+            })?;
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Campaign { command }) = &args.command {
+        let bible = get_bible_structure();
+        let mut progress = load_progress(&config)?;
+        match command {
+            CampaignCommand::Start { name } => {
+                progress
+                    .start_campaign(name, Utc::now().date_naive())
+                    .map_err(|e| color_eyre::eyre::eyre!(e))?;
+                save_progress(&progress, &config)?;
+                println!("Started \"{}\"; new readings count towards it", name.trim());
+            }
+            CampaignCommand::List => {
+                if progress.campaigns.is_empty() {
+                    println!("No campaigns yet. Start one with `brp campaign start <NAME>`.");
+                }
+                for campaign in &progress.campaigns {
+                    let layer = progress.campaign_layer(&campaign.name);
+                    println!(
+                        "{}{} (since {}): {:.1}% read",
+                        if progress.active_campaign.as_ref() == Some(&campaign.name) {
+                            "* "
+                        } else {
+                            "  "
+                        },
+                        campaign.name,
+                        campaign.started,
+                        percent_read(bible, &layer, BookFilter::All)
+                    );
+                }
+            }
+            CampaignCommand::Switch { name } => {
+                if let Some(name) = name {
+                    if !progress.campaigns.iter().any(|c| c.name == *name) {
+                        return Err(color_eyre::eyre::eyre!("No campaign called \"{}\"", name));
+                    }
+                }
+                progress.active_campaign = name.clone();
+                save_progress(&progress, &config)?;
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Stats { neglected, days }) = &args.command {
+        let bible = get_bible_structure();
+        let progress = load_progress(&config)?;
+        let today = Utc::now().date_naive();
+        if *neglected {
+            let days = days.unwrap_or(config.neglected_days);
+            let books = neglected_books(bible, &progress, today, days);
+            if books.is_empty() {
+                println!("Every book has been read in the last {} days", days);
+            }
+            for book in books {
+                println!("{}: {}", book.book, book.describe(today));
+            }
+        } else {
+            println!(
+                "Current streak: {} days",
+                current_streak(&progress.history, today)
+            );
+            println!(
+                "Days with a reading: {}",
+                reading_days(&progress.history).len()
+            );
+            for filter in [
+                BookFilter::All,
+                BookFilter::OldTestament,
+                BookFilter::NewTestament,
+            ] {
+                println!(
+                    "{}: {:.1}% of verses read",
+                    filter.name(),
+                    percent_read(bible, &progress, filter)
+                );
+            }
+            let plugins = Plugins::from_config(&config);
+            for (label, value) in plugin_stats(&plugins, bible, &progress, &config, today) {
+                println!("{}: {}", label, value);
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Export { output }) = &args.command {
+        let progress = load_progress(&config)?;
+        let document = interchange::export(&progress, Utc::now().date_naive());
+        let json = serde_json::to_string_pretty(&document)?;
+        match output {
+            Some(path) => std::fs::write(path, json)?,
+            None => println!("{}", json),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Import { file }) = &args.command {
+        let bible = get_bible_structure();
+        let mut progress = load_progress(&config)?;
+        let document = interchange::parse(&std::fs::read_to_string(file)?)
+            .map_err(|e| color_eyre::eyre::eyre!(e))?;
+        let summary = interchange::import(bible, &mut progress, &document)
+            .map_err(|e| color_eyre::eyre::eyre!(e))?;
+        if summary.imported > 0 {
+            save_progress(&progress, &config)?;
+        }
+        println!(
+            "Imported {} readings ({} already recorded)",
+            summary.imported, summary.duplicates
+        );
+        return Ok(());
+    }
+
+    if let Some(Command::Chart { output, books }) = &args.command {
+        let bible = get_bible_structure();
+        let progress = load_progress(&config)?;
+        let filter = books.unwrap_or_else(|| load_ui_state(&config).dashboard.filter);
+        let svg = chart_svg(bible, &progress, filter, Utc::now().date_naive());
+        match output {
+            Some(path) => std::fs::write(path, svg)?,
+            None => print!("{}", svg),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Queue {
+        count,
+        json,
+        output,
+    }) = &args.command
+    {
+        let bible = get_bible_structure();
+        let progress = load_progress(&config)?;
+        let queue = audio_queue(bible, &progress, &config.audio_file_pattern, *count)
+            .map_err(|e| color_eyre::eyre::eyre!(e))?;
+        let json = *json
+            || output
+                .as_ref()
+                .is_some_and(|path| path.extension().is_some_and(|ext| ext == "json"));
+        let queue = if json {
+            serde_json::to_string_pretty(&queue)? + "\n"
+        } else {
+            queue_to_m3u(&queue)
+        };
+        match output {
+            Some(path) => std::fs::write(path, queue)?,
+            None => print!("{}", queue),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Badge { output, label }) = &args.command {
+        let bible = get_bible_structure();
+        let progress = load_progress(&config)?;
+        let value = badge_value(bible, &progress, Utc::now().date_naive());
+        let color = badge_color(percent_read(bible, &progress, BookFilter::All));
+        let svg = badge_svg(label, &value, color);
+        match output {
+            Some(path) => std::fs::write(path, svg)?,
+            None => print!("{}", svg),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Next {
+        review,
+        balanced,
+        count,
+    }) = args.command
+    {
+        let bible = get_bible_structure();
+        let progress = load_progress(&config)?;
+        let today = Utc::now().date_naive();
+        if review {
+            let suggestions = review_suggestions(bible, &progress.history, today);
+            if suggestions.is_empty() {
+                println!("Nothing is due for re-reading yet.");
+            }
+            for suggestion in suggestions.iter().take(count) {
+                println!(
+                    "{} {} ({})",
+                    suggestion.book,
+                    suggestion.chapter,
+                    suggestion.describe()
+                );
+            }
+        } else {
+            let items = if balanced {
+                balanced_items(bible, &progress, today)
+            } else {
+                today_items(
+                    bible,
+                    &progress,
+                    today,
+                    config.daily_chapter_goal,
+                    config.daily_minutes,
+                )
+            };
+            for item in items.into_iter().filter(|item| !item.done) {
+                println!(
+                    "{} {} ({}, ~{} min)",
+                    item.book,
+                    item.chapter,
+                    item.source.label(),
+                    item.minutes
+                );
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Plan { command }) = &args.command {
+        let bible = get_bible_structure();
+        let mut progress = load_progress(&config)?;
+        if let PlanCommand::Import { file } = command {
+            let (id, plan) = import_plan_file(bible, file, &config.plans_dir)
+                .map_err(|e| color_eyre::eyre::eyre!(e))?;
+            let today = Utc::now().date_naive();
+            progress.plan = Some(PlanState::new(id, plan.start.unwrap_or(today)));
+            save_progress(&progress, &config)?;
+            println!("Now following {}", plan.name);
+            return Ok(());
+        }
+        let Some(state) = &progress.plan else {
+            println!("No reading plan is being followed. Choose one in the Plans tab.");
+            return Ok(());
+        };
+        let plan = state
+            .plan
+            .load(bible, &config.plans_dir)
+            .map_err(|e| color_eyre::eyre::eyre!(e))?;
+        match command {
+            PlanCommand::Today => {
+                for line in today_lines(&plan, state, &progress.history, Utc::now().date_naive()) {
+                    println!("{}", line);
+                }
+            }
+            PlanCommand::Export { output } => {
+                let file = shared_plan_file(&plan, state);
+                match output {
+                    Some(path) => std::fs::write(path, file)?,
+                    None => print!("{}", file),
+                }
+            }
+            // Handled above, without needing a plan already
+            PlanCommand::Import { .. } => {}
+        }
+        return Ok(());
+    }
+
+    // Books both sides read differently are resolved in the app; everything else merges here
+    let mut merge = None;
+    if let Some(Command::Merge { file }) = &args.command {
+        let bible = get_bible_structure();
+        let mine = load_progress(&config)?;
+        let theirs: ReadingProgress = serde_yaml::from_str(&std::fs::read_to_string(file)?)?;
+        let conflicts = find_conflicts(bible, &mine, &theirs);
+        if conflicts.is_empty() {
+            let merged = merge_progress(&mine, &theirs, &HashMap::new());
+            let added = merged.history.len() - mine.history.len();
+            save_progress(&merged, &config)?;
+            println!("Merged {} readings, with no conflicting books", added);
+            return Ok(());
+        }
+        let source = file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        merge = Some((MergeWidget::new(source, conflicts), theirs));
+    }
+
     let mut terminal = ratatui::init();
-    let mut app = App::new_with_config(config)?;
-    let result = app.run(&mut terminal);
+    execute!(std::io::stdout(), EnableMouseCapture)?;
+    let result = App::new_with_config(config, args.demo).and_then(|mut app| {
+        app.merge = merge;
+        app.run(&mut terminal, started)
+    });
+    execute!(std::io::stdout(), DisableMouseCapture)?;
     ratatui::restore();
     result
 }
@@ -0,0 +1,133 @@
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Ease a new passage starts with: after the first two reviews, each interval is the previous
+/// one times the ease
+const INITIAL_EASE: f64 = 2.5;
+/// The ease never drops below this, so hard passages still get longer intervals
+const MIN_EASE: f64 = 1.3;
+/// Highest recall grade
+pub const MAX_GRADE: u8 = 5;
+
+/// A passage being memorized, scheduled for review with the SM-2 algorithm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryCard {
+    pub book: String,
+    pub chapter: u32,
+    pub verse_start: u32,
+    /// Inclusive
+    pub verse_end: u32,
+    /// Multiplier applied to the interval after each successful review
+    pub ease: f64,
+    /// Days between the last review and the next
+    pub interval: u32,
+    /// Successful reviews in a row
+    pub repetitions: u32,
+    /// Date of the next review
+    pub due: NaiveDate,
+}
+
+impl MemoryCard {
+    /// A passage to start memorizing, due for review `today`
+    pub fn new(
+        book: String,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+        today: NaiveDate,
+    ) -> Self {
+        Self {
+            book,
+            chapter,
+            verse_start,
+            verse_end,
+            ease: INITIAL_EASE,
+            interval: 0,
+            repetitions: 0,
+            due: today,
+        }
+    }
+
+    /// e.g. "Psalms 23:1-6"
+    pub fn reference(&self) -> String {
+        if self.verse_start == self.verse_end {
+            format!("{} {}:{}", self.book, self.chapter, self.verse_start)
+        } else {
+            format!(
+                "{} {}:{}-{}",
+                self.book, self.chapter, self.verse_start, self.verse_end
+            )
+        }
+    }
+
+    /// Whether this card covers the same verses as `other`
+    pub fn same_passage(&self, other: &MemoryCard) -> bool {
+        self.book == other.book
+            && self.chapter == other.chapter
+            && self.verse_start == other.verse_start
+            && self.verse_end == other.verse_end
+    }
+
+    /// Schedule the next review after recalling the passage `today` with `grade`, from 0
+    /// (forgotten) to 5 (perfect). Grades below 3 start the passage over the next day.
+    pub fn grade(&mut self, grade: u8, today: NaiveDate) {
+        let grade = grade.min(MAX_GRADE);
+        if grade < 3 {
+            self.repetitions = 0;
+            self.interval = 1;
+        } else {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval as f64 * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        }
+        let miss = (MAX_GRADE - grade) as f64;
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.due = today + Duration::days(self.interval as i64);
+    }
+}
+
+/// Indices of the cards due for review by `today`, longest overdue first
+pub fn due_cards(cards: &[MemoryCard], today: NaiveDate) -> Vec<usize> {
+    let mut due: Vec<usize> = (0..cards.len())
+        .filter(|&idx| cards[idx].due <= today)
+        .collect();
+    due.sort_by_key(|&idx| cards[idx].due);
+    due
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn grades_schedule_the_next_review() {
+        let mut card = MemoryCard::new("Psalms".to_string(), 23, 1, 6, date(3, 1));
+        assert_eq!(card.reference(), "Psalms 23:1-6");
+
+        card.grade(4, date(3, 1));
+        assert_eq!((card.interval, card.due), (1, date(3, 2)));
+        card.grade(5, date(3, 2));
+        assert_eq!((card.interval, card.due), (6, date(3, 8)));
+        // Ease is 2.6 after a 4 and a 5
+        card.grade(3, date(3, 8));
+        assert_eq!((card.interval, card.due), (16, date(3, 24)));
+        assert!((card.ease - 2.46).abs() < 1e-9);
+
+        // Forgetting starts over, and lowers the ease
+        card.grade(1, date(3, 24));
+        assert_eq!((card.repetitions, card.interval), (0, 1));
+        assert!((card.ease - 1.92).abs() < 1e-9);
+
+        let new = MemoryCard::new("John".to_string(), 1, 1, 1, date(3, 20));
+        let cards = vec![card, new];
+        assert_eq!(due_cards(&cards, date(3, 24)), vec![1]);
+        assert_eq!(due_cards(&cards, date(3, 25)), vec![1, 0]);
+    }
+}
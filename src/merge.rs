@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use chrono::NaiveDate;
+
+use crate::bible_structure::BibleStructure;
+use crate::progress::{InsideBookBibleReference, ReadingProgress, ReadingRecord};
+use crate::range_query::RangeMap;
+use crate::utils::get_all_books;
+
+type BookRecords = RangeMap<InsideBookBibleReference, ReadingRecord>;
+
+/// Which side wins for a book read differently in two progress files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep this device's records
+    Mine,
+    /// Take the other file's records
+    Theirs,
+    /// Keep the higher read count and the later date of each verse
+    #[default]
+    Max,
+}
+
+impl Resolution {
+    pub fn name(self) -> &'static str {
+        match self {
+            Resolution::Mine => "mine",
+            Resolution::Theirs => "theirs",
+            Resolution::Max => "max",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Resolution::Mine => Resolution::Theirs,
+            Resolution::Theirs => Resolution::Max,
+            Resolution::Max => Resolution::Mine,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        self.next().next()
+    }
+}
+
+/// How much of a chapter one side has read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChapterSide {
+    /// Verses read at least once
+    pub verses_read: u32,
+    /// Highest read count of any verse
+    pub most_read: u32,
+    pub last_read: Option<NaiveDate>,
+}
+
+/// A chapter whose records differ between the two sides
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChapterDiff {
+    pub chapter: u32,
+    pub verses: u32,
+    pub mine: ChapterSide,
+    pub theirs: ChapterSide,
+}
+
+/// A book each side has readings of that the other doesn't, so neither can simply win
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookConflict {
+    pub book: String,
+    pub chapters: Vec<ChapterDiff>,
+}
+
+/// The higher read count and later date of every verse read on either side
+fn max_records(mine: &BookRecords, theirs: &BookRecords) -> BookRecords {
+    let mut merged = mine.clone();
+    for (range, record) in theirs.iter() {
+        merged.insert_with(range, record.clone(), |old, new| ReadingRecord {
+            read_count: old.read_count.max(new.read_count),
+            last_read: old.last_read.max(new.last_read),
+        });
+    }
+    merged
+}
+
+/// True if both sides have the same records within `range`, however they are split
+fn agree_in(a: &BookRecords, b: &BookRecords, range: Range<InsideBookBibleReference>) -> bool {
+    let within = |x: &BookRecords, y: &BookRecords| {
+        x.overlaps(range.clone()).all(|(part, record)| {
+            y.covers(part.clone()) && y.overlaps(part).all(|(_, other)| other == record)
+        })
+    };
+    within(a, b) && within(b, a)
+}
+
+fn chapter_range(chapter: u32) -> Range<InsideBookBibleReference> {
+    InsideBookBibleReference { chapter, verse: 0 }..InsideBookBibleReference {
+        chapter: chapter + 1,
+        verse: 0,
+    }
+}
+
+fn chapter_side(records: &BookRecords, chapter: u32, verses: u32) -> ChapterSide {
+    let mut side = ChapterSide::default();
+    for (range, record) in records.overlaps(chapter_range(chapter)) {
+        // A range running into the next chapter ends after the last verse
+        let end = if range.end.chapter > chapter {
+            verses + 1
+        } else {
+            range.end.verse
+        };
+        side.verses_read += end.saturating_sub(range.start.verse.max(1));
+        side.most_read = side.most_read.max(record.read_count);
+        side.last_read = side.last_read.max(Some(record.last_read));
+    }
+    side
+}
+
+/// Books whose records differ so that neither side includes the other, in canonical order,
+/// with the chapters that differ. Other books merge without asking: the side that has read
+/// more wins.
+pub fn find_conflicts(
+    bible: &BibleStructure,
+    mine: &ReadingProgress,
+    theirs: &ReadingProgress,
+) -> Vec<BookConflict> {
+    get_all_books(bible)
+        .into_iter()
+        .filter_map(|book| {
+            let (Some(my_records), Some(their_records)) = (
+                mine.books.get(book.as_str()),
+                theirs.books.get(book.as_str()),
+            ) else {
+                return None;
+            };
+            let merged = max_records(my_records, their_records);
+            let everything = InsideBookBibleReference {
+                chapter: 0,
+                verse: 0,
+            }..InsideBookBibleReference {
+                chapter: u32::MAX,
+                verse: 0,
+            };
+            if agree_in(&merged, my_records, everything.clone())
+                || agree_in(&merged, their_records, everything)
+            {
+                return None;
+            }
+            let chapters = bible
+                .ot
+                .get(&book)
+                .or_else(|| bible.nt.get(&book))?
+                .iter()
+                .zip(1..)
+                .filter(|(_, chapter)| {
+                    !agree_in(my_records, their_records, chapter_range(*chapter))
+                })
+                .map(|(&verses, chapter)| ChapterDiff {
+                    chapter,
+                    verses,
+                    mine: chapter_side(my_records, chapter, verses),
+                    theirs: chapter_side(their_records, chapter, verses),
+                })
+                .collect();
+            Some(BookConflict { book, chapters })
+        })
+        .collect()
+}
+
+/// Merge another device's progress into `mine`. Each book's records come from the side chosen
+/// in `resolutions`, or the larger of the two; readings, timed sessions and journal entries
+/// only one side has are added, as are achievements, milestones and campaigns.
+#[tracing::instrument(skip_all)]
+pub fn merge_progress(
+    mine: &ReadingProgress,
+    theirs: &ReadingProgress,
+    resolutions: &HashMap<String, Resolution>,
+) -> ReadingProgress {
+    let mut merged = mine.clone();
+    for (book, their_records) in &theirs.books {
+        let records = match (mine.books.get(book), resolutions.get(book.as_str())) {
+            (None, _) | (Some(_), Some(Resolution::Theirs)) => their_records.clone(),
+            (Some(my_records), Some(Resolution::Mine)) => my_records.clone(),
+            (Some(my_records), _) => max_records(my_records, their_records),
+        };
+        merged.books.insert(book.clone(), records);
+    }
+
+    // Each reading on this side matches one on theirs, so a passage read twice a day on the
+    // other device is added twice
+    let mut unmatched: Vec<_> = mine.history.iter().collect();
+    for event in &theirs.history {
+        match unmatched.iter().position(|recorded| *recorded == event) {
+            Some(idx) => {
+                unmatched.swap_remove(idx);
+            }
+            None => merged.history.push(event.clone()),
+        }
+    }
+    merged.history.sort_by_key(|event| event.date);
+
+    let mut unmatched: Vec<_> = mine.sessions.iter().collect();
+    for session in &theirs.sessions {
+        match unmatched.iter().position(|recorded| *recorded == session) {
+            Some(idx) => {
+                unmatched.swap_remove(idx);
+            }
+            None => merged.sessions.push(session.clone()),
+        }
+    }
+    merged.sessions.sort_by_key(|session| session.date);
+
+    for (date, entry) in &theirs.journal {
+        merged.journal.entry(*date).or_insert_with(|| entry.clone());
+    }
+    for (id, date) in &theirs.achievements {
+        let unlocked = merged.achievements.entry(id.clone()).or_insert(*date);
+        *unlocked = (*unlocked).min(*date);
+    }
+    for milestone in &theirs.milestones {
+        if !merged.milestones.contains(milestone) {
+            merged.milestones.push(milestone.clone());
+        }
+    }
+    merged.milestones.sort_by_key(|milestone| milestone.date);
+    for campaign in &theirs.campaigns {
+        if !merged.campaigns.iter().any(|c| c.name == campaign.name) {
+            merged.campaigns.push(campaign.clone());
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn only_diverging_books_conflict() {
+        let bible = get_bible_structure();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let mut mine = ReadingProgress::new();
+        mine.record_reading_on("Ruth", 1, 1, 22, 1, day(1));
+        mine.record_reading_on("Jude", 1, 1, 25, 1, day(1));
+        let mut theirs = mine.clone();
+        // Theirs is ahead in Ruth, and each side read different parts of Jude
+        theirs.record_reading_on("Ruth", 2, 1, 23, 1, day(2));
+        theirs.record_reading_on("Jude", 1, 1, 10, 1, day(3));
+        mine.record_reading_on("Jude", 1, 11, 25, 2, day(2));
+
+        let conflicts = find_conflicts(bible, &mine, &theirs);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].book, "Jude");
+        let jude = &conflicts[0].chapters[0];
+        assert_eq!((jude.mine.most_read, jude.theirs.most_read), (3, 2));
+        assert_eq!(jude.theirs.last_read, Some(day(3)));
+
+        let merged = merge_progress(&mine, &theirs, &HashMap::new());
+        assert_eq!(merged.books["Ruth"], theirs.books["Ruth"]);
+        assert_eq!(merged.history.len(), 5);
+        let jude_counts: Vec<u32> = merged.books["Jude"]
+            .iter()
+            .map(|(_, record)| record.read_count)
+            .collect();
+        assert_eq!(jude_counts, vec![2, 3]);
+
+        let resolutions = HashMap::from([("Jude".to_string(), Resolution::Theirs)]);
+        let merged = merge_progress(&mine, &theirs, &resolutions);
+        assert_eq!(merged.books["Jude"], theirs.books["Jude"]);
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::bible_structure::BibleStructure;
+use crate::coverage::book_min_read_count;
+use crate::progress::ReadingProgress;
+
+/// Chapters read in a year are celebrated at every multiple of this
+pub const CHAPTERS_MILESTONE: u32 = 100;
+
+/// A point reached in the reading, celebrated when a save reaches it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MilestoneKind {
+    /// Every verse of a book read `times` times
+    Book {
+        book: String,
+        times: u32,
+    },
+    OldTestament {
+        times: u32,
+    },
+    NewTestament {
+        times: u32,
+    },
+    Bible {
+        times: u32,
+    },
+    /// The `chapters`th chapter read in `year`, a multiple of [`CHAPTERS_MILESTONE`]
+    ChaptersInYear {
+        year: i32,
+        chapters: u32,
+    },
+}
+
+/// A milestone and the day it was reached
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Milestone {
+    pub date: NaiveDate,
+    pub kind: MilestoneKind,
+}
+
+impl MilestoneKind {
+    /// e.g. "Finished Romans" or "Finished the New Testament for the 2nd time"
+    pub fn description(&self) -> String {
+        let finished = |what: &str, times: u32| match times {
+            1 => format!("Finished {}", what),
+            times => format!("Finished {} for the {} time", what, ordinal(times)),
+        };
+        match self {
+            MilestoneKind::Book { book, times } => finished(book, *times),
+            MilestoneKind::OldTestament { times } => finished("the Old Testament", *times),
+            MilestoneKind::NewTestament { times } => finished("the New Testament", *times),
+            MilestoneKind::Bible { times } => finished("the whole Bible", *times),
+            MilestoneKind::ChaptersInYear { year, chapters } => {
+                format!("Read your {} chapter of {}", ordinal(*chapters), year)
+            }
+        }
+    }
+}
+
+/// "1st", "2nd", "3rd", "4th", ..., "11th", ..., "21st"
+fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// How far the reading has come, compared before and after a save to find the milestones it
+/// reached
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MilestoneLevels {
+    /// Times each book has been read through
+    books: HashMap<String, u32>,
+    old_testament: u32,
+    new_testament: u32,
+    /// Year of `chapters_in_year`
+    year: i32,
+    /// Chapters read in `year`, a chapter read on several days counting once for each
+    chapters_in_year: u32,
+}
+
+impl MilestoneLevels {
+    pub fn new(bible: &BibleStructure, progress: &ReadingProgress, today: NaiveDate) -> Self {
+        let times_through = |books: &indexmap::IndexMap<String, Vec<u32>>| {
+            books
+                .keys()
+                .map(|book| book_min_read_count(bible, progress, book))
+                .min()
+                .unwrap_or(0)
+        };
+        let chapters_in_year = progress
+            .history
+            .iter()
+            .filter(|event| event.date.year() == today.year())
+            .map(|event| (event.date, event.book.as_str(), event.chapter))
+            .collect::<HashSet<_>>()
+            .len() as u32;
+        Self {
+            books: progress
+                .books
+                .keys()
+                .map(|book| (book.to_string(), book_min_read_count(bible, progress, book)))
+                .collect(),
+            old_testament: times_through(&bible.ot),
+            new_testament: times_through(&bible.nt),
+            year: today.year(),
+            chapters_in_year,
+        }
+    }
+
+    /// Milestones reached between `earlier` and these levels, dated `today`: books first, then
+    /// testaments and the Bible, then the chapter count
+    pub fn reached_since(
+        &self,
+        bible: &BibleStructure,
+        earlier: &MilestoneLevels,
+        today: NaiveDate,
+    ) -> Vec<Milestone> {
+        let mut reached = Vec::new();
+        for book in bible.ot.keys().chain(bible.nt.keys()) {
+            let times = self.books.get(book).copied().unwrap_or(0);
+            if times > earlier.books.get(book).copied().unwrap_or(0) {
+                reached.push(MilestoneKind::Book {
+                    book: book.clone(),
+                    times,
+                });
+            }
+        }
+        if self.old_testament > earlier.old_testament {
+            reached.push(MilestoneKind::OldTestament {
+                times: self.old_testament,
+            });
+        }
+        if self.new_testament > earlier.new_testament {
+            reached.push(MilestoneKind::NewTestament {
+                times: self.new_testament,
+            });
+        }
+        let bible_times = self.old_testament.min(self.new_testament);
+        if bible_times > earlier.old_testament.min(earlier.new_testament) {
+            reached.push(MilestoneKind::Bible { times: bible_times });
+        }
+        // A new year starts counting from zero
+        let earlier_chapters = if earlier.year == self.year {
+            earlier.chapters_in_year
+        } else {
+            0
+        };
+        let hundreds = self.chapters_in_year / CHAPTERS_MILESTONE;
+        if hundreds > earlier_chapters / CHAPTERS_MILESTONE {
+            reached.push(MilestoneKind::ChaptersInYear {
+                year: self.year,
+                chapters: hundreds * CHAPTERS_MILESTONE,
+            });
+        }
+        reached
+            .into_iter()
+            .map(|kind| Milestone { date: today, kind })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn milestones_are_reached_once() {
+        let bible = get_bible_structure();
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading_on("Jude", 1, 1, 20, 1, today);
+        let before = MilestoneLevels::new(bible, &progress, today);
+
+        progress.record_reading_on("Jude", 1, 21, 25, 1, today);
+        let after = MilestoneLevels::new(bible, &progress, today);
+        let reached = after.reached_since(bible, &before, today);
+        assert_eq!(
+            reached,
+            vec![Milestone {
+                date: today,
+                kind: MilestoneKind::Book {
+                    book: "Jude".to_string(),
+                    times: 1
+                },
+            }]
+        );
+        assert_eq!(reached[0].kind.description(), "Finished Jude");
+        assert!(after.reached_since(bible, &after, today).is_empty());
+
+        for (chapter_idx, &verses) in bible.ot["Psalms"].iter().take(99).enumerate() {
+            progress.record_reading_on("Psalms", chapter_idx as u32 + 1, 1, verses, 1, today);
+        }
+        let hundred = MilestoneLevels::new(bible, &progress, today);
+        assert_eq!(
+            hundred.reached_since(bible, &after, today)[0].kind,
+            MilestoneKind::ChaptersInYear {
+                year: 2025,
+                chapters: 100
+            }
+        );
+        assert_eq!(
+            MilestoneKind::NewTestament { times: 2 }.description(),
+            "Finished the New Testament for the 2nd time"
+        );
+    }
+}
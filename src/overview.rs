@@ -0,0 +1,143 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::bible_structure::{BibleStructure, BookFilter};
+use crate::coverage::{book_min_read_count, book_percent_read, chapter_read_count, percent_read};
+use crate::progress::ReadingProgress;
+use crate::stats::{current_streak, longest_streak, neglected_books, reading_days};
+
+/// Coverage of a testament, for front ends drawing their own version of the dashboard's tree
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TestamentOverview {
+    pub name: &'static str,
+    pub percent_read: f64,
+    pub books: Vec<BookOverview>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookOverview {
+    pub book: String,
+    pub percent_read: f64,
+    /// Times every verse of the book has been read
+    pub times_read: u32,
+    pub chapters: Vec<ChapterOverview>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChapterOverview {
+    pub chapter: u32,
+    /// Times every verse of the chapter has been read
+    pub times_read: u32,
+    /// True if any verse has been read
+    pub started: bool,
+}
+
+/// The figures of the Stats tab's overview
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsOverview {
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub days_read: usize,
+    pub percent_read: f64,
+    pub old_testament_percent_read: f64,
+    pub new_testament_percent_read: f64,
+    /// Books not read in the last `days` days, never-read books first
+    pub neglected_books: Vec<String>,
+}
+
+/// Both testaments with every book and chapter, in canonical order
+pub fn progress_tree(bible: &BibleStructure, progress: &ReadingProgress) -> Vec<TestamentOverview> {
+    [
+        ("Old Testament", &bible.ot, BookFilter::OldTestament),
+        ("New Testament", &bible.nt, BookFilter::NewTestament),
+    ]
+    .into_iter()
+    .map(|(name, books, filter)| TestamentOverview {
+        name,
+        percent_read: percent_read(bible, progress, filter),
+        books: books
+            .iter()
+            .map(|(book, chapters)| BookOverview {
+                book: book.clone(),
+                percent_read: book_percent_read(bible, progress, book),
+                times_read: book_min_read_count(bible, progress, book),
+                chapters: (1..=chapters.len() as u32)
+                    .map(|chapter| {
+                        let (times_read, started) =
+                            chapter_read_count(bible, progress, book, chapter);
+                        ChapterOverview {
+                            chapter,
+                            times_read,
+                            started,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect(),
+    })
+    .collect()
+}
+
+pub fn stats_overview(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    today: NaiveDate,
+    neglected_days: u32,
+) -> StatsOverview {
+    StatsOverview {
+        current_streak: current_streak(&progress.history, today),
+        longest_streak: longest_streak(&progress.history),
+        days_read: reading_days(&progress.history).len(),
+        percent_read: percent_read(bible, progress, BookFilter::All),
+        old_testament_percent_read: percent_read(bible, progress, BookFilter::OldTestament),
+        new_testament_percent_read: percent_read(bible, progress, BookFilter::NewTestament),
+        neglected_books: neglected_books(bible, progress, today, neglected_days)
+            .into_iter()
+            .map(|neglected| neglected.book)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn tree_and_stats_cover_the_readings() {
+        let bible = get_bible_structure();
+        let day = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading_on("Jude", 1, 1, 25, 2, day);
+        progress.record_reading_on("John", 3, 1, 18, 1, day.succ_opt().unwrap());
+
+        let tree = progress_tree(bible, &progress);
+        assert_eq!(tree.len(), 2);
+        let jude = tree[1]
+            .books
+            .iter()
+            .find(|book| book.book == "Jude")
+            .unwrap();
+        assert_eq!((jude.percent_read, jude.times_read), (100.0, 2));
+        let john = tree[1]
+            .books
+            .iter()
+            .find(|book| book.book == "John")
+            .unwrap();
+        assert_eq!(john.chapters.len(), 21);
+        assert_eq!(
+            john.chapters[2],
+            ChapterOverview {
+                chapter: 3,
+                times_read: 0,
+                started: true
+            }
+        );
+
+        let stats = stats_overview(bible, &progress, day.succ_opt().unwrap(), 180);
+        assert_eq!((stats.current_streak, stats.days_read), (2, 2));
+        assert_eq!(stats.old_testament_percent_read, 0.0);
+        assert!(!stats.neglected_books.contains(&"Jude".to_string()));
+        assert_eq!(stats.neglected_books.len(), 64);
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::bible_structure::BibleStructure;
+use crate::progress::{ReadingProgress, ReadingSource};
+
+/// Reading speed assumed before any reading is timed: about 250 words a minute, at roughly
+/// 25 words a verse
+pub const DEFAULT_SECONDS_PER_VERSE: f64 = 6.0;
+/// Weight of the default speed, as minutes of reading at that speed, so the first timed
+/// sessions nudge the estimate and many of them replace it
+const PRIOR_MINUTES: f64 = 60.0;
+
+/// Estimated reading speed in seconds per verse, from the timed sessions: the minutes timed on
+/// each day against the verses read that day, blended with the default speed. Listened and
+/// overwritten readings don't count.
+pub fn seconds_per_verse(progress: &ReadingProgress) -> f64 {
+    let mut minutes_by_date: HashMap<NaiveDate, u32> = HashMap::new();
+    for session in &progress.sessions {
+        *minutes_by_date.entry(session.date).or_default() += session.minutes;
+    }
+    let mut verses_by_date: HashMap<NaiveDate, u32> = HashMap::new();
+    for event in &progress.history {
+        if minutes_by_date.contains_key(&event.date)
+            && event.source == ReadingSource::Read
+            && !event.overwrite
+        {
+            *verses_by_date.entry(event.date).or_default() +=
+                (event.verse_end + 1 - event.verse_start) * event.read_count;
+        }
+    }
+    // Days with a session but nothing recorded say nothing about speed
+    let timed_minutes: u32 = verses_by_date
+        .keys()
+        .map(|date| minutes_by_date[date])
+        .sum();
+    let verses: u32 = verses_by_date.values().sum();
+
+    let prior_seconds = PRIOR_MINUTES * 60.0;
+    (prior_seconds + timed_minutes as f64 * 60.0)
+        / (prior_seconds / DEFAULT_SECONDS_PER_VERSE + verses as f64)
+}
+
+/// Estimated minutes to read a chapter at `seconds_per_verse`
+pub fn chapter_minutes(
+    bible: &BibleStructure,
+    book: &str,
+    chapter: u32,
+    seconds_per_verse: f64,
+) -> f64 {
+    let verses = bible
+        .ot
+        .get(book)
+        .or_else(|| bible.nt.get(book))
+        .and_then(|chapters| chapters.get((chapter as usize).checked_sub(1)?))
+        .copied()
+        .unwrap_or(0);
+    verses as f64 * seconds_per_verse / 60.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn pace_moves_towards_timed_sessions() {
+        let bible = get_bible_structure();
+        let day = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let mut progress = ReadingProgress::new();
+        assert_eq!(seconds_per_verse(&progress), DEFAULT_SECONDS_PER_VERSE);
+        // Psalm 119 (176 verses) at the default speed
+        assert!((chapter_minutes(bible, "Psalms", 119, 6.0) - 17.6).abs() < 1e-9);
+
+        // Genesis 1-20 timed at 3 seconds a verse, against an hour at the default speed
+        for chapter in 1..=20 {
+            let verses = bible.ot["Genesis"][chapter as usize - 1];
+            progress.record_reading_on("Genesis", chapter, 1, verses, 1, day);
+        }
+        let verses: u32 = bible.ot["Genesis"][..20].iter().sum();
+        progress.record_session(day, verses / 20);
+        let pace = seconds_per_verse(&progress);
+        assert!(pace < DEFAULT_SECONDS_PER_VERSE && pace > 3.0);
+        // Untimed days don't count
+        progress.record_reading_on("Exodus", 1, 1, 22, 1, day.succ_opt().unwrap());
+        assert_eq!(seconds_per_verse(&progress), pace);
+    }
+}
@@ -0,0 +1,1009 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::bible_structure::BibleStructure;
+use crate::progress::ReadingEvent;
+use crate::reference::parse_passage;
+use crate::stats::reading_days;
+
+/// Longest plan a plan file can describe, in days. Past this a day is taken to be a typo (e.g. a
+/// date written without dashes) rather than allocating a day for every number up to it.
+const MAX_PLAN_DAYS: usize = 10 * 366;
+
+/// Books in the approximate order their events happened (or they were written, for the
+/// epistles), used by the chronological plan
+const CHRONOLOGICAL_BOOKS: [&str; 66] = [
+    "Genesis",
+    "Job",
+    "Exodus",
+    "Leviticus",
+    "Numbers",
+    "Deuteronomy",
+    "Joshua",
+    "Judges",
+    "Ruth",
+    "I Samuel",
+    "II Samuel",
+    "I Chronicles",
+    "Psalms",
+    "Proverbs",
+    "Ecclesiastes",
+    "Song of Solomon",
+    "I Kings",
+    "II Kings",
+    "II Chronicles",
+    "Obadiah",
+    "Joel",
+    "Jonah",
+    "Amos",
+    "Hosea",
+    "Isaiah",
+    "Micah",
+    "Nahum",
+    "Zephaniah",
+    "Habakkuk",
+    "Jeremiah",
+    "Lamentations",
+    "Ezekiel",
+    "Daniel",
+    "Ezra",
+    "Haggai",
+    "Zechariah",
+    "Esther",
+    "Nehemiah",
+    "Malachi",
+    "Matthew",
+    "Mark",
+    "Luke",
+    "John",
+    "Acts",
+    "James",
+    "Galatians",
+    "I Thessalonians",
+    "II Thessalonians",
+    "I Corinthians",
+    "II Corinthians",
+    "Romans",
+    "Ephesians",
+    "Philippians",
+    "Colossians",
+    "Philemon",
+    "I Timothy",
+    "Titus",
+    "I Peter",
+    "II Peter",
+    "II Timothy",
+    "Hebrews",
+    "Jude",
+    "I John",
+    "II John",
+    "III John",
+    "Revelation of John",
+];
+
+/// A built-in reading plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanKind {
+    Canonical,
+    Chronological,
+    #[serde(rename = "mcheyne")]
+    MCheyne,
+    #[serde(rename = "new_testament_90")]
+    NewTestament90,
+    BibleInAYear,
+}
+
+impl PlanKind {
+    /// Every built-in plan, in the order they are listed
+    pub const ALL: [PlanKind; 5] = [
+        PlanKind::Canonical,
+        PlanKind::Chronological,
+        PlanKind::MCheyne,
+        PlanKind::NewTestament90,
+        PlanKind::BibleInAYear,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PlanKind::Canonical => "Canonical",
+            PlanKind::Chronological => "Chronological",
+            PlanKind::MCheyne => "M'Cheyne",
+            PlanKind::NewTestament90 => "New Testament in 90 Days",
+            PlanKind::BibleInAYear => "Bible in a Year",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            PlanKind::Canonical => "Genesis to Revelation in order, over a year",
+            PlanKind::Chronological => "The whole Bible in the order events happened, over a year",
+            PlanKind::MCheyne => {
+                "Four readings a day: the Old Testament once, the New Testament and Psalms twice"
+            }
+            PlanKind::NewTestament90 => "Matthew to Revelation in 90 days",
+            PlanKind::BibleInAYear => {
+                "A portion of the Old Testament and of the New Testament each day, over a year"
+            }
+        }
+    }
+
+    /// The plan's daily assignments
+    pub fn build(self, bible: &BibleStructure) -> Plan {
+        let chapters_of = |books: &[&str]| -> Vec<PlanItem> {
+            books
+                .iter()
+                .flat_map(|book| book_chapters(bible, book))
+                .collect()
+        };
+        let ot: Vec<&str> = bible.ot.keys().map(String::as_str).collect();
+        let nt: Vec<&str> = bible.nt.keys().map(String::as_str).collect();
+        let days = match self {
+            PlanKind::Canonical => spread(&chapters_of(&[ot, nt].concat()), 365),
+            PlanKind::Chronological => spread(&chapters_of(&CHRONOLOGICAL_BOOKS), 365),
+            PlanKind::MCheyne => {
+                // The plan's four columns start at Genesis, Matthew, Ezra and Acts
+                let ezra = ot
+                    .iter()
+                    .position(|&book| book == "Ezra")
+                    .unwrap_or(ot.len());
+                let acts = nt.iter().position(|&book| book == "Acts").unwrap_or(0);
+                let gospels_after_acts = [&nt[acts..], &nt[..acts]].concat();
+                merge(&[
+                    spread(&chapters_of(&ot[..ezra]), 365),
+                    spread(&chapters_of(&nt), 365),
+                    spread(&chapters_of(&ot[ezra..]), 365),
+                    spread(&chapters_of(&gospels_after_acts), 365),
+                ])
+            }
+            PlanKind::NewTestament90 => spread(&chapters_of(&nt), 90),
+            PlanKind::BibleInAYear => merge(&[
+                spread(&chapters_of(&ot), 365),
+                spread(&chapters_of(&nt), 365),
+            ]),
+        };
+        Plan {
+            name: self.name().to_string(),
+            start: None,
+            days,
+        }
+    }
+}
+
+/// Which plan is being followed: a built-in one, or a plan file from the plans directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PlanId {
+    BuiltIn(PlanKind),
+    /// A plan file, by its file name without the extension
+    Custom {
+        custom: String,
+    },
+}
+
+impl PlanId {
+    /// The plan's daily assignments, reading its file for a custom plan
+    pub fn load(&self, bible: &BibleStructure, plans_dir: &Path) -> Result<Plan, String> {
+        match self {
+            PlanId::BuiltIn(kind) => Ok(kind.build(bible)),
+            PlanId::Custom { custom } => custom_plan_files(plans_dir)
+                .into_iter()
+                .find(|path| file_stem(path) == *custom)
+                .ok_or_else(|| format!("No plan file named {} in {}", custom, plans_dir.display()))
+                .and_then(|path| load_custom_plan(bible, &path)),
+        }
+    }
+}
+
+/// One chapter assigned by a plan
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlanItem {
+    pub book: String,
+    pub chapter: u32,
+}
+
+/// A reading plan: the chapters to read on each day, first day first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub name: String,
+    /// The date of the first day, for a plan file written with dates rather than day numbers
+    pub start: Option<NaiveDate>,
+    pub days: Vec<Vec<PlanItem>>,
+}
+
+impl Plan {
+    /// Whether each item of each day is done, given the readings logged in `history` since
+    /// the plan was started. A chapter counts once per day it was read, so a plan that
+    /// assigns a chapter twice needs it read on two days.
+    pub fn completion(&self, state: &PlanState, history: &[ReadingEvent]) -> Vec<Vec<bool>> {
+        let mut times_read: HashMap<(&str, u32), usize> = HashMap::new();
+        for date in reading_days(history).range(state.start..) {
+            let mut read_that_day: Vec<(&str, u32)> = history
+                .iter()
+                .filter(|event| event.date == *date)
+                .map(|event| (event.book.as_str(), event.chapter))
+                .collect();
+            read_that_day.sort();
+            read_that_day.dedup();
+            for chapter in read_that_day {
+                *times_read.entry(chapter).or_default() += 1;
+            }
+        }
+
+        self.days
+            .iter()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| {
+                        times_read
+                            .get_mut(&(item.book.as_str(), item.chapter))
+                            .is_some_and(|times| {
+                                let done = *times > 0;
+                                *times = times.saturating_sub(1);
+                                done
+                            })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A way to reschedule the days that fell behind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatchUp {
+    /// Spread the missed days over the next `days` days, on top of those days' own readings
+    Spread { days: usize },
+    /// Move every unfinished day later, so the first one falls on today
+    Shift,
+    /// Read the missed days on the coming Saturdays and Sundays, one extra day on each
+    Weekends,
+}
+
+impl CatchUp {
+    pub fn description(self) -> String {
+        match self {
+            CatchUp::Spread { days } => format!(
+                "Spread the missed days over the next {} day{}",
+                days,
+                if days == 1 { "" } else { "s" }
+            ),
+            CatchUp::Shift => "Shift the rest of the plan so it resumes today".to_string(),
+            CatchUp::Weekends => "Double up on the coming weekends".to_string(),
+        }
+    }
+}
+
+/// The plan being followed and when it was started, stored in the progress file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanState {
+    pub plan: PlanId,
+    pub start: NaiveDate,
+    /// Days moved by catching up: the day each now falls on, by its day in the plan, both
+    /// counted from the start date
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub moved: BTreeMap<usize, usize>,
+}
+
+impl PlanState {
+    pub fn new(plan: PlanId, start: NaiveDate) -> Self {
+        Self {
+            plan,
+            start,
+            moved: BTreeMap::new(),
+        }
+    }
+
+    /// The day plan day `day` falls on, counting from the start date
+    fn scheduled_day(&self, day: usize) -> usize {
+        self.moved.get(&day).copied().unwrap_or(day)
+    }
+
+    /// The plan as laid out on the calendar after catching up: the chapters to read on each
+    /// day from the start date
+    pub fn schedule(&self, plan: &Plan) -> Plan {
+        let mut days = vec![Vec::new(); plan.days.len()];
+        for (day, items) in plan.days.iter().enumerate() {
+            let scheduled = self.scheduled_day(day);
+            if days.len() <= scheduled {
+                days.resize(scheduled + 1, Vec::new());
+            }
+            days[scheduled].extend(items.iter().cloned());
+        }
+        Plan {
+            name: plan.name.clone(),
+            start: plan.start,
+            days,
+        }
+    }
+
+    /// Reschedule the days before `today` that aren't finished yet, given the readings in
+    /// `history`
+    pub fn catch_up(
+        &mut self,
+        plan: &Plan,
+        history: &[ReadingEvent],
+        today: NaiveDate,
+        strategy: CatchUp,
+    ) {
+        let scheduled = self.schedule(plan);
+        let completion = scheduled.completion(self, history);
+        let today_day = (today - self.start).num_days().max(0) as usize;
+        let missed: Vec<usize> = (0..today_day.min(scheduled.days.len()))
+            .filter(|&day| !completion[day].iter().all(|&done| done))
+            .collect();
+        let Some(&first_missed) = missed.first() else {
+            return;
+        };
+
+        // Where each day of the calendar moves to
+        let targets: HashMap<usize, usize> = match strategy {
+            CatchUp::Shift => (first_missed..scheduled.days.len())
+                .map(|day| (day, day + today_day - first_missed))
+                .collect(),
+            CatchUp::Spread { days } => missed
+                .iter()
+                .enumerate()
+                .map(|(index, &day)| (day, today_day + index * days.max(1) / missed.len()))
+                .collect(),
+            CatchUp::Weekends => {
+                let weekend_days = (today_day..).filter(|&day| {
+                    let date = self.start + Duration::days(day as i64);
+                    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+                });
+                missed.iter().copied().zip(weekend_days).collect()
+            }
+        };
+        for day in 0..plan.days.len() {
+            if let Some(&target) = targets.get(&self.scheduled_day(day)) {
+                if target == day {
+                    self.moved.remove(&day);
+                } else {
+                    self.moved.insert(day, target);
+                }
+            }
+        }
+    }
+
+    /// Index of the plan day `today` falls on, counting from 0 for the start date. Past the
+    /// end of the plan this is the last day.
+    pub fn current_day(&self, plan: &Plan, today: NaiveDate) -> usize {
+        let elapsed = (today - self.start).num_days().max(0) as usize;
+        elapsed.min(plan.days.len().saturating_sub(1))
+    }
+}
+
+/// How many days ahead of schedule (positive) or behind (negative) the plan is on
+/// `current_day`, by the number of finished days. Today's reading isn't due until the day is
+/// over, so finishing it keeps the plan on schedule and each further day puts it a day ahead.
+pub fn schedule_offset(completion: &[Vec<bool>], current_day: usize) -> i64 {
+    let finished = completion
+        .iter()
+        .filter(|items| items.iter().all(|&done| done))
+        .count();
+    if finished < current_day {
+        -((current_day - finished) as i64)
+    } else {
+        finished.saturating_sub(current_day + 1) as i64
+    }
+}
+
+/// "on schedule", "1 day ahead" or "3 days behind", for a `schedule_offset`
+pub fn describe_offset(offset: i64) -> String {
+    let days = offset.unsigned_abs();
+    let plural = if days == 1 { "" } else { "s" };
+    match offset {
+        0 => "on schedule".to_string(),
+        offset if offset > 0 => format!("{} day{} ahead", days, plural),
+        _ => format!("{} day{} behind", days, plural),
+    }
+}
+
+/// Today's chapters of the plan being followed, after a line such as
+/// "M'Cheyne, day 12 of 365 (2 days behind)", as printed by `brp plan today`
+pub fn today_lines(
+    plan: &Plan,
+    state: &PlanState,
+    history: &[ReadingEvent],
+    today: NaiveDate,
+) -> Vec<String> {
+    let plan = state.schedule(plan);
+    let completion = plan.completion(state, history);
+    let day = state.current_day(&plan, today);
+    let mut lines = vec![format!(
+        "{}, day {} of {} ({})",
+        plan.name,
+        day + 1,
+        plan.days.len(),
+        describe_offset(schedule_offset(&completion, day))
+    )];
+    if plan.days[day].is_empty() {
+        lines.push("Nothing to read today".to_string());
+    }
+    lines.extend(
+        plan.days[day]
+            .iter()
+            .zip(&completion[day])
+            .map(|(item, &done)| {
+                format!(
+                    "[{}] {} {}",
+                    if done { "x" } else { " " },
+                    item.book,
+                    item.chapter
+                )
+            }),
+    );
+    lines
+}
+
+/// The plan being followed as a YAML plan file dated day by day, so someone importing it reads
+/// the same chapters on the same days, catching up included
+pub fn shared_plan_file(plan: &Plan, state: &PlanState) -> String {
+    #[derive(Serialize)]
+    struct SharedPlan {
+        name: String,
+        days: BTreeMap<NaiveDate, Vec<String>>,
+    }
+
+    let days = state
+        .schedule(plan)
+        .days
+        .iter()
+        .enumerate()
+        .filter(|(_, items)| !items.is_empty())
+        .map(|(day, items)| {
+            let date = state.start + Duration::days(day as i64);
+            (date, day_references(items))
+        })
+        .collect();
+    let shared = SharedPlan {
+        name: plan.name.clone(),
+        days,
+    };
+    serde_yaml::to_string(&shared).unwrap_or_default()
+}
+
+/// A day's chapters as references, runs of chapters of a book joined, e.g. "Matthew 1-2"
+fn day_references(items: &[PlanItem]) -> Vec<String> {
+    let mut runs: Vec<(&str, u32, u32)> = Vec::new();
+    for item in items {
+        match runs.last_mut() {
+            Some((book, _, last)) if *book == item.book && *last + 1 == item.chapter => {
+                *last = item.chapter;
+            }
+            _ => runs.push((&item.book, item.chapter, item.chapter)),
+        }
+    }
+    runs.into_iter()
+        .map(|(book, first, last)| {
+            if first == last {
+                format!("{} {}", book, first)
+            } else {
+                format!("{} {}-{}", book, first, last)
+            }
+        })
+        .collect()
+}
+
+/// Copy a plan file someone shared into `plans_dir`, after checking it, and return the plan
+/// with its id. A different plan file of the same name isn't overwritten.
+pub fn import_plan_file(
+    bible: &BibleStructure,
+    path: &Path,
+    plans_dir: &Path,
+) -> Result<(PlanId, Plan), String> {
+    let plan = load_custom_plan(bible, path)?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("{} is not a file", path.display()))?;
+    let destination = plans_dir.join(file_name);
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    match fs::read_to_string(&destination) {
+        Ok(existing) if existing != content => {
+            return Err(format!(
+                "{} already exists; rename the file to import it",
+                destination.display()
+            ));
+        }
+        Ok(_) => {}
+        Err(_) => fs::create_dir_all(plans_dir)
+            .and_then(|_| fs::write(&destination, &content))
+            .map_err(|e| format!("Couldn't write {}: {}", destination.display(), e))?,
+    }
+    let id = PlanId::Custom {
+        custom: file_stem(path),
+    };
+    Ok((id, plan))
+}
+
+/// Every built-in plan, then the plan files in `plans_dir`, each with its assignments or why
+/// its file couldn't be used
+pub fn available_plans(
+    bible: &BibleStructure,
+    plans_dir: &Path,
+) -> Vec<(PlanId, Result<Plan, String>)> {
+    let built_in = PlanKind::ALL
+        .into_iter()
+        .map(|kind| (PlanId::BuiltIn(kind), Ok(kind.build(bible))));
+    let custom = custom_plan_files(plans_dir).into_iter().map(|path| {
+        (
+            PlanId::Custom {
+                custom: file_stem(&path),
+            },
+            load_custom_plan(bible, &path),
+        )
+    });
+    built_in.chain(custom).collect()
+}
+
+/// The `.yaml`, `.yml` and `.csv` files in `plans_dir`, by name
+fn custom_plan_files(plans_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(plans_dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| matches!(extension, "yaml" | "yml" | "csv"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Read a plan file, named after the file unless it sets its own name
+fn load_custom_plan(bible: &BibleStructure, path: &Path) -> Result<Plan, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    let is_csv = path.extension().is_some_and(|extension| extension == "csv");
+    parse_plan_file(bible, &file_stem(path), &content, is_csv)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parse a plan file. YAML files map each day to one reference or a list of them, under
+/// `days`, with an optional `name`:
+///
+/// ```yaml
+/// name: Gospels in Lent
+/// days:
+///   1: [Matthew 1-2, Psalms 1]
+///   2: Matthew 3-4
+/// ```
+///
+/// CSV files have a `day,reference` row per reference. Days are numbered from 1, or are all
+/// dates (YYYY-MM-DD), in which case the plan starts on the first date. References can be
+/// anything the Passage field accepts, and each chapter they touch is assigned.
+pub fn parse_plan_file(
+    bible: &BibleStructure,
+    default_name: &str,
+    content: &str,
+    is_csv: bool,
+) -> Result<Plan, String> {
+    let (name, entries) = if is_csv {
+        (None, csv_plan_entries(content)?)
+    } else {
+        yaml_plan_entries(content)?
+    };
+
+    let days: Vec<(DayKey, &str)> = entries
+        .iter()
+        .map(|(day, reference)| Ok((parse_day_key(day)?, reference.as_str())))
+        .collect::<Result<_, String>>()?;
+    let first_date = days
+        .iter()
+        .filter_map(|(day, _)| match day {
+            DayKey::Date(date) => Some(*date),
+            DayKey::Number(_) => None,
+        })
+        .min();
+    if first_date.is_some() && days.iter().any(|(day, _)| matches!(day, DayKey::Number(_))) {
+        return Err("Days must be all numbers or all dates".to_string());
+    }
+
+    let mut plan = Plan {
+        name: name.unwrap_or_else(|| default_name.to_string()),
+        start: first_date,
+        days: Vec::new(),
+    };
+    for (day, reference) in days {
+        let index = match day {
+            DayKey::Number(number) => number as usize - 1,
+            DayKey::Date(date) => first_date.map_or(0, |first| (date - first).num_days() as usize),
+        };
+        if index >= MAX_PLAN_DAYS {
+            return Err(format!(
+                "Day {} is too far out (plans can be at most {} days long)",
+                day, MAX_PLAN_DAYS
+            ));
+        }
+        let passage = parse_passage(bible, reference).map_err(|e| format!("Day {}: {}", day, e))?;
+        if plan.days.len() <= index {
+            plan.days.resize(index + 1, Vec::new());
+        }
+        plan.days[index].extend(passage.chapter_ranges(bible).into_iter().map(
+            |(chapter, _, _)| PlanItem {
+                book: passage.book.clone(),
+                chapter,
+            },
+        ));
+    }
+    if plan.days.is_empty() {
+        return Err("The plan has no days".to_string());
+    }
+    Ok(plan)
+}
+
+/// A day of a plan file: its number, or the date it falls on
+#[derive(Debug, Clone, Copy)]
+enum DayKey {
+    Number(u32),
+    Date(NaiveDate),
+}
+
+impl std::fmt::Display for DayKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayKey::Number(number) => write!(f, "{}", number),
+            DayKey::Date(date) => write!(f, "{}", date),
+        }
+    }
+}
+
+fn parse_day_key(day: &str) -> Result<DayKey, String> {
+    let day = day.trim();
+    if let Ok(number) = day.parse::<u32>() {
+        return if number == 0 {
+            Err("Days are numbered from 1".to_string())
+        } else {
+            Ok(DayKey::Number(number))
+        };
+    }
+    NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .map(DayKey::Date)
+        .map_err(|_| format!("Invalid day: {}. Expected a day number or YYYY-MM-DD", day))
+}
+
+/// The (day, reference) pairs of a plan file, in file order
+type PlanEntries = Vec<(String, String)>;
+
+/// The name and entries of a YAML plan file
+fn yaml_plan_entries(content: &str) -> Result<(Option<String>, PlanEntries), String> {
+    let file: serde_yaml::Value = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+    let name = file
+        .get("name")
+        .and_then(|name| name.as_str())
+        .map(String::from);
+    let days = file
+        .get("days")
+        .and_then(|days| days.as_mapping())
+        .ok_or_else(|| "Expected a `days` mapping".to_string())?;
+
+    let scalar = |value: &serde_yaml::Value| match value {
+        serde_yaml::Value::String(text) => Some(text.clone()),
+        serde_yaml::Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    };
+    let mut entries = Vec::new();
+    for (day, references) in days {
+        let day = scalar(day).ok_or_else(|| "Days must be numbers or dates".to_string())?;
+        let references = match references {
+            serde_yaml::Value::Sequence(references) => references.iter().map(scalar).collect(),
+            reference => scalar(reference).map(|reference| vec![reference]),
+        }
+        .ok_or_else(|| format!("Day {}: expected a reference or a list of them", day))?;
+        entries.extend(
+            references
+                .into_iter()
+                .map(|reference| (day.clone(), reference)),
+        );
+    }
+    Ok((name, entries))
+}
+
+/// The entries of a CSV plan file, skipping a header row, blank lines and `#`
+/// comments
+fn csv_plan_entries(content: &str) -> Result<PlanEntries, String> {
+    let mut entries = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (day, reference) = line
+            .split_once(',')
+            .ok_or_else(|| format!("Line {}: expected \"day,reference\"", number + 1))?;
+        if entries.is_empty() && parse_day_key(day).is_err() {
+            // A header row
+            continue;
+        }
+        entries.push((
+            day.to_string(),
+            reference.trim().trim_matches('"').to_string(),
+        ));
+    }
+    Ok(entries)
+}
+
+/// Every chapter of `book`, in order
+fn book_chapters(bible: &BibleStructure, book: &str) -> Vec<PlanItem> {
+    let chapters = bible
+        .ot
+        .get(book)
+        .or_else(|| bible.nt.get(book))
+        .map_or(0, |chapters| chapters.len() as u32);
+    (1..=chapters)
+        .map(|chapter| PlanItem {
+            book: book.to_string(),
+            chapter,
+        })
+        .collect()
+}
+
+/// `chapters` split as evenly as possible over `days` days, in order. With fewer chapters
+/// than days, the days without one are spread out.
+fn spread(chapters: &[PlanItem], days: usize) -> Vec<Vec<PlanItem>> {
+    let boundary = |day: usize| (day * chapters.len() + days / 2) / days;
+    (0..days)
+        .map(|day| chapters[boundary(day)..boundary(day + 1)].to_vec())
+        .collect()
+}
+
+/// Readings from several plans read side by side, day by day
+fn merge(columns: &[Vec<Vec<PlanItem>>]) -> Vec<Vec<PlanItem>> {
+    let days = columns.iter().map(Vec::len).max().unwrap_or(0);
+    (0..days)
+        .map(|day| {
+            columns
+                .iter()
+                .filter_map(|column| column.get(day))
+                .flatten()
+                .cloned()
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::progress::ReadingProgress;
+    use crate::utils::get_all_books;
+
+    /// Whether `books` lists every book of `bible`, each once
+    fn covers_canon(bible: &BibleStructure, books: &[&str]) -> bool {
+        let mut all_books = get_all_books(bible);
+        let mut listed: Vec<String> = books.iter().map(|book| book.to_string()).collect();
+        all_books.sort();
+        listed.sort();
+        all_books == listed
+    }
+
+    #[test]
+    fn built_in_plans_cover_their_chapters() {
+        let bible = get_bible_structure();
+        assert!(covers_canon(bible, &CHRONOLOGICAL_BOOKS));
+
+        let total = |plan: &Plan| plan.days.iter().map(Vec::len).sum::<usize>();
+        for kind in [
+            PlanKind::Canonical,
+            PlanKind::Chronological,
+            PlanKind::BibleInAYear,
+        ] {
+            let plan = kind.build(bible);
+            assert_eq!(plan.days.len(), 365, "{}", kind.name());
+            assert_eq!(total(&plan), 1189, "{}", kind.name());
+        }
+
+        let nt = PlanKind::NewTestament90.build(bible);
+        assert_eq!(nt.days.len(), 90);
+        assert_eq!(total(&nt), 260);
+
+        let mcheyne = PlanKind::MCheyne.build(bible);
+        assert_eq!(total(&mcheyne), 1189 + 260);
+        let first_day: Vec<String> = mcheyne.days[0]
+            .iter()
+            .map(|item| format!("{} {}", item.book, item.chapter))
+            .collect();
+        assert_eq!(
+            first_day,
+            vec!["Genesis 1", "Matthew 1", "Ezra 1", "Acts 1"]
+        );
+    }
+
+    #[test]
+    fn reading_an_assigned_chapter_marks_it_done() {
+        let bible = get_bible_structure();
+        let plan = PlanKind::NewTestament90.build(bible);
+        let start = NaiveDate::from_ymd_opt(2024, 11, 1).unwrap();
+        let state = PlanState::new(PlanId::BuiltIn(PlanKind::NewTestament90), start);
+
+        let mut progress = ReadingProgress::new();
+        // Read before the plan started, so it doesn't count
+        progress.record_reading_on("Matthew", 1, 1, 25, 1, start.pred_opt().unwrap());
+        progress.record_reading_on("Matthew", 2, 1, 23, 1, start);
+        progress.record_reading_on("Matthew", 1, 1, 25, 1, start.succ_opt().unwrap());
+
+        let done = plan.completion(&state, &progress.history);
+        assert_eq!(plan.days[0].len(), 3);
+        assert_eq!(done[0], vec![true, true, false]);
+        assert!(done[1].iter().all(|done| !done));
+
+        assert_eq!(schedule_offset(&done, 0), 0);
+        assert_eq!(schedule_offset(&done, 3), -3);
+        assert_eq!(schedule_offset(&[vec![true], vec![true], vec![]], 0), 2);
+
+        assert_eq!(state.current_day(&plan, start), 0);
+        assert_eq!(state.current_day(&plan, start.pred_opt().unwrap()), 0);
+        assert_eq!(
+            state.current_day(&plan, NaiveDate::from_ymd_opt(2025, 6, 1).unwrap()),
+            89
+        );
+    }
+
+    #[test]
+    fn plan_files_are_parsed_and_validated() {
+        let bible = get_bible_structure();
+        let chapters = |plan: &Plan| -> Vec<String> {
+            plan.days
+                .iter()
+                .map(|items| {
+                    items
+                        .iter()
+                        .map(|item| format!("{} {}", item.book, item.chapter))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect()
+        };
+
+        let yaml = "name: Gospels in Lent\ndays:\n  1: [matt 1-2, ps 1]\n  3: Matthew 3:1-12\n";
+        let plan = parse_plan_file(bible, "lent", yaml, false).unwrap();
+        assert_eq!(plan.name, "Gospels in Lent");
+        assert_eq!(plan.start, None);
+        assert_eq!(
+            chapters(&plan),
+            vec!["Matthew 1, Matthew 2, Psalms 1", "", "Matthew 3"]
+        );
+
+        let csv = "date,reference\n2025-03-06,John 1\n2025-03-05,Ruth 1-2\n";
+        let plan = parse_plan_file(bible, "march", csv, true).unwrap();
+        assert_eq!(plan.name, "march");
+        assert_eq!(plan.start, NaiveDate::from_ymd_opt(2025, 3, 5));
+        assert_eq!(chapters(&plan), vec!["Ruth 1, Ruth 2", "John 1"]);
+
+        assert_eq!(
+            parse_plan_file(bible, "bad", "days:\n  1: Ruth 5\n", false),
+            Err("Day 1: Ruth has no chapter 5 (max: 4)".to_string())
+        );
+        assert!(parse_plan_file(bible, "bad", "1,Ruth 1\n2025-03-05,Ruth 2\n", true).is_err());
+    }
+
+    #[test]
+    fn days_too_far_out_are_rejected() {
+        let bible = get_bible_structure();
+        for day in ["20250301", "4000000000"] {
+            assert_eq!(
+                parse_plan_file(bible, "typo", &format!("days:\n  {}: Ruth 1\n", day), false),
+                Err(format!(
+                    "Day {} is too far out (plans can be at most 3660 days long)",
+                    day
+                ))
+            );
+        }
+        let csv = "2025-03-05,Ruth 1\n2525-03-05,Ruth 2\n";
+        assert!(parse_plan_file(bible, "typo", csv, true)
+            .unwrap_err()
+            .starts_with("Day 2525-03-05 is too far out"));
+        assert!(parse_plan_file(bible, "long", "3660,Ruth 1\n", true).is_ok());
+    }
+
+    #[test]
+    fn plan_ids_are_stored_by_name() {
+        let mut state = PlanState::new(
+            PlanId::Custom {
+                custom: "lent".to_string(),
+            },
+            NaiveDate::from_ymd_opt(2025, 3, 5).unwrap(),
+        );
+        state.moved.insert(3, 5);
+        let yaml = serde_yaml::to_string(&state).unwrap();
+        assert_eq!(serde_yaml::from_str::<PlanState>(&yaml).unwrap(), state);
+        assert_eq!(
+            serde_yaml::from_str::<PlanId>("mcheyne").unwrap(),
+            PlanId::BuiltIn(PlanKind::MCheyne)
+        );
+    }
+
+    #[test]
+    fn missed_days_can_be_rescheduled() {
+        let bible = get_bible_structure();
+        let plan = PlanKind::NewTestament90.build(bible);
+        // A Monday
+        let start = NaiveDate::from_ymd_opt(2024, 11, 4).unwrap();
+        let today = start + Duration::days(3);
+        let mut progress = ReadingProgress::new();
+        for chapter in 1..=3 {
+            progress.record_reading_on("Matthew", chapter, 1, 20, 1, start);
+        }
+        let state = PlanState::new(PlanId::BuiltIn(PlanKind::NewTestament90), start);
+        let behind = |state: &PlanState| {
+            let scheduled = state.schedule(&plan);
+            let completion = scheduled.completion(state, &progress.history);
+            schedule_offset(&completion, state.current_day(&scheduled, today))
+        };
+        let first_chapters = |state: &PlanState| -> Vec<String> {
+            state.schedule(&plan).days[3..7]
+                .iter()
+                .map(|items| format!("{} {}", items[0].book, items[0].chapter))
+                .collect()
+        };
+        assert_eq!(behind(&state), -2);
+        assert_eq!(
+            today_lines(&plan, &state, &progress.history, today),
+            vec![
+                "New Testament in 90 Days, day 4 of 90 (2 days behind)",
+                "[ ] Matthew 10",
+                "[ ] Matthew 11",
+                "[ ] Matthew 12"
+            ]
+        );
+
+        // Days 2 and 3 move to today and tomorrow, before their own readings
+        let mut spread = state.clone();
+        spread.catch_up(&plan, &progress.history, today, CatchUp::Spread { days: 2 });
+        assert_eq!(behind(&spread), 0);
+        assert_eq!(spread.schedule(&plan).days[3].len(), 6);
+        assert_eq!(spread.schedule(&plan).days.len(), 90);
+
+        let mut shift = state.clone();
+        shift.catch_up(&plan, &progress.history, today, CatchUp::Shift);
+        assert_eq!(behind(&shift), 0);
+        assert_eq!(
+            first_chapters(&shift),
+            vec!["Matthew 4", "Matthew 7", "Matthew 10", "Matthew 13"]
+        );
+        assert_eq!(shift.schedule(&plan).days.len(), 92);
+
+        // Saturday and Sunday are days 5 and 6
+        let mut weekends = state;
+        weekends.catch_up(&plan, &progress.history, today, CatchUp::Weekends);
+        assert_eq!(behind(&weekends), 0);
+        assert_eq!(
+            first_chapters(&weekends),
+            vec!["Matthew 10", "Matthew 13", "Matthew 4", "Matthew 7"]
+        );
+    }
+
+    #[test]
+    fn shared_plan_keeps_the_schedule() {
+        let bible = get_bible_structure();
+        let plan = PlanKind::NewTestament90.build(bible);
+        let start = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let mut state = PlanState::new(PlanId::BuiltIn(PlanKind::NewTestament90), start);
+        // Day 2 was moved to day 3
+        state.moved.insert(1, 2);
+
+        let file = shared_plan_file(&plan, &state);
+        assert!(file.contains("Matthew 1-3"));
+        let shared = parse_plan_file(bible, "shared", &file, false).unwrap();
+        assert_eq!(shared.name, plan.name);
+        assert_eq!(shared.start, Some(start));
+        assert_eq!(shared.days, state.schedule(&plan).days);
+        assert!(shared.days[1].is_empty());
+    }
+}
@@ -0,0 +1,231 @@
+//! Plugins keep features only some readers want (posting to a church Slack, a stat nobody else
+//! tracks) out of the core. A plugin is told about readings as they are recorded and saved, and
+//! can add lines to the Stats tab and `brp stats`. Plugins are either external commands listed
+//! under `plugins` in the config, or compiled in and added with [`Plugins::register`].
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::hook::{run_with_input, shell, HookPayload};
+use crate::overview::StatsOverview;
+use crate::progress::ReadingEvent;
+
+pub trait Plugin: Send + Sync {
+    /// Shown with the plugin's errors
+    fn name(&self) -> &str;
+
+    /// Readings were just recorded
+    fn on_record(&self, _recorded: &[ReadingEvent]) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// The progress file was saved with these readings
+    fn on_save(&self, _payload: &HookPayload) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Extra (label, value) lines for the stats
+    fn stats(&self, _overview: &StatsOverview) -> Result<Vec<(String, String)>, String> {
+        Ok(Vec::new())
+    }
+}
+
+/// What an external plugin is told about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginEvent {
+    Record,
+    Save,
+    Stats,
+}
+
+fn all_events() -> Vec<PluginEvent> {
+    vec![PluginEvent::Record, PluginEvent::Save, PluginEvent::Stats]
+}
+
+/// A shell command run for each of its events with a JSON object on its standard input: the
+/// `event`, plus the `recorded` readings for `record`, the post-save hook's payload for `save`
+/// and the stats overview for `stats`. For `stats` it prints `Label: value` lines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandPlugin {
+    pub name: String,
+    pub command: String,
+    /// Events the command is run for; all of them by default
+    #[serde(default = "all_events")]
+    pub events: Vec<PluginEvent>,
+}
+
+#[derive(Serialize)]
+struct Message<'a, T: Serialize> {
+    event: PluginEvent,
+    #[serde(flatten)]
+    data: &'a T,
+}
+
+#[derive(Serialize)]
+struct Recorded<'a> {
+    recorded: &'a [ReadingEvent],
+}
+
+impl CommandPlugin {
+    /// Run the command for `event` if it wants it, returning what it printed
+    fn send(&self, event: PluginEvent, data: &impl Serialize) -> Result<Option<String>, String> {
+        if !self.events.contains(&event) {
+            return Ok(None);
+        }
+        let json = serde_json::to_string(&Message { event, data }).map_err(|e| e.to_string())?;
+        run_with_input(shell(&self.command), &json)
+            .map(Some)
+            .map_err(|e| format!("`{}` failed: {}", self.command, e))
+    }
+}
+
+impl Plugin for CommandPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_record(&self, recorded: &[ReadingEvent]) -> Result<(), String> {
+        self.send(PluginEvent::Record, &Recorded { recorded })
+            .map(drop)
+    }
+
+    fn on_save(&self, payload: &HookPayload) -> Result<(), String> {
+        self.send(PluginEvent::Save, payload).map(drop)
+    }
+
+    fn stats(&self, overview: &StatsOverview) -> Result<Vec<(String, String)>, String> {
+        let output = self.send(PluginEvent::Stats, overview)?.unwrap_or_default();
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let (label, value) = line.split_once(':')?;
+                Some((label.trim().to_string(), value.trim().to_string()))
+            })
+            .collect())
+    }
+}
+
+/// The post-save hook is the built-in plugin
+impl Plugin for crate::hook::PostSaveHook {
+    fn name(&self) -> &str {
+        "Post-save hook"
+    }
+
+    fn on_save(&self, payload: &HookPayload) -> Result<(), String> {
+        self.run(payload)
+    }
+}
+
+/// Every plugin in use, cheap to clone into a background thread
+#[derive(Clone, Default)]
+pub struct Plugins {
+    plugins: Vec<Arc<dyn Plugin>>,
+}
+
+impl Plugins {
+    /// The post-save hook, if set, and the config's external plugins
+    pub fn from_config(config: &Config) -> Self {
+        let mut plugins = Self::default();
+        if config.post_save_hook.is_set() {
+            plugins.register(config.post_save_hook.clone());
+        }
+        for plugin in &config.plugins {
+            plugins.register(plugin.clone());
+        }
+        plugins
+    }
+
+    pub fn register(&mut self, plugin: impl Plugin + 'static) {
+        self.plugins.push(Arc::new(plugin));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Tell every plugin about the readings in `payload`, then that they were saved. The error
+    /// names each plugin that failed and why.
+    pub fn recorded_and_saved(&self, payload: &HookPayload) -> Result<(), String> {
+        let errors: Vec<String> = self
+            .plugins
+            .iter()
+            .filter_map(|plugin| {
+                plugin
+                    .on_record(&payload.recorded)
+                    .and_then(|()| plugin.on_save(payload))
+                    .err()
+                    .map(|e| format!("{}: {}", plugin.name(), e))
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Every plugin's stats lines, in the order the plugins were added; a plugin that fails
+    /// gets a line saying why
+    pub fn stats(&self, overview: &StatsOverview) -> Vec<(String, String)> {
+        self.plugins
+            .iter()
+            .flat_map(|plugin| {
+                plugin
+                    .stats(overview)
+                    .unwrap_or_else(|e| vec![(plugin.name().to_string(), format!("failed: {}", e))])
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::overview::stats_overview;
+    use crate::progress::ReadingProgress;
+
+    #[test]
+    fn command_plugin_gets_its_events_and_adds_stats() {
+        let bible = get_bible_structure();
+        let today = chrono::Utc::now().date_naive();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Jude", 1, 1, 25);
+
+        let path = std::env::temp_dir().join(format!("brp-plugin-{}.json", std::process::id()));
+        let mut plugins = Plugins::default();
+        plugins.register(CommandPlugin {
+            name: "Log".to_string(),
+            command: format!(
+                "cat >> '{}'; echo; echo 'Chapters logged: 1'",
+                path.display()
+            ),
+            events: vec![PluginEvent::Save, PluginEvent::Stats],
+        });
+        plugins.register(CommandPlugin {
+            name: "Broken".to_string(),
+            command: "echo down >&2; exit 1".to_string(),
+            events: vec![PluginEvent::Record],
+        });
+
+        let payload = HookPayload::new(bible, &progress, 0, today);
+        assert_eq!(
+            plugins.recorded_and_saved(&payload),
+            Err("Broken: `echo down >&2; exit 1` failed: down".to_string())
+        );
+        let sent: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(sent["event"], "save");
+        assert_eq!(sent["recorded"][0]["book"], "Jude");
+
+        let overview = stats_overview(bible, &progress, today, 180);
+        assert_eq!(
+            plugins.stats(&overview),
+            vec![("Chapters logged".to_string(), "1".to_string())]
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,7 +1,13 @@
 use chrono::{NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
 
+use crate::book_name::BookName;
+use crate::date_range::DateRange;
+use crate::memorize::MemoryCard;
+use crate::milestones::Milestone;
+use crate::plans::PlanState;
 use crate::range_query::RangeMap;
 
 /// Represents the start position of a range for comparison purposes.
@@ -30,6 +36,102 @@ impl Default for ReadingRecord {
     }
 }
 
+/// A single reading of a verse range within one chapter.
+/// The `books` map only keeps the most recent date for each range, so this log is what
+/// per-day statistics (streaks, activity) are derived from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReadingEvent {
+    pub date: NaiveDate,
+    pub book: BookName,
+    pub chapter: u32,
+    pub verse_start: u32,
+    /// Inclusive
+    pub verse_end: u32,
+    pub read_count: u32,
+    /// Free-text note entered with the reading
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "ReadingSource::is_read")]
+    pub source: ReadingSource,
+    /// Bible translation read, e.g. "ESV"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    /// Read-through the reading was recorded for, if one was active
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub campaign: Option<String>,
+    /// Whether the reading set the verses' read count to `read_count` (as in Manual Add mode)
+    /// instead of adding to it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub overwrite: bool,
+}
+
+/// How a passage was taken in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingSource {
+    #[default]
+    Read,
+    /// Listened to, e.g. an audio Bible
+    Listened,
+}
+
+impl ReadingSource {
+    pub fn is_read(&self) -> bool {
+        *self == ReadingSource::Read
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            ReadingSource::Read => ReadingSource::Listened,
+            ReadingSource::Listened => ReadingSource::Read,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ReadingSource::Read => "Read",
+            ReadingSource::Listened => "Listened",
+        }
+    }
+}
+
+/// Whether reading verses again on a day they were already read counts again
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SameDayReadings {
+    /// Every reading counts
+    #[default]
+    Each,
+    /// Verses count once a day, so a passage recorded twice by mistake isn't counted twice
+    Once,
+}
+
+/// Time spent reading in one Record session, stored when the session is saved
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReadingSession {
+    pub date: NaiveDate,
+    pub minutes: u32,
+}
+
+/// A named read-through, such as "2025 canonical read-through" or "Gospels deep dive". Readings
+/// recorded while it is active count towards its own coverage as well as the lifetime totals.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Campaign {
+    pub name: String,
+    pub started: NaiveDate,
+}
+
+/// How a reading about to be recorded overlaps what is already recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlap {
+    /// Some of the verses were already read today
+    ReadToday,
+    /// Every verse has been read before
+    AlreadyRead,
+}
+
 /// Main data structure for tracking bible reading progress.
 /// Organized by book for efficient querying.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,7 +139,40 @@ pub struct ReadingProgress {
     /// Maps each book to its reading records.
     /// Within each book, ranges are stored in a RangeQueryMap for efficient overlap queries.
     #[serde(default)]
-    pub books: HashMap<String, RangeMap<InsideBookBibleReference, ReadingRecord>>,
+    pub books: HashMap<BookName, RangeMap<InsideBookBibleReference, ReadingRecord>>,
+    /// Log of every reading, oldest first
+    #[serde(default)]
+    pub history: Vec<ReadingEvent>,
+    /// Unlocked achievements, by id, with the date each was earned
+    #[serde(default)]
+    pub achievements: BTreeMap<String, NaiveDate>,
+    /// Reading time of the sessions the user chose to time, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<ReadingSession>,
+    /// The reading plan being followed, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<PlanState>,
+    /// Passages being memorized, with their review schedule
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub memorization: Vec<MemoryCard>,
+    /// Read-throughs, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub campaigns: Vec<Campaign>,
+    /// Name of the campaign new readings are recorded for; None records lifetime totals only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_campaign: Option<String>,
+    /// Journal entries, by day
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub journal: BTreeMap<NaiveDate, String>,
+    /// Milestones reached, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub milestones: Vec<Milestone>,
+    /// Books modified since the last `take_changed_books`, so views can update incrementally
+    #[serde(skip)]
+    changed_books: HashSet<BookName>,
+    /// Whether new readings of verses already read that day count again; from the config
+    #[serde(skip)]
+    pub same_day_readings: SameDayReadings,
 }
 
 impl ReadingProgress {
@@ -45,12 +180,37 @@ impl ReadingProgress {
     pub fn new() -> Self {
         Self {
             books: HashMap::new(),
+            history: Vec::new(),
+            achievements: BTreeMap::new(),
+            sessions: Vec::new(),
+            plan: None,
+            memorization: Vec::new(),
+            campaigns: Vec::new(),
+            active_campaign: None,
+            journal: BTreeMap::new(),
+            milestones: Vec::new(),
+            changed_books: HashSet::new(),
+            same_day_readings: SameDayReadings::default(),
         }
     }
 
+    /// The records of a book, created if needed, marking the book as changed
+    fn book_records_mut(
+        &mut self,
+        book: &str,
+    ) -> &mut RangeMap<InsideBookBibleReference, ReadingRecord> {
+        let book = BookName::new(book);
+        self.changed_books.insert(book.clone());
+        self.books.entry(book).or_insert_with(RangeMap::new)
+    }
+
+    /// Books modified since the last call
+    pub fn take_changed_books(&mut self) -> HashSet<BookName> {
+        std::mem::take(&mut self.changed_books)
+    }
+
     pub fn mark_read(&mut self, book: String, reference: InsideBookBibleReference) {
-        let records: &mut RangeMap<InsideBookBibleReference, ReadingRecord> =
-            self.books.entry(book).or_insert_with(RangeMap::new);
+        let records = self.book_records_mut(&book);
         // For a single verse, use exclusive end (verse + 1)
         let next_reference = InsideBookBibleReference {
             chapter: reference.chapter,
@@ -73,8 +233,7 @@ impl ReadingProgress {
         read_count: u32,
         last_read: Option<NaiveDate>,
     ) {
-        let records: &mut RangeMap<InsideBookBibleReference, ReadingRecord> =
-            self.books.entry(book).or_insert_with(RangeMap::new);
+        let records = self.book_records_mut(&book);
         records.insert_replace(
             reference..reference,
             ReadingRecord {
@@ -92,8 +251,7 @@ impl ReadingProgress {
         read_count: u32,
         last_read: Option<NaiveDate>,
     ) {
-        let records: &mut RangeMap<InsideBookBibleReference, ReadingRecord> =
-            self.books.entry(book).or_insert_with(RangeMap::new);
+        let records = self.book_records_mut(&book);
         // For a single verse, use exclusive end (verse + 1)
         let next_reference = InsideBookBibleReference {
             chapter: reference.chapter,
@@ -107,6 +265,361 @@ impl ReadingProgress {
             },
         );
     }
+
+    /// The stored records overlapping verses `verse_start..=verse_end` of a chapter
+    pub fn records_overlapping(
+        &self,
+        book: &str,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+    ) -> impl Iterator<Item = (Range<InsideBookBibleReference>, &ReadingRecord)> + '_ {
+        let range = InsideBookBibleReference {
+            chapter,
+            verse: verse_start,
+        }..InsideBookBibleReference {
+            chapter,
+            verse: verse_end + 1,
+        };
+        self.books
+            .get(book)
+            .into_iter()
+            .flat_map(move |records| records.overlaps(range.clone()))
+    }
+
+    /// The readings logged along with the most recent one: the run at the end of the history
+    /// from the same day and book in consecutive chapters, such as both chapters of
+    /// "John 3:16-4:2"
+    pub fn last_passage(&self) -> &[ReadingEvent] {
+        let Some(last) = self.history.last() else {
+            return &[];
+        };
+        let mut start = self.history.len() - 1;
+        while start > 0 {
+            let (previous, next) = (&self.history[start - 1], &self.history[start]);
+            if previous.date != last.date
+                || previous.book != last.book
+                || !(previous.chapter == next.chapter || previous.chapter + 1 == next.chapter)
+            {
+                break;
+            }
+            start -= 1;
+        }
+        &self.history[start..]
+    }
+
+    /// Whether any of verses `verse_start..=verse_end` of a chapter were already read on
+    /// `today`, or else whether all of them have been read before
+    pub fn overlap(
+        &self,
+        book: &str,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+        today: NaiveDate,
+    ) -> Option<Overlap> {
+        let records = self.books.get(book)?;
+        let range = InsideBookBibleReference {
+            chapter,
+            verse: verse_start,
+        }..InsideBookBibleReference {
+            chapter,
+            verse: verse_end + 1,
+        };
+        if records
+            .overlaps(range.clone())
+            .any(|(_, record)| record.last_read == today)
+        {
+            Some(Overlap::ReadToday)
+        } else if records.covers(range) {
+            Some(Overlap::AlreadyRead)
+        } else {
+            None
+        }
+    }
+
+    /// Marks verses `verse_start..=verse_end` of a chapter as read once more today,
+    /// and logs the reading in the history.
+    pub fn record_reading(&mut self, book: &str, chapter: u32, verse_start: u32, verse_end: u32) {
+        self.record_reading_times(book, chapter, verse_start, verse_end, 1);
+    }
+
+    /// Marks verses `verse_start..=verse_end` of a chapter as read `times` more times today,
+    /// and logs the reading in the history, following `same_day_readings`.
+    pub fn record_reading_times(
+        &mut self,
+        book: &str,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+        times: u32,
+    ) {
+        let today = Utc::now().date_naive();
+        self.record_new_reading(book, chapter, verse_start, verse_end, times, today);
+    }
+
+    /// Like `record_reading_on`, except that with `SameDayReadings::Once` the verses already
+    /// read on `date` are left out, and nothing is recorded if all of them were
+    pub fn record_new_reading(
+        &mut self,
+        book: &str,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+        times: u32,
+        date: NaiveDate,
+    ) {
+        let runs = match self.same_day_readings {
+            SameDayReadings::Each => vec![(verse_start, verse_end)],
+            SameDayReadings::Once => {
+                self.verses_not_read_on(book, chapter, verse_start, verse_end, date)
+            }
+        };
+        for (start, end) in runs {
+            self.record_reading_on(book, chapter, start, end, times, date);
+        }
+    }
+
+    /// Runs of verses `verse_start..=verse_end` of a chapter that no reading in the history
+    /// covers on `date`, as (verse_start, verse_end)
+    pub fn verses_not_read_on(
+        &self,
+        book: &str,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+        date: NaiveDate,
+    ) -> Vec<(u32, u32)> {
+        let read: Vec<(u32, u32)> = self
+            .history
+            .iter()
+            .filter(|event| event.date == date && event.book == book && event.chapter == chapter)
+            .map(|event| (event.verse_start, event.verse_end))
+            .collect();
+        let mut runs = Vec::new();
+        let mut run_start = None;
+        for verse in verse_start..=verse_end {
+            let is_read = read
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&verse));
+            match (is_read, run_start) {
+                (false, None) => run_start = Some(verse),
+                (true, Some(start)) => {
+                    runs.push((start, verse - 1));
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = run_start {
+            runs.push((start, verse_end));
+        }
+        runs
+    }
+
+    /// Marks verses `verse_start..=verse_end` of a chapter as read `times` more times on
+    /// `date`, and logs the reading in the history. A verse's last read date only moves
+    /// forward, so past readings can be added after more recent ones.
+    pub fn record_reading_on(
+        &mut self,
+        book: &str,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+        times: u32,
+        date: NaiveDate,
+    ) {
+        let record = ReadingRecord {
+            read_count: times,
+            last_read: date,
+        };
+        let records = self.book_records_mut(book);
+        records.insert_with(
+            InsideBookBibleReference {
+                chapter,
+                verse: verse_start,
+            }..InsideBookBibleReference {
+                chapter,
+                verse: verse_end + 1,
+            },
+            record.clone(),
+            |old, new| ReadingRecord {
+                read_count: old.read_count + new.read_count,
+                last_read: old.last_read.max(new.last_read),
+            },
+        );
+        self.history.push(ReadingEvent {
+            date: record.last_read,
+            book: BookName::new(book),
+            chapter,
+            verse_start,
+            verse_end,
+            read_count: record.read_count,
+            note: None,
+            tags: Vec::new(),
+            source: ReadingSource::Read,
+            translation: None,
+            campaign: self.active_campaign.clone(),
+            overwrite: false,
+        });
+    }
+
+    /// Sets verses `verse_start..=verse_end` of a chapter to the given read count and date,
+    /// overwriting overlapping ranges, and logs the reading in the history.
+    pub fn record_reading_overwrite(
+        &mut self,
+        book: &str,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+        read_count: u32,
+        last_read: Option<NaiveDate>,
+    ) {
+        let record = ReadingRecord {
+            read_count,
+            last_read: last_read.unwrap_or_else(|| Utc::now().date_naive()),
+        };
+        let records = self.book_records_mut(book);
+        records.insert_replace(
+            InsideBookBibleReference {
+                chapter,
+                verse: verse_start,
+            }..InsideBookBibleReference {
+                chapter,
+                verse: verse_end + 1,
+            },
+            record.clone(),
+        );
+        self.history.push(ReadingEvent {
+            date: record.last_read,
+            book: BookName::new(book),
+            chapter,
+            verse_start,
+            verse_end,
+            read_count,
+            note: None,
+            tags: Vec::new(),
+            source: ReadingSource::Read,
+            translation: None,
+            campaign: self.active_campaign.clone(),
+            overwrite: true,
+        });
+    }
+
+    /// Records a reading logged elsewhere, e.g. exported from another device, keeping its
+    /// details
+    pub fn replay(&mut self, event: ReadingEvent) {
+        if event.overwrite {
+            self.record_reading_overwrite(
+                &event.book,
+                event.chapter,
+                event.verse_start,
+                event.verse_end,
+                event.read_count,
+                Some(event.date),
+            );
+        } else {
+            self.record_reading_on(
+                &event.book,
+                event.chapter,
+                event.verse_start,
+                event.verse_end,
+                event.read_count,
+                event.date,
+            );
+        }
+        if let Some(last) = self.history.last_mut() {
+            *last = event;
+        }
+    }
+
+    /// Logs `minutes` spent reading on `date`
+    pub fn record_session(&mut self, date: NaiveDate, minutes: u32) {
+        self.sessions.push(ReadingSession { date, minutes });
+    }
+
+    /// Fill in details (note, tags, source, ...) of the readings logged since the history had
+    /// `first_event` entries
+    pub fn annotate_since(&mut self, first_event: usize, annotate: impl Fn(&mut ReadingEvent)) {
+        self.history.iter_mut().skip(first_event).for_each(annotate);
+    }
+
+    /// Write the journal entry of `date`, removing it if `text` is blank
+    pub fn set_journal_entry(&mut self, date: NaiveDate, text: &str) {
+        let text = text.trim_end();
+        if text.trim().is_empty() {
+            self.journal.remove(&date);
+        } else {
+            self.journal.insert(date, text.to_string());
+        }
+    }
+
+    /// Start a read-through called `name` and record new readings for it
+    pub fn start_campaign(&mut self, name: &str, started: NaiveDate) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("A campaign needs a name".to_string());
+        }
+        if self.campaigns.iter().any(|campaign| campaign.name == name) {
+            return Err(format!("There is already a campaign called \"{}\"", name));
+        }
+        self.campaigns.push(Campaign {
+            name: name.to_string(),
+            started,
+        });
+        self.active_campaign = Some(name.to_string());
+        Ok(())
+    }
+
+    /// The campaign after the active one, going from lifetime totals (None) through every
+    /// campaign and back
+    pub fn next_campaign(&self) -> Option<String> {
+        let next = match &self.active_campaign {
+            None => 0,
+            Some(active) => self
+                .campaigns
+                .iter()
+                .position(|campaign| campaign.name == *active)
+                .map_or(0, |idx| idx + 1),
+        };
+        self.campaigns
+            .get(next)
+            .map(|campaign| campaign.name.clone())
+    }
+
+    /// The readings of one campaign, as progress of their own: its records are built from
+    /// the campaign's part of the history only
+    pub fn campaign_layer(&self, name: &str) -> ReadingProgress {
+        self.layer(name.to_string(), |event| {
+            event.campaign.as_deref() == Some(name)
+        })
+    }
+
+    /// The readings within `range` (and of the active campaign, if there is one), as progress
+    /// of their own, named after the range in place of the campaign, e.g. "Lent · 2025-03-05
+    /// to 2025-04-19"
+    pub fn range_layer(&self, range: DateRange) -> ReadingProgress {
+        let campaign = self.active_campaign.as_deref();
+        let name = match campaign {
+            Some(campaign) => format!("{} · {}", campaign, range),
+            None => range.to_string(),
+        };
+        self.layer(name, |event| {
+            range.contains(event.date)
+                && campaign.is_none_or(|name| event.campaign.as_deref() == Some(name))
+        })
+    }
+
+    /// The readings `keep` keeps, replayed into progress named `name`
+    fn layer(&self, name: String, keep: impl Fn(&ReadingEvent) -> bool) -> ReadingProgress {
+        let mut layer = ReadingProgress::new();
+        for event in self.history.iter().filter(|event| keep(event)) {
+            layer.replay(event.clone());
+        }
+        layer.campaigns = self.campaigns.clone();
+        layer.active_campaign = Some(name);
+        layer
+    }
 }
 
 impl Default for ReadingProgress {
@@ -114,3 +627,182 @@ impl Default for ReadingProgress {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(progress: &ReadingProgress, book: &str) -> Vec<(u32, u32, u32, u32, u32)> {
+        progress.books[book]
+            .iter()
+            .map(|(range, record)| {
+                (
+                    range.start.chapter,
+                    range.start.verse,
+                    range.end.chapter,
+                    range.end.verse,
+                    record.read_count,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn record_reading_accumulates_and_logs() {
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("John", 3, 1, 36);
+        progress.record_reading("John", 3, 16, 16);
+
+        assert_eq!(
+            records(&progress, "John"),
+            vec![(3, 1, 3, 16, 1), (3, 16, 3, 17, 2), (3, 17, 3, 37, 1)]
+        );
+        assert_eq!(progress.history.len(), 2);
+        assert_eq!(progress.history[1].verse_start, 16);
+        assert_eq!(progress.history[1].verse_end, 16);
+    }
+
+    #[test]
+    fn same_day_readings_can_count_once() {
+        let mut progress = ReadingProgress::new();
+        progress.same_day_readings = SameDayReadings::Once;
+        progress.record_reading("John", 3, 1, 21);
+        progress.record_reading("John", 3, 1, 21);
+        progress.record_reading("John", 3, 14, 36);
+        // Another day counts again
+        let tomorrow = Utc::now().date_naive() + chrono::Duration::days(1);
+        progress.record_new_reading("John", 3, 1, 10, 1, tomorrow);
+
+        assert_eq!(
+            records(&progress, "John"),
+            vec![(3, 1, 3, 11, 2), (3, 11, 3, 37, 1)]
+        );
+        assert_eq!(progress.history.len(), 3);
+        assert_eq!(
+            (
+                progress.history[1].verse_start,
+                progress.history[1].verse_end
+            ),
+            (22, 36)
+        );
+    }
+
+    #[test]
+    fn past_readings_keep_the_latest_date() {
+        let mut progress = ReadingProgress::new();
+        let today = Utc::now().date_naive();
+        let last_year = today - chrono::Duration::days(365);
+        progress.record_reading("Ruth", 1, 1, 22);
+        progress.record_reading_on("Ruth", 1, 1, 22, 1, last_year);
+
+        let (_, record) = progress.books["Ruth"].iter().next().unwrap();
+        assert_eq!(record.read_count, 2);
+        assert_eq!(record.last_read, today);
+        assert_eq!(progress.history[1].date, last_year);
+    }
+
+    #[test]
+    fn campaigns_keep_their_own_coverage() {
+        let today = Utc::now().date_naive();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        progress.start_campaign("Gospels deep dive", today).unwrap();
+        assert!(progress.start_campaign("Gospels deep dive", today).is_err());
+        progress.record_reading("Ruth", 1, 1, 22);
+        progress.record_reading("Mark", 1, 1, 45);
+
+        let layer = progress.campaign_layer("Gospels deep dive");
+        assert_eq!(records(&layer, "Ruth"), vec![(1, 1, 1, 23, 1)]);
+        assert_eq!(records(&layer, "Mark"), vec![(1, 1, 1, 46, 1)]);
+        assert_eq!(layer.history.len(), 2);
+        // The lifetime totals still count every reading
+        assert_eq!(records(&progress, "Ruth"), vec![(1, 1, 1, 23, 2)]);
+
+        assert_eq!(progress.next_campaign(), None);
+        progress.active_campaign = None;
+        assert_eq!(
+            progress.next_campaign().as_deref(),
+            Some("Gospels deep dive")
+        );
+    }
+
+    #[test]
+    fn range_layers_keep_only_the_readings_in_the_range() {
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        let date = NaiveDate::from_ymd_opt(2024, 11, 2).unwrap();
+        progress.record_reading_overwrite("Romans", 8, 1, 10, 2, Some(date));
+
+        let layer = progress.range_layer(DateRange::parse("2024").unwrap());
+        assert_eq!(records(&layer, "Romans"), vec![(8, 1, 8, 11, 2)]);
+        assert!(!layer.books.contains_key("Ruth"));
+        assert_eq!(layer.active_campaign.as_deref(), Some("2024"));
+    }
+
+    #[test]
+    fn record_reading_overwrite_replaces_counts() {
+        let mut progress = ReadingProgress::new();
+        let date = NaiveDate::from_ymd_opt(2024, 11, 2).unwrap();
+        progress.record_reading("Romans", 8, 1, 39);
+        progress.record_reading_overwrite("Romans", 8, 1, 10, 5, Some(date));
+
+        assert_eq!(
+            records(&progress, "Romans"),
+            vec![(8, 1, 8, 11, 5), (8, 11, 8, 40, 1)]
+        );
+        assert_eq!(progress.history[1].date, date);
+        assert_eq!(progress.history[1].read_count, 5);
+    }
+
+    #[test]
+    fn overlap_distinguishes_today_from_earlier_readings() {
+        let mut progress = ReadingProgress::new();
+        let earlier = NaiveDate::from_ymd_opt(2024, 11, 2).unwrap();
+        let today = NaiveDate::from_ymd_opt(2024, 11, 9).unwrap();
+        progress.record_reading_overwrite("Romans", 8, 1, 39, 1, Some(earlier));
+        progress.record_reading_overwrite("Romans", 9, 1, 10, 1, Some(today));
+
+        assert_eq!(
+            progress.overlap("Romans", 8, 28, 30, today),
+            Some(Overlap::AlreadyRead)
+        );
+        assert_eq!(
+            progress.overlap("Romans", 9, 5, 20, today),
+            Some(Overlap::ReadToday)
+        );
+        assert_eq!(progress.overlap("Romans", 10, 1, 21, today), None);
+        assert_eq!(progress.overlap("John", 3, 16, 16, today), None);
+    }
+
+    #[test]
+    fn last_passage_spans_the_chapters_recorded_together() {
+        let mut progress = ReadingProgress::new();
+        assert!(progress.last_passage().is_empty());
+        progress.record_reading("Psalms", 23, 1, 6);
+        progress.record_reading("John", 3, 16, 36);
+        progress.record_reading("John", 4, 1, 2);
+        let chapters: Vec<(&str, u32)> = progress
+            .last_passage()
+            .iter()
+            .map(|event| (event.book.as_str(), event.chapter))
+            .collect();
+        assert_eq!(chapters, vec![("John", 3), ("John", 4)]);
+    }
+
+    #[test]
+    fn history_defaults_to_empty_for_old_files() {
+        let progress: ReadingProgress = serde_yaml::from_str("books: {}").unwrap();
+        assert!(progress.history.is_empty());
+    }
+
+    #[test]
+    fn changed_books_are_tracked_until_taken() {
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("John", 3, 1, 16);
+        progress.record_reading_overwrite("Psalms", 23, 1, 6, 2, None);
+        let changed = progress.take_changed_books();
+        assert_eq!(changed.len(), 2);
+        assert!(changed.contains("John") && changed.contains("Psalms"));
+        assert!(progress.take_changed_books().is_empty());
+    }
+}
@@ -22,7 +22,7 @@ impl<T: Eq + Clone> CanCoalesce for T {
 /// A map of disjoint half-open ranges `Range<T>` and values V where
 /// an overlap (during insert) triggers merging of the values on the
 /// intersection of the overlapping ranges
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RangeMap<K, V>
 where
     K: Ord + Copy,
@@ -76,68 +76,29 @@ where
     where
         F: Fn(&V, &V) -> V,
     {
-        let mut to_insert = Vec::new();
-        let mut to_remove = Vec::new();
-
-        let mut start_cursor = range.start;
-
-        if let Some((s, (e, v))) = self.map.range_mut(..range.start).next_back() {
-            if start_cursor == *e {
-                if let Some(v2) = v.coalesce(&value) {
-                    *v = v2;
-                    start_cursor = *s;
-                }
-            } else if start_cursor < *e {
-                if let Some(v2) = v.coalesce(&value) {
-                    *v = v2;
-                    start_cursor = *s;
-                } else {
-                    if range.end < *e {
-                        to_insert.push((range.end, (*e, v.clone())));
-                    }
-
-                    let next_end = (*e).min(range.end);
-                    *e = start_cursor;
-                    to_insert.push((start_cursor, (next_end, merge(v, &value))));
-                    start_cursor = next_end;
-                }
-            }
+        if range.start >= range.end {
+            return;
         }
 
-        let mut coalesced_value = value.clone();
+        // Split any stored ranges straddling either end of the new range, so that every
+        // stored range is then either fully inside or fully outside of it
+        self.split_at(range.start);
+        self.split_at(range.end);
 
-        for (s, (e, v)) in self.map.range_mut(range.start..range.end) {
-            if let Some(v2) = v.coalesce(&coalesced_value) {
-                coalesced_value = v2;
-                to_remove.push(*s);
-                continue;
-            }
+        let mut to_insert = Vec::new();
+        let mut start_cursor = range.start;
 
+        for (s, (e, v)) in self.map.range_mut(range.start..range.end) {
             if start_cursor < *s {
-                // no overlap here
-                to_insert.push((start_cursor, (*s, coalesced_value)));
-                coalesced_value = value.clone();
-                // no need to update start_cursor here
-            }
-            // conceptually, start_cursor == *s
-            if *e <= range.end {
-                *v = merge(v, &coalesced_value);
-                start_cursor = *e;
-            } else {
-                to_insert.push((range.end, (*e, v.clone())));
-                *e = range.end;
-                *v = merge(v, &coalesced_value);
-                start_cursor = range.end;
-                // this is the last loop
+                // Gap before this range: nothing to merge with
+                to_insert.push((start_cursor, (*s, value.clone())));
             }
+            *v = merge(v, &value);
+            start_cursor = *e;
         }
 
         if start_cursor < range.end {
-            to_insert.push((start_cursor, (range.end, coalesced_value)));
-        }
-
-        for s in to_remove {
-            self.map.remove(&s);
+            to_insert.push((start_cursor, (range.end, value)));
         }
 
         for (s, v) in to_insert {
@@ -148,6 +109,18 @@ where
         self.coalesce_in_range(range.start..=range.end);
     }
 
+    /// Split the stored range containing `at` (if any) into two ranges meeting at `at`
+    fn split_at(&mut self, at: K) {
+        let Some((_, (e, v))) = self.map.range_mut(..at).next_back() else {
+            return;
+        };
+        if *e > at {
+            let tail = (*e, v.clone());
+            *e = at;
+            self.map.insert(at, tail);
+        }
+    }
+
     pub fn coalesce_in_range(&mut self, range: RangeInclusive<K>) {
         let mut cursor = None;
         let mut to_remove = Vec::new();
@@ -199,6 +172,25 @@ where
         self.insert_with(range, value, |_, new| new.clone());
     }
 
+    /// The parts of stored ranges that overlap `range`, clipped to it, in order.
+    pub fn overlaps(&self, range: Range<K>) -> impl Iterator<Item = (Range<K>, &V)> + '_ {
+        let (start, end) = (range.start, range.end);
+        self.range(range)
+            .map(move |(r, v)| ((*r.start).max(start)..(*r.end).min(end), v))
+    }
+
+    /// True if every point of `range` lies inside a stored range.
+    pub fn covers(&self, range: Range<K>) -> bool {
+        let mut cursor = range.start;
+        for (r, _) in self.overlaps(range.clone()) {
+            if r.start > cursor {
+                return false;
+            }
+            cursor = r.end;
+        }
+        cursor >= range.end
+    }
+
     /// Iterator over disjoint ranges and their values.
     pub fn iter(&self) -> impl Iterator<Item = (Range<K>, &V)> + '_ {
         self.map.iter().map(|(&s, &(e, ref v))| (s..e, v))
@@ -221,6 +213,22 @@ mod tests {
         assert_eq!(v, vec![(1..3, "A"), (3..4, "B")]);
     }
 
+    #[test]
+    fn overlaps_are_clipped() {
+        let mut rm = RangeMap::new();
+        rm.insert_replace(0..5, "A");
+        rm.insert_replace(8..12, "B");
+        let v: Vec<_> = rm.overlaps(3..10).map(|(r, &s)| (r, s)).collect();
+        assert_eq!(v, vec![(3..5, "A"), (8..10, "B")]);
+        assert_eq!(rm.overlaps(5..8).count(), 0);
+
+        assert!(rm.covers(1..4));
+        assert!(!rm.covers(3..10));
+        rm.insert_replace(5..8, "C");
+        assert!(rm.covers(3..10));
+        assert!(!rm.covers(10..13));
+    }
+
     #[test]
     fn empty_map() {
         let rm = RangeMap::<i32, &str>::new();
@@ -486,6 +494,33 @@ mod tests {
         assert_eq!(v, vec![(0..5, "A"), (5..15, "B")]);
     }
 
+    #[test]
+    fn merge_equal_values_on_overlap() {
+        let mut rm = RangeMap::new();
+        rm.insert_with(0..20, 1, |old, new| old + new);
+        rm.insert_with(5..10, 1, |old, new| old + new);
+        let v: Vec<_> = rm.iter().map(|(r, &n)| (r, n)).collect();
+        assert_eq!(v, vec![(0..5, 1), (5..10, 2), (10..20, 1)]);
+    }
+
+    #[test]
+    fn merge_equal_values_on_partial_overlap() {
+        let mut rm = RangeMap::new();
+        rm.insert_with(0..10, 1, |old, new| old + new);
+        rm.insert_with(5..15, 1, |old, new| old + new);
+        let v: Vec<_> = rm.iter().map(|(r, &n)| (r, n)).collect();
+        assert_eq!(v, vec![(0..5, 1), (5..10, 2), (10..15, 1)]);
+    }
+
+    #[test]
+    fn empty_range_is_ignored() {
+        let mut rm = RangeMap::new();
+        rm.insert_replace(0..10, "A");
+        rm.insert_replace(5..5, "B");
+        let v: Vec<_> = rm.iter().map(|(r, &s)| (r, s)).collect();
+        assert_eq!(v, vec![(0..10, "A")]);
+    }
+
     #[test]
     fn merge_function_different_types() {
         let mut rm = RangeMap::new();
@@ -503,4 +538,48 @@ mod tests {
             ]
         );
     }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Up to 20 ranges within 0..100, some empty or reversed
+        fn ranges() -> impl Strategy<Value = Vec<Range<u32>>> {
+            prop::collection::vec((0u32..100, 0u32..100).prop_map(|(s, e)| s..e), 0..20)
+        }
+
+        proptest! {
+            #[test]
+            fn stored_ranges_are_disjoint_and_coalesced(inserted in ranges()) {
+                let mut rm = RangeMap::new();
+                for range in &inserted {
+                    rm.insert_with(range.clone(), 1u32, |old, new| old + new);
+                }
+                let stored: Vec<_> = rm.iter().map(|(r, &n)| (r, n)).collect();
+                for (range, _) in &stored {
+                    prop_assert!(range.start < range.end);
+                }
+                for pair in stored.windows(2) {
+                    let ((first, a), (second, b)) = (&pair[0], &pair[1]);
+                    prop_assert!(first.end <= second.start);
+                    // Touching ranges with the same value would have been merged
+                    prop_assert!(first.end < second.start || a != b);
+                }
+            }
+
+            #[test]
+            fn coverage_is_the_union_of_the_insertions(inserted in ranges()) {
+                let mut rm = RangeMap::new();
+                for range in &inserted {
+                    rm.insert_with(range.clone(), 1u32, |old, new| old + new);
+                }
+                for point in 0..100 {
+                    let times = inserted.iter().filter(|r| r.contains(&point)).count() as u32;
+                    let stored = rm.overlaps(point..point + 1).map(|(_, &n)| n).next();
+                    prop_assert_eq!(stored, (times > 0).then_some(times));
+                    prop_assert_eq!(rm.covers(point..point + 1), times > 0);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,531 @@
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use crate::bible_structure::BibleStructure;
+use crate::utils::{get_all_books, get_book_aliases};
+
+/// A parsed reference such as "Psalms", "ps 23" or "1 John 3:16"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub book: String,
+    pub chapter: Option<u32>,
+    pub verse: Option<u32>,
+}
+
+/// Parse a loosely written reference. The book may be abbreviated or use Arabic numerals
+/// ("ps", "1 jn"); the chapter and verse are validated against the bible structure.
+#[tracing::instrument(level = "debug", skip(bible))]
+pub fn parse_reference(bible: &BibleStructure, input: &str) -> Result<Reference, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter a reference, e.g. \"ps 23\"".to_string());
+    }
+
+    // Split off a trailing "chapter" or "chapter:verse" token, if any
+    let (book_part, numbers) = match input.rsplit_once(char::is_whitespace) {
+        Some((book_part, last))
+            if !book_part.trim().is_empty()
+                && last.chars().all(|c| c.is_ascii_digit() || c == ':') =>
+        {
+            (book_part.trim(), Some(last))
+        }
+        _ => (input, None),
+    };
+
+    let book = resolve_book(bible, book_part)
+        .ok_or_else(|| format!("No book matches \"{}\"", book_part))?;
+    let chapters = bible
+        .ot
+        .get(&book)
+        .or_else(|| bible.nt.get(&book))
+        .ok_or_else(|| format!("Book '{}' not found", book))?;
+
+    let (chapter, verse) = match numbers {
+        None => (None, None),
+        Some(numbers) => {
+            let (chapter_str, verse_str) = match numbers.split_once(':') {
+                Some((chapter, verse)) => (chapter, Some(verse)),
+                None => (numbers, None),
+            };
+            let chapter = chapter_str
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid chapter: {}", chapter_str))?;
+            if chapter == 0 || chapter > chapters.len() as u32 {
+                return Err(format!(
+                    "{} has no chapter {} (max: {})",
+                    book,
+                    chapter,
+                    chapters.len()
+                ));
+            }
+            let verse = match verse_str {
+                None => None,
+                Some(verse_str) => {
+                    let verse = verse_str
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid verse: {}", verse_str))?;
+                    let max_verse = chapters[chapter as usize - 1];
+                    if verse == 0 || verse > max_verse {
+                        return Err(format!(
+                            "{} {} has no verse {} (max: {})",
+                            book, chapter, verse, max_verse
+                        ));
+                    }
+                    Some(verse)
+                }
+            };
+            (Some(chapter), verse)
+        }
+    };
+
+    Ok(Reference {
+        book,
+        chapter,
+        verse,
+    })
+}
+
+/// A contiguous run of verses within one book, such as "John 3:16-4:2". Both ends are
+/// inclusive (chapter, verse) pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passage {
+    pub book: String,
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+}
+
+impl Passage {
+    /// The verses covered in each chapter, as (chapter, verse_start, verse_end)
+    pub fn chapter_ranges(&self, bible: &BibleStructure) -> Vec<(u32, u32, u32)> {
+        let Some(chapters) = bible
+            .ot
+            .get(&self.book)
+            .or_else(|| bible.nt.get(&self.book))
+        else {
+            return Vec::new();
+        };
+        (self.start.0..=self.end.0)
+            .map(|chapter| {
+                let verse_start = if chapter == self.start.0 {
+                    self.start.1
+                } else {
+                    1
+                };
+                let verse_end = if chapter == self.end.0 {
+                    self.end.1
+                } else {
+                    chapters[chapter as usize - 1]
+                };
+                (chapter, verse_start, verse_end)
+            })
+            .collect()
+    }
+
+    /// True if the passage covers every verse of its book
+    pub fn is_whole_book(&self, bible: &BibleStructure) -> bool {
+        let chapters = bible
+            .ot
+            .get(&self.book)
+            .or_else(|| bible.nt.get(&self.book));
+        chapters.is_some_and(|chapters| {
+            self.start == (1, 1)
+                && self.end == (chapters.len() as u32, chapters[chapters.len() - 1])
+        })
+    }
+}
+
+impl std::fmt::Display for Passage {
+    /// The shortest usual form: "Jude", "John 3", "John 3:16", "John 3:16-18", "John 3-4" or
+    /// "John 3:16-4:2"
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let bible = crate::bible_structure::get_bible_structure();
+        let last_verse = |chapter: u32| {
+            bible
+                .ot
+                .get(&self.book)
+                .or_else(|| bible.nt.get(&self.book))
+                .and_then(|chapters| chapters.get(chapter as usize - 1).copied())
+        };
+        let whole_chapters = self.start.1 == 1 && Some(self.end.1) == last_verse(self.end.0);
+        let (start, end) = (self.start, self.end);
+        if self.is_whole_book(bible) {
+            write!(f, "{}", self.book)
+        } else if whole_chapters && start.0 == end.0 {
+            write!(f, "{} {}", self.book, start.0)
+        } else if whole_chapters {
+            write!(f, "{} {}-{}", self.book, start.0, end.0)
+        } else if start == end {
+            write!(f, "{} {}:{}", self.book, start.0, start.1)
+        } else if start.0 == end.0 {
+            write!(f, "{} {}:{}-{}", self.book, start.0, start.1, end.1)
+        } else {
+            write!(
+                f,
+                "{} {}:{}-{}:{}",
+                self.book, start.0, start.1, end.0, end.1
+            )
+        }
+    }
+}
+
+/// Parse a passage such as "John 3", "ps 23-24", "John 3:16-18" or "John 3:16-4:2". The book may
+/// be abbreviated as in `parse_reference`; a book on its own covers the whole book, and a
+/// chapter without verses covers the whole chapter.
+#[tracing::instrument(level = "debug", skip(bible))]
+pub fn parse_passage(bible: &BibleStructure, input: &str) -> Result<Passage, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter a passage, e.g. \"John 3:16-4:2\"".to_string());
+    }
+
+    // The numbers start after the first space that is followed only by digits and separators,
+    // so numbered books like "1 John" keep their number
+    let is_number_char = |c: char| c.is_ascii_digit() || matches!(c, ':' | '-' | '–' | ' ');
+    let split = input.char_indices().find(|&(idx, c)| {
+        let rest = input[idx..].trim();
+        c.is_whitespace()
+            && !input[..idx].trim().is_empty()
+            && !rest.is_empty()
+            && rest.chars().all(is_number_char)
+    });
+    let (book_part, numbers) = match split {
+        Some((idx, _)) => (input[..idx].trim(), Some(input[idx..].trim())),
+        None => (input, None),
+    };
+
+    let book = resolve_book(bible, book_part)
+        .ok_or_else(|| format!("No book matches \"{}\"", book_part))?;
+    let chapters = bible
+        .ot
+        .get(&book)
+        .or_else(|| bible.nt.get(&book))
+        .ok_or_else(|| format!("Book '{}' not found", book))?;
+    let last_verse = |chapter: u32| chapters[chapter as usize - 1];
+
+    let Some(numbers) = numbers else {
+        let last_chapter = chapters.len() as u32;
+        return Ok(Passage {
+            book,
+            start: (1, 1),
+            end: (last_chapter, last_verse(last_chapter)),
+        });
+    };
+
+    let numbers: String = numbers.chars().filter(|c| *c != ' ').collect();
+    let (start_str, end_str) = match numbers.split_once(['-', '–']) {
+        Some((start, end)) => (start, Some(end)),
+        None => (numbers.as_str(), None),
+    };
+
+    let check_chapter = |chapter_str: &str| -> Result<u32, String> {
+        let chapter = chapter_str
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid chapter: {}", chapter_str))?;
+        if chapter == 0 || chapter > chapters.len() as u32 {
+            return Err(format!(
+                "{} has no chapter {} (max: {})",
+                book,
+                chapter,
+                chapters.len()
+            ));
+        }
+        Ok(chapter)
+    };
+    let check_verse = |chapter: u32, verse_str: &str| -> Result<u32, String> {
+        let verse = verse_str
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid verse: {}", verse_str))?;
+        if verse == 0 || verse > last_verse(chapter) {
+            return Err(format!(
+                "{} {} has no verse {} (max: {})",
+                book,
+                chapter,
+                verse,
+                last_verse(chapter)
+            ));
+        }
+        Ok(verse)
+    };
+
+    let (start, start_has_verse) = match start_str.split_once(':') {
+        Some((chapter_str, verse_str)) => {
+            let chapter = check_chapter(chapter_str)?;
+            ((chapter, check_verse(chapter, verse_str)?), true)
+        }
+        None => ((check_chapter(start_str)?, 1), false),
+    };
+
+    let end = match end_str {
+        // "3" is the whole chapter, "3:16" is a single verse
+        None if start_has_verse => start,
+        None => (start.0, last_verse(start.0)),
+        Some(end_str) => match end_str.split_once(':') {
+            Some((chapter_str, verse_str)) => {
+                let chapter = check_chapter(chapter_str)?;
+                (chapter, check_verse(chapter, verse_str)?)
+            }
+            // After a verse, a bare number is a verse in the same chapter ("3:16-18");
+            // otherwise it is a chapter ("3-4")
+            None if start_has_verse => (start.0, check_verse(start.0, end_str)?),
+            None => {
+                let chapter = check_chapter(end_str)?;
+                (chapter, last_verse(chapter))
+            }
+        },
+    };
+
+    if end < start {
+        return Err(format!(
+            "The passage ends ({}:{}) before it starts ({}:{})",
+            end.0, end.1, start.0, start.1
+        ));
+    }
+
+    Ok(Passage { book, start, end })
+}
+
+/// Find the book a (possibly abbreviated) name refers to: an exact match wins, then the first
+/// book in canonical order starting with the name, then the best fuzzy match
+pub fn resolve_book(bible: &BibleStructure, name: &str) -> Option<String> {
+    let query = normalize(name);
+    if query.is_empty() {
+        return None;
+    }
+
+    let mut candidates: Vec<(String, String)> = get_all_books(bible)
+        .into_iter()
+        .map(|book| (book.clone(), book))
+        .collect();
+    candidates.extend(get_book_aliases(bible));
+
+    if let Some((_, book)) = candidates
+        .iter()
+        .find(|(alias, _)| normalize(alias) == query)
+    {
+        return Some(book.clone());
+    }
+
+    // Candidates are in canonical order, so "jo" resolves to Joshua before John
+    if let Some((_, book)) = candidates
+        .iter()
+        .filter(|(alias, _)| normalize(alias).starts_with(&query))
+        .min_by_key(|(_, book)| canonical_index(bible, book))
+    {
+        return Some(book.clone());
+    }
+
+    let matcher = SkimMatcherV2::default();
+    candidates
+        .iter()
+        .filter_map(|(alias, book)| {
+            matcher
+                .fuzzy_match(alias, name.trim())
+                .map(|score| (score, book))
+        })
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, book)| book.clone())
+}
+
+/// The only book `name` can be the start of, counting aliases: "rom" or "romans" for Romans,
+/// but nothing for "jo" (Joshua, Job, Joel, John, Jonah) or a name that isn't a book
+pub fn unambiguous_book(bible: &BibleStructure, name: &str) -> Option<String> {
+    let mut books = books_starting_with(bible, name).into_iter();
+    let first = books.next()?;
+    books.all(|book| book == first).then_some(first)
+}
+
+/// Whether `name` is the start of `book` or one of its aliases, e.g. "1 joh" for I John
+pub fn is_book_prefix(bible: &BibleStructure, book: &str, name: &str) -> bool {
+    books_starting_with(bible, name).iter().any(|b| b == book)
+}
+
+/// The book of every name or alias that starts with `name`
+fn books_starting_with(bible: &BibleStructure, name: &str) -> Vec<String> {
+    let query = normalize(name);
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut candidates: Vec<(String, String)> = get_all_books(bible)
+        .into_iter()
+        .map(|book| (book.clone(), book))
+        .collect();
+    candidates.extend(get_book_aliases(bible));
+    candidates
+        .into_iter()
+        .filter(|(alias, _)| normalize(alias).starts_with(&query))
+        .map(|(_, book)| book)
+        .collect()
+}
+
+/// Chapters as a list of references, books in the order they first appear and each book's
+/// chapters sorted with runs joined, e.g. "Psalms 23-25, John 3"
+pub fn format_chapters<'a>(chapters: impl IntoIterator<Item = (&'a str, u32)>) -> String {
+    let mut books: Vec<(&str, Vec<u32>)> = Vec::new();
+    for (book, chapter) in chapters {
+        match books.iter_mut().find(|(name, _)| *name == book) {
+            Some((_, book_chapters)) => book_chapters.push(chapter),
+            None => books.push((book, vec![chapter])),
+        }
+    }
+
+    let mut parts = Vec::new();
+    for (book, mut chapters) in books {
+        chapters.sort_unstable();
+        chapters.dedup();
+        let mut runs: Vec<(u32, u32)> = Vec::new();
+        for chapter in chapters {
+            match runs.last_mut() {
+                Some((_, last)) if *last + 1 == chapter => *last = chapter,
+                _ => runs.push((chapter, chapter)),
+            }
+        }
+        for (first, last) in runs {
+            if first == last {
+                parts.push(format!("{} {}", book, first));
+            } else {
+                parts.push(format!("{} {}-{}", book, first, last));
+            }
+        }
+    }
+    parts.join(", ")
+}
+
+fn canonical_index(bible: &BibleStructure, book: &str) -> usize {
+    bible
+        .ot
+        .get_index_of(book)
+        .or_else(|| bible.nt.get_index_of(book).map(|idx| idx + bible.ot.len()))
+        .unwrap_or(usize::MAX)
+}
+
+/// Lowercase and drop spaces and periods, so "1 Jn." and "1jn" compare equal
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace() && *c != '.')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_unique_book_prefixes_are_unambiguous() {
+        let bible = get_bible_structure();
+        assert_eq!(unambiguous_book(bible, "rom").as_deref(), Some("Romans"));
+        assert_eq!(unambiguous_book(bible, "John").as_deref(), Some("John"));
+        assert_eq!(unambiguous_book(bible, "1 jo").as_deref(), Some("I John"));
+        assert_eq!(unambiguous_book(bible, "jo"), None);
+        assert_eq!(unambiguous_book(bible, "phil"), None);
+        assert_eq!(unambiguous_book(bible, "xyz"), None);
+        assert!(is_book_prefix(bible, "I John", "1 joh"));
+        assert!(!is_book_prefix(bible, "John", "1 joh"));
+    }
+    use crate::bible_structure::get_bible_structure;
+
+    fn parse(input: &str) -> Result<Reference, String> {
+        parse_reference(get_bible_structure(), input)
+    }
+
+    #[test]
+    fn parses_abbreviated_book_and_chapter() {
+        let reference = parse("ps 23").unwrap();
+        assert_eq!(reference.book, "Psalms");
+        assert_eq!(reference.chapter, Some(23));
+        assert_eq!(reference.verse, None);
+    }
+
+    #[test]
+    fn parses_numbered_books_and_verses() {
+        let reference = parse("1 John 3:16").unwrap();
+        assert_eq!(reference.book, "I John");
+        assert_eq!(reference.chapter, Some(3));
+        assert_eq!(reference.verse, Some(16));
+
+        assert_eq!(parse("2 kings").unwrap().book, "II Kings");
+        assert_eq!(parse("Romans").unwrap().chapter, None);
+    }
+
+    #[test]
+    fn rejects_out_of_range_chapters() {
+        assert!(parse("jude 2").is_err());
+        assert!(parse("genesis 0").is_err());
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn parses_passages_across_chapters() {
+        let bible = get_bible_structure();
+        let passage = parse_passage(bible, "John 3:16-4:2").unwrap();
+        assert_eq!(passage.book, "John");
+        assert_eq!(passage.chapter_ranges(bible), vec![(3, 16, 36), (4, 1, 2)]);
+
+        let ranges = |input: &str| parse_passage(bible, input).unwrap().chapter_ranges(bible);
+        assert_eq!(ranges("John 3:16-18"), vec![(3, 16, 18)]);
+        assert_eq!(ranges("John 3:16"), vec![(3, 16, 16)]);
+        assert_eq!(ranges("1 John 2 - 3"), vec![(2, 1, 29), (3, 1, 24)]);
+        assert_eq!(ranges("jude"), vec![(1, 1, 25)]);
+        assert!(parse_passage(bible, "jude").unwrap().is_whole_book(bible));
+
+        let canonical = |input: &str| parse_passage(bible, input).unwrap().to_string();
+        assert_eq!(canonical("jude 1"), "Jude");
+        assert_eq!(canonical("john 3:1-36"), "John 3");
+        assert_eq!(canonical("john 3:1-4:54"), "John 3-4");
+        assert_eq!(canonical("john 3:16-4:2"), "John 3:16-4:2");
+    }
+
+    #[test]
+    fn rejects_invalid_passages() {
+        let bible = get_bible_structure();
+        assert!(parse_passage(bible, "John 4-3").is_err());
+        assert!(parse_passage(bible, "John 3:40").is_err());
+        assert!(parse_passage(bible, "John 3:16-4:60").is_err());
+        assert!(parse_passage(bible, "").is_err());
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// Any passage of the Bible: a book, then a start and an end verse within it
+        fn passages() -> impl Strategy<Value = Passage> {
+            let bible = get_bible_structure();
+            let books: Vec<(String, Vec<u32>)> = bible
+                .ot
+                .iter()
+                .chain(&bible.nt)
+                .map(|(book, chapters)| (book.clone(), chapters.clone()))
+                .collect();
+            prop::sample::select(books).prop_flat_map(|(book, chapters)| {
+                let verses: Vec<(u32, u32)> = chapters
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(idx, &count)| (1..=count).map(move |verse| (idx as u32 + 1, verse)))
+                    .collect();
+                let len = verses.len();
+                (0..len, 0..len).prop_map(move |(a, b)| Passage {
+                    book: book.clone(),
+                    start: verses[a.min(b)],
+                    end: verses[a.max(b)],
+                })
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn formatted_passages_parse_back(passage in passages()) {
+                let formatted = passage.to_string();
+                let parsed = parse_passage(get_bible_structure(), &formatted);
+                prop_assert_eq!(parsed, Ok(passage), "{}", formatted);
+            }
+
+            #[test]
+            fn parsing_never_panics(input in "\\PC{0,30}") {
+                let bible = get_bible_structure();
+                let _ = parse_reference(bible, &input);
+                let _ = parse_passage(bible, &input);
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{Duration, NaiveDate};
+
+use crate::bible_structure::BibleStructure;
+use crate::progress::ReadingEvent;
+use crate::utils::get_all_books;
+
+/// Days before a chapter read once is due to be read again; each further reading doubles it
+pub const BASE_INTERVAL_DAYS: i64 = 30;
+/// Longest gap between re-readings, however often a chapter has been read
+pub const MAX_INTERVAL_DAYS: i64 = 720;
+
+/// A chapter that is due to be read again
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewSuggestion {
+    pub book: String,
+    pub chapter: u32,
+    /// Number of days the chapter was read on
+    pub reads: u32,
+    pub last_read: NaiveDate,
+    /// When the chapter became due
+    pub due: NaiveDate,
+}
+
+impl ReviewSuggestion {
+    /// e.g. "read 3 times, last on 2025-01-05"
+    pub fn describe(&self) -> String {
+        format!(
+            "read {} time{}, last on {}",
+            self.reads,
+            if self.reads == 1 { "" } else { "s" },
+            self.last_read
+        )
+    }
+}
+
+/// Days to wait after the last of `reads` readings before a chapter is due again
+pub fn review_interval(reads: u32) -> i64 {
+    let doublings = reads.saturating_sub(1).min(10);
+    (BASE_INTERVAL_DAYS << doublings).min(MAX_INTERVAL_DAYS)
+}
+
+/// Chapters due to be re-read as of `today`, most overdue first. A chapter is overdue in
+/// proportion to its interval, so a chapter read once a month late comes before one read
+/// many times that is a month late. Only readings from before today are considered, so the
+/// suggestions stay put as they are read.
+pub fn review_suggestions(
+    bible: &BibleStructure,
+    history: &[ReadingEvent],
+    today: NaiveDate,
+) -> Vec<ReviewSuggestion> {
+    let mut dates: HashMap<(&str, u32), BTreeSet<NaiveDate>> = HashMap::new();
+    for event in history.iter().filter(|event| event.date < today) {
+        dates
+            .entry((event.book.as_str(), event.chapter))
+            .or_default()
+            .insert(event.date);
+    }
+
+    let book_order: HashMap<String, usize> = get_all_books(bible)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, book)| (book, idx))
+        .collect();
+    let mut due: Vec<(f64, usize, ReviewSuggestion)> = dates
+        .into_iter()
+        .filter_map(|((book, chapter), dates)| {
+            let reads = dates.len() as u32;
+            let last_read = *dates.last()?;
+            let interval = review_interval(reads);
+            let elapsed = (today - last_read).num_days();
+            (elapsed >= interval).then(|| {
+                (
+                    elapsed as f64 / interval as f64,
+                    book_order.get(book).copied().unwrap_or(usize::MAX),
+                    ReviewSuggestion {
+                        book: book.to_string(),
+                        chapter,
+                        reads,
+                        last_read,
+                        due: last_read + Duration::days(interval),
+                    },
+                )
+            })
+        })
+        .collect();
+    due.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then(a.1.cmp(&b.1))
+            .then(a.2.chapter.cmp(&b.2.chapter))
+    });
+    due.into_iter()
+        .map(|(_, _, suggestion)| suggestion)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::progress::ReadingSource;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn event(date: NaiveDate, book: &str, chapter: u32) -> ReadingEvent {
+        ReadingEvent {
+            date,
+            book: book.into(),
+            chapter,
+            verse_start: 1,
+            verse_end: 1,
+            read_count: 1,
+            note: None,
+            tags: Vec::new(),
+            source: ReadingSource::Read,
+            translation: None,
+            campaign: None,
+            overwrite: false,
+        }
+    }
+
+    #[test]
+    fn rarely_and_long_ago_read_chapters_come_first() {
+        let bible = get_bible_structure();
+        let history = vec![
+            // Read once, 60 days before Mar 31: twice its interval
+            event(date(1, 30), "Ruth", 1),
+            // Read three times, last 64 days ago: not yet due (120 days)
+            event(date(1, 1), "John", 1),
+            event(date(1, 10), "John", 1),
+            event(date(1, 26), "John", 1),
+            // Read twice, last 64 days ago: just over its interval (60 days)
+            event(date(1, 1), "Genesis", 1),
+            event(date(1, 26), "Genesis", 1),
+            // Read once, 30 days ago: due today
+            event(date(3, 1), "Jonah", 2),
+            // Read recently
+            event(date(3, 20), "Mark", 1),
+        ];
+
+        let suggestions = review_suggestions(bible, &history, date(3, 31));
+        let summary: Vec<(&str, u32, u32)> = suggestions
+            .iter()
+            .map(|s| (s.book.as_str(), s.chapter, s.reads))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("Ruth", 1, 1), ("Genesis", 1, 2), ("Jonah", 2, 1)]
+        );
+        assert_eq!(suggestions[1].due, date(3, 27));
+        assert_eq!(
+            suggestions[1].describe(),
+            "read 2 times, last on 2025-01-26"
+        );
+        assert_eq!(review_interval(0), 30);
+        assert_eq!(review_interval(20), MAX_INTERVAL_DAYS);
+    }
+}
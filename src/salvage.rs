@@ -0,0 +1,194 @@
+//! Reading what can still be read from a damaged progress file, e.g. one cut short by a full
+//! disk or broken by a hand edit. Each top-level key is read on its own, and a list or map
+//! that doesn't read as a whole is read an entry at a time, so one bad reading costs that
+//! reading rather than the whole history.
+
+use serde_yaml::{Mapping, Value};
+
+use crate::progress::ReadingProgress;
+use crate::storage::starts_section;
+
+/// What could be read from a damaged progress file, and what was left out
+#[derive(Debug)]
+pub struct Salvage {
+    pub progress: ReadingProgress,
+    /// Why each part left out couldn't be read, e.g. "history, entry at line 120: missing
+    /// field `date`"
+    pub skipped: Vec<String>,
+}
+
+/// A top-level key of a progress file and the lines of its value, starting with the key's
+struct Section<'a> {
+    key: &'a str,
+    /// 1-based line number of the key
+    line: usize,
+    lines: Vec<&'a str>,
+}
+
+pub fn salvage(content: &str) -> Salvage {
+    let mut kept = Mapping::new();
+    let mut skipped = Vec::new();
+    for section in sections(content) {
+        let error = match read(&section.lines.concat()) {
+            Ok(value) => {
+                merge(&mut kept, value);
+                continue;
+            }
+            Err(e) => e,
+        };
+        let entries = entries(&section);
+        if entries.is_empty() {
+            skipped.push(format!("{}, line {}: {}", section.key, section.line, error));
+        }
+        for (line, text) in entries {
+            match read(&format!("{}:\n{}", section.key, text)) {
+                Ok(value) => merge(&mut kept, value),
+                Err(e) => skipped.push(format!("{}, entry at line {}: {}", section.key, line, e)),
+            }
+        }
+    }
+    let progress = serde_yaml::from_value(Value::Mapping(kept)).unwrap_or_else(|e| {
+        skipped.push(format!("everything: {}", e));
+        ReadingProgress::new()
+    });
+    Salvage { progress, skipped }
+}
+
+impl Salvage {
+    /// What was read and what was left out, for the diagnostic file written next to the damaged
+    /// one. `error` is why the file couldn't be loaded normally.
+    pub fn report(&self, error: &str) -> String {
+        let mut report = format!(
+            "The progress file couldn't be loaded: {}\n\n\
+             Opened read-only with {} readings in {} books. Left out:\n",
+            error,
+            self.progress.history.len(),
+            self.progress.books.len()
+        );
+        for skipped in &self.skipped {
+            report.push_str(&format!("- {}\n", skipped));
+        }
+        if self.skipped.is_empty() {
+            report.push_str("- nothing\n");
+        }
+        report
+    }
+}
+
+/// The top-level keys of `content`, in order. Lines before the first are skipped; they can
+/// only be comments or a document marker in a file the app wrote.
+fn sections(content: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = Vec::new();
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        if starts_section(line) {
+            sections.push(Section {
+                key: line.split(':').next().unwrap_or_default().trim(),
+                line: index + 1,
+                lines: vec![line],
+            });
+        } else if let Some(section) = sections.last_mut() {
+            section.lines.push(line);
+        }
+    }
+    sections
+}
+
+/// The entries of a section's list or map, each with its line number; none for other values
+fn entries(section: &Section) -> Vec<(usize, String)> {
+    let is_content = |line: &str| !line.trim().is_empty() && !line.trim().starts_with('#');
+    let Some(first) = section.lines[1..].iter().find(|line| is_content(line)) else {
+        return Vec::new();
+    };
+    let indent = first.len() - first.trim_start().len();
+    let is_list = first.trim_start().starts_with('-');
+    // A map's entries are its keys; a map of plans or campaigns isn't split this way
+    if !is_list && !matches!(section.key, "books" | "achievements" | "journal") {
+        return Vec::new();
+    }
+    let mut entries: Vec<(usize, String)> = Vec::new();
+    for (index, line) in section.lines.iter().enumerate().skip(1) {
+        let starts_entry = is_content(line)
+            && line.len() - line.trim_start().len() == indent
+            && line.trim_start().starts_with('-') == is_list;
+        if starts_entry {
+            entries.push((section.line + index, line.to_string()));
+        } else if let Some((_, text)) = entries.last_mut() {
+            text.push_str(line);
+        }
+    }
+    entries
+}
+
+/// A section or entry, as a one-key map, if it parses and holds what a progress file should
+fn read(text: &str) -> Result<Mapping, String> {
+    let value: Value = serde_yaml::from_str(text).map_err(|e| without_location(&e))?;
+    let Value::Mapping(mapping) = value else {
+        return Err("not a key and value".to_string());
+    };
+    serde_yaml::from_value::<ReadingProgress>(Value::Mapping(mapping.clone()))
+        .map_err(|e| without_location(&e))?;
+    Ok(mapping)
+}
+
+/// The error's message, without where in the section or entry it was, which the caller reports
+/// as a line of the whole file instead
+fn without_location(error: &serde_yaml::Error) -> String {
+    let message = error.to_string();
+    match message.find(" at line ") {
+        Some(end) if error.location().is_some() => message[..end].to_string(),
+        _ => message,
+    }
+}
+
+/// Add a section or entry that was read to what's kept so far
+fn merge(kept: &mut Mapping, value: Mapping) {
+    for (key, value) in value {
+        match (kept.get_mut(&key), value) {
+            (Some(Value::Sequence(items)), Value::Sequence(more)) => items.extend(more),
+            (Some(Value::Mapping(entries)), Value::Mapping(more)) => entries.extend(more),
+            (_, value) => {
+                kept.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad_entries_are_left_out_and_the_rest_kept() {
+        let mut progress = ReadingProgress::new();
+        for chapter in 1..=4 {
+            progress.record_reading("Ruth", chapter, 1, 20);
+        }
+        progress.record_reading("Jonah", 1, 1, 17);
+        let day = progress.history[0].date;
+        progress.set_journal_entry(day, "Ruth and Naomi");
+        let content = serde_yaml::to_string(&progress).unwrap();
+        assert!(salvage(&content).skipped.is_empty());
+
+        // A reading cut off mid-line, one with a date that isn't one, and a broken plan
+        let mut lines: Vec<&str> = content.lines().collect();
+        let readings: Vec<usize> = (0..lines.len())
+            .filter(|&index| lines[index].starts_with("- date:"))
+            .collect();
+        let (second, third) = (readings[1], readings[2]);
+        lines[second + 1] = "  book: [Ruth";
+        let third_date = format!("- date: {}x", day);
+        lines[third] = &third_date;
+        lines.push("plan: {broken");
+        let damaged = lines.join("\n");
+        assert!(serde_yaml::from_str::<ReadingProgress>(&damaged).is_err());
+
+        let salvaged = salvage(&damaged);
+        assert_eq!(salvaged.progress.history.len(), 3);
+        assert_eq!(salvaged.progress.books, progress.books);
+        assert_eq!(salvaged.progress.journal, progress.journal);
+        assert!(salvaged.progress.plan.is_none());
+        assert_eq!(salvaged.skipped.len(), 3, "{:?}", salvaged.skipped);
+        assert!(salvaged.skipped[0].starts_with(&format!("history, entry at line {}", second + 1)));
+        assert!(salvaged.report("broken").contains("3 readings in 2 books"));
+    }
+}
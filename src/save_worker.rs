@@ -0,0 +1,150 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::progress::ReadingProgress;
+use crate::storage::{save_progress_with, FileStorage};
+
+/// How long the worker waits for newer progress before writing, so a burst of changes (e.g.
+/// marking a batch of chapters) is written once
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+enum Request {
+    Save(Box<ReadingProgress>),
+    /// Write any pending progress now and report how it went
+    Flush(Sender<Result<(), String>>),
+}
+
+/// Serializes and writes the progress file on a background thread, so recording even a whole
+/// book never holds up the interface. Saves are debounced; before the first one, the existing
+/// file is copied to `<file>.bak`, so a bad session can be undone by hand.
+pub struct SaveWorker {
+    sender: Sender<Request>,
+}
+
+impl SaveWorker {
+    /// Start the worker for the progress file at `path`. `on_error` is called from the worker
+    /// with why a debounced save failed.
+    pub fn spawn(path: PathBuf, on_error: impl Fn(String) + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || run(&path, &receiver, on_error));
+        Self { sender }
+    }
+
+    /// Save `progress` once no newer progress arrives for `DEBOUNCE`
+    pub fn save(&self, progress: ReadingProgress) {
+        // The worker only stops once every sender is gone
+        let _ = self.sender.send(Request::Save(Box::new(progress)));
+    }
+
+    /// Write any pending progress and wait until it's on disk, e.g. before quitting
+    pub fn flush(&self) -> Result<(), String> {
+        let (reply, result) = mpsc::channel();
+        self.sender
+            .send(Request::Flush(reply))
+            .map_err(|_| "The save worker stopped".to_string())?;
+        result
+            .recv()
+            .map_err(|_| "The save worker stopped".to_string())?
+    }
+}
+
+fn run(path: &Path, receiver: &Receiver<Request>, on_error: impl Fn(String)) {
+    let mut backed_up = false;
+    let mut write = |progress: &ReadingProgress| -> Result<(), String> {
+        if !backed_up {
+            back_up(path).map_err(|e| format!("Couldn't back up {}: {}", path.display(), e))?;
+            backed_up = true;
+        }
+        save_progress_with(&FileStorage, progress, path)
+            .map_err(|e| format!("Couldn't save {}: {}", path.display(), e))
+    };
+
+    let mut pending: Option<Box<ReadingProgress>> = None;
+    loop {
+        let request = match pending {
+            Some(_) => match receiver.recv_timeout(DEBOUNCE) {
+                Ok(request) => request,
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Err(e) = write(&pending.take().unwrap()) {
+                        on_error(e);
+                    }
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match receiver.recv() {
+                Ok(request) => request,
+                Err(_) => break,
+            },
+        };
+        match request {
+            Request::Save(progress) => pending = Some(progress),
+            Request::Flush(reply) => {
+                let result = pending.take().map_or(Ok(()), |progress| write(&progress));
+                let _ = reply.send(result);
+            }
+        }
+    }
+    // The app went away without flushing; still keep what it recorded
+    if let Some(progress) = pending {
+        if let Err(e) = write(&progress) {
+            on_error(e);
+        }
+    }
+}
+
+/// Where the worker backs up the file at `path`: `<path>.bak`
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Copy the file at `path`, if there is one, to its backup path
+fn back_up(path: &Path) -> std::io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    std::fs::copy(path, backup_path(path)).map(drop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::load_progress_from;
+
+    #[test]
+    fn bursts_are_saved_once_after_a_backup() {
+        let dir = std::env::temp_dir().join(format!("brp-save-worker-{}", std::process::id()));
+        let path = dir.join("reading_progress.yaml");
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        save_progress_with(&FileStorage, &progress, &path).unwrap();
+
+        let worker = SaveWorker::spawn(path.clone(), |e| panic!("{}", e));
+        progress.record_reading("Ruth", 2, 1, 23);
+        worker.save(progress.clone());
+        progress.record_reading("Ruth", 3, 1, 18);
+        worker.save(progress.clone());
+        worker.flush().unwrap();
+
+        assert_eq!(
+            load_progress_from(&FileStorage, &path)
+                .unwrap()
+                .history
+                .len(),
+            3
+        );
+        let backup = dir.join("reading_progress.yaml.bak");
+        assert_eq!(
+            load_progress_from(&FileStorage, &backup)
+                .unwrap()
+                .history
+                .len(),
+            1
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
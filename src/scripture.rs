@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use color_eyre::Result;
+
+use crate::bible_structure::BibleStructure;
+use crate::reference::resolve_book;
+
+/// Bible text loaded from a local plain-text file with one verse per line, in the form
+/// `Book Chapter:Verse Text` (e.g. `Genesis 1:1 In the beginning...`). Book names may be
+/// abbreviated or use Arabic numerals, like in the quick-jump prompt; lines that don't parse
+/// (headers, blank lines) are skipped.
+#[derive(Debug, Default)]
+pub struct ScriptureText {
+    /// Verses of each (book, chapter), in the order they appear in the file
+    chapters: HashMap<(String, u32), Vec<(u32, String)>>,
+}
+
+impl ScriptureText {
+    pub fn load(bible: &BibleStructure, path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(Self::parse(bible, &content))
+    }
+
+    pub fn parse(bible: &BibleStructure, content: &str) -> Self {
+        let mut text = Self::default();
+        // Consecutive lines almost always share a book, so avoid resolving it again each time
+        let mut last_book: Option<(String, String)> = None;
+        for line in content.lines() {
+            let Some((book_name, chapter, verse, verse_text)) = split_verse_line(line) else {
+                continue;
+            };
+            let book = match &last_book {
+                Some((name, book)) if name == book_name => book.clone(),
+                _ => {
+                    let Some(book) = resolve_book(bible, book_name) else {
+                        continue;
+                    };
+                    last_book = Some((book_name.to_string(), book.clone()));
+                    book
+                }
+            };
+            text.chapters
+                .entry((book, chapter))
+                .or_default()
+                .push((verse, verse_text.to_string()));
+        }
+        text
+    }
+
+    /// Verses of a chapter as (verse number, text), if the file contains it
+    pub fn chapter(&self, book: &str, chapter: u32) -> Option<&[(u32, String)]> {
+        self.chapters
+            .get(&(book.to_string(), chapter))
+            .map(Vec::as_slice)
+    }
+}
+
+/// Split `Book Chapter:Verse Text` into its parts
+fn split_verse_line(line: &str) -> Option<(&str, u32, u32, &str)> {
+    let line = line.trim();
+    // The book name may contain spaces ("1 John", "Song of Solomon"), so look for the first
+    // `chapter:verse` token after it
+    let mut offset = 0;
+    for token in line.split_whitespace() {
+        let start = offset + line[offset..].find(token)?;
+        offset = start + token.len();
+        let Some((chapter, verse)) = token.split_once(':') else {
+            continue;
+        };
+        let (Ok(chapter), Ok(verse)) = (chapter.parse(), verse.parse()) else {
+            continue;
+        };
+        let book = line[..start].trim();
+        if book.is_empty() {
+            return None;
+        }
+        return Some((book, chapter, verse, line[offset..].trim()));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn parses_verse_lines_by_chapter() {
+        let content = "\
+King James Version
+
+Genesis 1:1 In the beginning God created the heaven and the earth.
+Genesis 1:2 And the earth was without form, and void.
+1 John 3:16 Hereby perceive we the love of God.
+Nonsense 1:1 Skipped.
+";
+        let text = ScriptureText::parse(get_bible_structure(), content);
+        let genesis = text.chapter("Genesis", 1).unwrap();
+        assert_eq!(genesis.len(), 2);
+        assert_eq!(
+            genesis[1],
+            (2, "And the earth was without form, and void.".to_string())
+        );
+        assert_eq!(text.chapter("I John", 3).unwrap()[0].0, 16);
+        assert!(text.chapter("Genesis", 2).is_none());
+    }
+}
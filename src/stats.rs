@@ -0,0 +1,514 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::bible_structure::BibleStructure;
+use crate::coverage::book_percent_read;
+use crate::progress::{ReadingEvent, ReadingProgress};
+use crate::reference::{format_chapters, resolve_book};
+use crate::utils::get_all_books;
+
+/// All dates on which at least one reading was recorded
+pub fn reading_days(history: &[ReadingEvent]) -> BTreeSet<NaiveDate> {
+    history.iter().map(|event| event.date).collect()
+}
+
+/// Number of consecutive days with at least one reading, ending today.
+/// A streak that ended yesterday still counts, since today's reading may not be recorded yet.
+pub fn current_streak(history: &[ReadingEvent], today: NaiveDate) -> u32 {
+    let days = reading_days(history);
+
+    let mut day = if days.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+
+    let mut streak = 0;
+    while days.contains(&day) {
+        streak += 1;
+        day -= Duration::days(1);
+    }
+    streak
+}
+
+/// Longest run of consecutive days with at least one reading, at any point in the history
+pub fn longest_streak(history: &[ReadingEvent]) -> u32 {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in reading_days(history) {
+        current = match previous {
+            Some(previous) if day - previous == Duration::days(1) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        previous = Some(day);
+    }
+    longest
+}
+
+/// Distinct (book, chapter) pairs read on a given date, in the order they were recorded
+pub fn chapters_read_on(history: &[ReadingEvent], date: NaiveDate) -> Vec<(String, u32)> {
+    let mut chapters: Vec<(String, u32)> = Vec::new();
+    for event in history.iter().filter(|event| event.date == date) {
+        let chapter = (event.book.to_string(), event.chapter);
+        if !chapters.contains(&chapter) {
+            chapters.push(chapter);
+        }
+    }
+    chapters
+}
+
+/// The readings of a chapter, newest first (by date, then latest recorded), for showing what
+/// its read count is made of
+pub fn chapter_readings<'a>(
+    history: &'a [ReadingEvent],
+    book: &str,
+    chapter: u32,
+) -> Vec<&'a ReadingEvent> {
+    let mut readings: Vec<&ReadingEvent> = history
+        .iter()
+        .rev()
+        .filter(|event| event.book == book && event.chapter == chapter)
+        .collect();
+    readings.sort_by_key(|event| std::cmp::Reverse(event.date));
+    readings
+}
+
+/// Number of distinct chapters read on each of the `days` days ending on `end`, oldest first
+pub fn chapters_per_day(history: &[ReadingEvent], end: NaiveDate, days: usize) -> Vec<u64> {
+    let mut chapters_by_date: HashMap<NaiveDate, HashSet<(&str, u32)>> = HashMap::new();
+    for event in history {
+        chapters_by_date
+            .entry(event.date)
+            .or_default()
+            .insert((event.book.as_str(), event.chapter));
+    }
+
+    (0..days)
+        .rev()
+        .map(|days_ago| {
+            let date = end - Duration::days(days_ago as i64);
+            chapters_by_date
+                .get(&date)
+                .map_or(0, |chapters| chapters.len() as u64)
+        })
+        .collect()
+}
+
+/// A reading time such as "45 min" or "3 h 05 min"
+pub fn format_minutes(minutes: u32) -> String {
+    if minutes < 60 {
+        format!("{} min", minutes)
+    } else {
+        format!("{} h {:02} min", minutes / 60, minutes % 60)
+    }
+}
+
+/// A reading goal from the config file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Goal {
+    /// Chapters to read each week, Monday to Sunday
+    ChaptersPerWeek(u32),
+    /// Chapters to read each calendar month
+    ChaptersPerMonth(u32),
+    /// Minutes of timed reading each day
+    MinutesPerDay(u32),
+    /// Read every verse of a book by a date
+    FinishBook { book: String, by: NaiveDate },
+}
+
+/// How a goal is going
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    /// Short progress for the status bar, e.g. "12/20 chapters this week"
+    pub summary: String,
+    pub met: bool,
+    /// Why the goal is at risk, if it is
+    pub warning: Option<String>,
+}
+
+/// Days over which the pace of reading is measured for a book deadline
+const PACE_DAYS: usize = 14;
+
+/// Progress on each of `goals` as of `today`. A chapter goal is at risk once fewer chapters
+/// were read before today than its pace calls for, a daily time goal once the last week
+/// averaged less, and a book deadline once it needs more chapters a day than the last two
+/// weeks averaged.
+pub fn evaluate_goals(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    goals: &[Goal],
+    today: NaiveDate,
+) -> Vec<GoalProgress> {
+    let chapters_since = |start: NaiveDate| -> u64 {
+        let days = (today - start).num_days() as usize + 1;
+        chapters_per_day(&progress.history, today, days)
+            .iter()
+            .sum()
+    };
+    let chapter_goal = |target: u32, period: &str, start: NaiveDate, length: i64| {
+        let done = chapters_since(start) as u32;
+        let elapsed = (today - start).num_days();
+        let due = (target as i64 * elapsed / length) as u32;
+        let days_left = length - elapsed;
+        GoalProgress {
+            summary: format!("{}/{} chapters this {}", done, target, period),
+            met: done >= target,
+            warning: (done < due).then(|| {
+                format!(
+                    "{} more chapters to read in {} day{} to meet the {} goal",
+                    target - done,
+                    days_left,
+                    if days_left == 1 { "" } else { "s" },
+                    period
+                )
+            }),
+        }
+    };
+
+    goals
+        .iter()
+        .map(|goal| match goal {
+            Goal::ChaptersPerWeek(target) => {
+                let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                chapter_goal(*target, "week", monday, 7)
+            }
+            Goal::ChaptersPerMonth(target) => {
+                let first = today.with_day(1).unwrap_or(today);
+                let next_month = first
+                    .checked_add_months(chrono::Months::new(1))
+                    .unwrap_or(first);
+                chapter_goal(*target, "month", first, (next_month - first).num_days())
+            }
+            Goal::MinutesPerDay(target) => {
+                let minutes_on = |date: NaiveDate| -> u32 {
+                    progress
+                        .sessions
+                        .iter()
+                        .filter(|session| session.date == date)
+                        .map(|session| session.minutes)
+                        .sum()
+                };
+                let done = minutes_on(today);
+                let last_week: u32 = (1..=7)
+                    .map(|days_ago| minutes_on(today - Duration::days(days_ago)))
+                    .sum();
+                GoalProgress {
+                    summary: format!("{}/{} min today", done, target),
+                    met: done >= *target,
+                    warning: (last_week < target * 7).then(|| {
+                        format!(
+                            "Averaging {} a day over the last week, below the {} goal",
+                            format_minutes(last_week / 7),
+                            format_minutes(*target)
+                        )
+                    }),
+                }
+            }
+            Goal::FinishBook { book: name, by } => {
+                let Some(book) = resolve_book(bible, name) else {
+                    return GoalProgress {
+                        summary: format!("Unknown book: {}", name),
+                        met: false,
+                        warning: Some(format!("No book matches \"{}\"", name)),
+                    };
+                };
+                let percent = book_percent_read(bible, progress, &book);
+                let chapters = bible
+                    .ot
+                    .get(&book)
+                    .or_else(|| bible.nt.get(&book))
+                    .map_or(0, |chapters| chapters.len());
+                let remaining = chapters as f64 * (100.0 - percent) / 100.0;
+                let days_left = (*by - today).num_days() + 1;
+                let pace = chapters_per_day(&progress.history, today, PACE_DAYS)
+                    .iter()
+                    .sum::<u64>() as f64
+                    / PACE_DAYS as f64;
+                let met = percent >= 100.0;
+                let warning = if met {
+                    None
+                } else if days_left <= 0 {
+                    Some(format!("The deadline for {} has passed", book))
+                } else if remaining / days_left as f64 > pace {
+                    Some(format!(
+                        "{} needs {:.1} chapters a day; the last two weeks averaged {:.1}",
+                        book,
+                        remaining / days_left as f64,
+                        pace
+                    ))
+                } else {
+                    None
+                };
+                GoalProgress {
+                    summary: format!("{} {:.0}% by {}", book, percent, by.format("%b %-d")),
+                    met,
+                    warning,
+                }
+            }
+        })
+        .collect()
+}
+
+/// Days without a reading after which a book counts as neglected, unless configured otherwise
+pub const DEFAULT_NEGLECTED_DAYS: u32 = 180;
+
+/// A book not read in a while, or never
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NeglectedBook {
+    pub book: String,
+    /// Last day any verse of the book was read, or None if it never was
+    pub last_read: Option<NaiveDate>,
+}
+
+impl NeglectedBook {
+    /// e.g. "last read 2024-05-01, 310 days ago" or "never read"
+    pub fn describe(&self, today: NaiveDate) -> String {
+        match self.last_read {
+            Some(date) => format!("last read {}, {} days ago", date, (today - date).num_days()),
+            None => "never read".to_string(),
+        }
+    }
+}
+
+/// Books with no verse read in the last `days` days, never-read books first, then the longest
+/// untouched, in canonical order otherwise
+pub fn neglected_books(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    today: NaiveDate,
+    days: u32,
+) -> Vec<NeglectedBook> {
+    let cutoff = today - Duration::days(days as i64);
+    let mut neglected: Vec<NeglectedBook> = get_all_books(bible)
+        .into_iter()
+        .map(|book| {
+            let last_read = progress
+                .books
+                .get(book.as_str())
+                .and_then(|records| records.iter().map(|(_, record)| record.last_read).max());
+            NeglectedBook { book, last_read }
+        })
+        .filter(|book| book.last_read.is_none_or(|date| date <= cutoff))
+        .collect();
+    // Stable, so books left as long keep their canonical order
+    neglected.sort_by_key(|book| book.last_read);
+    neglected
+}
+
+/// One-line summary of the readings recorded in a session, e.g.
+/// "Recorded: John 3-4, Psalms 23 · streak 8 days", or None if nothing was recorded
+pub fn session_summary(session: &[ReadingEvent], streak: u32) -> Option<String> {
+    if session.is_empty() {
+        return None;
+    }
+    let streak = match streak {
+        1 => "1 day".to_string(),
+        n => format!("{} days", n),
+    };
+    Some(format!(
+        "Recorded: {} · streak {}",
+        format_chapters(
+            session
+                .iter()
+                .map(|event| (event.book.as_str(), event.chapter))
+        ),
+        streak
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::ReadingSource;
+
+    fn event(date: NaiveDate, book: &str, chapter: u32) -> ReadingEvent {
+        ReadingEvent {
+            date,
+            book: book.into(),
+            chapter,
+            verse_start: 1,
+            verse_end: 10,
+            read_count: 1,
+            note: None,
+            tags: Vec::new(),
+            source: ReadingSource::Read,
+            translation: None,
+            campaign: None,
+            overwrite: false,
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    #[test]
+    fn streak_counts_consecutive_days() {
+        let history = vec![
+            event(day(1), "John", 1),
+            event(day(3), "John", 2),
+            event(day(4), "John", 3),
+            event(day(5), "John", 4),
+        ];
+        assert_eq!(current_streak(&history, day(5)), 3);
+    }
+
+    #[test]
+    fn streak_survives_until_today_is_recorded() {
+        let history = vec![event(day(4), "John", 1), event(day(5), "John", 2)];
+        assert_eq!(current_streak(&history, day(6)), 2);
+        assert_eq!(current_streak(&history, day(7)), 0);
+    }
+
+    #[test]
+    fn longest_streak_finds_the_best_run() {
+        let history = vec![
+            event(day(1), "John", 1),
+            event(day(2), "John", 2),
+            event(day(3), "John", 3),
+            event(day(3), "John", 4),
+            event(day(10), "John", 5),
+        ];
+        assert_eq!(longest_streak(&history), 3);
+        assert_eq!(longest_streak(&[]), 0);
+    }
+
+    #[test]
+    fn chapters_read_on_deduplicates() {
+        let history = vec![
+            event(day(5), "John", 3),
+            event(day(5), "Psalms", 23),
+            event(day(5), "John", 3),
+            event(day(4), "John", 2),
+        ];
+        assert_eq!(
+            chapters_read_on(&history, day(5)),
+            vec![("John".to_string(), 3), ("Psalms".to_string(), 23)]
+        );
+    }
+
+    #[test]
+    fn chapter_readings_are_newest_first() {
+        let mut backdated = event(day(2), "John", 3);
+        backdated.note = Some("backdated".to_string());
+        let history = vec![
+            event(day(4), "John", 3),
+            event(day(5), "John", 4),
+            event(day(2), "John", 3),
+            backdated.clone(),
+            event(day(6), "John", 3),
+        ];
+        let readings = chapter_readings(&history, "John", 3);
+        let dates: Vec<NaiveDate> = readings.iter().map(|event| event.date).collect();
+        assert_eq!(dates, [day(6), day(4), day(2), day(2)]);
+        assert_eq!(readings[2], &backdated);
+    }
+
+    #[test]
+    fn chapters_per_day_counts_distinct_chapters_oldest_first() {
+        let history = vec![
+            event(day(3), "John", 1),
+            event(day(3), "John", 1),
+            event(day(3), "John", 2),
+            event(day(5), "Acts", 1),
+            event(day(9), "Acts", 2),
+        ];
+        assert_eq!(chapters_per_day(&history, day(5), 4), vec![0, 2, 0, 1]);
+    }
+
+    #[test]
+    fn session_summary_merges_chapters() {
+        assert_eq!(session_summary(&[], 3), None);
+        let session = [
+            event(day(1), "John", 3),
+            event(day(1), "John", 4),
+            event(day(1), "Psalms", 23),
+        ];
+        assert_eq!(
+            session_summary(&session, 8).as_deref(),
+            Some("Recorded: John 3-4, Psalms 23 · streak 8 days")
+        );
+    }
+
+    #[test]
+    fn goals_warn_when_behind_pace() {
+        let bible = crate::bible_structure::get_bible_structure();
+        // A Thursday
+        let today = day(13);
+        let mut progress = ReadingProgress::new();
+        progress.record_reading_on("Ruth", 1, 1, 22, 1, day(10));
+        progress.record_reading_on("Ruth", 2, 1, 23, 1, day(12));
+        progress.record_session(day(12), 30);
+        progress.record_session(today, 10);
+
+        let goals = [
+            Goal::ChaptersPerWeek(7),
+            Goal::ChaptersPerWeek(4),
+            Goal::ChaptersPerMonth(2),
+            Goal::MinutesPerDay(10),
+            Goal::FinishBook {
+                book: "ruth".to_string(),
+                by: day(20),
+            },
+        ];
+        let evaluated = evaluate_goals(bible, &progress, &goals, today);
+        let summaries: Vec<&str> = evaluated.iter().map(|goal| goal.summary.as_str()).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "2/7 chapters this week",
+                "2/4 chapters this week",
+                "2/2 chapters this month",
+                "10/10 min today",
+                "Ruth 53% by Mar 20"
+            ]
+        );
+        assert_eq!(
+            evaluated[0].warning.as_deref(),
+            Some("5 more chapters to read in 4 days to meet the week goal")
+        );
+        assert_eq!(evaluated[1].warning, None);
+        assert!(evaluated[2].met);
+        assert!(evaluated[3].warning.is_some());
+        // Two chapters in two weeks is too slow to finish the other two in eight days
+        assert!(evaluated[4].warning.is_some());
+    }
+
+    #[test]
+    fn neglected_books_are_the_longest_untouched() {
+        let bible = crate::bible_structure::get_bible_structure();
+        let today = NaiveDate::from_ymd_opt(2025, 12, 1).unwrap();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading_on("Ruth", 1, 1, 5, 1, day(1));
+        progress.record_reading_on("Jonah", 1, 1, 5, 1, day(2));
+        progress.record_reading_on("Jude", 1, 1, 5, 1, today);
+        for book in get_all_books(bible) {
+            if !["Ruth", "Jonah", "Jude"].contains(&book.as_str()) {
+                progress.record_reading_on(&book, 1, 1, 1, 1, today);
+            }
+        }
+        progress.books.remove("Obadiah");
+
+        let neglected = neglected_books(bible, &progress, today, 180);
+        let books: Vec<&str> = neglected.iter().map(|b| b.book.as_str()).collect();
+        assert_eq!(books, vec!["Obadiah", "Ruth", "Jonah"]);
+        assert_eq!(neglected[0].describe(today), "never read");
+        assert_eq!(
+            neglected[2].describe(today),
+            "last read 2025-03-02, 274 days ago"
+        );
+        // Over a longer period only the book never read is left
+        assert_eq!(neglected_books(bible, &progress, today, 300).len(), 1);
+    }
+
+    #[test]
+    fn reading_times_switch_to_hours() {
+        assert_eq!(format_minutes(45), "45 min");
+        assert_eq!(format_minutes(185), "3 h 05 min");
+    }
+}
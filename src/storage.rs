@@ -0,0 +1,189 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use color_eyre::Result;
+use serde::Deserialize;
+
+use crate::progress::{ReadingEvent, ReadingProgress};
+
+/// Where progress files are kept: the file system for the app, memory or the browser for other
+/// front ends
+pub trait Storage {
+    /// Contents of the file at `path`, or `None` if there is none
+    fn read(&self, path: &Path) -> io::Result<Option<String>>;
+    /// Replace the file at `path`, creating it and its directory if needed
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()>;
+}
+
+/// Files on disk, behind the `fs` feature so the library builds where there is no file system,
+/// e.g. for the web
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileStorage;
+
+#[cfg(feature = "fs")]
+impl Storage for FileStorage {
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)
+    }
+}
+
+/// Files kept in memory, e.g. a progress file uploaded to a web page
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    files: RefCell<HashMap<PathBuf, String>>,
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self, path: &Path) -> io::Result<Option<String>> {
+        Ok(self.files.borrow().get(path).cloned())
+    }
+
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+        self.files
+            .borrow_mut()
+            .insert(path.to_path_buf(), contents.to_string());
+        Ok(())
+    }
+}
+
+/// The progress file at `path`, or empty progress if there is none yet
+#[tracing::instrument(skip(storage))]
+pub fn load_progress_from(storage: &impl Storage, path: &Path) -> Result<ReadingProgress> {
+    match storage.read(path)? {
+        Some(content) => Ok(serde_yaml::from_str(&content)?),
+        None => Ok(ReadingProgress::new()),
+    }
+}
+
+/// The reading history of a progress file, split off by [`load_progress_lazily_from`] and not
+/// parsed yet
+#[derive(Debug)]
+pub struct UnparsedHistory(String);
+
+impl UnparsedHistory {
+    #[tracing::instrument(skip(self), fields(bytes = self.0.len()))]
+    pub fn parse(self) -> Result<Vec<ReadingEvent>> {
+        #[derive(Deserialize)]
+        struct History {
+            #[serde(default)]
+            history: Vec<ReadingEvent>,
+        }
+        Ok(serde_yaml::from_str::<History>(&self.0)?.history)
+    }
+}
+
+/// Like `load_progress_from`, but the reading history, most of a file kept for years, is left
+/// for [`UnparsedHistory::parse`], e.g. on another thread while the dashboard is already shown.
+/// The history is parsed right away if the file isn't laid out the way the app writes it.
+#[tracing::instrument(skip(storage))]
+pub fn load_progress_lazily_from(
+    storage: &impl Storage,
+    path: &Path,
+) -> Result<(ReadingProgress, Option<UnparsedHistory>)> {
+    let Some(content) = storage.read(path)? else {
+        return Ok((ReadingProgress::new(), None));
+    };
+    match split_history(&content) {
+        Some((rest, history)) => Ok((serde_yaml::from_str(&rest)?, Some(UnparsedHistory(history)))),
+        None => Ok((serde_yaml::from_str(&content)?, None)),
+    }
+}
+
+/// Split a progress file into its `history:` section and everything else. In the block style
+/// the app writes, each top-level key starts a line, and the rest of its section is indented,
+/// `- ` list items or blank. None for anything else, e.g. flow style.
+fn split_history(content: &str) -> Option<(String, String)> {
+    let mut rest = String::with_capacity(content.len());
+    let mut history = String::new();
+    let mut in_history = false;
+    for line in content.split_inclusive('\n') {
+        if starts_section(line) {
+            in_history = line.starts_with("history:");
+            if in_history && !history.is_empty() {
+                return None;
+            }
+        }
+        if in_history {
+            history.push_str(line);
+        } else {
+            rest.push_str(line);
+        }
+    }
+    (!history.is_empty()).then_some((rest, history))
+}
+
+/// Whether `line` of a progress file in block style is a top-level key, rather than part of
+/// the value of the key before it
+pub(crate) fn starts_section(line: &str) -> bool {
+    !line.starts_with([' ', '\t', '-', '#', '\r', '\n'])
+}
+
+#[tracing::instrument(skip(storage, progress), fields(events = progress.history.len()))]
+pub fn save_progress_with(
+    storage: &impl Storage,
+    progress: &ReadingProgress,
+    path: &Path,
+) -> Result<()> {
+    storage.write(path, &serde_yaml::to_string(progress)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_round_trips_through_memory() {
+        let storage = MemoryStorage::default();
+        let path = Path::new("reading_progress.yaml");
+        assert!(load_progress_from(&storage, path)
+            .unwrap()
+            .history
+            .is_empty());
+
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        save_progress_with(&storage, &progress, path).unwrap();
+        let loaded = load_progress_from(&storage, path).unwrap();
+        assert_eq!(loaded.history, progress.history);
+        assert_eq!(loaded.books, progress.books);
+    }
+
+    #[test]
+    fn lazy_load_matches_a_full_load() {
+        let storage = MemoryStorage::default();
+        let path = Path::new("reading_progress.yaml");
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        progress.record_reading("Ruth", 2, 1, 23);
+        let day = progress.history[0].date;
+        progress.set_journal_entry(day, "Naomi's return\n\nhistory: not a key");
+        save_progress_with(&storage, &progress, path).unwrap();
+
+        let (mut loaded, history) = load_progress_lazily_from(&storage, path).unwrap();
+        assert!(loaded.history.is_empty());
+        assert_eq!(loaded.books, progress.books);
+        assert_eq!(loaded.journal, progress.journal);
+        loaded.history = history.unwrap().parse().unwrap();
+        assert_eq!(loaded.history, progress.history);
+
+        // Other layouts are parsed in one go
+        storage.write(path, "{history: [], books: {}}").unwrap();
+        let (_, history) = load_progress_lazily_from(&storage, path).unwrap();
+        assert!(history.is_none());
+    }
+}
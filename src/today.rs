@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::bible_structure::{BibleStructure, Genre};
+use crate::pace::{chapter_minutes, seconds_per_verse};
+use crate::progress::{ReadingEvent, ReadingProgress};
+use crate::review::review_suggestions;
+use crate::stats::chapters_read_on;
+use crate::utils::get_all_books;
+
+/// Why a chapter is suggested for today
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodaySource {
+    /// One psalm a day, cycling through all 150
+    PsalmOfTheDay,
+    /// The chapter of Proverbs matching the day of the month
+    ProverbOfTheDay,
+    /// The chapters after the last one read before today, enough to meet the daily goal or
+    /// fill the daily reading time
+    Continue,
+    /// A chapter read long ago or rarely, due to be read again
+    Review,
+    /// The least-read chapter of the Old Testament's narrative books (Law and History)
+    Narrative,
+    /// The least-read chapter of the Psalms and wisdom books
+    Wisdom,
+    /// The least-read chapter of the New Testament
+    NewTestament,
+}
+
+impl TodaySource {
+    pub fn label(self) -> &'static str {
+        match self {
+            TodaySource::PsalmOfTheDay => "Psalm of the day",
+            TodaySource::ProverbOfTheDay => "Proverb of the day",
+            TodaySource::Continue => "Continue reading",
+            TodaySource::Review => "Due for re-reading",
+            TodaySource::Narrative => "Old Testament narrative",
+            TodaySource::Wisdom => "Psalms & wisdom",
+            TodaySource::NewTestament => "New Testament",
+        }
+    }
+}
+
+/// A chapter suggested for today
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodayItem {
+    pub book: String,
+    pub chapter: u32,
+    pub source: TodaySource,
+    /// True once the chapter has been read today
+    pub done: bool,
+    /// Estimated reading time at the reader's pace, rounded up
+    pub minutes: u32,
+}
+
+/// Number of chapters due for re-reading suggested each day
+const REVIEW_ITEMS: usize = 1;
+
+/// Chapters suggested for `today`: the psalm and proverb of the day, where to continue
+/// reading, then the chapters most due for re-reading. The suggestions only depend on readings from before today, so they stay put as
+/// they are marked done.
+///
+/// With `daily_minutes`, the chapters to continue with are as many as fit in that time along
+/// with the psalm and proverb (at least one), and the chapter due for re-reading is only
+/// suggested if it fits too. Otherwise `daily_chapter_goal` chapters are continued with.
+pub fn today_items(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    today: NaiveDate,
+    daily_chapter_goal: Option<u32>,
+    daily_minutes: Option<u32>,
+) -> Vec<TodayItem> {
+    let pace = seconds_per_verse(progress);
+    let minutes = |chapters: &[(String, u32, TodaySource)]| -> f64 {
+        chapters
+            .iter()
+            .map(|(book, chapter, _)| chapter_minutes(bible, book, *chapter, pace))
+            .sum()
+    };
+    let psalms = bible
+        .ot
+        .get("Psalms")
+        .map_or(150, |chapters| chapters.len()) as u32;
+    let mut chapters = vec![
+        (
+            "Psalms".to_string(),
+            today.ordinal0() % psalms + 1,
+            TodaySource::PsalmOfTheDay,
+        ),
+        (
+            "Proverbs".to_string(),
+            today.day(),
+            TodaySource::ProverbOfTheDay,
+        ),
+    ];
+
+    let last_before_today = progress
+        .history
+        .iter()
+        .rev()
+        .find(|event| event.date < today)
+        .map(|event| (event.book.to_string(), event.chapter));
+    if let Some((book, chapter)) = last_before_today {
+        // With a time budget, stop before going over it, or at the latest after going once
+        // around the Bible
+        let count = match daily_minutes {
+            Some(_) => get_all_books(bible)
+                .iter()
+                .filter_map(|book| bible.ot.get(book).or_else(|| bible.nt.get(book)))
+                .map(|chapters| chapters.len() as u32)
+                .sum(),
+            None => daily_chapter_goal.unwrap_or(1).max(1),
+        };
+        let mut next = (book, chapter);
+        for continued in 0..count {
+            let Some(following) = next_chapter(bible, &next.0, next.1) else {
+                break;
+            };
+            let item = (following.0.clone(), following.1, TodaySource::Continue);
+            if let Some(budget) = daily_minutes {
+                let total = minutes(&chapters) + minutes(std::slice::from_ref(&item));
+                if continued > 0 && total > budget as f64 {
+                    break;
+                }
+            }
+            chapters.push(item);
+            next = following;
+        }
+    }
+
+    let reviews: Vec<(String, u32, TodaySource)> =
+        review_suggestions(bible, &progress.history, today)
+            .into_iter()
+            .filter(|s| {
+                !chapters
+                    .iter()
+                    .any(|(b, c, _)| *b == s.book && *c == s.chapter)
+            })
+            .take(REVIEW_ITEMS)
+            .map(|s| (s.book, s.chapter, TodaySource::Review))
+            .collect();
+    let fits =
+        daily_minutes.is_none_or(|budget| minutes(&chapters) + minutes(&reviews) <= budget as f64);
+    if fits {
+        chapters.extend(reviews);
+    }
+
+    with_done(bible, progress, today, chapters)
+}
+
+/// A "balanced diet" for `today`, as an alternative to a plan: one chapter each of Old
+/// Testament narrative, Psalms and wisdom, and the New Testament. Each is the least-read
+/// chapter of its part of the Bible, the first such chapter after the last one read there,
+/// so gaps are filled in order. Like `today_items`, only readings from before today count.
+pub fn balanced_items(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    today: NaiveDate,
+) -> Vec<TodayItem> {
+    let counts = chapter_read_counts(bible, &progress.history, today);
+    let mut chapters = Vec::new();
+    for source in [
+        TodaySource::Narrative,
+        TodaySource::Wisdom,
+        TodaySource::NewTestament,
+    ] {
+        let in_part = |book: &str| match source {
+            TodaySource::Narrative => {
+                bible.ot.contains_key(book)
+                    && matches!(Genre::of_book(book), Some(Genre::Law | Genre::History))
+            }
+            TodaySource::Wisdom => Genre::of_book(book) == Some(Genre::Wisdom),
+            _ => bible.nt.contains_key(book),
+        };
+        let part: Vec<(&str, u32, u32)> = bible
+            .ot
+            .iter()
+            .chain(&bible.nt)
+            .filter(|(book, _)| in_part(book))
+            .flat_map(|(book, verses)| {
+                (1..=verses.len() as u32).map(|chapter| {
+                    let count = counts.get(&(book.as_str(), chapter)).copied().unwrap_or(0);
+                    (book.as_str(), chapter, count)
+                })
+            })
+            .collect();
+        let Some(least) = part.iter().map(|(_, _, count)| *count).min() else {
+            continue;
+        };
+        let last_read = progress
+            .history
+            .iter()
+            .rev()
+            .filter(|event| event.date < today && in_part(&event.book))
+            .find_map(|event| {
+                part.iter()
+                    .position(|(book, chapter, _)| event.book == *book && *chapter == event.chapter)
+            });
+        let start = last_read.map_or(0, |idx| idx + 1);
+        let next = part[start..]
+            .iter()
+            .chain(&part[..start])
+            .find(|(_, _, count)| *count == least);
+        if let Some((book, chapter, _)) = next {
+            chapters.push((book.to_string(), *chapter, source));
+        }
+    }
+    with_done(bible, progress, today, chapters)
+}
+
+/// The suggested chapters as items, checked if they were read `today`, with their reading time
+fn with_done(
+    bible: &BibleStructure,
+    progress: &ReadingProgress,
+    today: NaiveDate,
+    chapters: Vec<(String, u32, TodaySource)>,
+) -> Vec<TodayItem> {
+    let read_today = chapters_read_on(&progress.history, today);
+    let pace = seconds_per_verse(progress);
+    chapters
+        .into_iter()
+        .map(|(book, chapter, source)| TodayItem {
+            done: read_today.contains(&(book.clone(), chapter)),
+            minutes: chapter_minutes(bible, &book, chapter, pace).ceil() as u32,
+            book,
+            chapter,
+            source,
+        })
+        .collect()
+}
+
+/// How many times every verse of each chapter had been read before `today`. Chapters none of
+/// which was read are left out.
+fn chapter_read_counts<'a>(
+    bible: &BibleStructure,
+    history: &'a [ReadingEvent],
+    today: NaiveDate,
+) -> HashMap<(&'a str, u32), u32> {
+    let mut verses: HashMap<(&str, u32), HashMap<u32, u32>> = HashMap::new();
+    for event in history.iter().filter(|event| event.date < today) {
+        let chapter = verses
+            .entry((event.book.as_str(), event.chapter))
+            .or_default();
+        for verse in event.verse_start..=event.verse_end {
+            *chapter.entry(verse).or_default() += event.read_count;
+        }
+    }
+    verses
+        .into_iter()
+        .map(|((book, chapter), counts)| {
+            let length = bible
+                .ot
+                .get(book)
+                .or_else(|| bible.nt.get(book))
+                .and_then(|chapters| {
+                    chapter
+                        .checked_sub(1)
+                        .and_then(|i| chapters.get(i as usize))
+                })
+                .copied()
+                .unwrap_or(0);
+            let all_read = (1..=length).all(|verse| counts.contains_key(&verse));
+            let times = if all_read {
+                counts.into_values().min().unwrap_or(0)
+            } else {
+                0
+            };
+            ((book, chapter), times)
+        })
+        .collect()
+}
+
+/// The chapter after `book` `chapter` in canonical order, wrapping from Revelation to Genesis
+fn next_chapter(bible: &BibleStructure, book: &str, chapter: u32) -> Option<(String, u32)> {
+    let chapters = bible.ot.get(book).or_else(|| bible.nt.get(book))?;
+    if (chapter as usize) < chapters.len() {
+        return Some((book.to_string(), chapter + 1));
+    }
+    let books = get_all_books(bible);
+    let idx = books.iter().position(|b| b == book)?;
+    Some((books[(idx + 1) % books.len()].clone(), 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::progress::{ReadingEvent, ReadingSource};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn event(date: NaiveDate, book: &str, chapter: u32) -> ReadingEvent {
+        ReadingEvent {
+            date,
+            book: book.into(),
+            chapter,
+            verse_start: 1,
+            verse_end: 1,
+            read_count: 1,
+            note: None,
+            tags: Vec::new(),
+            source: ReadingSource::Read,
+            translation: None,
+            campaign: None,
+            overwrite: false,
+        }
+    }
+
+    fn summary(items: &[TodayItem]) -> Vec<(&str, u32, bool)> {
+        items
+            .iter()
+            .map(|item| (item.book.as_str(), item.chapter, item.done))
+            .collect()
+    }
+
+    #[test]
+    fn suggests_psalm_proverb_and_next_chapters() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.history.push(event(date(3, 1), "Malachi", 3));
+        progress.history.push(event(date(3, 2), "Matthew", 1));
+
+        let items = today_items(bible, &progress, date(3, 2), Some(2), None);
+        assert_eq!(
+            summary(&items),
+            vec![
+                ("Psalms", 61, false),
+                ("Proverbs", 2, false),
+                ("Malachi", 4, false),
+                ("Matthew", 1, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn time_budget_sizes_the_batch() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.history.push(event(date(3, 1), "Mark", 1));
+
+        // Psalm 61 (8 verses) and Proverbs 2 (22) take 3 minutes at the default pace, Mark 2
+        // (28) 2.8, Mark 3 (35) 3.5 and Mark 4 (41) 4.1
+        let items = today_items(bible, &progress, date(3, 2), Some(5), Some(10));
+        assert_eq!(
+            summary(&items),
+            vec![
+                ("Psalms", 61, false),
+                ("Proverbs", 2, false),
+                ("Mark", 2, false),
+                ("Mark", 3, false),
+            ]
+        );
+        assert_eq!(items[2].minutes, 3);
+        // The first chapter to continue with is suggested even if it doesn't fit
+        let items = today_items(bible, &progress, date(3, 2), None, Some(1));
+        assert_eq!(items.len(), 3);
+    }
+
+    #[test]
+    fn balanced_mix_follows_the_least_read_chapters() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        for (book, chapter) in [("Genesis", 1), ("Matthew", 1), ("Revelation of John", 22)] {
+            let mut reading = event(date(3, 1), book, chapter);
+            reading.verse_end =
+                bible.ot.get(book).or_else(|| bible.nt.get(book)).unwrap()[chapter as usize - 1];
+            progress.history.push(reading);
+        }
+        // Only part of Psalm 1 was read, so it is still among the least read chapters
+        progress.history.push(event(date(3, 1), "Psalms", 1));
+        progress.history.push(event(date(3, 2), "Genesis", 2));
+
+        let items = balanced_items(bible, &progress, date(3, 2));
+        assert_eq!(
+            summary(&items),
+            vec![
+                ("Genesis", 2, true),
+                ("Psalms", 2, false),
+                // After Revelation, back to the first chapter not read yet
+                ("Matthew", 2, false),
+            ]
+        );
+        assert_eq!(items[1].source, TodaySource::Wisdom);
+    }
+
+    #[test]
+    fn wraps_from_revelation_to_genesis() {
+        let bible = get_bible_structure();
+        assert_eq!(
+            next_chapter(bible, "Revelation of John", 22),
+            Some(("Genesis".to_string(), 1))
+        );
+    }
+}
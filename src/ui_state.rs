@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::bible_structure::BookFilter;
+use crate::date_range::DateRange;
+use crate::widgets::tree_builder::TreeId;
+
+/// UI state remembered between sessions, so reopening the app returns to where the user was
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UiState {
+    #[serde(default)]
+    pub dashboard: DashboardUiState,
+    /// Last year whose review was shown at the start of January
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year_review_shown: Option<i32>,
+    /// Whether the Today panel suggests a balanced mix instead of the daily readings
+    #[serde(default)]
+    pub balanced_suggestions: bool,
+}
+
+/// Dashboard tree state: which nodes are expanded, which is selected, and active filters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DashboardUiState {
+    #[serde(default)]
+    pub opened: Vec<Vec<TreeId>>,
+    #[serde(default)]
+    pub selected: Vec<TreeId>,
+    #[serde(default)]
+    pub show_only_unread: bool,
+    #[serde(default)]
+    pub filter: BookFilter,
+    /// Only the readings of these days are shown, if set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_range: Option<DateRange>,
+}
@@ -1,8 +1,18 @@
 use crate::config::Config;
 use crate::progress::ReadingProgress;
+#[cfg(feature = "fs")]
+use crate::salvage::{salvage, Salvage};
+#[cfg(feature = "fs")]
+use crate::storage::{
+    load_progress_from, load_progress_lazily_from, save_progress_with, FileStorage, UnparsedHistory,
+};
+#[cfg(feature = "tui")]
+use crate::{storage::Storage, ui_state::UiState};
 use color_eyre::Result;
-use std::fs;
+#[cfg(feature = "fs")]
+use std::path::Path;
 use std::path::PathBuf;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub fn get_all_books(bible: &crate::bible_structure::BibleStructure) -> Vec<String> {
     let mut books: Vec<String> = Vec::new();
@@ -11,6 +21,35 @@ pub fn get_all_books(bible: &crate::bible_structure::BibleStructure) -> Vec<Stri
     books
 }
 
+/// Books to offer before any search is typed: the `pinned` books (resolved like references, so
+/// "ps" pins Psalms), then the five books read most recently
+pub fn quick_books(
+    bible: &crate::bible_structure::BibleStructure,
+    progress: &ReadingProgress,
+    pinned: &[String],
+) -> Vec<String> {
+    let mut books: Vec<String> = Vec::new();
+    let pinned = pinned
+        .iter()
+        .filter_map(|name| crate::reference::resolve_book(bible, name));
+    for book in pinned {
+        if !books.contains(&book) {
+            books.push(book);
+        }
+    }
+    let mut recent = 0;
+    for event in progress.history.iter().rev() {
+        if recent == 5 {
+            break;
+        }
+        if !books.iter().any(|book| event.book == *book) {
+            books.push(event.book.to_string());
+            recent += 1;
+        }
+    }
+    books
+}
+
 /// Generate alternate names for a book (e.g., "I Peter" -> ["1 Peter", "1st Peter"])
 /// Returns a list of (alias, canonical_name) tuples for all books
 pub fn get_book_aliases(bible: &crate::bible_structure::BibleStructure) -> Vec<(String, String)> {
@@ -100,23 +139,128 @@ pub fn get_progress_file_path(config: &Config) -> PathBuf {
     config.progress_path.clone()
 }
 
+#[cfg(feature = "fs")]
 pub fn load_progress(config: &Config) -> Result<ReadingProgress> {
+    load_progress_from(&FileStorage, &get_progress_file_path(config))
+}
+
+/// The progress, without the reading history if it can be parsed later
+#[cfg(feature = "fs")]
+pub fn load_progress_lazily(config: &Config) -> Result<(ReadingProgress, Option<UnparsedHistory>)> {
+    load_progress_lazily_from(&FileStorage, &get_progress_file_path(config))
+}
+
+#[cfg(feature = "fs")]
+pub fn save_progress(progress: &ReadingProgress, config: &Config) -> Result<()> {
+    save_progress_to(progress, &get_progress_file_path(config))
+}
+
+/// Save progress to an explicit path, e.g. when the configured file can't be written
+#[cfg(feature = "fs")]
+pub fn save_progress_to(progress: &ReadingProgress, path: &Path) -> Result<()> {
+    save_progress_with(&FileStorage, progress, path)
+}
+
+/// Replace a damaged progress file with a copy of it, such as the save worker's backup,
+/// keeping the damaged one as `<file>.corrupt`. Nothing is touched if the copy can't be loaded
+/// either.
+#[cfg(feature = "fs")]
+pub fn restore_progress_from(config: &Config, copy: &Path) -> Result<ReadingProgress> {
     let path = get_progress_file_path(config);
-    if !path.exists() {
-        return Ok(ReadingProgress::new());
-    }
-    let content = fs::read_to_string(&path)?;
-    let progress: ReadingProgress = serde_yaml::from_str(&content)?;
+    let progress = load_progress_from(&FileStorage, copy)?;
+    std::fs::copy(&path, with_suffix(&path, ".corrupt"))?;
+    std::fs::copy(copy, &path)?;
     Ok(progress)
 }
 
-pub fn save_progress(progress: &ReadingProgress, config: &Config) -> Result<()> {
+/// Whatever can be read of a damaged progress file, writing what was left out to
+/// `<file>.salvage.txt`, whose path is returned with it. `error` is why it couldn't be loaded.
+#[cfg(feature = "fs")]
+pub fn salvage_progress(config: &Config, error: &str) -> Result<(Salvage, PathBuf)> {
     let path = get_progress_file_path(config);
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-    let content = serde_yaml::to_string(progress)?;
-    fs::write(&path, content)?;
+    let salvaged = salvage(&std::fs::read_to_string(&path)?);
+    let report_path = with_suffix(&path, ".salvage.txt");
+    std::fs::write(&report_path, salvaged.report(error))?;
+    Ok((salvaged, report_path))
+}
+
+#[cfg(feature = "fs")]
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Load the remembered UI state. A missing or unreadable state file is not an error,
+/// the app simply starts from the default state.
+#[cfg(feature = "tui")]
+pub fn load_ui_state(config: &Config) -> UiState {
+    FileStorage
+        .read(&config.ui_state_path)
+        .ok()
+        .flatten()
+        .and_then(|content| serde_yaml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "tui")]
+pub fn save_ui_state(ui_state: &UiState, config: &Config) -> Result<()> {
+    let content = serde_yaml::to_string(ui_state)?;
+    FileStorage.write(&config.ui_state_path, &content)?;
     Ok(())
 }
+
+/// Cut `text` down to at most `max_width` terminal columns, ending it with "…" if anything was
+/// removed. Widths are measured in columns, so wide (e.g. CJK) characters count as two.
+pub fn truncate_with_ellipsis(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        // Leave a column for the ellipsis
+        if width + char_width > max_width - 1 {
+            break;
+        }
+        truncated.push(c);
+        width += char_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_by_display_width() {
+        assert_eq!(truncate_with_ellipsis("Genesis", 10), "Genesis");
+        assert_eq!(truncate_with_ellipsis("Genesis", 5), "Gene…");
+        assert_eq!(truncate_with_ellipsis("Genesis", 0), "");
+        // Each of these characters is two columns wide
+        assert_eq!(truncate_with_ellipsis("創世記", 4), "創…");
+        assert_eq!(truncate_with_ellipsis("創世記", 6), "創世記");
+    }
+
+    #[test]
+    fn quick_books_put_pinned_before_recent() {
+        let bible = crate::bible_structure::get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        for book in [
+            "Genesis", "Psalms", "Mark", "Luke", "John", "Acts", "Romans",
+        ] {
+            progress.record_reading(book, 1, 1, 1);
+        }
+        let pinned = vec!["ps".to_string(), "not a book at all 123".to_string()];
+        let books = quick_books(bible, &progress, &pinned);
+        assert_eq!(books[0], "Psalms");
+        assert_eq!(books[1..], ["Romans", "Acts", "John", "Luke", "Mark"]);
+    }
+}
@@ -0,0 +1,64 @@
+//! JavaScript bindings for a web viewer of progress files, built with the `wasm` feature:
+//!
+//! ```js
+//! import init, { loadProgress } from "./pkg/bible_reading_progress.js";
+//! await init();
+//! const progress = loadProgress(await file.text());
+//! progress.tree();                  // testaments, books and chapters with their coverage
+//! progress.stats("2025-03-01");     // streaks, days read and neglected books
+//! progress.percentRead("nt");
+//! ```
+
+use chrono::NaiveDate;
+use wasm_bindgen::prelude::*;
+
+use crate::bible_structure::{get_bible_structure, BookFilter};
+use crate::coverage::percent_read;
+use crate::overview::{progress_tree, stats_overview};
+use crate::progress::ReadingProgress;
+use crate::stats::DEFAULT_NEGLECTED_DAYS;
+
+/// A loaded progress file
+#[wasm_bindgen]
+pub struct Progress {
+    progress: ReadingProgress,
+}
+
+/// Parse the contents of a progress file (`reading_progress.yaml`)
+#[wasm_bindgen(js_name = loadProgress)]
+pub fn load_progress(yaml: &str) -> Result<Progress, JsError> {
+    Ok(Progress {
+        progress: serde_yaml::from_str(yaml)?,
+    })
+}
+
+#[wasm_bindgen]
+impl Progress {
+    /// Both testaments with every book and chapter: `{ name, percent_read, books: [{ book,
+    /// percent_read, times_read, chapters: [{ chapter, times_read, started }] }] }`
+    pub fn tree(&self) -> Result<JsValue, JsError> {
+        let tree = progress_tree(get_bible_structure(), &self.progress);
+        Ok(serde_wasm_bindgen::to_value(&tree)?)
+    }
+
+    /// Streaks, days read, coverage and neglected books as of `today` (YYYY-MM-DD)
+    pub fn stats(&self, today: &str) -> Result<JsValue, JsError> {
+        let today = NaiveDate::parse_from_str(today, "%Y-%m-%d")?;
+        let stats = stats_overview(
+            get_bible_structure(),
+            &self.progress,
+            today,
+            DEFAULT_NEGLECTED_DAYS,
+        );
+        Ok(serde_wasm_bindgen::to_value(&stats)?)
+    }
+
+    /// Percentage of the verses read among the books of `filter`: "all", "ot", "nt" or a genre
+    /// such as "gospels"
+    #[wasm_bindgen(js_name = percentRead)]
+    pub fn percent_read(&self, filter: &str) -> Result<f64, JsError> {
+        let filter = BookFilter::parse(filter)
+            .ok_or_else(|| JsError::new(&format!("Unknown filter \"{}\"", filter)))?;
+        Ok(percent_read(get_bible_structure(), &self.progress, filter))
+    }
+}
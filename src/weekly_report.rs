@@ -0,0 +1,297 @@
+//! A summary of the last seven days of reading, written by `brp report --weekly` for a cron job
+//! to file away or mail: what was read, how the streak moved and how the plan being followed is
+//! keeping up.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::process::Command;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::hook::run_with_input;
+use crate::plans::{describe_offset, schedule_offset, Plan, PlanState};
+use crate::progress::ReadingEvent;
+use crate::stats::current_streak;
+
+/// The week ending on a given day
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklyReport {
+    /// First day of the week, six days before `end`
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Chapters read, counting a chapter once per day it was read
+    pub chapters: usize,
+    /// Chapters read the week before, counted the same way
+    pub chapters_previous_week: usize,
+    pub days_read: usize,
+    /// Chapters read in each book, the most read first
+    pub books: Vec<(String, usize)>,
+    /// The streak at the end of the week, and at the end of the week before
+    pub streak: u32,
+    pub streak_previous_week: u32,
+    pub plan: Option<PlanWeek>,
+}
+
+/// How the plan being followed went over the week
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanWeek {
+    pub name: String,
+    /// Chapters the plan scheduled for the week
+    pub due: usize,
+    /// How many of those are read
+    pub done: usize,
+    /// Days ahead (positive) or behind (negative) at the end of the week, and at the end of the
+    /// week before
+    pub offset: i64,
+    pub offset_previous_week: i64,
+}
+
+impl WeeklyReport {
+    /// The week ending on `end`, with `plan` being the plan being followed, if any
+    pub fn new(
+        history: &[ReadingEvent],
+        plan: Option<(&Plan, &PlanState)>,
+        end: NaiveDate,
+    ) -> Self {
+        let start = end - Duration::days(6);
+        let week = chapter_days(history, start, end);
+        let mut by_book: HashMap<&str, usize> = HashMap::new();
+        for (_, book, _) in &week {
+            *by_book.entry(book).or_default() += 1;
+        }
+        let mut books: Vec<(String, usize)> = by_book
+            .into_iter()
+            .map(|(book, chapters)| (book.to_string(), chapters))
+            .collect();
+        books.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        Self {
+            start,
+            end,
+            chapters: week.len(),
+            chapters_previous_week: chapter_days(
+                history,
+                start - Duration::days(7),
+                start - Duration::days(1),
+            )
+            .len(),
+            days_read: week
+                .iter()
+                .map(|(date, _, _)| date)
+                .collect::<HashSet<_>>()
+                .len(),
+            books,
+            streak: current_streak(history, end),
+            streak_previous_week: current_streak(history, start - Duration::days(1)),
+            plan: plan.map(|(plan, state)| PlanWeek::new(plan, state, history, start, end)),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        format!("Week of {} to {}", self.start, self.end)
+    }
+
+    /// Each line of the summary as (label, value)
+    pub fn highlights(&self) -> Vec<(&'static str, String)> {
+        let change = self.chapters as i64 - self.chapters_previous_week as i64;
+        let mut highlights = vec![
+            (
+                "Chapters read",
+                format!(
+                    "{} ({}{} on the week before)",
+                    self.chapters,
+                    if change >= 0 { "+" } else { "" },
+                    change
+                ),
+            ),
+            ("Days with a reading", format!("{} of 7", self.days_read)),
+            (
+                "Streak",
+                format!(
+                    "{} days (was {} days a week ago)",
+                    self.streak, self.streak_previous_week
+                ),
+            ),
+        ];
+        if !self.books.is_empty() {
+            let books: Vec<String> = self
+                .books
+                .iter()
+                .map(|(book, chapters)| format!("{} ({})", book, chapters))
+                .collect();
+            highlights.push(("Books", books.join(", ")));
+        }
+        if let Some(plan) = &self.plan {
+            highlights.push((
+                "Plan",
+                format!(
+                    "{}: {} of {} chapters due this week read, {} (was {})",
+                    plan.name,
+                    plan.done,
+                    plan.due,
+                    describe_offset(plan.offset),
+                    describe_offset(plan.offset_previous_week)
+                ),
+            ));
+        }
+        highlights
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n\n", self.title());
+        for (label, value) in self.highlights() {
+            markdown.push_str(&format!("- **{}**: {}\n", label, value));
+        }
+        markdown
+    }
+
+    /// Name of the file the report is written to, e.g. "brp-week-2025-03-09.md"
+    pub fn file_name(&self) -> String {
+        format!("brp-week-{}.md", self.end)
+    }
+
+    /// Mail the report to `to` with the local `sendmail`, as sent by cron jobs
+    pub fn send_mail(&self, markdown: &str, to: &str) -> io::Result<()> {
+        let message = format!(
+            "To: {}\nSubject: Bible reading: {}\nContent-Type: text/plain; charset=utf-8\n\n{}",
+            to,
+            self.title(),
+            markdown
+        );
+        let mut sendmail = Command::new("sendmail");
+        sendmail.arg("-i").arg(to);
+        run_with_input(sendmail, &message).map(|_| ())
+    }
+}
+
+impl PlanWeek {
+    fn new(
+        plan: &Plan,
+        state: &PlanState,
+        history: &[ReadingEvent],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Self {
+        let scheduled = state.schedule(plan);
+        let until = |date: NaiveDate| -> Vec<ReadingEvent> {
+            history
+                .iter()
+                .filter(|event| event.date <= date)
+                .cloned()
+                .collect()
+        };
+        let completion = scheduled.completion(state, &until(end));
+        let before = start - Duration::days(1);
+        let completion_before = scheduled.completion(state, &until(before));
+
+        let day_of = |date: NaiveDate| (date - state.start).num_days();
+        let week: Vec<&Vec<bool>> = completion
+            .iter()
+            .enumerate()
+            .filter(|(day, _)| (day_of(start)..=day_of(end)).contains(&(*day as i64)))
+            .map(|(_, items)| items)
+            .collect();
+        Self {
+            name: plan.name.clone(),
+            due: week.iter().map(|items| items.len()).sum(),
+            done: week
+                .iter()
+                .flat_map(|items| items.iter())
+                .filter(|&&done| done)
+                .count(),
+            offset: schedule_offset(&completion, state.current_day(&scheduled, end)),
+            offset_previous_week: schedule_offset(
+                &completion_before,
+                state.current_day(&scheduled, before),
+            ),
+        }
+    }
+}
+
+/// Distinct (date, book, chapter) readings from `start` to `end`, inclusive
+fn chapter_days(
+    history: &[ReadingEvent],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> HashSet<(NaiveDate, &str, u32)> {
+    history
+        .iter()
+        .filter(|event| (start..=end).contains(&event.date))
+        .map(|event| (event.date, event.book.as_str(), event.chapter))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plans::{PlanId, PlanItem};
+    use crate::progress::ReadingSource;
+
+    fn event(day: u32, book: &str, chapter: u32) -> ReadingEvent {
+        ReadingEvent {
+            date: NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+            book: book.into(),
+            chapter,
+            verse_start: 1,
+            verse_end: 10,
+            read_count: 1,
+            note: None,
+            tags: Vec::new(),
+            source: ReadingSource::Read,
+            translation: None,
+            campaign: None,
+            overwrite: false,
+        }
+    }
+
+    #[test]
+    fn summarizes_the_week_and_the_plan() {
+        let history = vec![
+            event(1, "Ruth", 1),
+            event(2, "Ruth", 2),
+            event(9, "Ruth", 3),
+            event(10, "Ruth", 4),
+            event(11, "John", 2),
+            event(11, "John", 2),
+        ];
+        let plan = Plan {
+            name: "Ruth and John".to_string(),
+            start: None,
+            days: [
+                ("Ruth", 1),
+                ("Ruth", 2),
+                ("Ruth", 3),
+                ("Ruth", 4),
+                ("John", 1),
+            ]
+            .into_iter()
+            .map(|(book, chapter)| {
+                vec![PlanItem {
+                    book: book.to_string(),
+                    chapter,
+                }]
+            })
+            .collect(),
+        };
+        let state = PlanState::new(
+            PlanId::Custom {
+                custom: "ruth-and-john".to_string(),
+            },
+            NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+        );
+
+        let end = NaiveDate::from_ymd_opt(2025, 3, 11).unwrap();
+        let report = WeeklyReport::new(&history, Some((&plan, &state)), end);
+        assert_eq!(
+            report.to_markdown(),
+            "# Week of 2025-03-05 to 2025-03-11\n\n\
+             - **Chapters read**: 3 (+1 on the week before)\n\
+             - **Days with a reading**: 3 of 7\n\
+             - **Streak**: 3 days (was 0 days a week ago)\n\
+             - **Books**: Ruth (2), John (1)\n\
+             - **Plan**: Ruth and John: 2 of 3 chapters due this week read, \
+             2 days behind (was 1 day behind)\n"
+        );
+        assert_eq!(report.file_name(), "brp-week-2025-03-11.md");
+    }
+}
@@ -0,0 +1,38 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+
+/// Remove colors and text styling from everything drawn into `buffer`, for accessibility mode.
+/// Reversed video is kept since it is the only cue left for the selected cell of a grid.
+pub fn strip_styles(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+        cell.modifier &= Modifier::REVERSED;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn strips_everything_but_reversed_video() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer[(0, 0)].set_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        );
+        buffer[(1, 0)].set_style(Style::default().fg(Color::Green));
+
+        strip_styles(&mut buffer);
+        for cell in &buffer.content {
+            assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset));
+        }
+        assert_eq!(buffer[(0, 0)].modifier, Modifier::REVERSED);
+        assert_eq!(buffer[(1, 0)].modifier, Modifier::empty());
+    }
+}
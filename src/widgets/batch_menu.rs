@@ -0,0 +1,136 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::widgets::popup::centered_rect;
+
+/// Action applied to every chapter marked in the dashboard tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchAction {
+    /// Record each chapter as read once more today
+    MarkRead,
+    /// Overwrite each chapter's read count, dated today
+    SetReadCount(u32),
+    /// Write the chapters to a reading list file
+    Export,
+    /// Unmark every chapter
+    ClearMarks,
+}
+
+/// Entries of the batch menu, in display order
+const CHOICES: [(&str, BatchAction); 4] = [
+    ("Mark read today", BatchAction::MarkRead),
+    ("Set read count…", BatchAction::SetReadCount(0)),
+    ("Export as a reading list", BatchAction::Export),
+    ("Clear marks", BatchAction::ClearMarks),
+];
+
+/// Result of a key press in the batch menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMenuAction {
+    None,
+    Cancel,
+    Apply(BatchAction),
+}
+
+/// Popup listing the actions that can be applied to the marked chapters
+pub struct BatchMenu {
+    /// Number of marked chapters, shown in the title
+    count: usize,
+    state: ListState,
+    /// Read count typed for "Set read count", while that prompt is open
+    count_input: Option<String>,
+}
+
+impl BatchMenu {
+    pub fn new(count: usize) -> Self {
+        Self {
+            count,
+            state: ListState::default().with_selected(Some(0)),
+            count_input: None,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let popup_area = centered_rect(50, 40, frame.area());
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(
+                "{} marked chapter{} (Enter: apply, Esc: cancel)",
+                self.count,
+                if self.count == 1 { "" } else { "s" }
+            ));
+
+        if let Some(input) = &self.count_input {
+            let prompt = Paragraph::new(Line::from(vec![
+                Span::styled("Read count: ", Style::default().fg(Color::Yellow)),
+                Span::raw(format!("{}_", input)),
+            ]))
+            .block(block);
+            frame.render_widget(prompt, popup_area);
+            return;
+        }
+
+        let items: Vec<ListItem> = CHOICES
+            .iter()
+            .map(|(label, _)| ListItem::new(*label))
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, popup_area, &mut self.state);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> BatchMenuAction {
+        if let Some(input) = self.count_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.count_input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+                KeyCode::Enter => {
+                    if let Ok(count) = input.parse() {
+                        return BatchMenuAction::Apply(BatchAction::SetReadCount(count));
+                    }
+                }
+                _ => {}
+            }
+            return BatchMenuAction::None;
+        }
+
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc => BatchMenuAction::Cancel,
+            KeyCode::Up => {
+                self.state.select(Some(selected.saturating_sub(1)));
+                BatchMenuAction::None
+            }
+            KeyCode::Down => {
+                self.state
+                    .select(Some((selected + 1).min(CHOICES.len() - 1)));
+                BatchMenuAction::None
+            }
+            KeyCode::Enter => match CHOICES[selected].1 {
+                BatchAction::SetReadCount(_) => {
+                    self.count_input = Some(String::new());
+                    BatchMenuAction::None
+                }
+                action => BatchMenuAction::Apply(action),
+            },
+            _ => BatchMenuAction::None,
+        }
+    }
+
+    /// True while the read count prompt consumes typed characters
+    pub fn is_capturing_input(&self) -> bool {
+        self.count_input.is_some()
+    }
+}
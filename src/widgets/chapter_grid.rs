@@ -0,0 +1,164 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::widgets::help::KeyBinding;
+use crate::widgets::tree_builder::ReadStatus;
+
+/// Keybindings available in the chapter grid, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("←/↑/↓/→", "Move between chapters"),
+    ("Home/End", "Jump to the first/last chapter"),
+    ("Enter", "Show the selected chapter in the tree"),
+    ("Esc", "Back to the tree"),
+];
+
+/// Columns taken by each chapter cell, including the gap after it
+const CELL_WIDTH: u16 = 5;
+
+/// Result of a key press in the chapter grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChapterGridAction {
+    None,
+    Close,
+    /// Show this chapter in the tree
+    Open(u32),
+}
+
+/// A book's chapters as a grid of cells colored like their tree labels, for surveying long
+/// books at a glance
+pub struct ChapterGrid {
+    pub book: String,
+    /// Label text, color and read status of each chapter
+    chapters: Vec<(String, Color, ReadStatus)>,
+    /// Put each chapter's `ReadStatus` marker in its cell, for when colors can't be told apart
+    text_markers: bool,
+    /// Index of the selected chapter
+    selected: usize,
+    /// Cells per row in the last render, used to move up and down
+    columns: usize,
+}
+
+impl ChapterGrid {
+    pub fn new(
+        book: String,
+        chapters: Vec<(String, Color, ReadStatus)>,
+        text_markers: bool,
+    ) -> Self {
+        Self {
+            book,
+            chapters,
+            text_markers,
+            selected: 0,
+            columns: 1,
+        }
+    }
+
+    /// Replace the chapter labels (e.g. after a reading was recorded), keeping the selection
+    pub fn set_chapters(&mut self, chapters: Vec<(String, Color, ReadStatus)>) {
+        self.selected = self.selected.min(chapters.len().saturating_sub(1));
+        self.chapters = chapters;
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "{} ({} chapters; arrows: move, Enter: show in tree, Esc: back)",
+            self.book,
+            self.chapters.len()
+        ));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        self.columns = ((inner.width / CELL_WIDTH) as usize).max(1);
+
+        // The selected chapter's label goes on the last line, below the grid
+        let [grid_area, label_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        let rows = grid_area.height as usize;
+        // Scroll so the selected row stays visible
+        let first_row = (self.selected / self.columns).saturating_sub(rows.saturating_sub(1));
+
+        let lines: Vec<Line> = self
+            .chapters
+            .chunks(self.columns)
+            .enumerate()
+            .skip(first_row)
+            .take(rows)
+            .map(|(row, cells)| {
+                let spans = cells
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(column, (_, color, status))| {
+                        let idx = row * self.columns + column;
+                        let mut style = Style::default().fg(Color::Black).bg(*color);
+                        if idx == self.selected {
+                            style = style
+                                .add_modifier(Modifier::BOLD)
+                                .add_modifier(Modifier::REVERSED);
+                        }
+                        let text = if self.text_markers {
+                            format!("{:>3}{}", idx + 1, status.marker())
+                        } else {
+                            format!("{:^4}", idx + 1)
+                        };
+                        [Span::styled(text, style), Span::raw(" ")]
+                    });
+                Line::from(spans.collect::<Vec<_>>())
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), grid_area);
+
+        if let Some((label, color, _)) = self.chapters.get(self.selected) {
+            frame.render_widget(
+                Paragraph::new(Line::styled(label.clone(), Style::default().fg(*color))),
+                label_area,
+            );
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> ChapterGridAction {
+        let last = self.chapters.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc => return ChapterGridAction::Close,
+            KeyCode::Enter => return ChapterGridAction::Open(self.selected as u32 + 1),
+            KeyCode::Left => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Right => self.selected = (self.selected + 1).min(last),
+            KeyCode::Up => self.selected = self.selected.saturating_sub(self.columns),
+            KeyCode::Down if self.selected + self.columns <= last => {
+                self.selected += self.columns;
+            }
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            _ => {}
+        }
+        ChapterGridAction::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(grid: &mut ChapterGrid, code: KeyCode) -> ChapterGridAction {
+        grid.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn arrows_move_by_cell_and_by_row() {
+        let chapters = vec![("Chapter".to_string(), Color::Gray, ReadStatus::Unread); 12];
+        let mut grid = ChapterGrid::new("Psalms".to_string(), chapters, false);
+        grid.columns = 5;
+
+        press(&mut grid, KeyCode::Down);
+        press(&mut grid, KeyCode::Right);
+        assert_eq!(press(&mut grid, KeyCode::Enter), ChapterGridAction::Open(7));
+
+        // The last row is partial, so moving down from its column stays put
+        press(&mut grid, KeyCode::Right);
+        press(&mut grid, KeyCode::Right);
+        press(&mut grid, KeyCode::Down);
+        assert_eq!(press(&mut grid, KeyCode::Enter), ChapterGridAction::Open(9));
+
+        press(&mut grid, KeyCode::End);
+        press(&mut grid, KeyCode::Up);
+        assert_eq!(press(&mut grid, KeyCode::Enter), ChapterGridAction::Open(7));
+    }
+}
@@ -0,0 +1,102 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::bible_structure::BibleStructure;
+use crate::i18n::{tr, trf};
+use crate::progress::ReadingEvent;
+use crate::stats::chapter_readings;
+use crate::widgets::history::{format_event_notes, format_event_passage};
+use crate::widgets::popup::centered_rect;
+
+/// How many of a chapter's readings the popup lists
+const SHOWN: usize = 10;
+
+/// A chapter's latest readings, which its read count on the dashboard adds up; shown until a
+/// key is pressed
+pub struct ChapterReadings {
+    pub book: String,
+    pub chapter: u32,
+    /// The latest `SHOWN` readings, newest first
+    readings: Vec<ReadingEvent>,
+    /// How many readings the chapter has in all
+    total: usize,
+}
+
+impl ChapterReadings {
+    pub fn new(history: &[ReadingEvent], book: &str, chapter: u32) -> Self {
+        let readings = chapter_readings(history, book, chapter);
+        Self {
+            book: book.to_string(),
+            chapter,
+            total: readings.len(),
+            readings: readings.into_iter().take(SHOWN).cloned().collect(),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, bible: &BibleStructure) {
+        let popup_area = centered_rect(70, 50, frame.area());
+        frame.render_widget(Clear, popup_area);
+
+        let date = Style::default().fg(Color::Yellow);
+        let count = Style::default().fg(Color::Cyan);
+        let mut lines: Vec<Line> = self
+            .readings
+            .iter()
+            .map(|event| {
+                let times = if event.overwrite {
+                    trf("set to {}x", &[&event.read_count])
+                } else {
+                    format!("+{}x", event.read_count)
+                };
+                Line::from(vec![
+                    Span::styled(event.date.format("%Y-%m-%d").to_string(), date),
+                    Span::raw("  "),
+                    Span::styled(format!("{:<8}", times), count),
+                    Span::raw(format!(
+                        "{}  {}",
+                        format_event_passage(bible, event),
+                        format_event_notes(event)
+                    )),
+                ])
+            })
+            .collect();
+        if self.readings.is_empty() {
+            lines.push(Line::raw(tr("Not read yet")));
+        }
+        if self.total > SHOWN {
+            lines.push(Line::styled(
+                trf("…and {} earlier", &[&(self.total - SHOWN)]),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(trf(
+                    "Readings of {} {} (press any key to close)",
+                    &[&self.book, &self.chapter],
+                )),
+        );
+        frame.render_widget(paragraph, popup_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::ReadingProgress;
+
+    #[test]
+    fn only_the_latest_readings_are_kept() {
+        let mut progress = ReadingProgress::new();
+        for _ in 0..12 {
+            progress.record_reading("Jonah", 2, 1, 10);
+        }
+        progress.record_reading("Jonah", 1, 1, 17);
+
+        let readings = ChapterReadings::new(&progress.history, "Jonah", 2);
+        assert_eq!((readings.readings.len(), readings.total), (SHOWN, 12));
+        assert_eq!(ChapterReadings::new(&progress.history, "Jonah", 3).total, 0);
+    }
+}
@@ -0,0 +1,288 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::bible_structure::BibleStructure;
+use crate::coverage::next_unread_chapter;
+use crate::i18n::{tr, trf};
+use crate::progress::ReadingProgress;
+use crate::reference::parse_reference;
+use crate::utils::get_all_books;
+use crate::widgets::help::KeyBinding;
+use crate::widgets::popup::centered_rect;
+use crate::widgets::text_input::TextInput;
+
+/// Keybindings available in the chapter ticker, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    (
+        "Type",
+        "Where to start (e.g. \"gen 12\"; a book alone starts at its next unread chapter)",
+    ),
+    ("Enter", "Start, then mark the next chapter as read today"),
+    ("→/←", "Skip ahead or go back a chapter without marking it"),
+    ("Esc", "Close the ticker"),
+];
+
+/// Action requested by the chapter ticker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TickerAction {
+    None,
+    Close,
+    /// Record the whole chapter as read today
+    MarkRead {
+        book: String,
+        chapter: u32,
+    },
+}
+
+/// Popup for recording a long session of consecutive chapters: pick where to start once, then
+/// each Enter marks the next chapter read, going on into the following book
+pub struct ChapterTicker {
+    bible: &'static BibleStructure,
+    /// Where to start, until it is picked
+    input: Option<TextInput>,
+    /// The chapter Enter marks next; None past the end of Revelation
+    next: Option<(String, u32)>,
+    /// Chapters marked since the ticker opened, in order, each once
+    marked: Vec<(String, u32)>,
+    error: Option<String>,
+}
+
+impl ChapterTicker {
+    /// Open the ticker asking where to start, with `start` (e.g. the selected book) filled in
+    pub fn new(bible: &'static BibleStructure, start: Option<String>) -> Self {
+        Self {
+            bible,
+            input: Some(TextInput::from(start.unwrap_or_default().as_str())),
+            next: None,
+            marked: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// True while asking where to start, so keys like `?` are typed into the input
+    pub fn is_capturing_input(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// The chapter after `chapter`, continuing with the next book after a book's last chapter
+    fn following(&self, book: &str, chapter: u32) -> Option<(String, u32)> {
+        let chapters = self
+            .bible
+            .ot
+            .get(book)
+            .or_else(|| self.bible.nt.get(book))?;
+        if (chapter as usize) < chapters.len() {
+            return Some((book.to_string(), chapter + 1));
+        }
+        let books = get_all_books(self.bible);
+        let index = books.iter().position(|name| name == book)?;
+        books.get(index + 1).map(|book| (book.clone(), 1))
+    }
+
+    /// The chapter before `chapter`, going back into the previous book from a first chapter
+    fn preceding(&self, book: &str, chapter: u32) -> Option<(String, u32)> {
+        if chapter > 1 {
+            return Some((book.to_string(), chapter - 1));
+        }
+        let books = get_all_books(self.bible);
+        let index = books.iter().position(|name| name == book)?;
+        let previous = books.get(index.checked_sub(1)?)?;
+        let chapters = self
+            .bible
+            .ot
+            .get(previous)
+            .or_else(|| self.bible.nt.get(previous))?;
+        Some((previous.clone(), chapters.len() as u32))
+    }
+
+    fn start(&mut self, progress: &ReadingProgress) {
+        let Some(input) = &self.input else {
+            return;
+        };
+        match parse_reference(self.bible, input) {
+            Ok(reference) => {
+                let chapter = reference
+                    .chapter
+                    .or_else(|| next_unread_chapter(self.bible, progress, &reference.book))
+                    .unwrap_or(1);
+                self.next = Some((reference.book, chapter));
+                self.input = None;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let popup_area = centered_rect(60, 50, frame.area());
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let label = Style::default().fg(Color::Yellow);
+        let mut lines = Vec::new();
+        if let Some(input) = &self.input {
+            let mut spans = vec![Span::styled(tr("Start at: "), label)];
+            spans.extend(input.cursor_spans());
+            lines.push(Line::from(spans));
+        } else {
+            let next = match &self.next {
+                Some((book, chapter)) => format!("{} {}", book, chapter),
+                None => tr("End of the Bible").to_string(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(tr("Next: "), label),
+                Span::styled(next, Style::default().add_modifier(Modifier::BOLD)),
+            ]));
+            let count = if self.marked.len() == 1 {
+                tr("1 chapter marked read today").to_string()
+            } else {
+                trf("{} chapters marked read today", &[&self.marked.len()])
+            };
+            lines.push(Line::styled(count, Style::default().fg(Color::Gray)));
+            // The latest marks, newest first, as far as they fit
+            for (book, chapter) in self.marked.iter().rev() {
+                lines.push(Line::styled(
+                    format!("✓ {} {}", book, chapter),
+                    Style::default().fg(Color::Green),
+                ));
+            }
+        }
+        if let Some(error) = &self.error {
+            lines.insert(
+                1,
+                Line::styled(error.as_str(), Style::default().fg(Color::Red)),
+            );
+        }
+
+        let title = if self.input.is_some() {
+            tr("Chapter ticker (Enter: start, Esc: close)")
+        } else {
+            tr("Chapter ticker (Enter: mark read, →/←: skip/back, Esc: close)")
+        };
+        frame.render_widget(Paragraph::new(lines).block(block.title(title)), popup_area);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, progress: &ReadingProgress) -> TickerAction {
+        if let Some(input) = self.input.as_mut() {
+            match key.code {
+                KeyCode::Esc => return TickerAction::Close,
+                KeyCode::Enter => self.start(progress),
+                _ => {
+                    input.handle_key(key, |c| !c.is_control());
+                }
+            }
+            return TickerAction::None;
+        }
+        match key.code {
+            KeyCode::Esc => TickerAction::Close,
+            KeyCode::Enter => match self.next.take() {
+                Some((book, chapter)) => {
+                    self.next = self.following(&book, chapter);
+                    // Marking a chapter again after going back still records the reading,
+                    // but lists the chapter once
+                    let chapter_marked = (book.clone(), chapter);
+                    if !self.marked.contains(&chapter_marked) {
+                        self.marked.push(chapter_marked);
+                    }
+                    TickerAction::MarkRead { book, chapter }
+                }
+                None => TickerAction::None,
+            },
+            KeyCode::Right => {
+                if let Some((book, chapter)) = &self.next {
+                    self.next = self.following(book, *chapter);
+                }
+                TickerAction::None
+            }
+            KeyCode::Left => {
+                let previous = match &self.next {
+                    Some((book, chapter)) => self.preceding(book, *chapter),
+                    None => self.marked.last().cloned(),
+                };
+                if previous.is_some() {
+                    self.next = previous;
+                }
+                TickerAction::None
+            }
+            _ => TickerAction::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    fn press(
+        ticker: &mut ChapterTicker,
+        code: KeyCode,
+        progress: &ReadingProgress,
+    ) -> TickerAction {
+        ticker.handle_key(KeyEvent::from(code), progress)
+    }
+
+    #[test]
+    fn each_enter_marks_the_next_chapter() {
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        let mut ticker = ChapterTicker::new(get_bible_structure(), Some("ruth".to_string()));
+
+        assert_eq!(
+            press(&mut ticker, KeyCode::Enter, &progress),
+            TickerAction::None
+        );
+        let mut marked = Vec::new();
+        for _ in 0..4 {
+            if let TickerAction::MarkRead { book, chapter } =
+                press(&mut ticker, KeyCode::Enter, &progress)
+            {
+                marked.push(format!("{} {}", book, chapter));
+            }
+        }
+        assert_eq!(marked, ["Ruth 2", "Ruth 3", "Ruth 4", "I Samuel 1"]);
+
+        press(&mut ticker, KeyCode::Left, &progress);
+        press(&mut ticker, KeyCode::Left, &progress);
+        assert_eq!(ticker.next, Some(("Ruth".to_string(), 4)));
+    }
+
+    #[test]
+    fn question_marks_are_typed_into_the_start() {
+        let progress = ReadingProgress::new();
+        let mut ticker = ChapterTicker::new(get_bible_structure(), None);
+        assert!(ticker.is_capturing_input());
+        press(&mut ticker, KeyCode::Char('?'), &progress);
+        assert_eq!(ticker.input.as_deref(), Some("?"));
+
+        press(&mut ticker, KeyCode::Backspace, &progress);
+        for c in "ruth".chars() {
+            press(&mut ticker, KeyCode::Char(c), &progress);
+        }
+        press(&mut ticker, KeyCode::Enter, &progress);
+        assert!(!ticker.is_capturing_input());
+    }
+
+    #[test]
+    fn marking_a_chapter_again_lists_it_once() {
+        let progress = ReadingProgress::new();
+        let mut ticker = ChapterTicker::new(get_bible_structure(), Some("ruth 1".to_string()));
+        press(&mut ticker, KeyCode::Enter, &progress);
+        press(&mut ticker, KeyCode::Enter, &progress);
+        press(&mut ticker, KeyCode::Enter, &progress);
+
+        press(&mut ticker, KeyCode::Left, &progress);
+        assert_eq!(
+            press(&mut ticker, KeyCode::Enter, &progress),
+            TickerAction::MarkRead {
+                book: "Ruth".to_string(),
+                chapter: 2
+            }
+        );
+        let ruth = |chapter| ("Ruth".to_string(), chapter);
+        assert_eq!(ticker.marked, [ruth(1), ruth(2)]);
+        assert_eq!(ticker.next, Some(ruth(3)));
+    }
+}
@@ -0,0 +1,280 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::i18n::{tr, trf};
+use crate::widgets::help::KeyBinding;
+use crate::widgets::popup::centered_rect;
+use crate::widgets::tabs::Tab;
+use crate::widgets::text_input::TextInput;
+use crate::widgets::{dashboard, history, plans, stats};
+
+/// Keybindings available in the command palette, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    (
+        "Type",
+        "Filter the commands (letters in order, e.g. \"rec\")",
+    ),
+    ("↑/↓", "Select a command"),
+    ("Enter", "Run the selected command"),
+    ("Esc", "Close the palette"),
+];
+
+/// The commands the palette lists, as (tab, key as listed in the tab's keybindings, key
+/// pressed). Their names are the keybindings' descriptions, so the palette and the help
+/// overlay always agree.
+const COMMANDS: &[(Tab, &str, KeyCode)] = &[
+    (Tab::Dashboard, "r", KeyCode::Char('r')),
+    (Tab::Dashboard, ".", KeyCode::Char('.')),
+    (Tab::Dashboard, "m", KeyCode::Char('m')),
+    (Tab::Dashboard, "I", KeyCode::Char('I')),
+    (Tab::Dashboard, "T", KeyCode::Char('T')),
+    (Tab::Dashboard, "Space", KeyCode::Char(' ')),
+    (Tab::Dashboard, "b", KeyCode::Char('b')),
+    (Tab::Dashboard, "Enter", KeyCode::Enter),
+    (Tab::Dashboard, "n/N", KeyCode::Char('n')),
+    (Tab::Dashboard, "E", KeyCode::Char('E')),
+    (Tab::Dashboard, "C", KeyCode::Char('C')),
+    (Tab::Dashboard, "o", KeyCode::Char('o')),
+    (Tab::Dashboard, "t", KeyCode::Char('t')),
+    (Tab::Dashboard, "G", KeyCode::Char('G')),
+    (Tab::Dashboard, "g", KeyCode::Char('g')),
+    (Tab::Dashboard, "D", KeyCode::Char('D')),
+    (Tab::Dashboard, "l", KeyCode::Char('l')),
+    (Tab::Dashboard, "v", KeyCode::Char('v')),
+    (Tab::Dashboard, "y", KeyCode::Char('y')),
+    (Tab::Dashboard, "Y", KeyCode::Char('Y')),
+    (Tab::Dashboard, "i", KeyCode::Char('i')),
+    (Tab::Dashboard, "K", KeyCode::Char('K')),
+    (Tab::Dashboard, "L", KeyCode::Char('L')),
+    (Tab::Dashboard, "M", KeyCode::Char('M')),
+    (Tab::Dashboard, "R", KeyCode::Char('R')),
+    (Tab::Dashboard, "c", KeyCode::Char('c')),
+    (Tab::Plans, "c", KeyCode::Char('c')),
+    (Tab::Plans, "t", KeyCode::Char('t')),
+    (Tab::Plans, "Enter", KeyCode::Enter),
+    (Tab::Plans, "r", KeyCode::Char('r')),
+    (Tab::History, "j", KeyCode::Char('j')),
+    (Tab::History, "e", KeyCode::Char('e')),
+    (Tab::History, "J", KeyCode::Char('J')),
+    (Tab::Stats, "v", KeyCode::Char('v')),
+    (Tab::Stats, "y", KeyCode::Char('y')),
+    (Tab::Dashboard, "q/Esc", KeyCode::Char('q')),
+];
+
+/// A command the palette can run: a key pressed on a tab, or going to the tab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteCommand {
+    /// What the command does, in the interface language
+    pub name: String,
+    /// The key that does the same, shown next to the name
+    pub keys: String,
+    pub tab: Tab,
+    /// Pressed on `tab`; None to just go to it
+    pub key: Option<KeyEvent>,
+}
+
+/// Every command, going to each tab first
+pub fn commands() -> Vec<PaletteCommand> {
+    let go_to = Tab::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, tab)| PaletteCommand {
+            name: trf("Go to {}", &[&tr(tab.title())]),
+            keys: format!("Alt+{}", index + 1),
+            tab: *tab,
+            key: None,
+        });
+    let pressed = COMMANDS.iter().filter_map(|(tab, keys, code)| {
+        let bindings = match tab {
+            Tab::Dashboard => dashboard::KEYBINDINGS,
+            Tab::Plans => plans::KEYBINDINGS,
+            Tab::History => history::KEYBINDINGS,
+            Tab::Stats => stats::KEYBINDINGS,
+            Tab::Settings => &[],
+        };
+        let (_, description) = bindings.iter().find(|(listed, _)| listed == keys)?;
+        Some(PaletteCommand {
+            name: tr(description).to_string(),
+            keys: keys.to_string(),
+            tab: *tab,
+            key: Some(KeyEvent::from(*code)),
+        })
+    });
+    go_to.chain(pressed).collect()
+}
+
+/// How well `query` matches `text`, if its letters appear in `text` in order (ignoring case
+/// and spaces): higher for letters at the start of words and in runs, lower for gaps
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (next..text.len()).find(|&index| text[index] == c)?;
+        score += 1;
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 2;
+        }
+        score -= (found - next).min(3) as i32;
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
+
+/// Popup for finding any command by typing part of its name, opened with Ctrl+P
+pub struct CommandPalette {
+    commands: Vec<PaletteCommand>,
+    input: TextInput,
+    /// Indices into `commands` of those matching the input, best first
+    matches: Vec<usize>,
+    list_state: ListState,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        let commands = commands();
+        Self {
+            matches: (0..commands.len()).collect(),
+            commands,
+            input: TextInput::new(),
+            list_state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i32, usize)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                let text = format!("{} {}", command.name, tr(command.tab.title()));
+                fuzzy_score(&self.input, &text).map(|score| (score, index))
+            })
+            .collect();
+        // Stable, so equally good matches keep their order
+        scored.sort_by_key(|(score, _)| -score);
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.list_state
+            .select((!self.matches.is_empty()).then_some(0));
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(70, 60, frame.area());
+        frame.render_widget(Clear, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+
+        let input = Paragraph::new(Line::from(self.input.cursor_spans())).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(tr("Command palette (Enter: run, Esc: close)")),
+        );
+        frame.render_widget(input, chunks[0]);
+        self.input.set_cursor(frame, chunks[0]);
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|&index| {
+                let command = &self.commands[index];
+                ListItem::new(Line::from(vec![
+                    Span::raw(command.name.clone()),
+                    Span::styled(
+                        format!("  {} · {}", command.keys, tr(command.tab.title())),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[1], &mut self.list_state);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteAction {
+        match key.code {
+            KeyCode::Esc => return PaletteAction::Close,
+            KeyCode::Enter => {
+                if let Some(&index) = self
+                    .list_state
+                    .selected()
+                    .and_then(|selected| self.matches.get(selected))
+                {
+                    return PaletteAction::Run(self.commands[index].clone());
+                }
+            }
+            KeyCode::Up => self.list_state.select_previous(),
+            KeyCode::Down => {
+                if let Some(selected) = self.list_state.selected() {
+                    self.list_state
+                        .select(Some((selected + 1).min(self.matches.len() - 1)));
+                }
+            }
+            _ => {
+                if self.input.handle_key(key, |c| !c.is_control()) {
+                    self.filter();
+                }
+            }
+        }
+        PaletteAction::None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteAction {
+    None,
+    Close,
+    /// Close the palette and run this command
+    Run(PaletteCommand),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_command_is_described_and_found_by_its_letters() {
+        assert_eq!(commands().len(), Tab::ALL.len() + COMMANDS.len());
+
+        let mut palette = CommandPalette::new();
+        for c in "genre".chars() {
+            palette.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        let PaletteAction::Run(command) = palette.handle_key(KeyEvent::from(KeyCode::Enter)) else {
+            panic!("no command matched");
+        };
+        assert_eq!(command.name, "Pick a genre (or testament) to show");
+        assert_eq!(command.key, Some(KeyEvent::from(KeyCode::Char('G'))));
+
+        assert!(
+            fuzzy_score("rec", "Record what you read today")
+                > fuzzy_score("rec", "Scroll the chart")
+        );
+        assert_eq!(fuzzy_score("zq", "Record what you read today"), None);
+    }
+}
@@ -1,38 +1,236 @@
-use chrono::NaiveDate;
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use chrono::{NaiveDate, Utc};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{prelude::*, widgets::*};
 use tui_tree_widget::{Tree, TreeItem, TreeState};
 
+use crate::bible_structure::{BibleStructure, BookFilter, Genre};
+use crate::book_name::BookName;
+use crate::config::Config;
+use crate::date_range::DateRange;
 use crate::progress::ReadingProgress;
+use crate::reference::{format_chapters, parse_reference};
+use crate::scripture::ScriptureText;
+use crate::stats::chapters_per_day;
+use crate::ui_state::DashboardUiState;
+use crate::utils::get_all_books;
+use crate::widgets::batch_menu::{BatchAction, BatchMenu, BatchMenuAction};
+use crate::widgets::chapter_grid::{ChapterGrid, ChapterGridAction};
+use crate::widgets::gradient::ColorGradient;
+use crate::widgets::help::KeyBinding;
+use crate::widgets::layout::{responsive_stack, Section};
+use crate::widgets::passage_preview::PassagePreview;
+use crate::widgets::popup::centered_rect;
+use crate::widgets::status_bar::StatusBar;
+use crate::widgets::text_input::TextInput;
 use crate::widgets::tree_builder::{
-    build_dashboard_tree_items, collect_recent_reads, RecentReadEntry, TreeId,
+    collect_recent_reads, DashboardTree, ReadStatus, RecentReadEntry, TreeId, TREE_INDENT,
 };
+use crate::widgets::tree_state::TreeStateExt;
+
+/// Keybindings available in the dashboard, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("↑/↓", "Navigate through passages"),
+    ("PgUp/PgDn", "Move a page up or down"),
+    ("Home/End", "Jump to the first/last passage"),
+    ("→", "Expand or collapse the selected passage"),
+    (
+        "Enter",
+        "Show the selected book as a grid of chapters (expands or collapses other passages)",
+    ),
+    (
+        "Space",
+        "Mark/unmark the selected chapter (expands or collapses other passages)",
+    ),
+    ("b", "Apply an action to the marked chapters"),
+    ("←", "Collapse the selected passage"),
+    (
+        "n/N",
+        "Jump to the next/previous chapter with unread verses",
+    ),
+    ("E", "Expand the whole tree"),
+    ("C", "Collapse the whole tree"),
+    ("1/2/3", "Expand to testament/book/chapter depth"),
+    (
+        "o",
+        "Show only the Old Testament (press again for all books)",
+    ),
+    (
+        "t",
+        "Show only the New Testament (press again for all books)",
+    ),
+    ("G", "Pick a genre (or testament) to show"),
+    ("g", "Go to a reference (e.g. \"ps 23\")"),
+    (
+        "D",
+        "Show only the readings of a date range (e.g. \"2024\"; empty for all)",
+    ),
+    ("l", "Show the color legend"),
+    ("v", "Read the selected chapter's text"),
+    ("T", "Show today's suggested readings"),
+    ("y", "Copy the selected passage's reference"),
+    ("Y", "Copy the selected passage's stats line"),
+    ("i", "List the selected chapter's last readings"),
+    (
+        "L",
+        "Jump to the least-read verses (press again for the next place)",
+    ),
+    (
+        "K",
+        "Tick off consecutive chapters: pick where to start, then Enter marks each one read",
+    ),
+    ("M", "Start memorizing the selected chapter or passage"),
+    ("R", "Review the passages you're memorizing"),
+    (
+        "c",
+        "Switch between lifetime totals and your campaigns (read-throughs)",
+    ),
+    ("r", "Record what you read today"),
+    (".", "Record the last recorded passage again, today"),
+    ("m", "Manually add readings (overwrite)"),
+    ("I", "Import readings from a text file or pasted lines"),
+    ("?", "Show this help"),
+    ("q/Esc", "Quit"),
+    (
+        "Click",
+        "Select a passage (double-click or click the arrow to expand)",
+    ),
+    ("Scroll wheel", "Scroll the tree"),
+];
+
+/// Maximum delay between two clicks on the same node to count as a double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Highlight symbol drawn before the selected tree node
+const HIGHLIGHT_SYMBOL: &str = ">> ";
+
+/// Number of days shown in the header's activity sparkline
+const ACTIVITY_DAYS: usize = 30;
 
 pub struct DashboardWidget {
+    bible: &'static BibleStructure,
+    /// Per-book tree items, reused across filter changes and recordings
+    tree: DashboardTree,
     pub tree_items: Vec<TreeItem<'static, TreeId>>,
+    /// Books whose chapter nodes are in `tree_items`; the rest only have a placeholder child
+    expanded_books: HashSet<String>,
+    /// Chapters marked for a batch action, as (book, chapter)
+    marked_chapters: BTreeSet<(String, u32)>,
+    batch_menu: Option<BatchMenu>,
+    /// Grid of one book's chapters, shown in place of the tree while it is open
+    chapter_grid: Option<ChapterGrid>,
+    /// Columns available to book labels in the last rendered tree, used to truncate them
+    label_width: Option<usize>,
     pub tree_state: TreeState<TreeId>,
     pub show_only_unread: bool,
+    /// Books shown in the tree
+    pub filter: BookFilter,
+    /// Percentage of verses read among the filtered books, shown in the header
+    percent_read: f64,
+    /// Campaign or date range whose coverage is shown, named in the header; None for
+    /// lifetime totals
+    campaign: Option<String>,
+    /// Only the readings of these days are shown, the app building the progress shown from
+    /// them; None for every reading
+    date_range: Option<DateRange>,
+    /// Selected entry of the genre picker popup, if it is open
+    genre_picker: Option<ListState>,
+    /// Text typed into the quick-jump prompt, if it is open
+    jump_input: Option<TextInput>,
+    /// Text typed into the date range prompt, if it is open
+    range_input: Option<TextInput>,
+    /// Why the quick-jump or date range input couldn't be used
+    prompt_error: Option<String>,
     pub recent_reads: Vec<(NaiveDate, Vec<RecentReadEntry>)>,
+    /// Chapters read on each of the last `ACTIVITY_DAYS` days, oldest first
+    activity: Vec<u64>,
+    /// Every chapter's tree path in canonical order, and whether it has unread verses
+    chapter_unread_flags: Vec<(Vec<TreeId>, bool)>,
+    pub status_bar: StatusBar,
+    gradient: ColorGradient,
+    /// Accessibility mode: text markers instead of color-only cues, and no decorative glyphs
+    accessible: bool,
+    show_legend: bool,
+    /// Bible text file for the passage preview, if one is configured
+    scripture_text_path: Option<PathBuf>,
+    /// Text loaded from `scripture_text_path` the first time a passage was previewed
+    scripture: Option<ScriptureText>,
+    passage_preview: Option<PassagePreview>,
+    /// Screen areas from the last render, used to resolve mouse clicks
+    tree_area: Rect,
+    record_button_area: Rect,
+    quit_button_area: Rect,
+    last_click: Option<(Instant, Vec<TreeId>)>,
 }
 
 impl DashboardWidget {
+    #[tracing::instrument(skip_all)]
     pub fn new(
-        bible: &'static crate::bible_structure::BibleStructure,
+        bible: &'static BibleStructure,
         progress: &ReadingProgress,
+        config: &Config,
     ) -> Self {
-        let tree_items = build_dashboard_tree_items(bible, progress);
+        let gradient = ColorGradient::from_config(&config.color_gradient);
+        let filter = BookFilter::All;
+        let mut tree = DashboardTree::new(bible, progress, &gradient);
+        tree.text_markers = config.accessibility_mode;
+        let expanded_books = HashSet::new();
+        let tree_items = tree.items(bible, filter, &expanded_books, &BTreeSet::new(), None);
+        let status_bar = StatusBar::new(
+            bible,
+            progress,
+            config.daily_chapter_goal,
+            config.goals.clone(),
+        );
         let recent_reads = collect_recent_reads(progress);
+        let activity = recent_activity(progress);
+        let chapter_unread_flags = tree.chapter_unread_flags(bible, filter);
+        let percent_read = tree.percent_read(bible, filter);
         let mut tree_state = TreeState::default();
         tree_state.select_first();
 
         Self {
+            bible,
+            tree,
             tree_items,
+            expanded_books,
+            marked_chapters: BTreeSet::new(),
+            batch_menu: None,
+            chapter_grid: None,
+            label_width: None,
             tree_state,
             show_only_unread: false,
+            filter,
+            percent_read,
+            campaign: progress.active_campaign.clone(),
+            date_range: None,
+            genre_picker: None,
+            jump_input: None,
+            range_input: None,
+            prompt_error: None,
             recent_reads,
+            activity,
+            chapter_unread_flags,
+            status_bar,
+            gradient,
+            accessible: config.accessibility_mode,
+            show_legend: false,
+            scripture_text_path: config.scripture_text_path.clone(),
+            scripture: None,
+            passage_preview: None,
+            tree_area: Rect::default(),
+            record_button_area: Rect::default(),
+            quit_button_area: Rect::default(),
+            last_click: None,
         }
     }
 
-    pub fn render(&mut self, frame: &mut Frame) {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        self.sync_expanded_books();
+
         // Calculate recent reads section height (if there are recent reads)
         let recent_reads_height = if self.recent_reads.is_empty() {
             0
@@ -41,31 +239,73 @@ impl DashboardWidget {
             (self.recent_reads.len() as u16) + 2
         };
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),                   // Header
-                Constraint::Length(recent_reads_height), // Recent reads (dynamic)
-                Constraint::Min(0),                      // Tree
-                Constraint::Length(3),                   // Footer
-            ])
-            .split(frame.area());
+        // On short terminals recent reads shrink and then disappear, followed by the header and
+        // the footer, so the tree keeps as many rows as possible. The header stays while the
+        // quick-jump prompt is open.
+        let focused_section = if self.jump_input.is_some() || self.range_input.is_some() {
+            0
+        } else {
+            2
+        };
+        let recent_reads =
+            Section::shrinkable(recent_reads_height, recent_reads_height.min(3)).optional(0);
+        let chunks = responsive_stack(
+            area,
+            &[
+                Section::fixed(4).optional(1), // Header and breadcrumb
+                recent_reads,                  // Recent reads (dynamic)
+                Section::fill(3),              // Tree
+                Section::fixed(3).optional(2), // Footer
+            ],
+            focused_section,
+        );
 
         // Header
-        let header_text = "Bible Reading Progress";
-        let header = Paragraph::new(header_text)
-            .style(
+        let header_text = format!(
+            "{} ({}: {:.1}% read)",
+            self.campaign.as_deref().unwrap_or("Bible Reading Progress"),
+            self.filter.name(),
+            self.percent_read
+        );
+        let header_lines = vec![
+            Line::styled(
+                header_text,
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             )
-            .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
-            );
-        frame.render_widget(header, chunks[0]);
+            .alignment(Alignment::Center),
+            self.breadcrumb_line(),
+        ];
+        let header = Paragraph::new(header_lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+
+        // Activity sparkline on the right of the header, when there is room for it. Its bars
+        // mean nothing to a screen reader, so accessibility mode leaves it out.
+        let sparkline_width = ACTIVITY_DAYS as u16 + 2;
+        if !self.accessible && chunks[0].width >= sparkline_width * 3 {
+            let header_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0), Constraint::Length(sparkline_width)])
+                .split(chunks[0]);
+            frame.render_widget(header, header_chunks[0]);
+
+            let sparkline = Sparkline::default()
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!("Chapters, {} days", ACTIVITY_DAYS))
+                        .border_style(Style::default().fg(Color::Cyan)),
+                )
+                .data(&self.activity)
+                .style(Style::default().fg(Color::Green));
+            frame.render_widget(sparkline, header_chunks[1]);
+        } else {
+            frame.render_widget(header, chunks[0]);
+        }
 
         // Recent reads section
         if !self.recent_reads.is_empty() {
@@ -79,13 +319,178 @@ impl DashboardWidget {
             frame.render_widget(recent_reads_widget, chunks[1]);
         }
 
-        // Render tree
+        // Render tree, with labels truncated to fit inside its borders, the highlight symbol,
+        // the book's indentation and its expand arrow
+        let label_width =
+            (chunks[2].width as usize).saturating_sub(2 + HIGHLIGHT_SYMBOL.len() + TREE_INDENT + 2);
+        if self.label_width != Some(label_width) {
+            self.label_width = Some(label_width);
+            self.tree_items = self.build_tree_items();
+        }
+        let title = if self.marked_chapters.is_empty() {
+            "Bible Structure (→: expand, Space: mark, ←: collapse, ↑↓: navigate, r: record, m: manual add, q: quit)".to_string()
+        } else {
+            format!(
+                "Bible Structure ({} marked; Space: mark/unmark, b: batch actions)",
+                self.marked_chapters.len()
+            )
+        };
         let tree = Tree::new(&self.tree_items[..])
             .expect("error rendering tree")
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(HIGHLIGHT_SYMBOL);
+
+        if let Some(chapter_grid) = self.chapter_grid.as_mut() {
+            chapter_grid.render(frame, chunks[2]);
+            self.tree_area = Rect::default();
+        } else {
+            frame.render_stateful_widget(tree, chunks[2], &mut self.tree_state);
+            self.tree_area = chunks[2];
+        }
+
+        // Footer, with clickable Record/Quit buttons on the right
+        let footer_block = Block::default().borders(Borders::ALL);
+        let footer_inner = footer_block.inner(chunks[3]);
+        frame.render_widget(footer_block, chunks[3]);
+
+        let footer_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(10), // [ Record ]
+                Constraint::Length(1),
+                Constraint::Length(8), // [ Quit ]
+            ])
+            .split(footer_inner);
+
+        let status = Paragraph::new(self.status_bar.line());
+        frame.render_widget(status, footer_chunks[0]);
+
+        let button_style = Style::default()
+            .fg(Color::Black)
+            .bg(Color::Gray)
+            .add_modifier(Modifier::BOLD);
+        frame.render_widget(
+            Paragraph::new("[ Record ]").style(button_style),
+            footer_chunks[1],
+        );
+        frame.render_widget(
+            Paragraph::new("[ Quit ]").style(button_style),
+            footer_chunks[3],
+        );
+        self.record_button_area = footer_chunks[1];
+        self.quit_button_area = footer_chunks[3];
+
+        if self.show_legend {
+            self.render_legend(frame);
+        }
+        if self.genre_picker.is_some() {
+            self.render_genre_picker(frame);
+        }
+        if let Some(preview) = self.passage_preview.as_mut() {
+            preview.render(frame);
+        }
+        if let Some(batch_menu) = self.batch_menu.as_mut() {
+            batch_menu.render(frame);
+        }
+    }
+
+    /// The selection path ("NT ▸ Romans ▸ Chapter 8"), or the quick-jump or date range prompt
+    /// while it is open
+    fn breadcrumb_line(&self) -> Line<'static> {
+        if let Some(input) = &self.range_input {
+            let mut spans = vec![Span::styled(
+                "Dates: ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )];
+            spans.extend(input.cursor_spans());
+            let (note, color) = match &self.prompt_error {
+                Some(error) => (error.as_str(), Color::Red),
+                None => (
+                    "e.g. 2024, 2025-03 or 2025-03-05..2025-04-19; empty for every reading",
+                    Color::Gray,
+                ),
+            };
+            spans.push(Span::styled(
+                format!("  {}", note),
+                Style::default().fg(color),
+            ));
+            return Line::from(spans);
+        }
+        if let Some(input) = &self.jump_input {
+            let mut spans = vec![Span::styled(
+                "Go to: ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )];
+            spans.extend(input.cursor_spans());
+            if let Some(error) = &self.prompt_error {
+                spans.push(Span::styled(
+                    format!("  {}", error),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            return Line::from(spans);
+        }
+
+        let parts: Vec<String> = self
+            .tree_state
+            .selected()
+            .iter()
+            .map(|id| match id {
+                TreeId::OldTestament => "OT".to_string(),
+                TreeId::NewTestament => "NT".to_string(),
+                TreeId::Book(book) => book.to_string(),
+                TreeId::Chapter { chapter, .. } => format!("Chapter {}", chapter),
+                TreeId::Passage {
+                    verse_start,
+                    verse_end,
+                    ..
+                } => format!("Verses {}-{}", verse_start, verse_end),
+            })
+            .collect();
+        let separator = if self.accessible { " > " } else { " ▸ " };
+        Line::styled(parts.join(separator), Style::default().fg(Color::Gray))
+    }
+
+    /// Filters offered by the genre picker, in display order
+    fn picker_filters() -> Vec<BookFilter> {
+        let mut filters = vec![
+            BookFilter::All,
+            BookFilter::OldTestament,
+            BookFilter::NewTestament,
+        ];
+        filters.extend(Genre::ALL.into_iter().map(BookFilter::Genre));
+        filters
+    }
+
+    /// Render the genre picker popup
+    fn render_genre_picker(&mut self, frame: &mut Frame) {
+        let Some(picker_state) = self.genre_picker.as_mut() else {
+            return;
+        };
+        let items: Vec<ListItem> = Self::picker_filters()
+            .iter()
+            .map(|filter| ListItem::new(filter.name()))
+            .collect();
+
+        let popup_area = centered_rect(40, 60, frame.area());
+        frame.render_widget(Clear, popup_area);
+        let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("Bible Structure (Space/→: expand, ←: collapse, ↑↓: navigate, r: record, m: manual add, q: quit)"),
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title("Show (Enter to pick, Esc to cancel)"),
             )
             .highlight_style(
                 Style::default()
@@ -93,18 +498,43 @@ impl DashboardWidget {
                     .bg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             )
-            .highlight_symbol(">> ");
+            .highlight_symbol(HIGHLIGHT_SYMBOL);
+        frame.render_stateful_widget(list, popup_area, picker_state);
+    }
 
-        frame.render_stateful_widget(tree, chunks[2], &mut self.tree_state);
+    /// Render a popup explaining what each passage color (or text marker) means
+    fn render_legend(&self, frame: &mut Frame) {
+        let lines: Vec<Line> = if self.accessible {
+            [
+                ReadStatus::Complete,
+                ReadStatus::Partial,
+                ReadStatus::Unread,
+            ]
+            .into_iter()
+            .map(|status| Line::raw(format!("{} {}", status.marker(), status.description())))
+            .collect()
+        } else {
+            self.gradient
+                .legend()
+                .into_iter()
+                .map(|(color, label)| {
+                    Line::from(vec![
+                        Span::styled("■ ", Style::default().fg(color)),
+                        Span::styled(label, Style::default().fg(color)),
+                    ])
+                })
+                .collect()
+        };
 
-        // Footer
-        let footer_text =
-            "Space/→: Expand | ←: Collapse | ↑↓: Navigate | r: Record | m: Manual Add | q: Quit";
-        let footer = Paragraph::new(footer_text)
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL));
-        frame.render_widget(footer, chunks[3]);
+        let popup_area = centered_rect(50, 40, frame.area());
+        frame.render_widget(Clear, popup_area);
+        let legend = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title("Color Legend (l/Esc to close)"),
+        );
+        frame.render_widget(legend, popup_area);
     }
 
     fn format_recent_reads(&self) -> Vec<Line<'static>> {
@@ -141,87 +571,242 @@ impl DashboardWidget {
 
     /// Format entries by consolidating contiguous chapters into ranges
     /// e.g., "Psalms 23, Psalms 24, Psalms 25" becomes "Psalms 23-25"
-    fn format_entries_with_ranges(entries: &[RecentReadEntry]) -> String {
-        use std::collections::BTreeMap;
-
-        // Group chapters by book, maintaining order of first appearance
-        let mut book_order: Vec<String> = Vec::new();
-        let mut book_chapters: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    pub(crate) fn format_entries_with_ranges(entries: &[RecentReadEntry]) -> String {
+        format_chapters(
+            entries
+                .iter()
+                .map(|entry| (entry.book.as_str(), entry.chapter)),
+        )
+    }
 
-        for entry in entries {
-            if !book_chapters.contains_key(&entry.book) {
-                book_order.push(entry.book.clone());
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> DashboardAction {
+        if let Some(preview) = self.passage_preview.as_mut() {
+            if preview.handle_key(key) {
+                self.passage_preview = None;
             }
-            book_chapters
-                .entry(entry.book.clone())
-                .or_default()
-                .push(entry.chapter);
+            return DashboardAction::None;
         }
 
-        // Sort chapters within each book and consolidate into ranges
-        let mut formatted_parts: Vec<String> = Vec::new();
-
-        for book in &book_order {
-            if let Some(chapters) = book_chapters.get_mut(book) {
-                chapters.sort();
-                chapters.dedup();
-
-                // Find contiguous ranges
-                let ranges = Self::find_contiguous_ranges(chapters);
+        if let Some(chapter_grid) = self.chapter_grid.as_mut() {
+            match chapter_grid.handle_key(key) {
+                ChapterGridAction::None => {}
+                ChapterGridAction::Close => self.chapter_grid = None,
+                ChapterGridAction::Open(chapter) => {
+                    let book = chapter_grid.book.clone();
+                    self.chapter_grid = None;
+                    self.select_chapter(&book, chapter);
+                }
+            }
+            return DashboardAction::None;
+        }
 
-                for (start, end) in ranges {
-                    if start == end {
-                        formatted_parts.push(format!("{} {}", book, start));
+        if let Some(batch_menu) = self.batch_menu.as_mut() {
+            return match batch_menu.handle_key(key) {
+                BatchMenuAction::None => DashboardAction::None,
+                BatchMenuAction::Cancel => {
+                    self.batch_menu = None;
+                    DashboardAction::None
+                }
+                BatchMenuAction::Apply(action) => {
+                    self.batch_menu = None;
+                    let chapters = self.take_marked_chapters();
+                    if action == BatchAction::ClearMarks {
+                        DashboardAction::None
                     } else {
-                        formatted_parts.push(format!("{} {}-{}", book, start, end));
+                        DashboardAction::Batch { action, chapters }
                     }
                 }
-            }
+            };
         }
 
-        formatted_parts.join(", ")
-    }
-
-    /// Find contiguous ranges in a sorted list of chapters
-    /// Returns a list of (start, end) tuples
-    fn find_contiguous_ranges(chapters: &[u32]) -> Vec<(u32, u32)> {
-        if chapters.is_empty() {
-            return Vec::new();
+        if self.show_legend {
+            if matches!(
+                key.code,
+                crossterm::event::KeyCode::Char('l') | crossterm::event::KeyCode::Esc
+            ) {
+                self.show_legend = false;
+            }
+            return DashboardAction::None;
         }
 
-        let mut ranges = Vec::new();
-        let mut range_start = chapters[0];
-        let mut range_end = chapters[0];
-
-        for &chapter in &chapters[1..] {
-            if chapter == range_end + 1 {
-                // Extend current range
-                range_end = chapter;
-            } else {
-                // Start new range
-                ranges.push((range_start, range_end));
-                range_start = chapter;
-                range_end = chapter;
+        if let Some(input) = self.jump_input.as_mut() {
+            match key.code {
+                crossterm::event::KeyCode::Enter => self.submit_jump(),
+                crossterm::event::KeyCode::Esc => {
+                    self.jump_input = None;
+                    self.prompt_error = None;
+                }
+                _ => {
+                    if input.handle_key(key, |c| !c.is_control()) {
+                        self.prompt_error = None;
+                    }
+                }
             }
+            return DashboardAction::None;
         }
 
-        // Don't forget the last range
-        ranges.push((range_start, range_end));
+        if let Some(input) = self.range_input.as_mut() {
+            match key.code {
+                crossterm::event::KeyCode::Enter => return self.submit_range(),
+                crossterm::event::KeyCode::Esc => {
+                    self.range_input = None;
+                    self.prompt_error = None;
+                }
+                _ => {
+                    if input.handle_key(key, |c| !c.is_control()) {
+                        self.prompt_error = None;
+                    }
+                }
+            }
+            return DashboardAction::None;
+        }
 
-        ranges
-    }
+        if let Some(picker_state) = self.genre_picker.as_mut() {
+            let filters = Self::picker_filters();
+            let selected = picker_state.selected().unwrap_or(0);
+            match key.code {
+                crossterm::event::KeyCode::Up => {
+                    picker_state.select(Some(selected.saturating_sub(1)));
+                }
+                crossterm::event::KeyCode::Down => {
+                    picker_state.select(Some((selected + 1).min(filters.len() - 1)));
+                }
+                crossterm::event::KeyCode::Enter => {
+                    self.genre_picker = None;
+                    self.set_filter(filters[selected]);
+                }
+                crossterm::event::KeyCode::Esc | crossterm::event::KeyCode::Char('G') => {
+                    self.genre_picker = None;
+                }
+                _ => {}
+            }
+            return DashboardAction::None;
+        }
 
-    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> DashboardAction {
         match (key.modifiers, key.code) {
             (_, crossterm::event::KeyCode::Char('q') | crossterm::event::KeyCode::Esc) => {
                 DashboardAction::Quit
             }
             (_, crossterm::event::KeyCode::Char('r')) => DashboardAction::StartRecord,
+            (_, crossterm::event::KeyCode::Char('.')) => DashboardAction::RepeatLast,
             (_, crossterm::event::KeyCode::Char('m')) => DashboardAction::StartManualAdd,
+            (_, crossterm::event::KeyCode::Char('I')) => DashboardAction::StartImport,
+            (_, crossterm::event::KeyCode::Char('T')) => DashboardAction::ShowToday,
             (_, crossterm::event::KeyCode::Char('u')) => {
                 self.show_only_unread = !self.show_only_unread;
                 DashboardAction::None
             }
+            (_, crossterm::event::KeyCode::Char('o')) => {
+                self.set_filter(self.toggled_filter(BookFilter::OldTestament));
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('t')) => {
+                self.set_filter(self.toggled_filter(BookFilter::NewTestament));
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('G')) => {
+                let current = Self::picker_filters()
+                    .iter()
+                    .position(|filter| *filter == self.filter);
+                self.genre_picker = Some(ListState::default().with_selected(current.or(Some(0))));
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('D')) => {
+                let current = self.date_range.map(|range| range.to_string());
+                self.range_input = Some(TextInput::from(current.as_deref().unwrap_or_default()));
+                self.prompt_error = None;
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('g')) => {
+                self.jump_input = Some(TextInput::new());
+                self.prompt_error = None;
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('l')) => {
+                self.show_legend = true;
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('v')) => {
+                self.open_passage_preview();
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('y')) => match self.tree_state.selected().last() {
+                Some(id) => DashboardAction::Copy(id.reference()),
+                None => DashboardAction::None,
+            },
+            (_, crossterm::event::KeyCode::Char('M')) => match self.tree_state.selected().last() {
+                Some(TreeId::Chapter { book, chapter }) => {
+                    let verses = self
+                        .bible
+                        .ot
+                        .get(book.as_str())
+                        .or_else(|| self.bible.nt.get(book.as_str()))
+                        .and_then(|chapters| {
+                            chapter
+                                .checked_sub(1)
+                                .and_then(|i| chapters.get(i as usize))
+                        })
+                        .copied()
+                        .unwrap_or(1);
+                    DashboardAction::Memorize {
+                        book: book.to_string(),
+                        chapter: *chapter,
+                        verse_start: 1,
+                        verse_end: verses,
+                    }
+                }
+                Some(TreeId::Passage {
+                    book,
+                    chapter,
+                    verse_start,
+                    verse_end,
+                }) => DashboardAction::Memorize {
+                    book: book.to_string(),
+                    chapter: *chapter,
+                    verse_start: *verse_start,
+                    verse_end: *verse_end,
+                },
+                _ => DashboardAction::None,
+            },
+            (_, crossterm::event::KeyCode::Char('i')) => match self.tree_state.selected().last() {
+                Some(TreeId::Chapter { book, chapter } | TreeId::Passage { book, chapter, .. }) => {
+                    DashboardAction::ShowChapterReadings {
+                        book: book.to_string(),
+                        chapter: *chapter,
+                    }
+                }
+                _ => DashboardAction::None,
+            },
+            (_, crossterm::event::KeyCode::Char('K')) => {
+                let start = match self.tree_state.selected().last() {
+                    Some(TreeId::Book(book)) => Some(book.to_string()),
+                    Some(
+                        TreeId::Chapter { book, chapter } | TreeId::Passage { book, chapter, .. },
+                    ) => Some(format!("{} {}", book, chapter)),
+                    _ => None,
+                };
+                DashboardAction::StartTicker { start }
+            }
+            (_, crossterm::event::KeyCode::Char('L')) => {
+                let after = match self.tree_state.selected().last() {
+                    Some(
+                        TreeId::Chapter { book, chapter } | TreeId::Passage { book, chapter, .. },
+                    ) => Some((book.to_string(), *chapter)),
+                    _ => None,
+                };
+                DashboardAction::FindLeastRead { after }
+            }
+            (_, crossterm::event::KeyCode::Char('R')) => DashboardAction::ReviewMemory,
+            (_, crossterm::event::KeyCode::Char('c')) => DashboardAction::SwitchCampaign,
+            (_, crossterm::event::KeyCode::Char('Y')) => {
+                // Testaments have no stats line of their own, so fall back to the reference
+                match self.tree_state.selected().last() {
+                    Some(id) => DashboardAction::Copy(
+                        self.tree.stats_line(id).unwrap_or_else(|| id.reference()),
+                    ),
+                    None => DashboardAction::None,
+                }
+            }
             (_, crossterm::event::KeyCode::Up) => {
                 self.tree_state.key_up();
                 DashboardAction::None
@@ -230,16 +815,82 @@ impl DashboardWidget {
                 self.tree_state.key_down();
                 DashboardAction::None
             }
+            (_, crossterm::event::KeyCode::PageUp) => {
+                let page = self.page_size();
+                self.tree_state
+                    .select_relative(|current| current.map_or(0, |c| c.saturating_sub(page)));
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::PageDown) => {
+                let page = self.page_size();
+                self.tree_state
+                    .select_relative(|current| current.map_or(0, |c| c.saturating_add(page)));
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Home) => {
+                self.tree_state.select_first();
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::End) => {
+                self.tree_state.select_last();
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('n')) => {
+                self.jump_to_unread(true);
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('N')) => {
+                self.jump_to_unread(false);
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('E')) => {
+                self.tree_state.expand_all(&self.tree_items);
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('C')) => {
+                self.tree_state.collapse_all();
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char(c @ '1'..='3')) => {
+                let depth = c.to_digit(10).unwrap_or(1) as usize;
+                self.tree_state.expand_to_depth(&self.tree_items, depth);
+                DashboardAction::None
+            }
             (_, crossterm::event::KeyCode::Left) => {
                 self.tree_state.key_left();
                 DashboardAction::None
             }
-            (
-                _,
-                crossterm::event::KeyCode::Right
-                | crossterm::event::KeyCode::Char(' ')
-                | crossterm::event::KeyCode::Enter,
-            ) => {
+            (_, crossterm::event::KeyCode::Char(' ')) => {
+                if let Some(TreeId::Chapter { book, chapter }) = self.tree_state.selected().last() {
+                    let chapter = (book.to_string(), *chapter);
+                    if !self.marked_chapters.remove(&chapter) {
+                        self.marked_chapters.insert(chapter);
+                    }
+                    self.tree_items = self.build_tree_items();
+                } else {
+                    self.tree_state.toggle_selected();
+                }
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Char('b')) => {
+                if !self.marked_chapters.is_empty() {
+                    self.batch_menu = Some(BatchMenu::new(self.marked_chapters.len()));
+                }
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Enter) => {
+                if let Some(TreeId::Book(book)) = self.tree_state.selected().last() {
+                    self.chapter_grid = Some(ChapterGrid::new(
+                        book.to_string(),
+                        self.tree.chapter_labels(book),
+                        self.accessible,
+                    ));
+                } else {
+                    self.tree_state.toggle_selected();
+                }
+                DashboardAction::None
+            }
+            (_, crossterm::event::KeyCode::Right) => {
                 self.tree_state.toggle_selected();
                 DashboardAction::None
             }
@@ -247,22 +898,636 @@ impl DashboardWidget {
         }
     }
 
-    pub fn update_tree(
-        &mut self,
-        bible: &'static crate::bible_structure::BibleStructure,
-        progress: &ReadingProgress,
-    ) {
-        self.tree_items = build_dashboard_tree_items(bible, progress);
-        self.recent_reads = collect_recent_reads(progress);
+    /// Parse the quick-jump input and select the book or chapter it refers to, keeping the
+    /// prompt open with an error if that fails
+    fn submit_jump(&mut self) {
+        let input = self.jump_input.as_deref().unwrap_or_default();
+        let selected = parse_reference(self.bible, input)
+            .and_then(|reference| self.select_reference(&reference.book, reference.chapter));
+        match selected {
+            Ok(()) => {
+                self.jump_input = None;
+                self.prompt_error = None;
+            }
+            Err(e) => self.prompt_error = Some(e),
+        }
+    }
+
+    /// Select a book, or one of its chapters (which must exist), expanding the tree down to it
+    pub fn select_reference(&mut self, book: &str, chapter: Option<u32>) -> Result<(), String> {
+        let testament = if self.bible.ot.contains_key(book) {
+            TreeId::OldTestament
+        } else {
+            TreeId::NewTestament
+        };
+        let book_name = BookName::new(book);
+        let mut path = vec![testament, TreeId::Book(book_name.clone())];
+        if let Some(chapter) = chapter {
+            path.push(TreeId::Chapter {
+                book: book_name,
+                chapter,
+            });
+        }
+
+        // Chapters aren't in the tree until their book is expanded
+        if !tree_contains_path(&self.tree_items, &path[..2]) {
+            return Err(format!("{} is hidden by the current filter", book));
+        }
+
+        for depth in 1..path.len() {
+            self.tree_state.open(path[..depth].to_vec());
+        }
+        self.tree_state.select(path);
+        Ok(())
+    }
+
+    /// Parse the date range input, empty for every reading, keeping the prompt open with an
+    /// error if that fails
+    fn submit_range(&mut self) -> DashboardAction {
+        let input = self.range_input.as_deref().unwrap_or_default().trim();
+        let date_range = if input.is_empty() {
+            None
+        } else {
+            match DateRange::parse(input) {
+                Ok(range) => Some(range),
+                Err(e) => {
+                    self.prompt_error = Some(e);
+                    return DashboardAction::None;
+                }
+            }
+        };
+        self.range_input = None;
+        self.prompt_error = None;
+        self.date_range = date_range;
+        DashboardAction::DateRangeChanged
+    }
+
+    /// The days whose readings are shown, if not all of them
+    pub fn date_range(&self) -> Option<DateRange> {
+        self.date_range
+    }
+
+    /// Show the text of the selected chapter (or the chapter of the selected passage), loading
+    /// the scripture text file on first use
+    fn open_passage_preview(&mut self) {
+        let (book, chapter, highlight) = match self.tree_state.selected().last() {
+            Some(TreeId::Chapter { book, chapter }) => (book.clone(), *chapter, None),
+            Some(TreeId::Passage {
+                book,
+                chapter,
+                verse_start,
+                verse_end,
+            }) => (book.clone(), *chapter, Some((*verse_start, *verse_end))),
+            _ => return,
+        };
+        let title = format!("{} {}", book, chapter);
+
+        if self.scripture.is_none() {
+            let Some(path) = &self.scripture_text_path else {
+                self.passage_preview = Some(PassagePreview::message(
+                    title,
+                    "No scripture text is configured. Set `scripture_text_path` in the config \
+                     file to a plain-text Bible with one `Book Chapter:Verse Text` line per verse."
+                        .to_string(),
+                ));
+                return;
+            };
+            match ScriptureText::load(self.bible, path) {
+                Ok(scripture) => self.scripture = Some(scripture),
+                Err(e) => {
+                    self.passage_preview = Some(PassagePreview::message(
+                        title,
+                        format!("Couldn't read {}: {}", path.display(), e),
+                    ));
+                    return;
+                }
+            }
+        }
+
+        let verses = self
+            .scripture
+            .as_ref()
+            .and_then(|scripture| scripture.chapter(&book, chapter));
+        self.passage_preview = Some(match verses {
+            Some(verses) => PassagePreview::new(title, verses, highlight),
+            None => PassagePreview::message(
+                title.clone(),
+                format!("{} is not in the scripture text file.", title),
+            ),
+        });
+    }
+
+    /// Expand `book` and select its `chapter`
+    fn select_chapter(&mut self, book: &str, chapter: u32) {
+        let testament = if self.bible.ot.contains_key(book) {
+            TreeId::OldTestament
+        } else {
+            TreeId::NewTestament
+        };
+        let book = BookName::new(book);
+        let book_id = TreeId::Book(book.clone());
+        self.tree_state.open(vec![testament.clone()]);
+        self.tree_state
+            .open(vec![testament.clone(), book_id.clone()]);
+        self.tree_state
+            .select(vec![testament, book_id, TreeId::Chapter { book, chapter }]);
+    }
+
+    /// True while the chapter grid is shown in place of the tree
+    pub fn is_showing_chapter_grid(&self) -> bool {
+        self.chapter_grid.is_some()
+    }
+
+    /// True while a prompt is open that consumes typed characters
+    pub fn is_capturing_input(&self) -> bool {
+        self.jump_input.is_some()
+            || self.range_input.is_some()
+            || self
+                .batch_menu
+                .as_ref()
+                .is_some_and(BatchMenu::is_capturing_input)
+    }
+
+    /// Unmark every chapter, returning the marked ones in canonical order
+    fn take_marked_chapters(&mut self) -> Vec<(String, u32)> {
+        let marked = std::mem::take(&mut self.marked_chapters);
+        self.tree_items = self.build_tree_items();
+        let mut chapters: Vec<(String, u32)> = marked.into_iter().collect();
+        let books = get_all_books(self.bible);
+        chapters.sort_by_key(|(book, chapter)| (books.iter().position(|b| b == book), *chapter));
+        chapters
+    }
+
+    /// `filter`, or all books if it is already active
+    fn toggled_filter(&self, filter: BookFilter) -> BookFilter {
+        if self.filter == filter {
+            BookFilter::All
+        } else {
+            filter
+        }
+    }
+
+    /// Restrict the tree to the books matching `filter`, keeping expanded nodes and the
+    /// selection where they still exist
+    pub fn set_filter(&mut self, filter: BookFilter) {
+        let state = self.ui_state();
+        self.filter = filter;
+        self.refresh_tree_items();
         self.tree_state = TreeState::default();
         self.tree_state.select_first();
+        self.restore_ui_state(&state);
+    }
+
+    /// Reassemble the tree and the values derived from it from the cached book summaries
+    fn refresh_tree_items(&mut self) {
+        self.expanded_books = self.opened_books();
+        self.tree_items = self.build_tree_items();
+        self.chapter_unread_flags = self.tree.chapter_unread_flags(self.bible, self.filter);
+        self.percent_read = self.tree.percent_read(self.bible, self.filter);
+    }
+
+    /// Books opened in the tree state
+    fn opened_books(&self) -> HashSet<String> {
+        self.tree_state
+            .opened()
+            .iter()
+            .filter_map(|path| match path.as_slice() {
+                [_, TreeId::Book(book)] => Some(book.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Rebuild the tree items if books were expanded or collapsed since they were last built,
+    /// so chapter nodes exist only for open books
+    fn sync_expanded_books(&mut self) {
+        let opened = self.opened_books();
+        if opened != self.expanded_books {
+            self.expanded_books = opened;
+            self.tree_items = self.build_tree_items();
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn build_tree_items(&self) -> Vec<TreeItem<'static, TreeId>> {
+        self.tree.items(
+            self.bible,
+            self.filter,
+            &self.expanded_books,
+            &self.marked_chapters,
+            self.label_width,
+        )
+    }
+
+    /// Move the selection to the next (or previous) chapter that still has unread verses,
+    /// wrapping around the end of the Bible and expanding its testament and book
+    fn jump_to_unread(&mut self, forward: bool) {
+        let chapters = &self.chapter_unread_flags;
+        if chapters.is_empty() {
+            return;
+        }
+
+        // Position of the selection among the chapters. A selected testament or book sits just
+        // before its first chapter, so jumping forward can land on that chapter.
+        let selected = self.tree_state.selected();
+        let position = chapters
+            .iter()
+            .position(|(path, _)| path.starts_with(selected) && !selected.is_empty());
+        let selected_is_chapter = position.is_some_and(|idx| chapters[idx].0 == selected);
+
+        let len = chapters.len();
+        let candidates: Vec<usize> = match (position, forward) {
+            (Some(idx), true) if selected_is_chapter => {
+                (1..=len).map(|i| (idx + i) % len).collect()
+            }
+            (Some(idx), true) => (0..len).map(|i| (idx + i) % len).collect(),
+            (Some(idx), false) => (1..=len).map(|i| (idx + len - i) % len).collect(),
+            (None, true) => (0..len).collect(),
+            (None, false) => (0..len).rev().collect(),
+        };
+
+        let Some(target) = candidates
+            .into_iter()
+            .find(|&idx| chapters[idx].1)
+            .map(|idx| chapters[idx].0.clone())
+        else {
+            return;
+        };
+
+        for depth in 1..target.len() {
+            self.tree_state.open(target[..depth].to_vec());
+        }
+        self.tree_state.select(target);
+    }
+
+    /// Number of tree rows visible at once (tree area minus borders)
+    fn page_size(&self) -> usize {
+        (self.tree_area.height.saturating_sub(2) as usize).max(1)
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> DashboardAction {
+        if self.chapter_grid.is_some() {
+            return DashboardAction::None;
+        }
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.record_button_area.contains(position) {
+                    return DashboardAction::StartRecord;
+                }
+                if self.quit_button_area.contains(position) {
+                    return DashboardAction::Quit;
+                }
+                self.click_tree(position);
+                DashboardAction::None
+            }
+            MouseEventKind::ScrollDown if self.tree_area.contains(position) => {
+                self.tree_state.scroll_down(3);
+                DashboardAction::None
+            }
+            MouseEventKind::ScrollUp if self.tree_area.contains(position) => {
+                self.tree_state.scroll_up(3);
+                DashboardAction::None
+            }
+            _ => DashboardAction::None,
+        }
+    }
+
+    /// Select the clicked node, expanding/collapsing it on a double-click or a click on its arrow
+    fn click_tree(&mut self, position: Position) {
+        let Some(identifier) = self
+            .tree_state
+            .rendered_at(position)
+            .map(<[TreeId]>::to_vec)
+        else {
+            self.last_click = None;
+            return;
+        };
+
+        // The arrow sits after the border, the highlight symbol and two columns per depth level
+        let depth = identifier.len().saturating_sub(1) as u16;
+        let arrow_x = self.tree_area.x + 1 + HIGHLIGHT_SYMBOL.len() as u16 + depth * 2;
+        let clicked_arrow = (arrow_x..arrow_x + 2).contains(&position.x);
+
+        let now = Instant::now();
+        let double_click = self.last_click.as_ref().is_some_and(|(time, last)| {
+            *last == identifier && now.duration_since(*time) <= DOUBLE_CLICK_INTERVAL
+        });
+
+        self.tree_state.select(identifier.clone());
+        if clicked_arrow || double_click {
+            self.tree_state.toggle_selected();
+            self.last_click = None;
+        } else {
+            self.last_click = Some((now, identifier));
+        }
+    }
+
+    /// Snapshot the expanded nodes, selection and filters so they can be restored later
+    pub fn ui_state(&self) -> DashboardUiState {
+        DashboardUiState {
+            opened: self.tree_state.opened().iter().cloned().collect(),
+            selected: self.tree_state.selected().to_vec(),
+            show_only_unread: self.show_only_unread,
+            filter: self.filter,
+            date_range: self.date_range,
+        }
+    }
+
+    /// Restore a previously saved UI state, skipping nodes that no longer exist in the tree.
+    /// The saved filter is applied separately with `set_filter`.
+    pub fn restore_ui_state(&mut self, state: &DashboardUiState) {
+        for path in &state.opened {
+            if tree_contains_path(&self.tree_items, path) {
+                self.tree_state.open(path.clone());
+            }
+        }
+        // The selection may be a chapter of a book that was just reopened
+        self.sync_expanded_books();
+        if tree_contains_path(&self.tree_items, &state.selected) {
+            self.tree_state.select(state.selected.clone());
+        }
+        self.show_only_unread = state.show_only_unread;
+        self.date_range = state.date_range;
+    }
+
+    /// Pick up new readings in `books`, rebuilding only those books' tree items. Expanded nodes
+    /// and the selection are kept, since the tree's shape doesn't change.
+    #[tracing::instrument(level = "debug", skip_all, fields(books = books.len()))]
+    pub fn update_books(&mut self, progress: &ReadingProgress, books: &HashSet<BookName>) {
+        self.tree
+            .update_books(self.bible, progress, &self.gradient, books);
+        self.refresh_tree_items();
+        self.refresh_day(progress);
+        self.recent_reads = collect_recent_reads(progress);
+        if let Some(chapter_grid) = self.chapter_grid.as_mut() {
+            chapter_grid.set_chapters(self.tree.chapter_labels(&chapter_grid.book));
+        }
+    }
+
+    /// Recompute what depends on today's date: the status bar and the activity sparkline
+    pub fn refresh_day(&mut self, progress: &ReadingProgress) {
+        self.status_bar = StatusBar::new(
+            self.bible,
+            progress,
+            self.status_bar.daily_chapter_goal,
+            self.status_bar.goals.clone(),
+        );
+        self.activity = recent_activity(progress);
+    }
+}
+
+/// Chapters read on each of the last `ACTIVITY_DAYS` days, ending today
+fn recent_activity(progress: &ReadingProgress) -> Vec<u64> {
+    chapters_per_day(&progress.history, Utc::now().date_naive(), ACTIVITY_DAYS)
+}
+
+/// True if `path` identifies a node in the tree (an empty path never does)
+fn tree_contains_path(items: &[TreeItem<'static, TreeId>], path: &[TreeId]) -> bool {
+    let Some((first, rest)) = path.split_first() else {
+        return false;
+    };
+    match items.iter().find(|item| item.identifier() == first) {
+        Some(_) if rest.is_empty() => true,
+        Some(item) => tree_contains_path(item.children(), rest),
+        None => false,
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DashboardAction {
     None,
     Quit,
     StartRecord,
     StartManualAdd,
+    /// Open the form for importing readings from text
+    StartImport,
+    /// Record the most recently recorded passage again
+    RepeatLast,
+    /// Apply `action` to the marked chapters, as (book, chapter) in canonical order
+    Batch {
+        action: BatchAction,
+        chapters: Vec<(String, u32)>,
+    },
+    ShowToday,
+    /// Copy this text to the clipboard
+    Copy(String),
+    /// Add this passage to the ones being memorized
+    Memorize {
+        book: String,
+        chapter: u32,
+        verse_start: u32,
+        verse_end: u32,
+    },
+    /// Review the memorized passages that are due
+    ReviewMemory,
+    /// The date range was set or cleared; the progress shown needs building again
+    DateRangeChanged,
+    /// List the latest readings of a chapter
+    ShowChapterReadings {
+        book: String,
+        chapter: u32,
+    },
+    /// Show and record for the next campaign
+    SwitchCampaign,
+    /// Open the chapter ticker, starting at this reference if one is selected
+    StartTicker {
+        start: Option<String>,
+    },
+    /// Select the least-read verses of the books shown, the first place after this chapter if
+    /// one is selected
+    FindLeastRead {
+        after: Option<(String, u32)>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::widgets::snapshot::{render, sample_progress};
+
+    fn dashboard(progress: &ReadingProgress) -> DashboardWidget {
+        DashboardWidget::new(get_bible_structure(), progress, &Config::default())
+    }
+
+    fn press(dashboard: &mut DashboardWidget, keys: &str) {
+        for c in keys.chars() {
+            press_code(dashboard, crossterm::event::KeyCode::Char(c));
+        }
+    }
+
+    /// Like the app, render before each key, as the tree only knows its rows once rendered
+    fn press_code(dashboard: &mut DashboardWidget, code: crossterm::event::KeyCode) {
+        screen(dashboard, 80, 30);
+        dashboard.handle_key(crossterm::event::KeyEvent::from(code));
+    }
+
+    fn screen(dashboard: &mut DashboardWidget, width: u16, height: u16) -> String {
+        render(width, height, |frame| dashboard.render(frame, frame.area()))
+    }
+
+    #[test]
+    fn empty_progress() {
+        let mut dashboard = dashboard(&ReadingProgress::new());
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
+
+    #[test]
+    fn sample_progress_collapsed() {
+        let mut dashboard = dashboard(&sample_progress());
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
+
+    #[test]
+    fn expanded_to_books() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "2");
+        assert_snapshot!(screen(&mut dashboard, 80, 30));
+    }
+
+    #[test]
+    fn expanded_to_chapters() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "t3");
+        assert_snapshot!(screen(&mut dashboard, 80, 30));
+    }
+
+    #[test]
+    fn book_selected_and_opened() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "2");
+        // Old Testament, then Genesis
+        press_code(&mut dashboard, crossterm::event::KeyCode::Down);
+        press_code(&mut dashboard, crossterm::event::KeyCode::Down);
+        press_code(&mut dashboard, crossterm::event::KeyCode::Right);
+        assert_snapshot!(screen(&mut dashboard, 80, 30));
+    }
+
+    #[test]
+    fn old_testament_only() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "o2");
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
+
+    #[test]
+    fn new_testament_only() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "t2");
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
+
+    #[test]
+    fn accessibility_markers() {
+        let mut config = Config::default();
+        config.accessibility_mode = true;
+        let mut dashboard =
+            DashboardWidget::new(get_bible_structure(), &sample_progress(), &config);
+        press(&mut dashboard, "t2");
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
+
+    #[test]
+    fn narrow_terminal_truncates_labels() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "2");
+        assert_snapshot!(screen(&mut dashboard, 40, 24));
+    }
+
+    #[test]
+    fn short_terminal_keeps_the_tree() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "2");
+        assert_snapshot!(screen(&mut dashboard, 80, 10));
+    }
+
+    #[test]
+    fn chapter_grid() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "o2");
+        press_code(&mut dashboard, crossterm::event::KeyCode::Down);
+        press_code(&mut dashboard, crossterm::event::KeyCode::Down);
+        press_code(&mut dashboard, crossterm::event::KeyCode::Enter);
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
+
+    #[test]
+    fn color_legend() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "l");
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
+
+    #[test]
+    fn quick_jump_prompt() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "gps 2");
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
+
+    #[test]
+    fn date_range_prompt() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "D2024-13");
+        press_code(&mut dashboard, crossterm::event::KeyCode::Enter);
+        assert!(dashboard
+            .prompt_error
+            .as_deref()
+            .is_some_and(|error| error.ends_with("not \"2024-13\"")));
+
+        press_code(&mut dashboard, crossterm::event::KeyCode::Backspace);
+        press_code(&mut dashboard, crossterm::event::KeyCode::Backspace);
+        let enter = crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Enter);
+        press(&mut dashboard, "02");
+        assert_eq!(
+            dashboard.handle_key(enter),
+            DashboardAction::DateRangeChanged
+        );
+        assert_eq!(dashboard.date_range().unwrap().to_string(), "2024-02");
+        assert_eq!(dashboard.ui_state().date_range, dashboard.date_range());
+
+        // Opened with the range chosen, which clearing shows every reading again
+        press(&mut dashboard, "D");
+        assert!(screen(&mut dashboard, 100, 24).contains("Dates: 2024-02"));
+        for _ in 0..7 {
+            press_code(&mut dashboard, crossterm::event::KeyCode::Backspace);
+        }
+        dashboard.handle_key(enter);
+        assert_eq!(dashboard.date_range(), None);
+    }
+
+    #[test]
+    fn genre_picker() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "G");
+        assert_snapshot!(screen(&mut dashboard, 80, 30));
+    }
+
+    #[test]
+    fn batch_menu_for_marked_chapters() {
+        let mut dashboard = dashboard(&sample_progress());
+        press(&mut dashboard, "t3");
+        // New Testament, Matthew, then its first two chapters
+        press_code(&mut dashboard, crossterm::event::KeyCode::Down);
+        press_code(&mut dashboard, crossterm::event::KeyCode::Down);
+        press_code(&mut dashboard, crossterm::event::KeyCode::Down);
+        press(&mut dashboard, " ");
+        press_code(&mut dashboard, crossterm::event::KeyCode::Down);
+        press(&mut dashboard, " b");
+        assert_snapshot!(screen(&mut dashboard, 80, 30));
+    }
+
+    #[test]
+    fn campaign_header() {
+        let today = Utc::now().date_naive();
+        let mut progress = sample_progress();
+        progress.start_campaign("2025 read-through", today).unwrap();
+        progress.record_reading_on("Ruth", 1, 1, 22, 1, today);
+        let layer = progress.campaign_layer("2025 read-through");
+        let mut dashboard = dashboard(&layer);
+        assert_snapshot!(screen(&mut dashboard, 80, 24));
+    }
 }
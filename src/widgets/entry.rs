@@ -0,0 +1,2884 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{NaiveDate, Utc};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::audio::{parse_listening, Listening};
+use crate::coverage::next_unread_chapter;
+use crate::progress::{Overlap, ReadingProgress, ReadingSource, SameDayReadings};
+use crate::reference::{is_book_prefix, parse_passage, unambiguous_book};
+use crate::stats::format_minutes;
+use crate::utils::{get_all_books, get_book_aliases, parse_verse_ranges};
+use crate::widgets::form::{self, Severity};
+use crate::widgets::help::{render_help, KeyBinding};
+use crate::widgets::layout::{responsive_stack, Section};
+use crate::widgets::text_input::TextInput;
+use crate::widgets::tree_builder::book_progress_summary;
+
+/// What saving a passage does to the verses already recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Count the passage as one more reading, today (Record mode)
+    Accumulate,
+    /// Set the passage to the read count and date in the advanced fields, replacing what is
+    /// recorded (Manual Add mode)
+    Overwrite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputFocus {
+    /// The free-text passage field, e.g. "John 3:16-4:2"
+    Reference,
+    Book,
+    Chapter,
+    Verse,
+    VerseEnd,
+    /// Advanced field, only shown in Overwrite mode
+    ReadCount,
+    /// Advanced field, only shown in Overwrite mode
+    Date,
+    /// Free-text note kept with the reading
+    Notes,
+    /// Comma or space separated tags kept with the reading
+    Tags,
+    /// The list of passages added so far
+    Added,
+}
+
+/// The read count and date an overwriting passage is set to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overwrite {
+    pub read_count: u32,
+    /// `None` for today
+    pub last_read: Option<NaiveDate>,
+}
+
+/// A passage added to the session, recorded along with the others when the session is saved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddedPassage {
+    /// How the passage is shown in the list, e.g. "John 3:16-4:2"
+    pub label: String,
+    pub book: String,
+    /// Verses per chapter, as (chapter, verse_start, verse_end)
+    pub ranges: Vec<(u32, u32, u32)>,
+    /// How many readings to add, e.g. 2 for "ps 23 2x"
+    pub times: u32,
+    /// Replace what is recorded for the verses instead of adding readings
+    pub overwrite: Option<Overwrite>,
+    /// Note and tags from the Notes and Tags fields, saved with each reading in the history
+    pub note: Option<String>,
+    pub tags: Vec<String>,
+    pub source: ReadingSource,
+    pub translation: Option<String>,
+    /// Count the verses even if they were already read today, whatever `same_day_readings`
+    /// says
+    pub count_again: bool,
+}
+
+impl AddedPassage {
+    /// A label such as "John 3-4, 5:1-5, 5:9" for `ranges` of `book`, whose chapters have
+    /// `chapters` verses each. Runs of whole chapters are joined into a chapter range.
+    fn describe(book: &str, ranges: &[(u32, u32, u32)], chapters: &[u32]) -> String {
+        let is_whole = |&(chapter, verse_start, verse_end): &(u32, u32, u32)| {
+            verse_start == 1 && verse_end == chapters[chapter as usize - 1]
+        };
+        let mut parts: Vec<String> = Vec::new();
+        let mut idx = 0;
+        while idx < ranges.len() {
+            let (chapter, verse_start, verse_end) = ranges[idx];
+            if is_whole(&ranges[idx]) {
+                let mut last = chapter;
+                while ranges
+                    .get(idx + 1)
+                    .is_some_and(|next| next.0 == last + 1 && is_whole(next))
+                {
+                    idx += 1;
+                    last += 1;
+                }
+                parts.push(if last == chapter {
+                    chapter.to_string()
+                } else {
+                    format!("{}-{}", chapter, last)
+                });
+            } else if verse_start == verse_end {
+                parts.push(format!("{}:{}", chapter, verse_start));
+            } else {
+                parts.push(format!("{}:{}-{}", chapter, verse_start, verse_end));
+            }
+            idx += 1;
+        }
+        format!("{} {}", book, parts.join(", "))
+    }
+
+    /// Add the passage's readings to `progress`
+    pub fn record(&self, progress: &mut ReadingProgress) {
+        let first_event = progress.history.len();
+        for &(chapter, verse_start, verse_end) in &self.ranges {
+            match self.overwrite {
+                Some(overwrite) => progress.record_reading_overwrite(
+                    &self.book,
+                    chapter,
+                    verse_start,
+                    verse_end,
+                    overwrite.read_count,
+                    overwrite.last_read,
+                ),
+                None if self.count_again => progress.record_reading_on(
+                    &self.book,
+                    chapter,
+                    verse_start,
+                    verse_end,
+                    self.times,
+                    Utc::now().date_naive(),
+                ),
+                None => progress.record_reading_times(
+                    &self.book,
+                    chapter,
+                    verse_start,
+                    verse_end,
+                    self.times,
+                ),
+            }
+        }
+        progress.annotate_since(first_event, |event| {
+            event.note = self.note.clone();
+            event.tags = self.tags.clone();
+            event.source = self.source;
+            event.translation = self.translation.clone();
+        });
+    }
+
+    /// Make the passage overwrite what is recorded, noting it in the label
+    fn set_overwrite(&mut self, overwrite: Overwrite) {
+        self.label = format!(
+            "{} (set to {}×, {})",
+            self.label,
+            overwrite.read_count,
+            overwrite
+                .last_read
+                .map_or_else(|| "today".to_string(), |date| date.to_string())
+        );
+        self.overwrite = Some(overwrite);
+    }
+
+    /// How the passage overlaps what `progress` already has recorded: read today if any of
+    /// it was, already read if all of it was
+    fn overlap(&self, progress: &ReadingProgress, today: NaiveDate) -> Option<Overlap> {
+        let overlaps: Vec<Option<Overlap>> = self
+            .ranges
+            .iter()
+            .map(|&(chapter, verse_start, verse_end)| {
+                progress.overlap(&self.book, chapter, verse_start, verse_end, today)
+            })
+            .collect();
+        if overlaps.contains(&Some(Overlap::ReadToday)) {
+            Some(Overlap::ReadToday)
+        } else if overlaps.iter().all(Option::is_some) {
+            Some(Overlap::AlreadyRead)
+        } else {
+            None
+        }
+    }
+}
+
+/// Number of book matches visible at once (list height minus borders)
+const BOOK_MATCHES_PAGE_SIZE: usize = 6;
+
+/// Keybindings available in the entry form, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    (
+        "Enter",
+        "Add the passage typed in the Passage field (e.g. \"John 3:16-4:2\")",
+    ),
+    (
+        "Ctrl+S",
+        "Save the added passages and return to the dashboard",
+    ),
+    (
+        "Ctrl+L",
+        "Switch between read and listened for the passages added next",
+    ),
+    (
+        "Ctrl+T",
+        "Cycle through the configured translations for the passages added next",
+    ),
+    (
+        "Ctrl+O",
+        "Switch between adding a reading and overwriting (read count and date fields)",
+    ),
+    (
+        "d/Delete",
+        "Remove the selected passage (Added passages list)",
+    ),
+    (
+        "n",
+        "Fill in the next unread chapter of the book (Chapter field)",
+    ),
+    (
+        "a/o/s",
+        "Add anyway / overwrite / skip a passage that was already read (warning)",
+    ),
+    (
+        ".",
+        "Add the last recorded passage again (empty Passage field)",
+    ),
+    (
+        "2x",
+        "Typed after a passage, record it as read twice (Passage field)",
+    ),
+    ("Tab", "Move to the next field"),
+    ("Shift+Tab", "Move to the previous field"),
+    ("↑/↓", "Select a book from the matches (Book field)"),
+    (
+        "↑/↓",
+        "Step the number or date up/down (Chapter, Verse, Read Count and Date fields)",
+    ),
+    ("PgUp/PgDn", "Move a page through the matches (Book field)"),
+    ("Home/End", "Jump to the first/last match (Book field)"),
+    (
+        "Enter",
+        "Select book / next field / add the passage / save (empty Passage field)",
+    ),
+    (
+        "F1",
+        "Show the accepted formats for the focused field, with examples",
+    ),
+    ("?", "Show this help"),
+    (
+        "Esc",
+        "Cancel and return to the dashboard (asks first if anything was entered)",
+    ),
+];
+
+/// What each field accepts, as (example, meaning) pairs for the F1 overlay
+const PASSAGE_FORMATS: &[KeyBinding] = &[
+    ("romans", "A whole book (asks for confirmation)"),
+    ("ps 23", "A whole chapter; book names may be abbreviated"),
+    ("John 3-5", "Several whole chapters"),
+    ("John 3:16", "A single verse"),
+    ("John 3:16-18", "A range of verses"),
+    ("John 3:16-4:2", "A range of verses crossing chapters"),
+    (
+        "1 jn 3, I John 3",
+        "Numbered books, with digits or roman numerals",
+    ),
+    ("ps 23 2x", "Read more than once (also ×2)"),
+    (
+        "35 min from Mark 4",
+        "What an audio Bible covers in that time, as listened",
+    ),
+    (".", "The passage you recorded last (in the empty field)"),
+];
+const BOOK_FORMATS: &[KeyBinding] = &[
+    ("rom", "Any letters of the name, in order (fuzzy search)"),
+    ("1 cor, 1co", "Numbered books"),
+    ("(empty)", "Pinned and recently read books are listed first"),
+];
+const CHAPTER_FORMATS: &[KeyBinding] = &[
+    ("3", "One chapter"),
+    (
+        "3-5",
+        "A range of chapters; verses can be given for the first and last",
+    ),
+    ("(empty)", "The whole book (asks for confirmation)"),
+    ("n", "The next unread chapter, as suggested in the title"),
+];
+const VERSE_FORMATS: &[KeyBinding] = &[
+    ("16", "One verse"),
+    ("16-18", "A range of verses"),
+    ("1-5, 8, 10-12", "Several ranges, separated by commas"),
+    ("(empty)", "The whole chapter"),
+];
+const VERSE_END_FORMATS: &[KeyBinding] = &[
+    (
+        "1-2",
+        "Verses of the last chapter in the range, as in the Verse field",
+    ),
+    ("(empty)", "The whole last chapter"),
+];
+const READ_COUNT_FORMATS: &[KeyBinding] = &[
+    (
+        "2",
+        "Set the passage to read twice, replacing its current count",
+    ),
+    (
+        "(empty)",
+        "Once, or the count typed after the passage (e.g. \"ps 23 2x\")",
+    ),
+];
+const DATE_FORMATS: &[KeyBinding] = &[
+    ("2024-11-02", "The date it was last read, as YYYY-MM-DD"),
+    ("(empty)", "Today"),
+];
+const NOTES_FORMATS: &[KeyBinding] = &[
+    (
+        "memorizing v1-6",
+        "Any text, saved with the reading in the history",
+    ),
+    ("(empty)", "No note"),
+];
+const TAGS_FORMATS: &[KeyBinding] = &[
+    ("morning, memory", "Tags separated by commas or spaces"),
+    ("#family", "A leading # is optional"),
+    ("(empty)", "No tags"),
+];
+const ADDED_FORMATS: &[KeyBinding] = &[
+    ("d/Delete", "Remove the selected passage"),
+    ("Enter", "Save every added passage"),
+];
+
+/// The form for entering readings, used both to record what was read today (Accumulate mode)
+/// and to set read counts and dates directly (Overwrite mode)
+pub struct EntryWidget {
+    pub write_mode: WriteMode,
+    /// Free-text passage, an alternative to the book, chapter and verse fields
+    pub reference_input: TextInput,
+    pub book_search: TextInput,
+    pub book_matches: Vec<String>,
+    /// Pinned and recently read books, listed first while the search is empty
+    quick_books: Vec<String>,
+    pub selected_book_index: usize,
+    /// What was typed of a book that got selected before its name was finished, so the rest
+    /// of the name doesn't spill into the Chapter field
+    typed_book: Option<String>,
+    pub chapter_input: TextInput,
+    pub verse_input: TextInput,
+    pub verse_end_input: TextInput,
+    pub read_count_input: TextInput,
+    pub date_input: TextInput,
+    pub notes_input: TextInput,
+    pub tags_input: TextInput,
+    pub error_message: Option<String>,
+    pub input_focus: InputFocus,
+    pub show_confirmation: bool,
+    /// Whether the accepted formats of the focused field are shown
+    show_field_help: bool,
+    /// When the form was opened, for the session timer in the header
+    started: Instant,
+    /// Whether Esc is waiting for the user to confirm discarding what was entered
+    confirm_discard: bool,
+    /// Minutes spent in the form, while asking whether to store them when saving
+    pending_duration: Option<u32>,
+    /// Minutes to store as the session's reading time when the passages are recorded
+    session_minutes: Option<u32>,
+    /// A passage that was already read, waiting for the user to add it anyway, overwrite or
+    /// skip it
+    pending_overlap: Option<(AddedPassage, Overlap)>,
+    /// In Overwrite mode, what adding the typed passage would do, or why it is invalid, kept
+    /// up to date as the fields are edited
+    preview: Option<Result<String, String>>,
+    /// Passages to record when the session is saved
+    pub added: Vec<AddedPassage>,
+    /// Where to continue reading each book that isn't finished, offered in the Chapter field
+    next_unread: HashMap<String, u32>,
+    /// Each book's completion and when it was last read, shown beside the book matches
+    book_progress: HashMap<String, String>,
+    /// Whether the passages being added were read or listened to
+    source: ReadingSource,
+    /// Translations to cycle through, and the index of the one being used, if any
+    translations: Vec<String>,
+    translation: Option<usize>,
+    selected_added: usize,
+    /// Whether verses already read today count again when recorded
+    same_day_readings: SameDayReadings,
+}
+
+impl EntryWidget {
+    pub fn new(
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+        quick_books: Vec<String>,
+        write_mode: WriteMode,
+    ) -> Self {
+        let books = get_all_books(bible);
+        let next_unread = books
+            .iter()
+            .filter_map(|book| {
+                next_unread_chapter(bible, progress, book).map(|chapter| (book.clone(), chapter))
+            })
+            .collect();
+        let book_progress = books
+            .iter()
+            .map(|book| (book.clone(), book_progress_summary(bible, progress, book)))
+            .collect();
+        Self {
+            write_mode,
+            next_unread,
+            book_progress,
+            source: ReadingSource::Read,
+            translations: Vec::new(),
+            translation: None,
+            reference_input: TextInput::new(),
+            book_search: TextInput::new(),
+            typed_book: None,
+            book_matches: Self::compute_book_matches(bible, "", &quick_books),
+            quick_books,
+            selected_book_index: 0,
+            chapter_input: TextInput::new(),
+            verse_input: TextInput::new(),
+            verse_end_input: TextInput::new(),
+            read_count_input: TextInput::new(),
+            date_input: TextInput::new(),
+            notes_input: TextInput::new(),
+            tags_input: TextInput::new(),
+            error_message: None,
+            input_focus: InputFocus::Reference,
+            show_confirmation: false,
+            show_field_help: false,
+            started: Instant::now(),
+            confirm_discard: false,
+            pending_duration: None,
+            session_minutes: None,
+            pending_overlap: None,
+            preview: None,
+            added: Vec::new(),
+            selected_added: 0,
+            same_day_readings: progress.same_day_readings,
+        }
+    }
+
+    /// Start with the configured source and translations (the first being the default)
+    pub fn with_reading_defaults(
+        mut self,
+        source: ReadingSource,
+        translations: Vec<String>,
+    ) -> Self {
+        self.source = source;
+        self.translation = (!translations.is_empty()).then_some(0);
+        self.translations = translations;
+        self
+    }
+
+    /// Name of the form in its current mode, for the header and help overlay
+    pub fn title(&self) -> &'static str {
+        match self.write_mode {
+            WriteMode::Accumulate => "Record",
+            WriteMode::Overwrite => "Manual Add",
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let overwrite = self.write_mode == WriteMode::Overwrite;
+        // On short terminals the matches list shrinks, then the help, footer and header are
+        // hidden, and finally the form scrolls to the focused field
+        let focused_section = match self.input_focus {
+            InputFocus::Reference => 1,
+            InputFocus::Book => 2,
+            InputFocus::Chapter => 4,
+            InputFocus::Verse | InputFocus::VerseEnd => 5,
+            InputFocus::ReadCount | InputFocus::Date => 6,
+            InputFocus::Notes | InputFocus::Tags => 7,
+            InputFocus::Added => 8,
+        };
+        let message = if self.error_message.is_some()
+            || self.preview.is_some()
+            || self.input_focus == InputFocus::Reference
+            || !self.added.is_empty()
+        {
+            Section::fill(3)
+        } else {
+            Section::fill(3).optional(0)
+        };
+        let chunks = responsive_stack(
+            area,
+            &[
+                Section::fixed(3).optional(2), // Header
+                Section::fixed(3),             // Passage
+                Section::fixed(3),             // Book search
+                Section::shrinkable(8, 3),     // Book matches list
+                Section::fixed(3),             // Chapter input
+                Section::fixed(3),             // Verse input(s)
+                // Read count and date, only in Overwrite mode
+                Section::fixed(if overwrite { 3 } else { 0 }),
+                Section::fixed(3),             // Notes and tags
+                message,                       // Error / preview / help
+                Section::fixed(3).optional(1), // Footer
+            ],
+            focused_section,
+        );
+
+        // Header, with the mode toggle
+        let color = if overwrite {
+            Color::Magenta
+        } else {
+            Color::Green
+        };
+        let mode_style = |active: bool| {
+            if active {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            }
+        };
+        let header = Paragraph::new(Line::from(vec![
+            Span::styled(
+                if overwrite {
+                    "Manual Add"
+                } else {
+                    "Record Reading"
+                },
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("   "),
+            Span::styled(" Add ", mode_style(!overwrite)),
+            Span::raw(" "),
+            Span::styled(" Overwrite ", mode_style(overwrite)),
+            Span::styled(" (Ctrl+O: switch)", Style::default().fg(Color::Gray)),
+            Span::raw("   "),
+            Span::styled(
+                match self.translation() {
+                    Some(translation) => format!("{} · {}", self.source.name(), translation),
+                    None => self.source.name().to_string(),
+                },
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" (Ctrl+L/Ctrl+T)", Style::default().fg(Color::Gray)),
+            Span::raw("   "),
+            Span::styled(
+                format_elapsed(self.started.elapsed()),
+                Style::default().fg(Color::Cyan),
+            ),
+        ]))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        );
+        frame.render_widget(header, chunks[0]);
+
+        // Passage field
+        let reference_focused = self.input_focus == InputFocus::Reference;
+        frame.render_widget(
+            form::field(
+                "Passage (e.g., John 3:16-4:2), or Tab to use the fields below",
+                &self.reference_input,
+                reference_focused,
+            ),
+            chunks[1],
+        );
+        if reference_focused && !self.popup_open() {
+            self.reference_input.set_cursor(frame, chunks[1]);
+        }
+
+        // Book search field
+        let book_focused = self.input_focus == InputFocus::Book;
+        frame.render_widget(
+            form::field("Book", &self.book_search, book_focused),
+            chunks[2],
+        );
+        if book_focused && !self.popup_open() {
+            self.book_search.set_cursor(frame, chunks[2]);
+        }
+
+        // Book matches list
+        if !self.book_matches.is_empty() {
+            let items: Vec<ListItem> = self
+                .book_matches
+                .iter()
+                .enumerate()
+                .map(|(idx, book)| {
+                    let style = if idx == self.selected_book_index {
+                        Style::default()
+                            .fg(Color::Black)
+                            .bg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    let summary = self
+                        .book_progress
+                        .get(book)
+                        .map(|summary| format!(" · {}", summary))
+                        .unwrap_or_default();
+                    ListItem::new(Line::from(vec![
+                        Span::raw(book.as_str()),
+                        Span::styled(
+                            summary,
+                            if idx == self.selected_book_index {
+                                Style::default()
+                            } else {
+                                Style::default().fg(Color::Gray)
+                            },
+                        ),
+                    ]))
+                    .style(style)
+                })
+                .collect();
+            let list = List::new(items).block(Block::default().borders(Borders::ALL).title(
+                if self.book_search.is_empty() && !self.quick_books.is_empty() {
+                    "Matches (↑↓: select; pinned and recently read books first)"
+                } else {
+                    "Matches (↑↓: select)"
+                },
+            ));
+            // Stateful rendering keeps the selected match scrolled into view
+            let mut list_state = ListState::default().with_selected(Some(self.selected_book_index));
+            frame.render_stateful_widget(list, chunks[3], &mut list_state);
+        } else {
+            let empty = Paragraph::new("No matches")
+                .style(Style::default().fg(Color::Gray))
+                .block(Block::default().borders(Borders::ALL).title("Matches"));
+            frame.render_widget(empty, chunks[3]);
+        }
+
+        // Chapter input field, flagged as soon as a chapter past the end of the book is typed
+        let chapter_title = match (
+            self.chapter_warning(crate::bible_structure::get_bible_structure()),
+            self.suggested_chapter(),
+        ) {
+            (Some(warning), _) => Line::styled(
+                format!("Chapter: {}", warning),
+                Style::default().fg(Color::Red),
+            ),
+            (None, Some(chapter)) => Line::raw(format!(
+                "Chapter (e.g., 1, 1-5, or empty for entire book; n: next unread, {})",
+                chapter
+            )),
+            (None, None) => Line::raw("Chapter (e.g., 1, 1-5, or leave empty for entire book)"),
+        };
+        let chapter_focused = self.input_focus == InputFocus::Chapter;
+        frame.render_widget(
+            form::field(chapter_title, &self.chapter_input, chapter_focused),
+            chunks[4],
+        );
+        if chapter_focused && !self.popup_open() {
+            self.chapter_input.set_cursor(frame, chunks[4]);
+        }
+
+        // Verse input field(s) - show two columns if chapter range is detected
+        let has_chapter_range = self.chapter_input.contains('-');
+        let verse_focused = self.input_focus == InputFocus::Verse;
+        if has_chapter_range {
+            let verse_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(chunks[5]);
+
+            // Start chapter verse input
+            frame.render_widget(
+                form::field(
+                    "Start Chapter Verses (e.g., 1, 1-5, or leave empty)",
+                    &self.verse_input,
+                    verse_focused,
+                ),
+                verse_chunks[0],
+            );
+            if verse_focused && !self.popup_open() {
+                self.verse_input.set_cursor(frame, verse_chunks[0]);
+            }
+
+            // End chapter verse input
+            let verse_end_focused = self.input_focus == InputFocus::VerseEnd;
+            frame.render_widget(
+                form::field(
+                    "End Chapter Verses (e.g., 1, 1-5, or leave empty)",
+                    &self.verse_end_input,
+                    verse_end_focused,
+                ),
+                verse_chunks[1],
+            );
+            if verse_end_focused && !self.popup_open() {
+                self.verse_end_input.set_cursor(frame, verse_chunks[1]);
+            }
+        } else {
+            // Single verse input field
+            frame.render_widget(
+                form::field(
+                    "Verse (e.g., 1, 1-5, or leave empty for full chapter)",
+                    &self.verse_input,
+                    verse_focused,
+                ),
+                chunks[5],
+            );
+            if verse_focused && !self.popup_open() {
+                self.verse_input.set_cursor(frame, chunks[5]);
+            }
+        }
+
+        // Read count and date fields, side by side
+        if overwrite {
+            let [read_count_area, date_area] =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .areas(chunks[6]);
+            let read_count_focused = self.input_focus == InputFocus::ReadCount;
+            frame.render_widget(
+                form::field(
+                    "Read Count (e.g., 1, 5, or leave empty for 1)",
+                    &self.read_count_input,
+                    read_count_focused,
+                ),
+                read_count_area,
+            );
+            if read_count_focused && !self.popup_open() {
+                self.read_count_input.set_cursor(frame, read_count_area);
+            }
+            let date_focused = self.input_focus == InputFocus::Date;
+            frame.render_widget(
+                form::field(
+                    "Date (YYYY-MM-DD, or leave empty for today)",
+                    &self.date_input,
+                    date_focused,
+                ),
+                date_area,
+            );
+            if date_focused && !self.popup_open() {
+                self.date_input.set_cursor(frame, date_area);
+            }
+        }
+
+        // Notes and tags fields, side by side
+        let [notes_area, tags_area] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(chunks[7]);
+        let notes_focused = self.input_focus == InputFocus::Notes;
+        frame.render_widget(
+            form::field("Notes (optional)", &self.notes_input, notes_focused),
+            notes_area,
+        );
+        if notes_focused && !self.popup_open() {
+            self.notes_input.set_cursor(frame, notes_area);
+        }
+        let tags_focused = self.input_focus == InputFocus::Tags;
+        frame.render_widget(
+            form::field(
+                "Tags (e.g., morning, memory)",
+                &self.tags_input,
+                tags_focused,
+            ),
+            tags_area,
+        );
+        if tags_focused && !self.popup_open() {
+            self.tags_input.set_cursor(frame, tags_area);
+        }
+
+        // Added passages, beside the message area once there are any
+        let message_area = if self.added.is_empty() {
+            chunks[8]
+        } else {
+            let [message_area, added_area] =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .areas(chunks[8]);
+            self.render_added(frame, added_area);
+            message_area
+        };
+
+        // Error message, preview or help
+        let help_text = if self.chapter_input.trim().is_empty() {
+            "Leave chapter empty to mark entire book as read (confirmation required)"
+        } else if has_chapter_range {
+            "Chapter range detected: Enter verses for start and end chapters. Middle chapters will be fully read."
+        } else {
+            "Enter a verse number (e.g., 1), a range (e.g., 1-5), or leave empty for the full chapter"
+        };
+        let help_line = Line::styled(help_text, Style::default().fg(Color::Gray));
+        let message = match (&self.error_message, &self.preview) {
+            (Some(error), _) => form::message(Severity::Error, "Error", error.as_str()),
+            (None, Some(Err(invalid))) => form::message(
+                Severity::Warning,
+                "Invalid",
+                vec![Line::raw(invalid.as_str()), help_line],
+            ),
+            (None, Some(Ok(preview))) => form::message(
+                Severity::Info,
+                "Preview",
+                vec![Line::raw(preview.as_str()), help_line],
+            ),
+            (None, None) if reference_focused => self.passage_preview(),
+            (None, None) => form::message(Severity::Help, "Help", help_text),
+        };
+        frame.render_widget(message, message_area);
+
+        // Footer
+        let footer = Paragraph::new(
+            "Tab: Next field | Enter: Add passage | Ctrl+S: Save | Ctrl+O: Add/Overwrite | F1: Formats | Esc: Cancel",
+        )
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(footer, chunks[9]);
+
+        // Show confirmation popup if needed
+        if self.show_confirmation {
+            let popup_area = Self::centered_rect(60, 25, frame.area());
+            frame.render_widget(Clear, popup_area);
+            frame.render_widget(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title("Confirm"),
+                popup_area,
+            );
+
+            let popup_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3),
+                    Constraint::Length(3),
+                    Constraint::Length(3),
+                ])
+                .margin(1)
+                .split(popup_area);
+
+            let book = match self.typed_passage(crate::bible_structure::get_bible_structure()) {
+                Ok(passage) => format!("all {} chapters of {}", passage.ranges.len(), passage.book),
+                Err(_) => "the entire book".to_string(),
+            };
+            let message = Paragraph::new(if overwrite {
+                format!(
+                    "Are you sure you want to mark {} as read? (This will overwrite overlapping ranges)",
+                    book
+                )
+            } else {
+                format!("Are you sure you want to mark {} as read?", book)
+            })
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+            frame.render_widget(message, popup_chunks[0]);
+
+            let instruction = Paragraph::new("Press Enter to confirm, Esc to cancel")
+                .style(Style::default().fg(Color::Gray))
+                .alignment(Alignment::Center);
+            frame.render_widget(instruction, popup_chunks[1]);
+        }
+
+        if self.show_field_help {
+            let (field, formats) = self.field_help();
+            render_help(frame, field, formats);
+        }
+
+        if self.confirm_discard {
+            let popup_area = Self::centered_rect(60, 25, frame.area());
+            frame.render_widget(Clear, popup_area);
+            let message = match self.added.len() {
+                0 => "Discard what you've typed?".to_string(),
+                1 => "Discard the added passage and what you've typed?".to_string(),
+                n => format!("Discard the {} added passages and what you've typed?", n),
+            };
+            let text = vec![
+                Line::styled(message, Style::default().fg(Color::Yellow)),
+                Line::raw(""),
+                Line::raw("d: Discard and return to the dashboard"),
+                Line::styled("Esc/k: Keep editing", Style::default().fg(Color::Gray)),
+            ];
+            let prompt = Paragraph::new(text).wrap(Wrap { trim: true }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title("Unsaved input"),
+            );
+            frame.render_widget(prompt, popup_area);
+        }
+
+        if let Some(minutes) = self.pending_duration {
+            let popup_area = Self::centered_rect(60, 25, frame.area());
+            frame.render_widget(Clear, popup_area);
+            let text = vec![
+                Line::styled(
+                    format!(
+                        "You've been recording for {}. Store it as this session's reading time?",
+                        format_minutes(minutes)
+                    ),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Line::raw(""),
+                Line::raw("Enter/y: Store it and save"),
+                Line::raw("n: Save without it"),
+                Line::styled("Esc: Back to editing", Style::default().fg(Color::Gray)),
+            ];
+            let prompt = Paragraph::new(text).wrap(Wrap { trim: true }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title("Session time"),
+            );
+            frame.render_widget(prompt, popup_area);
+        }
+
+        if let Some((passage, overlap)) = &self.pending_overlap {
+            let popup_area = Self::centered_rect(60, 30, frame.area());
+            frame.render_widget(Clear, popup_area);
+            let message = match overlap {
+                Overlap::ReadToday => format!("{} was already recorded today.", passage.label),
+                Overlap::AlreadyRead => format!("You have already read all of {}.", passage.label),
+            };
+            let add = match (overlap, self.same_day_readings) {
+                (Overlap::ReadToday, SameDayReadings::Once) => vec![
+                    Line::raw("Enter: Add it, counting only the verses not read today"),
+                    Line::raw("a: Count all of it again anyway"),
+                ],
+                _ => vec![Line::raw("Enter/a: Add anyway (count it again)")],
+            };
+            let mut text = vec![
+                Line::styled(message, Style::default().fg(Color::Yellow)),
+                Line::raw(""),
+            ];
+            text.extend(add);
+            text.extend([
+                Line::raw("o: Overwrite (set it to read once, today)"),
+                Line::raw("s: Skip it"),
+                Line::styled("Esc: Back to editing", Style::default().fg(Color::Gray)),
+            ]);
+            let warning = Paragraph::new(text).wrap(Wrap { trim: true }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title("Already read"),
+            );
+            frame.render_widget(warning, popup_area);
+        }
+    }
+
+    /// True while a popup is waiting for an answer
+    fn popup_open(&self) -> bool {
+        self.show_confirmation
+            || self.show_field_help
+            || self.pending_overlap.is_some()
+            || self.pending_duration.is_some()
+            || self.confirm_discard
+    }
+
+    /// Name of the focused field and the formats it accepts, for the F1 overlay
+    fn field_help(&self) -> (&'static str, &'static [KeyBinding]) {
+        match self.input_focus {
+            InputFocus::Reference => ("Passage field", PASSAGE_FORMATS),
+            InputFocus::Book => ("Book field", BOOK_FORMATS),
+            InputFocus::Chapter => ("Chapter field", CHAPTER_FORMATS),
+            InputFocus::Verse => ("Verse field", VERSE_FORMATS),
+            InputFocus::VerseEnd => ("End verse field", VERSE_END_FORMATS),
+            InputFocus::ReadCount => ("Read Count field", READ_COUNT_FORMATS),
+            InputFocus::Date => ("Date field", DATE_FORMATS),
+            InputFocus::Notes => ("Notes field", NOTES_FORMATS),
+            InputFocus::Tags => ("Tags field", TAGS_FORMATS),
+            InputFocus::Added => ("Added passages", ADDED_FORMATS),
+        }
+    }
+
+    fn render_added(&self, frame: &mut Frame, area: Rect) {
+        let focused = self.input_focus == InputFocus::Added;
+        let items: Vec<ListItem> = self
+            .added
+            .iter()
+            .map(|passage| ListItem::new(passage.label.clone()))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(if focused {
+                        "Added passages (d: remove, Enter: save)"
+                    } else {
+                        "Added passages (Ctrl+S: save)"
+                    })
+                    .border_style(if focused {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    }),
+            )
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let mut state = ListState::default().with_selected(focused.then_some(self.selected_added));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// What the Passage field will record, one line per chapter, or why it can't be parsed
+    fn passage_preview(&self) -> Paragraph<'static> {
+        const TITLE: &str = "Will record";
+        if self.reference_input.trim().is_empty() {
+            return form::message(
+                Severity::Help,
+                TITLE,
+                "Type a passage such as \"ps 23\", \"John 3:16-18\" or \"John 3:16-4:2\"; a book \
+                 on its own records the whole book. Add e.g. \"2x\" if you read it twice, or type \
+                 e.g. \"35 min from Mark 4\" for what an audio Bible covered.",
+            );
+        }
+
+        let bible = crate::bible_structure::get_bible_structure();
+        match parse_listening(bible, &self.reference_input) {
+            Some(Ok(listening)) => {
+                let ranges = listening.ranges(bible);
+                let verses: u32 = ranges
+                    .iter()
+                    .map(|(_, _, start, end)| end - start + 1)
+                    .sum();
+                let mut lines = vec![Line::raw(format!(
+                    "About {} verses listened to in {} min",
+                    verses, listening.minutes
+                ))];
+                lines.extend(ranges.iter().map(|(book, chapter, start, end)| {
+                    Line::styled(
+                        format!("  {} {}:{}-{}", book, chapter, start, end),
+                        Style::default().fg(Color::Reset),
+                    )
+                }));
+                return form::message(Severity::Info, TITLE, lines);
+            }
+            Some(Err(e)) => return form::message(Severity::Warning, TITLE, e),
+            None => {}
+        }
+        let parsed = split_times(&self.reference_input).and_then(|(reference, times)| {
+            parse_passage(bible, reference).map(|passage| (passage, times))
+        });
+        match parsed {
+            Ok((passage, times)) => {
+                let ranges = passage.chapter_ranges(bible);
+                let verses: u32 = ranges.iter().map(|(_, start, end)| end - start + 1).sum();
+                let mut lines = vec![Line::raw(format!(
+                    "{} ({} verse{}{})",
+                    passage,
+                    verses,
+                    if verses == 1 { "" } else { "s" },
+                    if times > 1 {
+                        format!(", read {}×", times)
+                    } else {
+                        String::new()
+                    }
+                ))];
+                lines.extend(ranges.iter().map(|(chapter, start, end)| {
+                    Line::styled(
+                        format!("  {} {}:{}-{}", passage.book, chapter, start, end),
+                        Style::default().fg(Color::Reset),
+                    )
+                }));
+                form::message(Severity::Info, TITLE, lines)
+            }
+            Err(e) => form::message(Severity::Warning, TITLE, e),
+        }
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+        let popup_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(r);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(popup_layout[1])[1]
+    }
+
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+    ) -> Result<EntryAction> {
+        let action = self.handle_form_key(key, bible, progress);
+        self.refresh_preview(bible, progress);
+        action
+    }
+
+    fn handle_form_key(
+        &mut self,
+        key: KeyEvent,
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+    ) -> Result<EntryAction> {
+        if self.show_field_help {
+            // Any key closes the field help
+            self.show_field_help = false;
+            return Ok(EntryAction::None);
+        }
+        if self.confirm_discard {
+            return Ok(match key.code {
+                KeyCode::Char('d') => EntryAction::Cancel,
+                KeyCode::Esc | KeyCode::Char('k') | KeyCode::Enter => {
+                    self.confirm_discard = false;
+                    EntryAction::None
+                }
+                _ => EntryAction::None,
+            });
+        }
+        if self.pending_duration.is_some() {
+            let minutes = self.pending_duration.take();
+            return Ok(match key.code {
+                KeyCode::Enter | KeyCode::Char('y') => {
+                    self.session_minutes = minutes;
+                    EntryAction::AddReading
+                }
+                KeyCode::Char('n') => EntryAction::AddReading,
+                KeyCode::Esc => EntryAction::None,
+                _ => {
+                    self.pending_duration = minutes;
+                    EntryAction::None
+                }
+            });
+        }
+        if key.code == KeyCode::F(1) {
+            self.show_field_help = true;
+            return Ok(EntryAction::None);
+        }
+        if let Some((passage, overlap)) = &mut self.pending_overlap {
+            match key.code {
+                KeyCode::Char('a')
+                    if *overlap == Overlap::ReadToday
+                        && self.same_day_readings == SameDayReadings::Once =>
+                {
+                    passage.count_again = true;
+                    passage.label.push_str(" (counted again)");
+                    let (passage, _) = self.pending_overlap.take().unwrap();
+                    self.push_passage(passage);
+                }
+                KeyCode::Enter | KeyCode::Char('a') => {
+                    let (passage, _) = self.pending_overlap.take().unwrap();
+                    self.push_passage(passage);
+                }
+                KeyCode::Char('o') => {
+                    passage.set_overwrite(Overwrite {
+                        read_count: 1,
+                        last_read: None,
+                    });
+                    let (passage, _) = self.pending_overlap.take().unwrap();
+                    self.push_passage(passage);
+                }
+                KeyCode::Char('s') => {
+                    self.pending_overlap = None;
+                    self.clear_inputs();
+                }
+                KeyCode::Esc => self.pending_overlap = None,
+                _ => {}
+            }
+            Ok(EntryAction::None)
+        } else if self.show_confirmation {
+            // Handle confirmation popup
+            match key.code {
+                KeyCode::Enter => {
+                    self.show_confirmation = false;
+                    // Proceed with adding the entire book
+                    self.add_passage(bible, progress);
+                    Ok(EntryAction::None)
+                }
+                KeyCode::Esc => {
+                    self.show_confirmation = false;
+                    Ok(EntryAction::None)
+                }
+                _ => Ok(EntryAction::None),
+            }
+        } else {
+            let typed_book = self.typed_book.take();
+            let has_chapter_range = self.chapter_input.contains('-');
+            let overwrite = self.write_mode == WriteMode::Overwrite;
+            // The last field of the verse fields, and the field after them
+            let last_verse_field = if has_chapter_range {
+                InputFocus::VerseEnd
+            } else {
+                InputFocus::Verse
+            };
+            let after_verses = if overwrite {
+                InputFocus::ReadCount
+            } else {
+                InputFocus::Notes
+            };
+            match (key.modifiers, key.code) {
+                (_, KeyCode::Esc) if self.has_unsaved_input() => {
+                    self.confirm_discard = true;
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Esc) => Ok(EntryAction::Cancel),
+                (KeyModifiers::CONTROL, KeyCode::Char('s')) => {
+                    // Whatever is still typed in is added before saving
+                    if self.has_input() && !self.add_passage(bible, progress) {
+                        return Ok(EntryAction::None);
+                    }
+                    if self.added.is_empty() {
+                        self.error_message = Some("Add a passage first".to_string());
+                        Ok(EntryAction::None)
+                    } else {
+                        Ok(self.save())
+                    }
+                }
+                (KeyModifiers::CONTROL, KeyCode::Char('l')) => {
+                    self.source = self.source.toggled();
+                    Ok(EntryAction::None)
+                }
+                (KeyModifiers::CONTROL, KeyCode::Char('t')) => {
+                    // Cycle through the translations, then none
+                    self.translation = match self.translation {
+                        None if !self.translations.is_empty() => Some(0),
+                        Some(i) if i + 1 < self.translations.len() => Some(i + 1),
+                        _ => None,
+                    };
+                    Ok(EntryAction::None)
+                }
+                (KeyModifiers::CONTROL, KeyCode::Char('o')) => {
+                    self.write_mode = if overwrite {
+                        WriteMode::Accumulate
+                    } else {
+                        WriteMode::Overwrite
+                    };
+                    // The advanced fields disappear in Accumulate mode
+                    if matches!(self.input_focus, InputFocus::ReadCount | InputFocus::Date) {
+                        self.input_focus = if self.reference_input.trim().is_empty() {
+                            last_verse_field
+                        } else {
+                            InputFocus::Reference
+                        };
+                    }
+                    self.error_message = None;
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Up) if self.input_focus == InputFocus::Added => {
+                    self.selected_added = self.selected_added.saturating_sub(1);
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Down) if self.input_focus == InputFocus::Added => {
+                    self.selected_added =
+                        (self.selected_added + 1).min(self.added.len().saturating_sub(1));
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Char('d') | KeyCode::Delete)
+                    if self.input_focus == InputFocus::Added =>
+                {
+                    if self.selected_added < self.added.len() {
+                        self.added.remove(self.selected_added);
+                    }
+                    self.selected_added =
+                        self.selected_added.min(self.added.len().saturating_sub(1));
+                    if self.added.is_empty() {
+                        self.input_focus = InputFocus::Reference;
+                    }
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Enter) if self.input_focus == InputFocus::Added => Ok(self.save()),
+                (_, KeyCode::Tab) => {
+                    // Navigate forward through input fields. The added passages list is only
+                    // part of the cycle when it has entries
+                    let after_fields = if self.added.is_empty() {
+                        InputFocus::Reference
+                    } else {
+                        InputFocus::Added
+                    };
+                    self.input_focus = match self.input_focus {
+                        // A typed passage skips straight to the fields after the verses
+                        InputFocus::Reference if !self.reference_input.trim().is_empty() => {
+                            after_verses
+                        }
+                        InputFocus::Reference => InputFocus::Book,
+                        InputFocus::Book => InputFocus::Chapter,
+                        InputFocus::Chapter => InputFocus::Verse,
+                        InputFocus::Verse if has_chapter_range => InputFocus::VerseEnd,
+                        InputFocus::Verse | InputFocus::VerseEnd => after_verses,
+                        InputFocus::ReadCount => InputFocus::Date,
+                        InputFocus::Date => InputFocus::Notes,
+                        InputFocus::Notes => InputFocus::Tags,
+                        InputFocus::Tags => after_fields,
+                        InputFocus::Added => InputFocus::Reference,
+                    };
+                    self.error_message = None;
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::BackTab) => {
+                    // Navigate backward through input fields
+                    let before_notes = if overwrite {
+                        InputFocus::Date
+                    } else if !self.reference_input.trim().is_empty() {
+                        InputFocus::Reference
+                    } else {
+                        last_verse_field
+                    };
+                    self.input_focus = match self.input_focus {
+                        InputFocus::Reference if !self.added.is_empty() => InputFocus::Added,
+                        InputFocus::Reference | InputFocus::Added => InputFocus::Tags,
+                        InputFocus::Book => InputFocus::Reference,
+                        InputFocus::Chapter => InputFocus::Book,
+                        InputFocus::Verse => InputFocus::Chapter,
+                        InputFocus::VerseEnd => InputFocus::Verse,
+                        InputFocus::ReadCount if !self.reference_input.trim().is_empty() => {
+                            InputFocus::Reference
+                        }
+                        InputFocus::ReadCount => last_verse_field,
+                        InputFocus::Date => InputFocus::ReadCount,
+                        InputFocus::Notes => before_notes,
+                        InputFocus::Tags => InputFocus::Notes,
+                    };
+                    self.error_message = None;
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Up) if self.input_focus == InputFocus::Book => {
+                    if self.selected_book_index > 0 {
+                        self.selected_book_index -= 1;
+                    }
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Down) if self.input_focus == InputFocus::Book => {
+                    if self.selected_book_index < self.book_matches.len().saturating_sub(1) {
+                        self.selected_book_index += 1;
+                    }
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::PageUp) if self.input_focus == InputFocus::Book => {
+                    self.selected_book_index = self
+                        .selected_book_index
+                        .saturating_sub(BOOK_MATCHES_PAGE_SIZE);
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::PageDown) if self.input_focus == InputFocus::Book => {
+                    self.selected_book_index = (self.selected_book_index + BOOK_MATCHES_PAGE_SIZE)
+                        .min(self.book_matches.len().saturating_sub(1));
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Home) if self.input_focus == InputFocus::Book => {
+                    self.selected_book_index = 0;
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::End) if self.input_focus == InputFocus::Book => {
+                    self.selected_book_index = self.book_matches.len().saturating_sub(1);
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Up) => {
+                    self.step(1, bible);
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Down) => {
+                    self.step(-1, bible);
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Enter) => {
+                    match self.input_focus {
+                        InputFocus::Reference => {
+                            // Enter on an empty field saves the passages added so far
+                            if self.reference_input.trim().is_empty() && !self.added.is_empty() {
+                                return Ok(self.save());
+                            }
+                            self.submit(bible, progress);
+                        }
+                        InputFocus::Book => self.select_book(bible),
+                        InputFocus::Chapter => self.input_focus = InputFocus::Verse,
+                        InputFocus::Verse if has_chapter_range => {
+                            self.input_focus = InputFocus::VerseEnd
+                        }
+                        InputFocus::Verse | InputFocus::VerseEnd if overwrite => {
+                            self.input_focus = InputFocus::ReadCount
+                        }
+                        InputFocus::ReadCount => self.input_focus = InputFocus::Date,
+                        InputFocus::Verse
+                        | InputFocus::VerseEnd
+                        | InputFocus::Date
+                        | InputFocus::Notes
+                        | InputFocus::Tags => self.submit(bible, progress),
+                        InputFocus::Added => {}
+                    }
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Char('.'))
+                    if self.input_focus == InputFocus::Reference
+                        && self.reference_input.is_empty() =>
+                {
+                    match last_passage(bible, progress)
+                        .map(|passage| self.apply_write_mode(passage))
+                    {
+                        Some(Ok(passage)) => {
+                            self.offer_passage(passage, progress);
+                        }
+                        Some(Err(e)) => self.error_message = Some(e),
+                        None => self.error_message = Some("Nothing recorded yet".to_string()),
+                    }
+                    Ok(EntryAction::None)
+                }
+                (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c))
+                    if self.input_focus == InputFocus::Chapter
+                        && self.chapter_input.is_empty()
+                        && typed_book.as_ref().is_some_and(|typed| {
+                            is_book_prefix(bible, &self.book_search, &format!("{}{}", typed, c))
+                        }) =>
+                {
+                    // Still finishing the name of the book that was already selected
+                    self.typed_book = typed_book.map(|typed| format!("{}{}", typed, c));
+                    Ok(EntryAction::None)
+                }
+                (_, KeyCode::Char('n')) if self.input_focus == InputFocus::Chapter => {
+                    if let Some(chapter) = self.suggested_chapter() {
+                        self.chapter_input.set(chapter.to_string());
+                        self.error_message = None;
+                    }
+                    Ok(EntryAction::None)
+                }
+                _ => {
+                    let edited = match self.input_focus {
+                        // Passages may use an en dash, so this field takes any printable character
+                        InputFocus::Reference => {
+                            self.reference_input.handle_key(key, |c| !c.is_control())
+                        }
+                        InputFocus::Book => self.book_search.handle_key(key, |c| c.is_ascii()),
+                        InputFocus::Chapter => self
+                            .chapter_input
+                            .handle_key(key, |c| c.is_ascii_digit() || c == '-'),
+                        InputFocus::Verse => self.verse_input.handle_key(key, is_verse_char),
+                        InputFocus::VerseEnd => self.verse_end_input.handle_key(key, is_verse_char),
+                        InputFocus::ReadCount => self
+                            .read_count_input
+                            .handle_key(key, |c| c.is_ascii_digit()),
+                        InputFocus::Date => self
+                            .date_input
+                            .handle_key(key, |c| c.is_ascii_digit() || c == '-'),
+                        InputFocus::Notes => self.notes_input.handle_key(key, |c| !c.is_control()),
+                        InputFocus::Tags => self.tags_input.handle_key(key, |c| !c.is_control()),
+                        InputFocus::Added => false,
+                    };
+                    if edited {
+                        if self.input_focus == InputFocus::Book {
+                            if matches!(key.code, KeyCode::Char(_)) {
+                                self.selected_book_index = 0;
+                            }
+                            self.book_matches = Self::compute_book_matches(
+                                bible,
+                                &self.book_search,
+                                &self.quick_books,
+                            );
+                            self.selected_book_index = self
+                                .selected_book_index
+                                .min(self.book_matches.len().saturating_sub(1));
+                            // A search that can only mean one book selects it right away
+                            if matches!(key.code, KeyCode::Char(_)) {
+                                let only_match = unambiguous_book(bible, &self.book_search)
+                                    .and_then(|book| {
+                                        self.book_matches.iter().position(|m| *m == book)
+                                    });
+                                if let Some(index) = only_match {
+                                    self.typed_book = Some(self.book_search.to_string());
+                                    self.selected_book_index = index;
+                                    self.select_book(bible);
+                                }
+                            }
+                        }
+                        self.error_message = None;
+                    }
+                    Ok(EntryAction::None)
+                }
+            }
+        }
+    }
+
+    /// Step the number at the end of the focused numeric field by `delta`, within the chapters
+    /// of the selected book or the verses of the chapter, or the date by `delta` days, up to
+    /// today
+    fn step(&mut self, delta: i64, bible: &'static crate::bible_structure::BibleStructure) {
+        let chapters = self
+            .book_matches
+            .get(self.selected_book_index)
+            .and_then(|book| bible.ot.get(book).or_else(|| bible.nt.get(book)));
+        // Verses of the chapter starting (or ending) the chapter range
+        let verses_of = |index: usize| {
+            let chapter: usize = self
+                .chapter_input
+                .split('-')
+                .nth(index)?
+                .trim()
+                .parse()
+                .ok()?;
+            chapters?.get(chapter.checked_sub(1)?).copied()
+        };
+        let stepped = match self.input_focus {
+            InputFocus::Chapter => chapters.and_then(|chapters| {
+                step_last_number(&self.chapter_input, delta, chapters.len() as u32)
+            }),
+            InputFocus::Verse => {
+                verses_of(0).and_then(|max| step_last_number(&self.verse_input, delta, max))
+            }
+            InputFocus::VerseEnd => {
+                verses_of(1).and_then(|max| step_last_number(&self.verse_end_input, delta, max))
+            }
+            InputFocus::ReadCount => {
+                // An empty read count means once
+                let count: i64 = match self.read_count_input.trim() {
+                    "" => 1,
+                    count => match count.parse() {
+                        Ok(count) => count,
+                        Err(_) => return,
+                    },
+                };
+                Some((count + delta).clamp(0, u32::MAX as i64).to_string())
+            }
+            InputFocus::Date => {
+                let today = Utc::now().date_naive();
+                let date = match self.date_input.trim() {
+                    // An empty date means today, so stepping down starts at yesterday
+                    "" => today - chrono::Duration::days(delta.max(0)),
+                    date => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                        Ok(date) => date,
+                        Err(_) => return,
+                    },
+                };
+                Some(
+                    (date + chrono::Duration::days(delta))
+                        .min(today)
+                        .format("%Y-%m-%d")
+                        .to_string(),
+                )
+            }
+            _ => None,
+        };
+        if let Some(stepped) = stepped {
+            let input = match self.input_focus {
+                InputFocus::Chapter => &mut self.chapter_input,
+                InputFocus::Verse => &mut self.verse_input,
+                InputFocus::VerseEnd => &mut self.verse_end_input,
+                InputFocus::ReadCount => &mut self.read_count_input,
+                _ => &mut self.date_input,
+            };
+            input.set(stepped);
+            self.error_message = None;
+        }
+    }
+
+    /// Fill the Book field with the selected match and move on to the Chapter field
+    fn select_book(&mut self, bible: &'static crate::bible_structure::BibleStructure) {
+        if self.book_matches.is_empty() {
+            return;
+        }
+        let selected_book = self.book_matches[self.selected_book_index].clone();
+        self.book_search.set(selected_book);
+        self.input_focus = InputFocus::Chapter;
+        self.book_matches = Self::compute_book_matches(bible, &self.book_search, &self.quick_books);
+        self.selected_book_index = self
+            .selected_book_index
+            .min(self.book_matches.len().saturating_sub(1));
+    }
+
+    /// Why the typed chapters don't fit the selected book, e.g. "Ruth has only 4 chapters"
+    fn chapter_warning(
+        &self,
+        bible: &'static crate::bible_structure::BibleStructure,
+    ) -> Option<String> {
+        let book = self.book_matches.get(self.selected_book_index)?;
+        let chapters = bible.ot.get(book).or_else(|| bible.nt.get(book))?.len() as u32;
+        let out_of_range = self
+            .chapter_input
+            .split('-')
+            .filter_map(|chapter| chapter.trim().parse::<u32>().ok())
+            .any(|chapter| chapter == 0 || chapter > chapters);
+        out_of_range.then(|| match chapters {
+            1 => format!("{} has only 1 chapter", book),
+            n => format!("{} has only {} chapters", book, n),
+        })
+    }
+
+    /// The next unread chapter of the selected book
+    fn suggested_chapter(&self) -> Option<u32> {
+        let book = self.book_matches.get(self.selected_book_index)?;
+        self.next_unread.get(book).copied()
+    }
+
+    /// True if the passage is taken from the Passage field rather than the book, chapter and
+    /// verse fields: while it has focus, or from the advanced fields when it has text
+    fn uses_reference(&self) -> bool {
+        match self.input_focus {
+            InputFocus::Reference => true,
+            InputFocus::ReadCount | InputFocus::Date | InputFocus::Notes | InputFocus::Tags => {
+                !self.reference_input.trim().is_empty()
+            }
+            _ => false,
+        }
+    }
+
+    /// True if any field has text or any passage was added, which Esc would discard
+    fn has_unsaved_input(&self) -> bool {
+        !self.added.is_empty()
+            || [
+                &self.reference_input,
+                &self.book_search,
+                &self.chapter_input,
+                &self.verse_input,
+                &self.verse_end_input,
+                &self.read_count_input,
+                &self.date_input,
+                &self.notes_input,
+                &self.tags_input,
+            ]
+            .iter()
+            .any(|input| !input.trim().is_empty())
+    }
+
+    /// True if the focused part of the form has anything typed into it
+    fn has_input(&self) -> bool {
+        match self.input_focus {
+            InputFocus::Added => false,
+            _ if self.uses_reference() => !self.reference_input.trim().is_empty(),
+            _ => !self.chapter_input.trim().is_empty(),
+        }
+    }
+
+    /// Add the typed passage, asking for confirmation first if it is a whole book
+    fn submit(
+        &mut self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+    ) {
+        if self.uses_reference() && parse_listening(bible, &self.reference_input).is_some() {
+            self.add_passage(bible, progress);
+        } else if self.uses_reference() {
+            match split_times(&self.reference_input)
+                .and_then(|(reference, _)| parse_passage(bible, reference))
+            {
+                Ok(passage) if passage.is_whole_book(bible) => self.show_confirmation = true,
+                Ok(_) => {
+                    self.add_passage(bible, progress);
+                }
+                Err(e) => self.error_message = Some(e),
+            }
+        } else if self.chapter_input.trim().is_empty() {
+            self.show_confirmation = true;
+        } else {
+            self.add_passage(bible, progress);
+        }
+    }
+
+    /// Add what is typed into the Passage field, or the book, chapter and verse fields, to the
+    /// added passages and clear those inputs. On failure the error is shown and false is
+    /// returned; false is also returned if the passage was already read, while the user is
+    /// asked what to do with it.
+    fn add_passage(
+        &mut self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+    ) -> bool {
+        if self.uses_reference() {
+            match parse_listening(bible, &self.reference_input) {
+                Some(Ok(listening)) => return self.add_listening(bible, &listening),
+                Some(Err(e)) => {
+                    self.error_message = Some(e);
+                    return false;
+                }
+                None => {}
+            }
+        }
+        match self
+            .typed_passage(bible)
+            .and_then(|passage| self.apply_write_mode(passage))
+        {
+            Ok(passage) => self.offer_passage(passage, progress),
+            Err(e) => {
+                self.error_message = Some(e);
+                false
+            }
+        }
+    }
+
+    /// Add the verses covered by a listening session, one passage per book, as listened to.
+    /// Listening often goes back over chapters, so they are added without asking about
+    /// overlaps.
+    fn add_listening(
+        &mut self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        listening: &Listening,
+    ) -> bool {
+        let mut by_book: Vec<AddedPassage> = Vec::new();
+        for (book, chapter, start, end) in listening.ranges(bible) {
+            match by_book.last_mut() {
+                Some(last) if last.book == book => last.ranges.push((chapter, start, end)),
+                _ => by_book.push(AddedPassage {
+                    label: String::new(),
+                    book,
+                    ranges: vec![(chapter, start, end)],
+                    times: 1,
+                    overwrite: None,
+                    note: None,
+                    tags: Vec::new(),
+                    source: ReadingSource::Listened,
+                    translation: None,
+                    count_again: false,
+                }),
+            }
+        }
+        let mut passages = Vec::new();
+        for mut passage in by_book {
+            let chapters = bible
+                .ot
+                .get(&passage.book)
+                .or_else(|| bible.nt.get(&passage.book))
+                .map_or(&[][..], |chapters| chapters.as_slice());
+            passage.label = AddedPassage::describe(&passage.book, &passage.ranges, chapters);
+            match self.apply_write_mode(passage) {
+                Ok(mut passage) => {
+                    if passage.source != ReadingSource::Listened {
+                        passage.source = ReadingSource::Listened;
+                        passage.label.push_str(" (listened)");
+                    }
+                    passages.push(passage);
+                }
+                Err(e) => {
+                    self.error_message = Some(e);
+                    return false;
+                }
+            }
+        }
+        for passage in passages {
+            self.push_passage(passage);
+        }
+        true
+    }
+
+    /// Set up `passage` to be recorded the way the current mode records passages
+    fn apply_write_mode(&self, mut passage: AddedPassage) -> Result<AddedPassage, String> {
+        match self.write_mode {
+            WriteMode::Overwrite => passage.set_overwrite(self.overwrite(passage.times)?),
+            WriteMode::Accumulate if passage.times > 1 => {
+                passage.label = format!("{} ({}×)", passage.label, passage.times)
+            }
+            WriteMode::Accumulate => {}
+        }
+        let note = self.notes_input.trim();
+        if !note.is_empty() {
+            passage.label.push_str(" ✎");
+            passage.note = Some(note.to_string());
+        }
+        passage.tags = parse_tags(&self.tags_input);
+        for tag in &passage.tags {
+            passage.label = format!("{} #{}", passage.label, tag);
+        }
+        passage.source = self.source;
+        if self.source == ReadingSource::Listened {
+            passage.label.push_str(" (listened)");
+        }
+        passage.translation = self.translation().map(str::to_string);
+        if let Some(translation) = &passage.translation {
+            passage.label = format!("{} [{}]", passage.label, translation);
+        }
+        Ok(passage)
+    }
+
+    /// The translation being used, if any
+    fn translation(&self) -> Option<&str> {
+        self.translation
+            .and_then(|i| self.translations.get(i))
+            .map(String::as_str)
+    }
+
+    /// Add `passage` to the list, unless it is being added to readings that already cover it,
+    /// in which case the user is asked first and false is returned
+    fn offer_passage(&mut self, passage: AddedPassage, progress: &ReadingProgress) -> bool {
+        if passage.overwrite.is_none() {
+            if let Some(overlap) = passage.overlap(progress, Utc::now().date_naive()) {
+                self.pending_overlap = Some((passage, overlap));
+                return false;
+            }
+        }
+        self.push_passage(passage);
+        true
+    }
+
+    fn push_passage(&mut self, passage: AddedPassage) {
+        self.added.push(passage);
+        self.selected_added = self.added.len() - 1;
+        self.error_message = None;
+        self.clear_inputs();
+    }
+
+    /// Clear the inputs a passage was typed into, along with its note and tags
+    fn clear_inputs(&mut self) {
+        self.notes_input.clear();
+        self.tags_input.clear();
+        if self.uses_reference() {
+            self.reference_input.clear();
+            self.input_focus = InputFocus::Reference;
+        } else {
+            // Keep the book, which the next passage is often from
+            self.chapter_input.clear();
+            self.verse_input.clear();
+            self.verse_end_input.clear();
+            self.input_focus = InputFocus::Chapter;
+        }
+    }
+
+    /// The passage typed into the Passage field, or the book, chapter and verse fields
+    fn typed_passage(
+        &self,
+        bible: &'static crate::bible_structure::BibleStructure,
+    ) -> Result<AddedPassage, String> {
+        if self.uses_reference() {
+            let (reference, times) = split_times(&self.reference_input)?;
+            parse_passage(bible, reference).map(|passage| AddedPassage {
+                label: passage.to_string(),
+                ranges: passage.chapter_ranges(bible),
+                book: passage.book,
+                times,
+                overwrite: None,
+                note: None,
+                tags: Vec::new(),
+                source: ReadingSource::Read,
+                translation: None,
+                count_again: false,
+            })
+        } else {
+            self.fields_passage(bible)
+        }
+    }
+
+    /// The read count and date from the advanced fields, with `default_count` used when the
+    /// read count is left empty
+    fn overwrite(&self, default_count: u32) -> Result<Overwrite, String> {
+        let read_count_str = self.read_count_input.trim();
+        let date_str = self.date_input.trim();
+        let read_count = if read_count_str.is_empty() {
+            default_count
+        } else {
+            read_count_str
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid read count: {}", read_count_str))?
+        };
+        let last_read = if date_str.is_empty() {
+            None
+        } else {
+            Some(
+                NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|_| {
+                    format!("Invalid date format: {}. Expected YYYY-MM-DD", date_str)
+                })?,
+            )
+        };
+        Ok(Overwrite {
+            read_count,
+            last_read,
+        })
+    }
+
+    /// In Overwrite mode, what adding the typed passage would do, e.g. "Will set Romans 3-5 to
+    /// 2×, last read 2024-11-02, replacing 3 existing ranges", or why the fields are invalid
+    fn overwrite_preview(
+        &self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+    ) -> Result<String, String> {
+        let passage = self.typed_passage(bible)?;
+        let overwrite = self.overwrite(passage.times)?;
+        let replaced: usize = passage
+            .ranges
+            .iter()
+            .map(|&(chapter, verse_start, verse_end)| {
+                progress
+                    .records_overlapping(&passage.book, chapter, verse_start, verse_end)
+                    .count()
+            })
+            .sum();
+        Ok(format!(
+            "Will set {} to {}×, last read {}{}",
+            passage.label,
+            overwrite.read_count,
+            overwrite
+                .last_read
+                .map_or_else(|| "today".to_string(), |date| date.to_string()),
+            match replaced {
+                0 => String::new(),
+                1 => ", replacing 1 existing range".to_string(),
+                n => format!(", replacing {} existing ranges", n),
+            }
+        ))
+    }
+
+    /// Recompute the Overwrite mode preview after the fields change. Nothing is previewed
+    /// until a passage, book or chapter has been entered.
+    fn refresh_preview(
+        &mut self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+    ) {
+        let typed = if self.uses_reference() {
+            !self.reference_input.trim().is_empty()
+        } else {
+            !self.book_search.is_empty() || !self.chapter_input.is_empty()
+        };
+        self.preview = (self.write_mode == WriteMode::Overwrite
+            && typed
+            && self.input_focus != InputFocus::Added)
+            .then(|| self.overwrite_preview(bible, progress));
+    }
+
+    /// The passage described by the book, chapter and verse fields
+    fn fields_passage(
+        &self,
+        bible: &'static crate::bible_structure::BibleStructure,
+    ) -> Result<AddedPassage, String> {
+        if self.book_matches.is_empty() {
+            return Err("Please select a book first".to_string());
+        }
+
+        let selected_book = self.book_matches[self.selected_book_index].clone();
+        let chapter_str = self.chapter_input.to_string();
+        let verse_str = self.verse_input.to_string();
+        let verse_end_str = self.verse_end_input.to_string();
+
+        // Get chapters for this book
+        let chapters = bible
+            .ot
+            .get(&selected_book)
+            .or_else(|| bible.nt.get(&selected_book))
+            .ok_or_else(|| format!("Book '{}' not found", selected_book))?;
+
+        // Handle empty chapter input (entire book)
+        if chapter_str.trim().is_empty() {
+            let ranges = chapters
+                .iter()
+                .enumerate()
+                .map(|(chapter_idx, &max_verse)| ((chapter_idx + 1) as u32, 1, max_verse))
+                .collect();
+            return Ok(AddedPassage {
+                label: selected_book.clone(),
+                book: selected_book,
+                ranges,
+                times: 1,
+                overwrite: None,
+                note: None,
+                tags: Vec::new(),
+                source: ReadingSource::Read,
+                translation: None,
+                count_again: false,
+            });
+        }
+
+        // Parse chapter(s) - handle ranges
+        let (chapter_start, chapter_end) = if chapter_str.contains('-') {
+            let parts: Vec<&str> = chapter_str.split('-').collect();
+            if parts.len() != 2 {
+                return Err(format!("Invalid chapter range format: {}", chapter_str));
+            }
+            let start = parts[0]
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid chapter number: {}", parts[0]))?;
+            let end = parts[1]
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid chapter number: {}", parts[1]))?;
+
+            if start == 0 || start > chapters.len() as u32 {
+                return Err(format!(
+                    "Start chapter {} doesn't exist (max: {})",
+                    start,
+                    chapters.len()
+                ));
+            }
+            if end == 0 || end > chapters.len() as u32 {
+                return Err(format!(
+                    "End chapter {} doesn't exist (max: {})",
+                    end,
+                    chapters.len()
+                ));
+            }
+            if start > end {
+                return Err(format!(
+                    "Start chapter ({}) must be <= end chapter ({})",
+                    start, end
+                ));
+            }
+
+            (start, end)
+        } else {
+            let chapter = chapter_str
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid chapter: {}", chapter_str))?;
+
+            if chapter == 0 || chapter > chapters.len() as u32 {
+                return Err(format!(
+                    "Chapter {} doesn't exist (max: {})",
+                    chapter,
+                    chapters.len()
+                ));
+            }
+
+            (chapter, chapter)
+        };
+
+        // Process each chapter in the range
+        let mut ranges = Vec::new();
+        for chapter in chapter_start..=chapter_end {
+            let max_verse = chapters[chapter as usize - 1];
+
+            // Determine which verse input to use
+            let verse_input = if chapter == chapter_start {
+                // Use start chapter verse input
+                &verse_str
+            } else if chapter == chapter_end && chapter_start != chapter_end {
+                // Use end chapter verse input
+                &verse_end_str
+            } else {
+                // Middle chapters: use empty (full chapter)
+                ""
+            };
+
+            // Parse verses
+            let verse_ranges = if verse_input.trim().is_empty() {
+                // Full chapter
+                vec![(1, max_verse)]
+            } else {
+                parse_verse_ranges(verse_input, max_verse)?
+            };
+
+            for (verse_start, verse_end) in verse_ranges {
+                ranges.push((chapter, verse_start, verse_end));
+            }
+        }
+
+        Ok(AddedPassage {
+            label: AddedPassage::describe(&selected_book, &ranges, chapters),
+            book: selected_book,
+            ranges,
+            times: 1,
+            overwrite: None,
+            note: None,
+            tags: Vec::new(),
+            source: ReadingSource::Read,
+            translation: None,
+            count_again: false,
+        })
+    }
+
+    /// Save the added passages, first offering to store the time spent in the form if it was
+    /// at least a minute
+    fn save(&mut self) -> EntryAction {
+        let minutes = (self.started.elapsed().as_secs() / 60) as u32;
+        if minutes == 0 {
+            EntryAction::AddReading
+        } else {
+            self.pending_duration = Some(minutes);
+            EntryAction::None
+        }
+    }
+
+    /// Record every added passage and clear the list, along with the session's reading time if
+    /// the user chose to store it
+    pub fn add_reading(&mut self, progress: &mut ReadingProgress) -> Result<(), String> {
+        if self.added.is_empty() {
+            return Err("Add a passage first".to_string());
+        }
+        for passage in self.added.drain(..) {
+            passage.record(progress);
+        }
+        if let Some(minutes) = self.session_minutes.take() {
+            progress.record_session(Utc::now().date_naive(), minutes);
+        }
+        self.selected_added = 0;
+        self.error_message = None;
+        self.show_confirmation = false;
+        self.pending_overlap = None;
+        self.preview = None;
+        self.input_focus = InputFocus::Reference;
+        Ok(())
+    }
+
+    /// Books matching `search_query`, best match first. With no search, `quick_books` come
+    /// first and the other books follow in canonical order.
+    fn compute_book_matches(
+        bible: &'static crate::bible_structure::BibleStructure,
+        search_query: &str,
+        quick_books: &[String],
+    ) -> Vec<String> {
+        let all_books = get_all_books(bible);
+        if search_query.is_empty() {
+            let mut books = quick_books.to_vec();
+            books.extend(
+                all_books
+                    .into_iter()
+                    .filter(|book| !quick_books.contains(book)),
+            );
+            books
+        } else {
+            let matcher = SkimMatcherV2::default();
+            let aliases = get_book_aliases(bible);
+
+            // Create a list of (match_text, canonical_name) pairs
+            let mut match_candidates: Vec<(&str, &str)> = all_books
+                .iter()
+                .map(|book| (book.as_str(), book.as_str()))
+                .collect();
+
+            // Add aliases as additional match candidates
+            for (alias, canonical) in &aliases {
+                match_candidates.push((alias.as_str(), canonical.as_str()));
+            }
+
+            // Score all candidates and collect unique canonical names
+            let mut scored: Vec<(i64, String)> = match_candidates
+                .into_iter()
+                .filter_map(|(match_text, canonical)| {
+                    matcher
+                        .fuzzy_match(match_text, search_query)
+                        .map(|score| (score, canonical.to_string()))
+                })
+                .collect();
+
+            scored.sort_by_key(|b| std::cmp::Reverse(b.0)); // Sort by score descending
+
+            // Deduplicate while preserving order (keep highest score for each book)
+            let mut seen = std::collections::HashSet::new();
+            scored
+                .into_iter()
+                .filter_map(|(_, book)| {
+                    if seen.insert(book.clone()) {
+                        Some(book)
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+/// The most recently recorded passage (see `ReadingProgress::last_passage`), to record again
+pub fn last_passage(
+    bible: &'static crate::bible_structure::BibleStructure,
+    progress: &ReadingProgress,
+) -> Option<AddedPassage> {
+    let events = progress.last_passage();
+    let book = events.first()?.book.as_str();
+    let chapters = bible.ot.get(book).or_else(|| bible.nt.get(book))?;
+    let ranges: Vec<(u32, u32, u32)> = events
+        .iter()
+        .map(|event| (event.chapter, event.verse_start, event.verse_end))
+        .collect();
+    Some(AddedPassage {
+        label: AddedPassage::describe(book, &ranges, chapters),
+        book: book.to_string(),
+        ranges,
+        times: 1,
+        overwrite: None,
+        note: None,
+        tags: Vec::new(),
+        source: ReadingSource::Read,
+        translation: None,
+        count_again: false,
+    })
+}
+
+/// Split a trailing read count such as "2x" or "×3" off a passage, e.g. "ps 23 2x" was read
+/// twice. A passage without one was read once.
+fn split_times(input: &str) -> Result<(&str, u32), String> {
+    let input = input.trim();
+    let Some((passage, last)) = input.rsplit_once(char::is_whitespace) else {
+        return Ok((input, 1));
+    };
+    let Some(count) = last
+        .strip_suffix(['x', 'X', '×'])
+        .or_else(|| last.strip_prefix(['x', 'X', '×']))
+    else {
+        return Ok((input, 1));
+    };
+    match count.parse::<u32>() {
+        Ok(0) => Err("The read count must be at least 1x".to_string()),
+        Ok(times) => Ok((passage.trim_end(), times)),
+        Err(_) => Ok((input, 1)),
+    }
+}
+
+/// Characters allowed in verse fields, e.g. "1-5,8"
+/// `input` with its last number stepped by `delta` within `1..=max`, e.g. "1-5" to "1-6". An
+/// empty input steps to 1 going up and to `max` going down.
+fn step_last_number(input: &str, delta: i64, max: u32) -> Option<String> {
+    if max == 0 {
+        return None;
+    }
+    let input = input.trim_end();
+    let start = input
+        .char_indices()
+        .rfind(|(_, c)| !c.is_ascii_digit())
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let (prefix, last) = input.split_at(start);
+    let stepped = match last {
+        "" if delta > 0 => 1,
+        "" => max as i64,
+        number => number.parse::<i64>().ok()? + delta,
+    };
+    Some(format!("{}{}", prefix, stepped.clamp(1, max as i64)))
+}
+
+/// The session timer, e.g. "12:05" for twelve minutes and five seconds
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Split the Tags field into tags, e.g. "morning, #memory" into "morning" and "memory",
+/// dropping repeats
+fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|tag| tag.trim_start_matches('#'))
+        .filter(|tag| !tag.is_empty())
+    {
+        if !tags.iter().any(|existing| existing == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+fn is_verse_char(c: char) -> bool {
+    c.is_ascii_digit() || c == '-' || c == ','
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryAction {
+    None,
+    Cancel,
+    AddReading,
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::widgets::snapshot::{render, sample_progress};
+
+    fn entry(write_mode: WriteMode) -> EntryWidget {
+        EntryWidget::new(
+            get_bible_structure(),
+            &ReadingProgress::new(),
+            Vec::new(),
+            write_mode,
+        )
+    }
+
+    fn type_text(entry: &mut EntryWidget, text: &str) {
+        type_text_with(entry, text, &ReadingProgress::new());
+    }
+
+    fn type_text_with(entry: &mut EntryWidget, text: &str, progress: &ReadingProgress) {
+        for c in text.chars() {
+            press_with(entry, KeyCode::Char(c), progress);
+        }
+    }
+
+    fn press(entry: &mut EntryWidget, code: KeyCode) -> EntryAction {
+        press_with(entry, code, &ReadingProgress::new())
+    }
+
+    fn press_with(
+        entry: &mut EntryWidget,
+        code: KeyCode,
+        progress: &ReadingProgress,
+    ) -> EntryAction {
+        entry
+            .handle_key(KeyEvent::from(code), get_bible_structure(), progress)
+            .unwrap()
+    }
+
+    #[test]
+    fn added_passages_are_saved_together() {
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "John 3:16-4:2");
+        assert_eq!(press(&mut record, KeyCode::Enter), EntryAction::None);
+        type_text(&mut record, "ps 23");
+        press(&mut record, KeyCode::Enter);
+        type_text(&mut record, "jude");
+        press(&mut record, KeyCode::Enter);
+        // Whole books need confirming; cancel this one
+        press(&mut record, KeyCode::Esc);
+        record.reference_input.clear();
+
+        let labels: Vec<&str> = record.added.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, vec!["John 3:16-4:2", "Psalms 23"]);
+
+        // Remove the psalm from the list, then save with Enter on the empty Passage field
+        press(&mut record, KeyCode::BackTab);
+        assert_eq!(record.input_focus, InputFocus::Added);
+        press(&mut record, KeyCode::Char('d'));
+        press(&mut record, KeyCode::Tab);
+        assert_eq!(press(&mut record, KeyCode::Enter), EntryAction::AddReading);
+
+        let mut progress = ReadingProgress::new();
+        record.add_reading(&mut progress).unwrap();
+        assert!(record.added.is_empty());
+        let chapters: Vec<(&str, u32)> = progress
+            .history
+            .iter()
+            .map(|event| (event.book.as_str(), event.chapter))
+            .collect();
+        assert_eq!(chapters, vec![("John", 3), ("John", 4)]);
+    }
+
+    #[test]
+    fn listening_time_is_added_as_listened_passages() {
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "15 min from mk 15");
+        assert_eq!(press(&mut record, KeyCode::Enter), EntryAction::None);
+
+        let labels: Vec<&str> = record.added.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec!["Mark 15-16 (listened)", "Luke 1:1-36 (listened)"]
+        );
+        let mut progress = ReadingProgress::new();
+        record.add_reading(&mut progress).unwrap();
+        assert_eq!(progress.history.len(), 3);
+        assert!(progress
+            .history
+            .iter()
+            .all(|event| event.source == ReadingSource::Listened));
+    }
+
+    #[test]
+    fn passages_already_read_today_need_confirming() {
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Psalms", 23, 1, 6);
+        let mut record = EntryWidget::new(
+            get_bible_structure(),
+            &progress,
+            Vec::new(),
+            WriteMode::Accumulate,
+        );
+
+        type_text(&mut record, "ps 23");
+        press_with(&mut record, KeyCode::Enter, &progress);
+        assert!(record.added.is_empty());
+        assert_eq!(
+            record.pending_overlap.as_ref().map(|(_, overlap)| *overlap),
+            Some(Overlap::ReadToday)
+        );
+
+        // Skipping drops the passage; overwriting adds it without counting it again
+        press(&mut record, KeyCode::Char('s'));
+        assert!(record.added.is_empty() && record.reference_input.is_empty());
+        type_text(&mut record, "ps 23");
+        press_with(&mut record, KeyCode::Enter, &progress);
+        press(&mut record, KeyCode::Char('o'));
+        assert_eq!(record.added[0].label, "Psalms 23 (set to 1×, today)");
+
+        record.add_reading(&mut progress).unwrap();
+        assert_eq!(
+            progress.books["Psalms"].iter().next().unwrap().1.read_count,
+            1
+        );
+    }
+
+    #[test]
+    fn counting_once_a_day_can_be_overridden() {
+        let mut progress = ReadingProgress::new();
+        progress.same_day_readings = SameDayReadings::Once;
+        progress.record_reading("Psalms", 23, 1, 6);
+        let mut record = EntryWidget::new(
+            get_bible_structure(),
+            &progress,
+            Vec::new(),
+            WriteMode::Accumulate,
+        );
+
+        // Enter adds it without counting it again, a counts it anyway
+        type_text(&mut record, "ps 23");
+        press_with(&mut record, KeyCode::Enter, &progress);
+        press(&mut record, KeyCode::Enter);
+        let mut once = progress.clone();
+        record.add_reading(&mut once).unwrap();
+        assert_eq!(once.books["Psalms"].iter().next().unwrap().1.read_count, 1);
+
+        record.added.clear();
+        type_text(&mut record, "ps 23");
+        press_with(&mut record, KeyCode::Enter, &progress);
+        press(&mut record, KeyCode::Char('a'));
+        assert_eq!(record.added[0].label, "Psalms 23 (counted again)");
+        record.add_reading(&mut progress).unwrap();
+        assert_eq!(
+            progress.books["Psalms"].iter().next().unwrap().1.read_count,
+            2
+        );
+    }
+
+    #[test]
+    fn overwrite_preview_follows_the_fields() {
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Romans", 3, 1, 10);
+        progress.record_reading("Romans", 4, 1, 25);
+        progress.record_reading("Romans", 5, 6, 6);
+        let mut manual_add = entry(WriteMode::Overwrite);
+        assert_eq!(manual_add.preview, None);
+
+        press_with(&mut manual_add, KeyCode::Tab, &progress);
+        type_text_with(&mut manual_add, "romans", &progress);
+        type_text_with(&mut manual_add, "3-5", &progress);
+        for _ in 0..3 {
+            press_with(&mut manual_add, KeyCode::Tab, &progress);
+        }
+        type_text_with(&mut manual_add, "2", &progress);
+        press_with(&mut manual_add, KeyCode::Tab, &progress);
+        type_text_with(&mut manual_add, "2024-11-02", &progress);
+        assert_eq!(
+            manual_add.preview,
+            Some(Ok(
+                "Will set Romans 3-5 to 2×, last read 2024-11-02, replacing 3 existing ranges"
+                    .to_string()
+            ))
+        );
+
+        // Invalid values are flagged as they are typed
+        press_with(&mut manual_add, KeyCode::Backspace, &progress);
+        press_with(&mut manual_add, KeyCode::Backspace, &progress);
+        type_text_with(&mut manual_add, "99", &progress);
+        assert!(matches!(manual_add.preview, Some(Err(_))));
+    }
+
+    #[test]
+    fn mode_toggle_switches_between_adding_and_overwriting() {
+        let mut form = entry(WriteMode::Accumulate);
+        type_text(&mut form, "ps 23");
+        press(&mut form, KeyCode::Enter);
+        form.handle_key(
+            KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL),
+            get_bible_structure(),
+            &ReadingProgress::new(),
+        )
+        .unwrap();
+        assert_eq!(form.write_mode, WriteMode::Overwrite);
+        type_text(&mut form, "ps 24");
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(form.input_focus, InputFocus::ReadCount);
+        type_text(&mut form, "3");
+        press(&mut form, KeyCode::Tab);
+        press(&mut form, KeyCode::Enter);
+
+        let labels: Vec<&str> = form.added.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, vec!["Psalms 23", "Psalms 24 (set to 3×, today)"]);
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Psalms", 24, 1, 10);
+        form.add_reading(&mut progress).unwrap();
+        let counts: Vec<u32> = progress.books["Psalms"]
+            .iter()
+            .map(|(_, record)| record.read_count)
+            .collect();
+        assert_eq!(counts, vec![1, 3]);
+    }
+
+    #[test]
+    fn errors_are_shown_below_the_fields() {
+        let mut manual_add = entry(WriteMode::Overwrite);
+        manual_add.error_message = Some("Invalid read count: 99999999999".to_string());
+        let mut terminal = Terminal::new(backend::TestBackend::new(80, 30)).unwrap();
+        terminal
+            .draw(|frame| manual_add.render(frame, frame.area()))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Read Count"));
+        assert!(screen.contains("Invalid read count: 99999999999"));
+    }
+
+    #[test]
+    fn empty_chapter_records_the_whole_book() {
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        let mut record = EntryWidget::new(
+            get_bible_structure(),
+            &progress,
+            Vec::new(),
+            WriteMode::Accumulate,
+        );
+        press(&mut record, KeyCode::Tab);
+        // "ru" can only be Ruth, so the book is selected as it's typed
+        type_text(&mut record, "ru");
+        assert_eq!(record.input_focus, InputFocus::Chapter);
+        // Skip the chapter and verse fields
+        press(&mut record, KeyCode::Enter);
+        press(&mut record, KeyCode::Enter);
+        assert!(record.show_confirmation);
+        assert!(record.added.is_empty());
+        press(&mut record, KeyCode::Enter);
+        assert_eq!(record.added[0].label, "Ruth");
+
+        // Ruth 1 was read before, so it is now read twice; the rest once
+        record.add_reading(&mut progress).unwrap();
+        let counts: Vec<u32> = progress.books["Ruth"]
+            .iter()
+            .map(|(_, stats)| stats.read_count)
+            .collect();
+        assert_eq!(counts[0], 2);
+        assert!(counts[1..].iter().all(|&count| count == 1));
+        assert_eq!(progress.history.len(), 5);
+    }
+
+    #[test]
+    fn f1_shows_the_formats_of_the_focused_field() {
+        let mut record = entry(WriteMode::Accumulate);
+        press(&mut record, KeyCode::Tab);
+        press(&mut record, KeyCode::F(1));
+        assert_eq!(record.field_help().0, "Book field");
+
+        let mut terminal = Terminal::new(backend::TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|frame| record.render(frame, frame.area()))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Help: Book field"));
+        assert!(screen.contains("fuzzy search"));
+
+        // The next key only closes it
+        press(&mut record, KeyCode::Char('r'));
+        assert!(!record.show_field_help);
+        assert!(record.book_search.is_empty());
+    }
+
+    #[test]
+    fn notes_and_tags_are_saved_with_the_reading() {
+        assert_eq!(
+            parse_tags("morning, #memory  morning"),
+            vec!["morning", "memory"]
+        );
+
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "ps 23");
+        press(&mut record, KeyCode::Tab);
+        assert_eq!(record.input_focus, InputFocus::Notes);
+        type_text(&mut record, "before work");
+        press(&mut record, KeyCode::Tab);
+        type_text(&mut record, "morning");
+        press(&mut record, KeyCode::Enter);
+        assert_eq!(record.added[0].label, "Psalms 23 ✎ #morning");
+        assert!(record.notes_input.is_empty());
+
+        let mut progress = ReadingProgress::new();
+        record.add_reading(&mut progress).unwrap();
+        assert_eq!(progress.history[0].note.as_deref(), Some("before work"));
+        assert_eq!(progress.history[0].tags, vec!["morning"]);
+    }
+
+    #[test]
+    fn saving_offers_to_store_the_session_time() {
+        assert_eq!(format_elapsed(Duration::from_secs(725)), "12:05");
+
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "ps 23");
+        press(&mut record, KeyCode::Enter);
+        record.started = Instant::now()
+            .checked_sub(Duration::from_secs(12 * 60 + 5))
+            .unwrap();
+        // Saving asks first; Esc goes back to the form
+        assert_eq!(press(&mut record, KeyCode::Enter), EntryAction::None);
+        assert_eq!(record.pending_duration, Some(12));
+        assert_eq!(press(&mut record, KeyCode::Esc), EntryAction::None);
+        assert_eq!(record.pending_duration, None);
+
+        press(&mut record, KeyCode::Enter);
+        assert_eq!(
+            press(&mut record, KeyCode::Char('y')),
+            EntryAction::AddReading
+        );
+        let mut progress = ReadingProgress::new();
+        record.add_reading(&mut progress).unwrap();
+        assert_eq!(progress.sessions.len(), 1);
+        assert_eq!(progress.sessions[0].minutes, 12);
+    }
+
+    #[test]
+    fn arrows_step_numeric_fields_within_bounds() {
+        assert_eq!(step_last_number("1-5", 1, 10), Some("1-6".to_string()));
+        assert_eq!(step_last_number("3, 7", -1, 10), Some("3, 6".to_string()));
+        assert_eq!(step_last_number("", -1, 10), Some("10".to_string()));
+        assert_eq!(step_last_number("10", 1, 10), Some("10".to_string()));
+        assert_eq!(step_last_number("1–5", 1, 10), Some("1–6".to_string()));
+
+        let mut manual_add = entry(WriteMode::Overwrite);
+        press(&mut manual_add, KeyCode::Tab);
+        type_text(&mut manual_add, "ruth");
+        // Ruth has four chapters, and chapter 4 has 22 verses
+        press(&mut manual_add, KeyCode::Down);
+        assert_eq!(manual_add.chapter_input.as_str(), "4");
+        press(&mut manual_add, KeyCode::Up);
+        assert_eq!(manual_add.chapter_input.as_str(), "4");
+        press(&mut manual_add, KeyCode::Tab);
+        press(&mut manual_add, KeyCode::Down);
+        assert_eq!(manual_add.verse_input.as_str(), "22");
+
+        press(&mut manual_add, KeyCode::Tab);
+        press(&mut manual_add, KeyCode::Up);
+        assert_eq!(manual_add.read_count_input.as_str(), "2");
+        press(&mut manual_add, KeyCode::Tab);
+        press(&mut manual_add, KeyCode::Up);
+        let today = Utc::now().date_naive();
+        assert_eq!(manual_add.date_input.as_str(), today.to_string());
+        press(&mut manual_add, KeyCode::Down);
+        assert_eq!(
+            manual_add.date_input.as_str(),
+            (today - chrono::Duration::days(1)).to_string()
+        );
+    }
+
+    #[test]
+    fn source_and_translation_start_from_the_defaults() {
+        let mut record = entry(WriteMode::Accumulate).with_reading_defaults(
+            ReadingSource::Listened,
+            vec!["ESV".to_string(), "KJV".to_string()],
+        );
+        type_text(&mut record, "ps 23");
+        press(&mut record, KeyCode::Enter);
+        assert_eq!(record.added[0].label, "Psalms 23 (listened) [ESV]");
+
+        // Switch to reading the KJV
+        record
+            .handle_key(
+                KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL),
+                get_bible_structure(),
+                &ReadingProgress::new(),
+            )
+            .unwrap();
+        record
+            .handle_key(
+                KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL),
+                get_bible_structure(),
+                &ReadingProgress::new(),
+            )
+            .unwrap();
+        type_text(&mut record, "ps 24");
+        press(&mut record, KeyCode::Enter);
+        assert_eq!(record.added[1].label, "Psalms 24 [KJV]");
+
+        let mut progress = ReadingProgress::new();
+        record.add_reading(&mut progress).unwrap();
+        assert_eq!(progress.history[0].source, ReadingSource::Listened);
+        assert_eq!(progress.history[0].translation.as_deref(), Some("ESV"));
+        assert_eq!(progress.history[1].source, ReadingSource::Read);
+        assert_eq!(progress.history[1].translation.as_deref(), Some("KJV"));
+    }
+
+    #[test]
+    fn esc_asks_before_discarding_input() {
+        let mut record = entry(WriteMode::Accumulate);
+        assert_eq!(press(&mut record, KeyCode::Esc), EntryAction::Cancel);
+
+        type_text(&mut record, "ps 23");
+        press(&mut record, KeyCode::Enter);
+        assert_eq!(press(&mut record, KeyCode::Esc), EntryAction::None);
+        assert!(record.confirm_discard);
+        // Esc again keeps editing, d discards
+        assert_eq!(press(&mut record, KeyCode::Esc), EntryAction::None);
+        assert!(!record.confirm_discard);
+        assert_eq!(record.added.len(), 1);
+        press(&mut record, KeyCode::Esc);
+        assert_eq!(press(&mut record, KeyCode::Char('d')), EntryAction::Cancel);
+    }
+
+    #[test]
+    fn unambiguous_searches_select_the_book() {
+        let mut record = entry(WriteMode::Accumulate);
+        press(&mut record, KeyCode::Tab);
+        type_text(&mut record, "jo");
+        assert_eq!(record.input_focus, InputFocus::Book);
+        type_text(&mut record, "h");
+        assert_eq!(record.input_focus, InputFocus::Chapter);
+        assert_eq!(record.book_search.as_str(), "John");
+        // The rest of the name doesn't reach the Chapter field
+        type_text(&mut record, "n3");
+        assert_eq!(record.chapter_input.as_str(), "3");
+
+        // Deleting back to a unique prefix leaves the search alone
+        press(&mut record, KeyCode::BackTab);
+        press(&mut record, KeyCode::Backspace);
+        assert_eq!(record.input_focus, InputFocus::Book);
+        assert_eq!(record.book_search.as_str(), "Joh");
+    }
+
+    #[test]
+    fn chapters_past_the_end_of_the_book_are_flagged() {
+        let mut record = entry(WriteMode::Accumulate);
+        press(&mut record, KeyCode::Tab);
+        type_text(&mut record, "ruth");
+        type_text(&mut record, "4");
+        assert_eq!(record.chapter_warning(get_bible_structure()), None);
+        type_text(&mut record, "-5");
+        assert_eq!(
+            record.chapter_warning(get_bible_structure()).as_deref(),
+            Some("Ruth has only 4 chapters")
+        );
+
+        let mut terminal = Terminal::new(backend::TestBackend::new(100, 40)).unwrap();
+        terminal
+            .draw(|frame| record.render(frame, frame.area()))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Chapter: Ruth has only 4 chapters"));
+    }
+
+    #[test]
+    fn count_suffix_records_several_readings() {
+        assert_eq!(split_times("ps 23 2x"), Ok(("ps 23", 2)));
+        assert_eq!(split_times("1 John 3 ×3"), Ok(("1 John 3", 3)));
+        assert_eq!(split_times("John 3"), Ok(("John 3", 1)));
+        assert!(split_times("John 3 0x").is_err());
+
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "ps 23 2x");
+        press(&mut record, KeyCode::Enter);
+        assert_eq!(record.added[0].label, "Psalms 23 (2×)");
+
+        let mut progress = ReadingProgress::new();
+        record.add_reading(&mut progress).unwrap();
+        assert_eq!(
+            progress.books["Psalms"].iter().next().unwrap().1.read_count,
+            2
+        );
+        assert_eq!(progress.history[0].read_count, 2);
+    }
+
+    #[test]
+    fn dot_adds_the_last_recorded_passage() {
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("John", 3, 16, 36);
+        progress.record_reading("John", 4, 1, 2);
+        let mut record = entry(WriteMode::Accumulate);
+        press_with(&mut record, KeyCode::Char('.'), &progress);
+        // Already read today, so it needs confirming
+        press(&mut record, KeyCode::Char('a'));
+        assert_eq!(record.added[0].label, "John 3:16-36, 4:1-2");
+    }
+
+    fn screen(entry: &mut EntryWidget, width: u16, height: u16) -> String {
+        // So the session timer reads 0:00
+        entry.started = Instant::now();
+        render(width, height, |frame| entry.render(frame, frame.area()))
+    }
+
+    #[test]
+    fn record_form() {
+        assert_snapshot!(screen(&mut entry(WriteMode::Accumulate), 80, 30));
+    }
+
+    #[test]
+    fn manual_add_form() {
+        assert_snapshot!(screen(&mut entry(WriteMode::Overwrite), 80, 30));
+    }
+
+    #[test]
+    fn passage_being_typed() {
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "John 3:16-4:2");
+        assert_snapshot!(screen(&mut record, 80, 30));
+    }
+
+    #[test]
+    fn added_passages_list() {
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "John 3:16-4:2");
+        press(&mut record, KeyCode::Enter);
+        type_text(&mut record, "ps 23 2x");
+        press(&mut record, KeyCode::Enter);
+        assert_snapshot!(screen(&mut record, 80, 30));
+    }
+
+    #[test]
+    fn book_matches() {
+        let mut record = entry(WriteMode::Accumulate);
+        press(&mut record, KeyCode::Tab);
+        type_text(&mut record, "jo");
+        assert_snapshot!(screen(&mut record, 80, 30));
+    }
+
+    #[test]
+    fn chapter_field_shows_the_book_progress() {
+        let progress = sample_progress();
+        let mut record = EntryWidget::new(
+            get_bible_structure(),
+            &progress,
+            Vec::new(),
+            WriteMode::Accumulate,
+        );
+        press(&mut record, KeyCode::Tab);
+        type_text_with(&mut record, "gen", &progress);
+        press_with(&mut record, KeyCode::Enter, &progress);
+        assert_snapshot!(screen(&mut record, 80, 30));
+    }
+
+    #[test]
+    fn whole_book_needs_confirming() {
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "jude");
+        press(&mut record, KeyCode::Enter);
+        assert_snapshot!(screen(&mut record, 80, 30));
+    }
+
+    #[test]
+    fn already_read_warning() {
+        let progress = sample_progress();
+        let mut record = EntryWidget::new(
+            get_bible_structure(),
+            &progress,
+            Vec::new(),
+            WriteMode::Accumulate,
+        );
+        type_text_with(&mut record, "John 3", &progress);
+        press_with(&mut record, KeyCode::Enter, &progress);
+        assert_snapshot!(screen(&mut record, 80, 30));
+    }
+
+    #[test]
+    fn manual_add_read_count_field() {
+        let mut manual_add = entry(WriteMode::Overwrite);
+        while manual_add.input_focus != InputFocus::ReadCount {
+            press(&mut manual_add, KeyCode::Tab);
+        }
+        press(&mut manual_add, KeyCode::Up);
+        assert_snapshot!(screen(&mut manual_add, 80, 30));
+    }
+
+    #[test]
+    fn listened_readings() {
+        let mut record = entry(WriteMode::Accumulate);
+        record
+            .handle_key(
+                KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL),
+                get_bible_structure(),
+                &ReadingProgress::new(),
+            )
+            .unwrap();
+        assert_snapshot!(screen(&mut record, 80, 30));
+    }
+
+    #[test]
+    fn field_help() {
+        let mut record = entry(WriteMode::Accumulate);
+        press(&mut record, KeyCode::F(1));
+        assert_snapshot!(screen(&mut record, 80, 30));
+    }
+
+    #[test]
+    fn narrow_record_form() {
+        let mut record = entry(WriteMode::Accumulate);
+        type_text(&mut record, "ps 23");
+        press(&mut record, KeyCode::Enter);
+        assert_snapshot!(screen(&mut record, 50, 20));
+    }
+
+    #[test]
+    fn short_manual_add_form() {
+        assert_snapshot!(screen(&mut entry(WriteMode::Overwrite), 80, 16));
+    }
+}
@@ -0,0 +1,312 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ratatui::{prelude::*, widgets::*};
+
+use crate::i18n::{tr, trf};
+use crate::widgets::help::KeyBinding;
+use crate::widgets::popup::centered_rect;
+
+/// Keybindings available in the file picker, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("↑/↓", "Select a file or folder"),
+    (
+        "Enter",
+        "Open the selected folder, or pick the selected file",
+    ),
+    ("→", "Open the selected folder"),
+    ("←/Backspace", "Go up to the parent folder"),
+    ("~", "Go to your home folder"),
+    (".", "Show or hide hidden files"),
+    ("a-z", "Jump to the next entry starting with the letter"),
+    ("Esc", "Cancel"),
+];
+
+/// Whether the picker picks a file or a folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickKind {
+    File,
+    Folder,
+}
+
+/// A line of the listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    path: PathBuf,
+    /// Opened rather than picked
+    is_dir: bool,
+}
+
+/// Popup for browsing to a file or folder, so paths never have to be typed
+pub struct FilePicker {
+    title: &'static str,
+    kind: PickKind,
+    /// The folder listed
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    list_state: ListState,
+    show_hidden: bool,
+    /// Why the last folder couldn't be opened
+    error: Option<String>,
+}
+
+impl FilePicker {
+    /// Browse from `start`: a folder, or a file, which is selected in its folder. Starts in the
+    /// home folder if neither exists.
+    pub fn new(title: &'static str, kind: PickKind, start: &Path) -> Self {
+        let (dir, selected) = if start.is_dir() {
+            (start.to_path_buf(), None)
+        } else {
+            (
+                start.parent().map(Path::to_path_buf).unwrap_or_default(),
+                start
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string()),
+            )
+        };
+        let dir = if dir.is_dir() {
+            dir
+        } else {
+            dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))
+        };
+        let mut picker = Self {
+            title,
+            kind,
+            dir: PathBuf::new(),
+            entries: Vec::new(),
+            list_state: ListState::default(),
+            show_hidden: false,
+            error: None,
+        };
+        picker.open(dir);
+        if let Some(index) = selected.and_then(|name| {
+            picker
+                .entries
+                .iter()
+                .position(|entry| !entry.is_dir && entry.name == name)
+        }) {
+            picker.list_state.select(Some(index));
+        }
+        picker
+    }
+
+    /// List `dir`, or keep the current listing and show why it couldn't be read
+    fn open(&mut self, dir: PathBuf) {
+        let dir = dir.canonicalize().unwrap_or(dir);
+        match list_dir(&dir, self.kind, self.show_hidden) {
+            Ok(entries) => {
+                self.list_state.select((!entries.is_empty()).then_some(0));
+                self.entries = entries;
+                self.dir = dir;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("{}: {}", dir.display(), e)),
+        }
+    }
+
+    fn selected(&self) -> Option<&Entry> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.entries.get(index))
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let area = centered_rect(70, 70, frame.area());
+        frame.render_widget(Clear, area);
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let style = if entry.path == self.dir {
+                    Style::default().fg(Color::Yellow)
+                } else if entry.is_dir {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::styled(entry.name.clone(), style))
+            })
+            .collect();
+        let footer = match &self.error {
+            Some(error) => Line::styled(
+                trf("Couldn't open {}", &[error]),
+                Style::default().fg(Color::Red),
+            ),
+            None => Line::styled(
+                tr("Enter: open/pick | ←: up | ~: home | .: hidden files | Esc: cancel"),
+                Style::default().fg(Color::Gray),
+            ),
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(format!("{}: {}", tr(self.title), self.dir.display()))
+                    .title_bottom(footer),
+            )
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> FilePickerAction {
+        match key.code {
+            crossterm::event::KeyCode::Esc => return FilePickerAction::Cancel,
+            crossterm::event::KeyCode::Up => self.list_state.select_previous(),
+            crossterm::event::KeyCode::Down => {
+                if let Some(selected) = self.list_state.selected() {
+                    self.list_state
+                        .select(Some((selected + 1).min(self.entries.len() - 1)));
+                }
+            }
+            crossterm::event::KeyCode::Home => self.list_state.select_first(),
+            crossterm::event::KeyCode::End => {
+                self.list_state.select(self.entries.len().checked_sub(1));
+            }
+            crossterm::event::KeyCode::Enter => match self.selected().cloned() {
+                Some(entry) if entry.is_dir => self.open(entry.path),
+                Some(entry) => return FilePickerAction::Picked(entry.path),
+                None => {}
+            },
+            crossterm::event::KeyCode::Right => {
+                if let Some(entry) = self.selected().filter(|entry| entry.is_dir).cloned() {
+                    self.open(entry.path);
+                }
+            }
+            crossterm::event::KeyCode::Left | crossterm::event::KeyCode::Backspace => {
+                if let Some(parent) = self.dir.parent() {
+                    let child = self.dir.clone();
+                    self.open(parent.to_path_buf());
+                    // Keep the folder just left selected
+                    if let Some(index) = self.entries.iter().position(|e| e.path == child) {
+                        self.list_state.select(Some(index));
+                    }
+                }
+            }
+            crossterm::event::KeyCode::Char('~') => {
+                if let Some(home) = dirs::home_dir() {
+                    self.open(home);
+                }
+            }
+            crossterm::event::KeyCode::Char('.') => {
+                self.show_hidden = !self.show_hidden;
+                self.open(self.dir.clone());
+            }
+            crossterm::event::KeyCode::Char(c) => self.jump_to(c),
+            _ => {}
+        }
+        FilePickerAction::None
+    }
+
+    /// Select the next entry after the selected one whose name starts with `c`
+    fn jump_to(&mut self, c: char) {
+        let c = c.to_lowercase().to_string();
+        let start = self
+            .list_state
+            .selected()
+            .map_or(0, |selected| selected + 1);
+        let len = self.entries.len();
+        if let Some(index) = (0..len)
+            .map(|offset| (start + offset) % len)
+            .find(|&index| self.entries[index].name.to_lowercase().starts_with(&c))
+        {
+            self.list_state.select(Some(index));
+        }
+    }
+}
+
+/// The lines listing `dir`: picking the folder itself (for folders), its parent, then its
+/// folders and (for files) files, by name
+fn list_dir(dir: &Path, kind: PickKind, show_hidden: bool) -> std::io::Result<Vec<Entry>> {
+    let mut listed: Vec<Entry> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| Entry {
+            name: entry.file_name().to_string_lossy().to_string(),
+            is_dir: entry.path().is_dir(),
+            path: entry.path(),
+        })
+        .filter(|entry| show_hidden || !entry.name.starts_with('.'))
+        .filter(|entry| entry.is_dir || kind == PickKind::File)
+        .collect();
+    listed.sort_by_key(|entry| (!entry.is_dir, entry.name.to_lowercase()));
+    for entry in listed.iter_mut().filter(|entry| entry.is_dir) {
+        entry.name.push('/');
+    }
+
+    let mut entries = Vec::new();
+    if kind == PickKind::Folder {
+        entries.push(Entry {
+            name: tr("(pick this folder)").to_string(),
+            path: dir.to_path_buf(),
+            is_dir: false,
+        });
+    }
+    if let Some(parent) = dir.parent() {
+        entries.push(Entry {
+            name: "../".to_string(),
+            path: parent.to_path_buf(),
+            is_dir: true,
+        });
+    }
+    entries.extend(listed);
+    Ok(entries)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilePickerAction {
+    None,
+    Cancel,
+    Picked(PathBuf),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    #[test]
+    fn folders_are_opened_and_files_picked() {
+        let dir = std::env::temp_dir().join(format!("brp-file-picker-{}", std::process::id()));
+        fs::create_dir_all(dir.join("Plans")).unwrap();
+        fs::write(dir.join("b.txt"), "").unwrap();
+        fs::write(dir.join(".hidden"), "").unwrap();
+        let dir = dir.canonicalize().unwrap();
+        let names = |picker: &FilePicker| -> Vec<String> {
+            picker
+                .entries
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect()
+        };
+
+        let mut picker = FilePicker::new("Import", PickKind::File, &dir.join("b.txt"));
+        assert_eq!(names(&picker), ["../", "Plans/", "b.txt"]);
+        assert_eq!(
+            picker.handle_key(KeyEvent::from(KeyCode::Enter)),
+            FilePickerAction::Picked(dir.join("b.txt"))
+        );
+        picker.handle_key(KeyEvent::from(KeyCode::Char('p')));
+        picker.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(picker.dir, dir.join("Plans"));
+        picker.handle_key(KeyEvent::from(KeyCode::Left));
+        assert_eq!(picker.selected().unwrap().name, "Plans/");
+        picker.handle_key(KeyEvent::from(KeyCode::Char('.')));
+        assert_eq!(names(&picker), ["../", "Plans/", ".hidden", "b.txt"]);
+
+        let mut picker = FilePicker::new("Plans", PickKind::Folder, &dir);
+        assert_eq!(names(&picker), ["(pick this folder)", "../", "Plans/"]);
+        assert_eq!(
+            picker.handle_key(KeyEvent::from(KeyCode::Enter)),
+            FilePickerAction::Picked(dir.clone())
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
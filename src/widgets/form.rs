@@ -0,0 +1,66 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::widgets::text_input::TextInput;
+
+/// How a form message should be read, which decides its color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Hints on filling in the form
+    Help,
+    /// What submitting the form will do
+    Info,
+    /// A value that isn't valid yet, flagged while typing
+    Warning,
+    /// Why the form couldn't be submitted
+    Error,
+}
+
+impl Severity {
+    fn color(self) -> Color {
+        match self {
+            Severity::Help => Color::Gray,
+            Severity::Info => Color::Green,
+            Severity::Warning => Color::Yellow,
+            Severity::Error => Color::Red,
+        }
+    }
+}
+
+/// A text field in a bordered block, highlighted while it has focus
+pub fn field<'a>(title: impl Into<Line<'a>>, input: &'a TextInput, focused: bool) -> Paragraph<'a> {
+    let (style, border_style) = if focused {
+        (
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            Style::default().fg(Color::Yellow),
+        )
+    } else {
+        (Style::default(), Style::default())
+    };
+    Paragraph::new(input.as_str()).style(style).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(border_style),
+    )
+}
+
+/// A form's message area: `text` wrapped to the area's width, colored by `severity`. Lines
+/// that set their own style keep it.
+pub fn message<'a>(severity: Severity, title: &'a str, text: impl Into<Text<'a>>) -> Paragraph<'a> {
+    let color = severity.color();
+    Paragraph::new(text)
+        .style(Style::default().fg(color))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(if severity == Severity::Help {
+                    Style::default()
+                } else {
+                    Style::default().fg(color)
+                }),
+        )
+}
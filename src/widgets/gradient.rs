@@ -0,0 +1,164 @@
+use std::str::FromStr;
+
+use chrono::{NaiveDate, Utc};
+use ratatui::style::Color;
+
+use crate::config::{GradientConfig, GradientMode};
+
+/// Maps a passage's read count (or recency) to a color along a configurable gradient
+#[derive(Debug, Clone)]
+pub struct ColorGradient {
+    mode: GradientMode,
+    colors: Vec<Color>,
+    recency_days: Vec<i64>,
+}
+
+impl ColorGradient {
+    /// Build a gradient from the config, falling back to the default colors if none parse
+    pub fn from_config(config: &GradientConfig) -> Self {
+        let mut colors: Vec<Color> = config
+            .colors
+            .iter()
+            .filter_map(|name| Color::from_str(name).ok())
+            .collect();
+        if colors.is_empty() {
+            colors = GradientConfig::default()
+                .colors
+                .iter()
+                .filter_map(|name| Color::from_str(name).ok())
+                .collect();
+        }
+
+        let mut recency_days = config.recency_days.clone();
+        // Oldest threshold first, so passing more thresholds means more recent
+        recency_days.sort_by_key(|days| std::cmp::Reverse(*days));
+
+        Self {
+            mode: config.mode,
+            colors,
+            recency_days,
+        }
+    }
+
+    /// Color for a passage whose least-read verse has been read `min_read_count` times and
+    /// which was most recently read on `last_read`
+    pub fn color_for(&self, min_read_count: u32, last_read: Option<NaiveDate>) -> Color {
+        let index = match self.mode {
+            GradientMode::ReadCount => min_read_count as usize,
+            GradientMode::Recency => match last_read {
+                None => 0,
+                Some(date) => {
+                    let days_ago = Utc::now()
+                        .date_naive()
+                        .signed_duration_since(date)
+                        .num_days();
+                    1 + self
+                        .recency_days
+                        .iter()
+                        .filter(|&&threshold| days_ago <= threshold)
+                        .count()
+                }
+            },
+        };
+        self.colors[index.min(self.colors.len() - 1)]
+    }
+
+    /// One (color, description) entry per gradient step, for the legend popup
+    pub fn legend(&self) -> Vec<(Color, String)> {
+        let last = self.colors.len() - 1;
+        self.colors
+            .iter()
+            .enumerate()
+            .map(|(index, &color)| {
+                let label = match self.mode {
+                    GradientMode::ReadCount => match index {
+                        0 => "Not fully read yet".to_string(),
+                        n if n == last => format!("Every verse read {}x or more", n),
+                        n => format!("Every verse read {}x", n),
+                    },
+                    GradientMode::Recency => self.recency_label(index),
+                };
+                (color, label)
+            })
+            .collect()
+    }
+
+    fn recency_label(&self, index: usize) -> String {
+        if index == 0 {
+            return "Never read".to_string();
+        }
+        // Step `index` covers passages within `index - 1` of the (oldest-first) thresholds
+        match (index - 1).checked_sub(1) {
+            None => match self.recency_days.first() {
+                Some(days) => format!("Last read over {} days ago", days),
+                None => "Read".to_string(),
+            },
+            Some(threshold) => match self.recency_days.get(threshold) {
+                Some(days) => format!("Read within {} days", days),
+                None => "Read recently".to_string(),
+            },
+        }
+    }
+}
+
+impl Default for ColorGradient {
+    fn default() -> Self {
+        Self::from_config(&GradientConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn read_count_mode_clamps_to_last_color() {
+        let gradient = ColorGradient::from_config(&GradientConfig {
+            mode: GradientMode::ReadCount,
+            colors: vec!["gray".into(), "blue".into(), "green".into()],
+            recency_days: vec![],
+        });
+        assert_eq!(gradient.color_for(0, None), Color::Gray);
+        assert_eq!(gradient.color_for(1, None), Color::Blue);
+        assert_eq!(gradient.color_for(7, None), Color::Green);
+    }
+
+    #[test]
+    fn recency_mode_uses_day_thresholds() {
+        let gradient = ColorGradient::from_config(&GradientConfig {
+            mode: GradientMode::Recency,
+            colors: vec![
+                "gray".into(),
+                "blue".into(),
+                "green".into(),
+                "yellow".into(),
+            ],
+            recency_days: vec![7, 30],
+        });
+        let today = Utc::now().date_naive();
+        assert_eq!(gradient.color_for(0, None), Color::Gray);
+        assert_eq!(
+            gradient.color_for(1, Some(today - Duration::days(100))),
+            Color::Blue
+        );
+        assert_eq!(
+            gradient.color_for(1, Some(today - Duration::days(10))),
+            Color::Green
+        );
+        assert_eq!(gradient.color_for(1, Some(today)), Color::Yellow);
+    }
+
+    #[test]
+    fn invalid_colors_fall_back_to_default() {
+        let gradient = ColorGradient::from_config(&GradientConfig {
+            mode: GradientMode::ReadCount,
+            colors: vec!["not-a-color".into()],
+            recency_days: vec![],
+        });
+        assert_eq!(
+            gradient.legend().len(),
+            GradientConfig::default().colors.len()
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::i18n::{tr, trf};
+use crate::widgets::popup::centered_rect;
+
+/// A key (or key combination) and a description of what it does
+pub type KeyBinding = (&'static str, &'static str);
+
+/// Render a popup listing the keybindings available in the current mode, in the interface
+/// language
+pub fn render_help(frame: &mut Frame, mode_name: &str, bindings: &[KeyBinding]) {
+    let popup_area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let key_width = bindings
+        .iter()
+        .map(|(key, _)| tr(key).chars().count())
+        .max()
+        .unwrap_or(0) as u16;
+
+    let rows: Vec<Row> = bindings
+        .iter()
+        .map(|(key, description)| {
+            Row::new(vec![
+                Cell::from(tr(key)).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Cell::from(tr(description)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Length(key_width), Constraint::Min(0)])
+        .column_spacing(3)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(trf("Help: {} (press any key to close)", &[&tr(mode_name)])),
+        );
+    frame.render_widget(table, popup_area);
+}
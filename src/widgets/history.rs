@@ -0,0 +1,411 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, Utc};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::bible_structure::BibleStructure;
+use crate::progress::{ReadingEvent, ReadingProgress};
+use crate::widgets::help::KeyBinding;
+use crate::widgets::journal::{JournalAction, JournalEditor};
+
+/// Keybindings available in the history tab, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("↑/↓", "Scroll through readings or journal entries"),
+    ("PgUp/PgDn", "Move a page up or down"),
+    ("Home/End", "Jump to the newest/oldest reading or entry"),
+    ("j", "Switch between readings and journal entries"),
+    ("e", "Write the journal entry of the selected day"),
+    ("J", "Write today's journal entry"),
+    ("?", "Show this help"),
+    ("q", "Quit"),
+];
+
+/// Marker after the date of readings whose day has a journal entry
+const JOURNAL_MARKER: &str = "✎";
+
+/// What the history tab lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryView {
+    Readings,
+    Journal,
+}
+
+/// Action requested by the history tab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryAction {
+    None,
+    /// Write the journal entry of `date`
+    SaveJournal {
+        date: NaiveDate,
+        text: String,
+    },
+}
+
+/// Every recorded reading, newest first, and the journal entries written alongside them
+pub struct HistoryWidget {
+    /// Date, passage, count, and note with tags
+    rows: Vec<(NaiveDate, [String; 4])>,
+    table_state: TableState,
+    /// Journal entries, newest first
+    journal: Vec<(NaiveDate, String)>,
+    journal_state: ListState,
+    /// Passages read each day, e.g. "John 3"
+    readings_by_date: BTreeMap<NaiveDate, Vec<String>>,
+    pub view: HistoryView,
+    /// Editor for a day's journal entry, shown over the tab
+    editor: Option<JournalEditor>,
+    /// Height of the table from the last render, used for paging
+    page_size: usize,
+}
+
+impl HistoryWidget {
+    pub fn new(bible: &BibleStructure, progress: &ReadingProgress) -> Self {
+        let mut history = Self {
+            rows: Vec::new(),
+            table_state: TableState::default(),
+            journal: Vec::new(),
+            journal_state: ListState::default(),
+            readings_by_date: BTreeMap::new(),
+            view: HistoryView::Readings,
+            editor: None,
+            page_size: 1,
+        };
+        history.refresh(bible, progress);
+        history
+    }
+
+    /// Reload the readings and journal entries, keeping the view and the selections
+    pub fn refresh(&mut self, bible: &BibleStructure, progress: &ReadingProgress) {
+        self.rows = progress
+            .history
+            .iter()
+            .rev()
+            .map(|event| {
+                let mut date = event.date.format("%Y-%m-%d").to_string();
+                if progress.journal.contains_key(&event.date) {
+                    date = format!("{} {}", date, JOURNAL_MARKER);
+                }
+                (
+                    event.date,
+                    [
+                        date,
+                        format_event_passage(bible, event),
+                        format!("{}x", event.read_count),
+                        format_event_notes(event),
+                    ],
+                )
+            })
+            .collect();
+        self.journal = progress
+            .journal
+            .iter()
+            .rev()
+            .map(|(date, text)| (*date, text.clone()))
+            .collect();
+        self.readings_by_date = BTreeMap::new();
+        for event in &progress.history {
+            let passage = format_event_passage(bible, event);
+            let readings = self.readings_by_date.entry(event.date).or_default();
+            if !readings.contains(&passage) {
+                readings.push(passage);
+            }
+        }
+        self.table_state.select(clamp_selection(
+            self.table_state.selected(),
+            self.rows.len(),
+        ));
+        self.journal_state.select(clamp_selection(
+            self.journal_state.selected(),
+            self.journal.len(),
+        ));
+    }
+
+    /// True while a journal entry is being written, so keys go to the editor
+    pub fn is_capturing_input(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        match self.view {
+            HistoryView::Readings => self.render_readings(frame, area),
+            HistoryView::Journal => self.render_journal(frame, area),
+        }
+        if let Some(editor) = self.editor.as_mut() {
+            editor.render(frame);
+        }
+    }
+
+    fn render_readings(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Reading History ({} readings)", self.rows.len()));
+
+        if self.rows.is_empty() {
+            let empty = Paragraph::new(
+                "Nothing recorded yet. Press r on the dashboard to record a reading.",
+            )
+            .style(Style::default().fg(Color::Gray))
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let header = Row::new(vec!["Date", "Passage", "Count", "Notes"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows: Vec<Row> = self
+            .rows
+            .iter()
+            .map(|(_, row)| Row::new(row.clone()))
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(24),
+                Constraint::Length(5),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header)
+        .column_spacing(3)
+        .block(block)
+        .row_highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        // Borders and the header row take three lines
+        self.page_size = (area.height.saturating_sub(3) as usize).max(1);
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+    }
+
+    fn render_journal(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Journal ({} entries)", self.journal.len()));
+        if self.journal.is_empty() {
+            let empty = Paragraph::new(
+                "No journal entries yet. Press J to write today's, or e on a reading to write about that day.",
+            )
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: true })
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(16), Constraint::Min(0)])
+            .split(area);
+        let items: Vec<ListItem> = self
+            .journal
+            .iter()
+            .map(|(date, _)| ListItem::new(date.format("%Y-%m-%d").to_string()))
+            .collect();
+        let list = List::new(items).block(block).highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+        // Borders take two lines
+        self.page_size = (area.height.saturating_sub(2) as usize).max(1);
+        frame.render_stateful_widget(list, chunks[0], &mut self.journal_state);
+
+        let Some((date, text)) = self
+            .journal_state
+            .selected()
+            .and_then(|idx| self.journal.get(idx))
+        else {
+            return;
+        };
+        let readings = self
+            .readings_by_date
+            .get(date)
+            .map_or("nothing recorded".to_string(), |readings| {
+                readings.join(", ")
+            });
+        let mut lines = vec![
+            Line::styled(
+                format!("Read: {}", readings),
+                Style::default().fg(Color::Gray),
+            ),
+            Line::default(),
+        ];
+        lines.extend(text.lines().map(|line| Line::raw(line.to_string())));
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(date.format("%A, %B %-d, %Y").to_string()),
+            ),
+            chunks[1],
+        );
+    }
+
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> HistoryAction {
+        if let Some(editor) = self.editor.as_mut() {
+            return match editor.handle_key(key) {
+                JournalAction::None => HistoryAction::None,
+                JournalAction::Cancel => {
+                    self.editor = None;
+                    HistoryAction::None
+                }
+                JournalAction::Save { date, text } => {
+                    self.editor = None;
+                    HistoryAction::SaveJournal { date, text }
+                }
+            };
+        }
+        match key.code {
+            crossterm::event::KeyCode::Char('j') => {
+                self.view = match self.view {
+                    HistoryView::Readings => HistoryView::Journal,
+                    HistoryView::Journal => HistoryView::Readings,
+                };
+                return HistoryAction::None;
+            }
+            crossterm::event::KeyCode::Char('J') => {
+                self.edit_journal(Utc::now().date_naive());
+                return HistoryAction::None;
+            }
+            crossterm::event::KeyCode::Char('e') | crossterm::event::KeyCode::Enter => {
+                if let Some(date) = self.selected_date() {
+                    self.edit_journal(date);
+                }
+                return HistoryAction::None;
+            }
+            _ => {}
+        }
+
+        let (len, selected) = match self.view {
+            HistoryView::Readings => (self.rows.len(), self.table_state.selected()),
+            HistoryView::Journal => (self.journal.len(), self.journal_state.selected()),
+        };
+        if len == 0 {
+            return HistoryAction::None;
+        }
+        let last = len - 1;
+        let selected = selected.unwrap_or(0);
+        let target = match key.code {
+            crossterm::event::KeyCode::Up => selected.saturating_sub(1),
+            crossterm::event::KeyCode::Down => selected + 1,
+            crossterm::event::KeyCode::PageUp => selected.saturating_sub(self.page_size),
+            crossterm::event::KeyCode::PageDown => selected + self.page_size,
+            crossterm::event::KeyCode::Home => 0,
+            crossterm::event::KeyCode::End => last,
+            _ => return HistoryAction::None,
+        };
+        match self.view {
+            HistoryView::Readings => self.table_state.select(Some(target.min(last))),
+            HistoryView::Journal => self.journal_state.select(Some(target.min(last))),
+        }
+        HistoryAction::None
+    }
+
+    /// Day of the selected reading or journal entry
+    fn selected_date(&self) -> Option<NaiveDate> {
+        match self.view {
+            HistoryView::Readings => self
+                .table_state
+                .selected()
+                .and_then(|idx| self.rows.get(idx))
+                .map(|(date, _)| *date),
+            HistoryView::Journal => self
+                .journal_state
+                .selected()
+                .and_then(|idx| self.journal.get(idx))
+                .map(|(date, _)| *date),
+        }
+    }
+
+    fn edit_journal(&mut self, date: NaiveDate) {
+        let entry = self
+            .journal
+            .iter()
+            .find(|(entry_date, _)| *entry_date == date)
+            .map_or("", |(_, text)| text.as_str());
+        let readings = self
+            .readings_by_date
+            .get(&date)
+            .cloned()
+            .unwrap_or_default();
+        self.editor = Some(JournalEditor::new(date, readings, entry));
+    }
+}
+
+/// Keep a selection on an item that exists, selecting the first one if needed
+fn clamp_selection(selected: Option<usize>, len: usize) -> Option<usize> {
+    match (selected, len) {
+        (_, 0) => None,
+        (None, _) => Some(0),
+        (Some(idx), len) => Some(idx.min(len - 1)),
+    }
+}
+
+/// How a reading was taken in, and its tags and note, e.g. "listened ESV #morning before work"
+pub(crate) fn format_event_notes(event: &ReadingEvent) -> String {
+    let source = (!event.source.is_read()).then(|| event.source.name().to_lowercase());
+    source
+        .into_iter()
+        .chain(event.translation.clone())
+        .chain(event.tags.iter().map(|tag| format!("#{}", tag)))
+        .chain(event.note.clone())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Format a reading as "John 3" for a whole chapter, or "John 3:1-16" otherwise
+pub(crate) fn format_event_passage(bible: &BibleStructure, event: &ReadingEvent) -> String {
+    let max_verse = bible
+        .ot
+        .get(event.book.as_str())
+        .or_else(|| bible.nt.get(event.book.as_str()))
+        .and_then(|chapters| {
+            event
+                .chapter
+                .checked_sub(1)
+                .and_then(|i| chapters.get(i as usize).copied())
+        });
+
+    if event.verse_start == 1 && Some(event.verse_end) == max_verse {
+        format!("{} {}", event.book, event.chapter)
+    } else if event.verse_start == event.verse_end {
+        format!("{} {}:{}", event.book, event.chapter, event.verse_start)
+    } else {
+        format!(
+            "{} {}:{}-{}",
+            event.book, event.chapter, event.verse_start, event.verse_end
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn passages_are_formatted_even_from_a_bad_chapter() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("John", 3, 1, 36);
+        progress.record_reading("John", 3, 1, 16);
+        let mut event = progress.history[0].clone();
+        assert_eq!(format_event_passage(bible, &event), "John 3");
+        assert_eq!(
+            format_event_passage(bible, &progress.history[1]),
+            "John 3:1-16"
+        );
+
+        // A hand-edited history file can hold a chapter 0
+        event.chapter = 0;
+        assert_eq!(format_event_passage(bible, &event), "John 0:1-36");
+    }
+}
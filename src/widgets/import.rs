@@ -0,0 +1,388 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::bible_structure::BibleStructure;
+use crate::progress::ReadingProgress;
+use crate::reference::parse_passage;
+use crate::widgets::form::{self, Severity};
+use crate::widgets::help::KeyBinding;
+use crate::widgets::layout::{responsive_stack, Section};
+use crate::widgets::text_input::TextInput;
+
+/// Keybindings available in Import mode, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    (
+        "Enter",
+        "Load the file at the typed path, or add the typed \"date reference\" line",
+    ),
+    (
+        "Ctrl+S",
+        "Record every valid line and return to the dashboard",
+    ),
+    ("Ctrl+O", "Browse for a file to load"),
+    ("↑/↓", "Select a parsed line"),
+    ("Ctrl+D", "Remove the selected line"),
+    ("Esc", "Cancel and return to the dashboard"),
+];
+
+/// A reading parsed from an import line such as "2024-11-02  John 3:16-4:2"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedReading {
+    pub date: NaiveDate,
+    /// The passage as it will be recorded, e.g. "John 3:16-4:2"
+    pub label: String,
+    pub book: String,
+    /// Verses per chapter, as (chapter, verse_start, verse_end)
+    pub ranges: Vec<(u32, u32, u32)>,
+}
+
+/// A line to import, with what it parsed to or why it couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportLine {
+    pub text: String,
+    pub parsed: Result<ImportedReading, String>,
+}
+
+/// Action requested by the import form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportAction {
+    None,
+    Cancel,
+    /// Record the valid lines
+    Import,
+    /// Browse for a file to load
+    PickFile,
+}
+
+/// Form for adding many readings at once, from a text file or pasted lines of
+/// `date  reference`
+pub struct ImportWidget {
+    /// A file path, or a single line to import
+    pub input: TextInput,
+    pub lines: Vec<ImportLine>,
+    list_state: ListState,
+    pub error_message: Option<String>,
+}
+
+impl Default for ImportWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportWidget {
+    pub fn new() -> Self {
+        Self {
+            input: TextInput::new(),
+            lines: Vec::new(),
+            list_state: ListState::default(),
+            error_message: None,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = responsive_stack(
+            area,
+            &[
+                Section::fixed(3).optional(1), // Header
+                Section::fixed(3),             // Input
+                Section::fill(3),              // Parsed lines
+                Section::fixed(3),             // Message
+            ],
+            1,
+        );
+
+        let header = Paragraph::new("Import Readings")
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            );
+        frame.render_widget(header, chunks[0]);
+
+        frame.render_widget(
+            form::field(
+                "File path, or a line such as \"2024-11-02  John 3\" (paste several to add them all)",
+                &self.input,
+                true,
+            ),
+            chunks[1],
+        );
+        self.input.set_cursor(frame, chunks[1]);
+
+        let items: Vec<ListItem> = self
+            .lines
+            .iter()
+            .map(|line| match &line.parsed {
+                Ok(reading) => ListItem::new(Line::from(vec![
+                    Span::styled("✓ ", Style::default().fg(Color::Green)),
+                    Span::raw(line.text.clone()),
+                    Span::styled(
+                        format!("  → {}, {}", reading.label, reading.date),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ])),
+                Err(e) => ListItem::new(Line::from(vec![
+                    Span::styled("✗ ", Style::default().fg(Color::Red)),
+                    Span::styled(line.text.clone(), Style::default().fg(Color::Red)),
+                    Span::styled(format!("  {}", e), Style::default().fg(Color::Yellow)),
+                ])),
+            })
+            .collect();
+        let valid = self.valid_count();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "Lines to import ({} valid, {} with errors)",
+                valid,
+                self.lines.len() - valid
+            )))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, chunks[2], &mut self.list_state);
+
+        let message = match &self.error_message {
+            Some(error) => form::message(Severity::Error, "Error", error.as_str()),
+            None if valid > 0 => form::message(
+                Severity::Info,
+                "Ready",
+                format!(
+                    "Ctrl+S records the {} valid line{}; lines with errors are skipped",
+                    valid,
+                    if valid == 1 { "" } else { "s" }
+                ),
+            ),
+            None => form::message(
+                Severity::Help,
+                "Help",
+                "Type a file path and press Enter (or Ctrl+O to browse) to load it, or paste lines of \"date  reference\"",
+            ),
+        };
+        frame.render_widget(message, chunks[3]);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, bible: &BibleStructure) -> ImportAction {
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) => ImportAction::Cancel,
+            (KeyModifiers::CONTROL, KeyCode::Char('s')) => {
+                if self.valid_count() == 0 {
+                    self.error_message = Some("Nothing valid to import yet".to_string());
+                    ImportAction::None
+                } else {
+                    ImportAction::Import
+                }
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('o')) => ImportAction::PickFile,
+            (KeyModifiers::CONTROL, KeyCode::Char('d')) => {
+                if let Some(selected) = self
+                    .list_state
+                    .selected()
+                    .filter(|&selected| selected < self.lines.len())
+                {
+                    self.lines.remove(selected);
+                    self.list_state.select(if self.lines.is_empty() {
+                        None
+                    } else {
+                        Some(selected.min(self.lines.len() - 1))
+                    });
+                }
+                ImportAction::None
+            }
+            (_, KeyCode::Up) => {
+                self.list_state.select_previous();
+                ImportAction::None
+            }
+            (_, KeyCode::Down) => {
+                if let Some(selected) = self.list_state.selected() {
+                    self.list_state
+                        .select(Some((selected + 1).min(self.lines.len().saturating_sub(1))));
+                }
+                ImportAction::None
+            }
+            (_, KeyCode::Enter) => {
+                self.submit(bible);
+                ImportAction::None
+            }
+            _ => {
+                if self.input.handle_key(key, |c| !c.is_control()) {
+                    self.error_message = None;
+                }
+                ImportAction::None
+            }
+        }
+    }
+
+    /// Add the typed line, or load the lines of the typed file
+    fn submit(&mut self, bible: &BibleStructure) {
+        let text = self.input.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let starts_with_date = text
+            .split_whitespace()
+            .next()
+            .is_some_and(|first| NaiveDate::parse_from_str(first, "%Y-%m-%d").is_ok());
+        if starts_with_date {
+            self.add_lines(bible, &text);
+        } else {
+            self.load_file(bible, &expand_home(&text));
+            if self.error_message.is_some() {
+                return;
+            }
+        }
+        self.input.clear();
+        self.error_message = None;
+    }
+
+    /// Add the lines of the file at `path`
+    pub fn load_file(&mut self, bible: &BibleStructure, path: &Path) {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                self.add_lines(bible, &content);
+                self.error_message = None;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Couldn't read {}: {}", path.display(), e));
+            }
+        }
+    }
+
+    /// Parse each line of `text`, skipping blank lines and `#` comments
+    fn add_lines(&mut self, bible: &BibleStructure, text: &str) {
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            self.lines.push(ImportLine {
+                text: line.to_string(),
+                parsed: parse_import_line(bible, line),
+            });
+        }
+        if !self.lines.is_empty() && self.list_state.selected().is_none() {
+            self.list_state.select(Some(0));
+        }
+    }
+
+    fn valid_count(&self) -> usize {
+        self.lines.iter().filter(|line| line.parsed.is_ok()).count()
+    }
+
+    /// Record every valid line, returning how many were recorded
+    pub fn import(&mut self, progress: &mut ReadingProgress) -> usize {
+        let mut imported = 0;
+        for line in self.lines.drain(..) {
+            if let Ok(reading) = line.parsed {
+                for (chapter, verse_start, verse_end) in reading.ranges {
+                    progress.record_reading_on(
+                        &reading.book,
+                        chapter,
+                        verse_start,
+                        verse_end,
+                        1,
+                        reading.date,
+                    );
+                }
+                imported += 1;
+            }
+        }
+        self.list_state.select(None);
+        imported
+    }
+}
+
+/// Parse a line such as "2024-11-02  John 3:16-4:2": a date, then a passage in any form the
+/// Passage field accepts
+pub fn parse_import_line(bible: &BibleStructure, line: &str) -> Result<ImportedReading, String> {
+    let (date, reference) = line
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| "Expected a date and a passage".to_string())?;
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}. Expected YYYY-MM-DD", date))?;
+    let passage = parse_passage(bible, reference.trim())?;
+    Ok(ImportedReading {
+        date,
+        label: passage.to_string(),
+        ranges: passage.chapter_ranges(bible),
+        book: passage.book,
+    })
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ if path == "~" => dirs::home_dir().unwrap_or_else(|| PathBuf::from(path)),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+
+    #[test]
+    fn lines_are_parsed_and_valid_ones_imported() {
+        let bible = get_bible_structure();
+        let mut import = ImportWidget::new();
+        import.add_lines(
+            bible,
+            "# my old log\n2024-11-02  John 3\n\n2024-11-03 ps 23-24\n2024-13-01 John 4\nyesterday Romans 8\n",
+        );
+
+        assert_eq!(import.lines.len(), 4);
+        assert_eq!(
+            import.lines[1].parsed.as_ref().unwrap().label,
+            "Psalms 23-24"
+        );
+        assert_eq!(
+            import.lines[2].parsed,
+            Err("Invalid date: 2024-13-01. Expected YYYY-MM-DD".to_string())
+        );
+        assert!(import.lines[3].parsed.is_err());
+
+        let mut progress = ReadingProgress::new();
+        assert_eq!(import.import(&mut progress), 2);
+        let dates: Vec<String> = progress
+            .history
+            .iter()
+            .map(|event| format!("{} {} {}", event.date, event.book, event.chapter))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                "2024-11-02 John 3",
+                "2024-11-03 Psalms 23",
+                "2024-11-03 Psalms 24"
+            ]
+        );
+    }
+
+    #[test]
+    fn typed_lines_are_added_one_at_a_time() {
+        let bible = get_bible_structure();
+        let mut import = ImportWidget::new();
+        for c in "2024-11-02 jude".chars() {
+            import.handle_key(KeyEvent::from(KeyCode::Char(c)), bible);
+        }
+        import.handle_key(KeyEvent::from(KeyCode::Enter), bible);
+        assert!(import.input.is_empty());
+        assert_eq!(import.lines[0].parsed.as_ref().unwrap().label, "Jude");
+
+        for c in "/no/such/file".chars() {
+            import.handle_key(KeyEvent::from(KeyCode::Char(c)), bible);
+        }
+        import.handle_key(KeyEvent::from(KeyCode::Enter), bible);
+        assert!(import.error_message.is_some());
+        assert_eq!(import.lines.len(), 1);
+    }
+}
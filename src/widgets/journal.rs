@@ -0,0 +1,92 @@
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::widgets::help::KeyBinding;
+use crate::widgets::popup::centered_rect;
+use crate::widgets::text_area::TextArea;
+
+/// Keybindings of the journal editor, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("Ctrl+S", "Save the entry (an empty entry is removed)"),
+    ("Esc", "Close without saving"),
+];
+
+/// Action requested by the journal editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalAction {
+    None,
+    Cancel,
+    Save { date: NaiveDate, text: String },
+}
+
+/// Popup for writing the journal entry of one day, listing what was read that day above it
+pub struct JournalEditor {
+    date: NaiveDate,
+    /// The day's readings, e.g. "John 3"
+    readings: Vec<String>,
+    text: TextArea,
+}
+
+impl JournalEditor {
+    pub fn new(date: NaiveDate, readings: Vec<String>, entry: &str) -> Self {
+        Self {
+            date,
+            readings,
+            text: TextArea::from(entry),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> JournalAction {
+        match key.code {
+            KeyCode::Esc => JournalAction::Cancel,
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                JournalAction::Save {
+                    date: self.date,
+                    text: self.text.text(),
+                }
+            }
+            _ => {
+                self.text.handle_key(key);
+                JournalAction::None
+            }
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let popup_area = centered_rect(70, 60, frame.area());
+        frame.render_widget(Clear, popup_area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)])
+            .split(popup_area);
+
+        let readings = if self.readings.is_empty() {
+            "Nothing recorded this day".to_string()
+        } else {
+            self.readings.join(", ")
+        };
+        frame.render_widget(
+            Paragraph::new(readings)
+                .style(Style::default().fg(Color::Gray))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!(
+                            "Journal for {}",
+                            self.date.format("%A, %B %-d, %Y")
+                        ))
+                        .border_style(Style::default().fg(Color::Cyan)),
+                ),
+            chunks[0],
+        );
+        self.text.render(
+            frame,
+            chunks[1],
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Entry (Ctrl+S: save, Esc: cancel)")
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+    }
+}
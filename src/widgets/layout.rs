@@ -0,0 +1,235 @@
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::i18n::{tr, trf};
+
+/// Smallest terminal the app draws into; anything smaller shows a notice instead
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 10;
+
+/// One section of a vertical layout that adapts to the available height
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section {
+    /// Preferred height
+    height: u16,
+    /// Height the section can shrink to before other sections start being hidden
+    min_height: u16,
+    /// Optional sections are hidden on short terminals, lowest rank first
+    drop_rank: Option<u8>,
+    /// Takes the height left over once every section has its preferred height
+    fill: bool,
+}
+
+impl Section {
+    /// A section that always wants `height` rows
+    pub const fn fixed(height: u16) -> Self {
+        Self {
+            height,
+            min_height: height,
+            drop_rank: None,
+            fill: false,
+        }
+    }
+
+    /// A section that prefers `height` rows but can shrink to `min_height`
+    pub const fn shrinkable(height: u16, min_height: u16) -> Self {
+        Self {
+            height,
+            min_height,
+            drop_rank: None,
+            fill: false,
+        }
+    }
+
+    /// A section that takes the leftover height, but at least `min_height` rows
+    pub const fn fill(min_height: u16) -> Self {
+        Self {
+            height: min_height,
+            min_height,
+            drop_rank: None,
+            fill: true,
+        }
+    }
+
+    /// Allow hiding this section when the terminal is too short, lowest `rank` first
+    pub const fn optional(mut self, rank: u8) -> Self {
+        self.drop_rank = Some(rank);
+        self
+    }
+}
+
+/// Lay `sections` out top to bottom in `area`. When they don't fit, shrinkable sections shrink
+/// first, then optional sections are hidden, and finally the layout scrolls so the `focus`
+/// section stays visible. Sections that aren't shown get an empty `Rect`, which widgets draw
+/// nothing into and mouse clicks never hit.
+pub fn responsive_stack(area: Rect, sections: &[Section], focus: usize) -> Vec<Rect> {
+    let available = area.height;
+    let mut heights: Vec<Option<u16>> = sections.iter().map(|s| Some(s.height)).collect();
+    let total = |heights: &[Option<u16>]| heights.iter().flatten().map(|&h| h as u32).sum::<u32>();
+
+    // Shrink sections towards their minimum height, in order
+    for (idx, section) in sections.iter().enumerate() {
+        let excess = total(&heights).saturating_sub(available as u32);
+        if excess == 0 {
+            break;
+        }
+        let shrink = (section.height - section.min_height).min(excess as u16);
+        heights[idx] = Some(section.height - shrink);
+    }
+
+    // Hide optional sections, lowest rank first, but never the focused one
+    let mut optional: Vec<(u8, usize)> = sections
+        .iter()
+        .enumerate()
+        .filter(|&(idx, _)| idx != focus)
+        .filter_map(|(idx, section)| section.drop_rank.map(|rank| (rank, idx)))
+        .collect();
+    optional.sort();
+    for (_, idx) in optional {
+        if total(&heights) <= available as u32 {
+            break;
+        }
+        heights[idx] = None;
+    }
+
+    if total(&heights) <= available as u32 {
+        // Give the leftover rows to the first fill section
+        let leftover = available - total(&heights) as u16;
+        if let Some(idx) =
+            (0..sections.len()).find(|&idx| sections[idx].fill && heights[idx].is_some())
+        {
+            heights[idx] = heights[idx].map(|h| h + leftover);
+        }
+    } else {
+        scroll_to_focus(&mut heights, focus, available);
+    }
+
+    let mut y = area.y;
+    heights
+        .into_iter()
+        .map(|height| {
+            let height = height.unwrap_or(0);
+            let rect = Rect::new(area.x, y, area.width, height);
+            y += height;
+            rect
+        })
+        .collect()
+}
+
+/// Keep only a window of consecutive sections that fits in `available` rows and contains the
+/// focused section (clipping it if it alone is taller than the window)
+fn scroll_to_focus(heights: &mut [Option<u16>], focus: usize, available: u16) {
+    let focus = focus.min(heights.len().saturating_sub(1));
+    let sum = |range: std::ops::RangeInclusive<usize>| {
+        heights[range]
+            .iter()
+            .flatten()
+            .map(|&h| h as u32)
+            .sum::<u32>()
+    };
+
+    let mut start = 0;
+    while start < focus && sum(start..=focus) > available as u32 {
+        start += 1;
+    }
+
+    let mut used: u32 = 0;
+    for (idx, height) in heights.iter_mut().enumerate() {
+        let Some(h) = *height else {
+            continue;
+        };
+        if idx < start || used >= available as u32 {
+            *height = None;
+        } else if used + h as u32 > available as u32 {
+            if idx == focus {
+                *height = Some(available - used as u16);
+                used = available as u32;
+            } else {
+                *height = None;
+                used = available as u32;
+            }
+        } else {
+            used += h as u32;
+        }
+    }
+}
+
+/// True if the terminal is too small to draw the app
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Replace the whole screen with a notice asking for a bigger terminal
+pub fn render_too_small(frame: &mut Frame, area: Rect) {
+    let lines = vec![
+        Line::styled(
+            tr("Terminal too small"),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::raw(trf(
+            "Need {}x{}, have {}x{}",
+            &[&MIN_WIDTH, &MIN_HEIGHT, &area.width, &area.height],
+        )),
+        Line::styled(
+            tr("Enlarge the window to continue"),
+            Style::default().fg(Color::Gray),
+        ),
+    ];
+    let top = area.y + area.height.saturating_sub(lines.len() as u16) / 2;
+    let notice_area = Rect::new(area.x, top, area.width, area.bottom().saturating_sub(top));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        notice_area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heights(rects: &[Rect]) -> Vec<u16> {
+        rects.iter().map(|rect| rect.height).collect()
+    }
+
+    fn form() -> Vec<Section> {
+        vec![
+            Section::fixed(3).optional(2), // header
+            Section::fixed(3),             // input
+            Section::shrinkable(8, 3),     // list
+            Section::fixed(3),             // input
+            Section::fill(3).optional(0),  // help
+            Section::fixed(3).optional(1), // footer
+        ]
+    }
+
+    #[test]
+    fn fill_section_takes_leftover_height() {
+        let rects = responsive_stack(Rect::new(0, 0, 80, 40), &form(), 1);
+        assert_eq!(heights(&rects), vec![3, 3, 8, 3, 20, 3]);
+        assert_eq!(rects[5].bottom(), 40);
+    }
+
+    #[test]
+    fn shrinks_then_hides_optional_sections() {
+        let rects = responsive_stack(Rect::new(0, 0, 80, 20), &form(), 1);
+        assert_eq!(heights(&rects), vec![3, 3, 5, 3, 3, 3]);
+
+        let rects = responsive_stack(Rect::new(0, 0, 80, 11), &form(), 1);
+        assert_eq!(heights(&rects), vec![0, 3, 3, 3, 0, 0]);
+    }
+
+    #[test]
+    fn scrolls_to_keep_focus_visible() {
+        let rects = responsive_stack(Rect::new(0, 0, 80, 6), &form(), 3);
+        assert_eq!(heights(&rects), vec![0, 0, 3, 3, 0, 0]);
+        assert_eq!(rects[2].y, 0);
+        assert_eq!(rects[3].y, 3);
+    }
+}
@@ -0,0 +1,138 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::memorize::MAX_GRADE;
+use crate::widgets::help::KeyBinding;
+use crate::widgets::popup::centered_rect;
+
+/// Keybindings available in the memory review, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("Space/Enter", "Reveal the passage"),
+    (
+        "0-5",
+        "Grade your recall, from 0 (forgotten) to 5 (perfect)",
+    ),
+    ("Esc", "Stop reviewing"),
+];
+
+/// Action requested by the memory review
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryReviewAction {
+    None,
+    Close,
+    /// Schedule the card at this index of the progress file's memorization list
+    Grade {
+        card: usize,
+        grade: u8,
+    },
+}
+
+/// A passage to recall
+pub struct ReviewCard {
+    /// Index in the progress file's memorization list
+    pub card: usize,
+    pub reference: String,
+    /// Text of the passage, when a scripture text file is configured
+    pub text: Option<String>,
+}
+
+/// Popup going through the passages due for review: each reference is shown first, and the
+/// passage is revealed once you have recited it
+pub struct MemoryReview {
+    cards: Vec<ReviewCard>,
+    position: usize,
+    revealed: bool,
+}
+
+impl MemoryReview {
+    pub fn new(cards: Vec<ReviewCard>) -> Self {
+        Self {
+            cards,
+            position: 0,
+            revealed: false,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let popup_area = centered_rect(60, 50, frame.area());
+        frame.render_widget(Clear, popup_area);
+
+        let label = Style::default().fg(Color::Gray);
+        let (title, lines) = match self.cards.get(self.position) {
+            None => (
+                "Memory review".to_string(),
+                vec![
+                    Line::from("Nothing else is due for review today."),
+                    Line::default(),
+                    Line::styled("Press any key to close", label),
+                ],
+            ),
+            Some(card) => {
+                let title = format!(
+                    "Memory review ({} of {})",
+                    self.position + 1,
+                    self.cards.len()
+                );
+                let mut lines = vec![
+                    Line::styled(
+                        card.reference.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Line::default(),
+                ];
+                if self.revealed {
+                    lines.push(Line::from(
+                        card.text
+                            .clone()
+                            .unwrap_or_else(|| "Check the passage in your Bible.".to_string()),
+                    ));
+                    lines.push(Line::default());
+                    lines.push(Line::styled(
+                        "How well did you recall it? 0 (forgotten) to 5 (perfect)",
+                        label,
+                    ));
+                } else {
+                    lines.push(Line::styled(
+                        "Recite the passage, then press Space to reveal it",
+                        label,
+                    ));
+                }
+                (title, lines)
+            }
+        };
+
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(title),
+        );
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> MemoryReviewAction {
+        let Some(card) = self.cards.get(self.position) else {
+            return MemoryReviewAction::Close;
+        };
+        match key.code {
+            KeyCode::Esc => MemoryReviewAction::Close,
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                self.revealed = true;
+                MemoryReviewAction::None
+            }
+            KeyCode::Char(c) if self.revealed => match c.to_digit(10) {
+                Some(grade) if grade <= MAX_GRADE as u32 => {
+                    let card = card.card;
+                    self.position += 1;
+                    self.revealed = false;
+                    MemoryReviewAction::Grade {
+                        card,
+                        grade: grade as u8,
+                    }
+                }
+                _ => MemoryReviewAction::None,
+            },
+            _ => MemoryReviewAction::None,
+        }
+    }
+}
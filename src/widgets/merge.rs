@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::merge::{BookConflict, ChapterSide, Resolution};
+use crate::widgets::help::KeyBinding;
+use crate::widgets::popup::centered_rect;
+
+/// Keybindings available in the merge view, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("↑/↓", "Select a book"),
+    ("←/→", "Change which side wins for the book"),
+    (
+        "m/t/x",
+        "Keep mine, take theirs, or keep the most read of each verse",
+    ),
+    ("Enter/Ctrl+S", "Merge and save"),
+    ("Esc", "Cancel without changing anything"),
+];
+
+/// Action requested by the merge view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeAction {
+    None,
+    Cancel,
+    /// Merge with the chosen resolution for each conflicting book
+    Apply(HashMap<String, Resolution>),
+}
+
+/// Popup showing the books two progress files read differently, side by side, so the user
+/// chooses per book which side wins
+pub struct MergeWidget {
+    /// Name of the other file, for the title
+    source: String,
+    conflicts: Vec<BookConflict>,
+    resolutions: Vec<Resolution>,
+    state: ListState,
+}
+
+fn side_label(side: &ChapterSide, verses: u32) -> String {
+    if side.verses_read == 0 {
+        return "unread".to_string();
+    }
+    let date = side
+        .last_read
+        .map(|date: NaiveDate| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    format!(
+        "{}/{} verses, up to {}× · {}",
+        side.verses_read, verses, side.most_read, date
+    )
+}
+
+impl MergeWidget {
+    pub fn new(source: String, conflicts: Vec<BookConflict>) -> Self {
+        let resolutions = vec![Resolution::default(); conflicts.len()];
+        Self {
+            source,
+            conflicts,
+            resolutions,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    fn resolutions(&self) -> HashMap<String, Resolution> {
+        self.conflicts
+            .iter()
+            .zip(&self.resolutions)
+            .map(|(conflict, resolution)| (conflict.book.clone(), *resolution))
+            .collect()
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let popup_area = centered_rect(80, 70, frame.area());
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(
+                "Merge {} (←/→: choose, Enter: merge, Esc: cancel)",
+                self.source
+            ));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [books_area, diff_area] =
+            Layout::horizontal([Constraint::Length(30), Constraint::Min(0)]).areas(inner);
+
+        let items: Vec<ListItem> = self
+            .conflicts
+            .iter()
+            .zip(&self.resolutions)
+            .map(|(conflict, resolution)| {
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{:<16}", conflict.book)),
+                    Span::styled(
+                        format!("◂ {:<6} ▸", resolution.name()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::RIGHT))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        frame.render_stateful_widget(list, books_area, &mut self.state);
+
+        let Some(conflict) = self.state.selected().and_then(|i| self.conflicts.get(i)) else {
+            return;
+        };
+        let rows = conflict.chapters.iter().map(|diff| {
+            Row::new(vec![
+                Cell::from(diff.chapter.to_string()),
+                Cell::from(side_label(&diff.mine, diff.verses)),
+                Cell::from(side_label(&diff.theirs, diff.verses)),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ],
+        )
+        .header(
+            Row::new(vec!["Chapter", "Mine", "Theirs"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::default().padding(Padding::horizontal(1)));
+        frame.render_widget(table, diff_area);
+    }
+
+    fn choose(&mut self, book: usize, choice: fn(Resolution) -> Resolution) -> MergeAction {
+        if let Some(resolution) = self.resolutions.get_mut(book) {
+            *resolution = choice(*resolution);
+        }
+        MergeAction::None
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> MergeAction {
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc => MergeAction::Cancel,
+            KeyCode::Enter => MergeAction::Apply(self.resolutions()),
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                MergeAction::Apply(self.resolutions())
+            }
+            KeyCode::Left => self.choose(selected, Resolution::previous),
+            KeyCode::Right => self.choose(selected, Resolution::next),
+            KeyCode::Char('m') => self.choose(selected, |_| Resolution::Mine),
+            KeyCode::Char('t') => self.choose(selected, |_| Resolution::Theirs),
+            KeyCode::Char('x') => self.choose(selected, |_| Resolution::Max),
+            KeyCode::Up => {
+                self.state.select(Some(selected.saturating_sub(1)));
+                MergeAction::None
+            }
+            KeyCode::Down => {
+                self.state.select(Some(
+                    (selected + 1).min(self.conflicts.len().saturating_sub(1)),
+                ));
+                MergeAction::None
+            }
+            _ => MergeAction::None,
+        }
+    }
+}
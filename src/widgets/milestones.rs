@@ -0,0 +1,35 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::milestones::Milestone;
+use crate::widgets::popup::centered_rect;
+
+/// Popup celebrating the milestones a save just reached; any key closes it
+pub fn render_milestones(frame: &mut Frame, milestones: &[Milestone]) {
+    let popup_area = centered_rect(50, 30, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let mut lines = vec![Line::default()];
+    lines.extend(milestones.iter().map(|milestone| {
+        Line::styled(
+            format!("🎉 {}!", milestone.kind.description()),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    }));
+    lines.push(Line::default());
+    lines.push(Line::styled(
+        "Well done. Keep going!",
+        Style::default().fg(Color::Gray),
+    ));
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green))
+                .title("Milestone reached (press any key to close)"),
+        );
+    frame.render_widget(paragraph, popup_area);
+}
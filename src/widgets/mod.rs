@@ -1,4 +1,37 @@
+pub mod accessibility;
+pub mod batch_menu;
+pub mod chapter_grid;
+pub mod chapter_readings;
+pub mod chapter_ticker;
+pub mod command_palette;
 pub mod dashboard;
-pub mod manual_add;
-pub mod record;
+pub mod entry;
+pub mod file_picker;
+pub mod form;
+pub mod gradient;
+pub mod help;
+pub mod history;
+pub mod import;
+pub mod journal;
+pub mod layout;
+pub mod memorize;
+pub mod merge;
+pub mod milestones;
+pub mod passage_preview;
+pub mod plans;
+pub mod popup;
+pub mod quit_dialog;
+pub mod recovery;
+pub mod settings;
+#[cfg(test)]
+pub(crate) mod snapshot;
+pub mod stats;
+pub mod status_bar;
+pub mod tabs;
+pub mod text_area;
+pub mod text_input;
+pub mod toast;
+pub mod today;
 pub mod tree_builder;
+pub mod tree_state;
+pub mod year_review;
@@ -0,0 +1,84 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::widgets::popup::centered_rect;
+
+/// Scrollable popup showing the text of a chapter
+pub struct PassagePreview {
+    title: String,
+    lines: Vec<Line<'static>>,
+    scroll: u16,
+    /// Number of lines visible in the last render, used for paging
+    page_size: u16,
+}
+
+impl PassagePreview {
+    /// Preview of `verses`, with the verses in `highlight` (start, end) emphasized
+    pub fn new(title: String, verses: &[(u32, String)], highlight: Option<(u32, u32)>) -> Self {
+        let lines = verses
+            .iter()
+            .map(|(verse, text)| {
+                let style = match highlight {
+                    Some((start, end)) if (start..=end).contains(verse) => {
+                        Style::default().fg(Color::Yellow)
+                    }
+                    _ => Style::default(),
+                };
+                Line::from(vec![
+                    Span::styled(format!("{} ", verse), Style::default().fg(Color::Cyan)),
+                    Span::styled(text.clone(), style),
+                ])
+            })
+            .collect();
+        Self {
+            title,
+            lines,
+            scroll: 0,
+            page_size: 1,
+        }
+    }
+
+    /// Popup explaining why no text can be shown
+    pub fn message(title: String, message: String) -> Self {
+        Self {
+            title,
+            lines: vec![Line::styled(message, Style::default().fg(Color::Gray))],
+            scroll: 0,
+            page_size: 1,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let popup_area = centered_rect(80, 80, frame.area());
+        frame.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!("{} (↑↓: scroll, v/Esc: close)", self.title))
+            .padding(Padding::horizontal(1));
+        let inner = block.inner(popup_area);
+        self.page_size = inner.height.max(1);
+
+        let paragraph = Paragraph::new(self.lines.clone()).wrap(Wrap { trim: true });
+        // Stop scrolling once the last line is at the bottom of the popup
+        let max_scroll = (paragraph.line_count(inner.width) as u16).saturating_sub(inner.height);
+        self.scroll = self.scroll.min(max_scroll);
+        frame.render_widget(paragraph.scroll((self.scroll, 0)).block(block), popup_area);
+    }
+
+    /// Handle a key, returning true when the preview should close
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('v') | KeyCode::Esc => return true,
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.page_size),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(self.page_size),
+            KeyCode::Home => self.scroll = 0,
+            // Rendering clamps this to the last page
+            KeyCode::End => self.scroll = u16::MAX,
+            _ => {}
+        }
+        false
+    }
+}
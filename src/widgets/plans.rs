@@ -0,0 +1,554 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{Duration, NaiveDate};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::bible_structure::BibleStructure;
+use crate::plans::{
+    available_plans, describe_offset, schedule_offset, CatchUp, Plan, PlanId, PlanState,
+};
+use crate::progress::ReadingProgress;
+use crate::widgets::help::KeyBinding;
+use crate::widgets::popup::centered_rect;
+
+/// Keybindings available in the plans tab, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("←/→", "Select the previous/next day of the plan"),
+    ("PgUp/PgDn", "Move a week back or forward"),
+    ("t", "Jump to today's reading"),
+    ("↑/↓", "Select a chapter of the day"),
+    ("Enter", "Mark the selected chapter as read today"),
+    (
+        "r",
+        "Catch up when behind: spread, shift or double up the missed days",
+    ),
+    ("c", "Choose a plan to follow"),
+    ("?", "Show this help"),
+    ("q", "Quit"),
+];
+
+/// Action requested by the plans tab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlansAction {
+    None,
+    /// Follow a plan from the given start date
+    Start(PlanState),
+    /// Reschedule the missed days of the active plan
+    CatchUp(CatchUp),
+    /// Record the whole chapter as read today
+    MarkRead {
+        book: String,
+        chapter: u32,
+    },
+}
+
+/// The plan being followed, with how far along it is
+struct ActivePlan {
+    plan: Plan,
+    start: NaiveDate,
+    /// Whether each item of each day is done
+    completion: Vec<Vec<bool>>,
+    /// Index of today's day in the plan
+    current_day: usize,
+}
+
+impl ActivePlan {
+    fn is_day_done(&self, day: usize) -> bool {
+        self.completion[day].iter().all(|&done| done)
+    }
+
+    /// "✓" for a finished day, "◐" for a started one and "·" for the rest
+    fn day_marker(&self, day: usize) -> (&'static str, Color) {
+        if self.is_day_done(day) {
+            ("✓", Color::Green)
+        } else if self.completion[day].iter().any(|&done| done) {
+            ("◐", Color::Yellow)
+        } else if day < self.current_day {
+            ("·", Color::Red)
+        } else {
+            ("·", Color::Gray)
+        }
+    }
+}
+
+/// Popup offering ways to reschedule missed days
+struct CatchUpMenu {
+    selected: usize,
+    /// Days to spread the missed days over, adjusted with ←/→
+    spread_days: usize,
+}
+
+impl CatchUpMenu {
+    fn strategies(&self) -> [CatchUp; 3] {
+        [
+            CatchUp::Spread {
+                days: self.spread_days,
+            },
+            CatchUp::Shift,
+            CatchUp::Weekends,
+        ]
+    }
+}
+
+/// The active reading plan's calendar and assignments, or a list of plans to choose from
+pub struct PlansWidget {
+    active: Option<ActivePlan>,
+    /// Plans to pick from, listed when no plan is active or another is being chosen
+    choices: Option<Vec<(PlanId, Result<Plan, String>)>>,
+    choice_state: ListState,
+    /// Day of the plan whose chapters are listed
+    selected_day: usize,
+    item_state: ListState,
+    /// Why the active plan couldn't be loaded
+    error_message: Option<String>,
+    /// Where custom plan files are found
+    plans_dir: PathBuf,
+    catch_up: Option<CatchUpMenu>,
+}
+
+impl PlansWidget {
+    pub fn new(
+        bible: &BibleStructure,
+        progress: &ReadingProgress,
+        plans_dir: &Path,
+        today: NaiveDate,
+    ) -> Self {
+        let mut error_message = None;
+        let active = progress
+            .plan
+            .as_ref()
+            .and_then(|state| match state.plan.load(bible, plans_dir) {
+                Ok(plan) => Some((state, plan)),
+                Err(e) => {
+                    error_message = Some(e);
+                    None
+                }
+            })
+            .map(|(state, plan)| {
+                let plan = state.schedule(&plan);
+                ActivePlan {
+                    completion: plan.completion(state, &progress.history),
+                    current_day: state.current_day(&plan, today),
+                    start: state.start,
+                    plan,
+                }
+            });
+        let mut widget = Self {
+            selected_day: active.as_ref().map_or(0, |active| active.current_day),
+            choices: None,
+            choice_state: ListState::default().with_selected(Some(0)),
+            item_state: ListState::default(),
+            active,
+            error_message,
+            plans_dir: plans_dir.to_path_buf(),
+            catch_up: None,
+        };
+        if widget.active.is_none() {
+            widget.choices = Some(available_plans(bible, plans_dir));
+        }
+        widget.select_first_open_item();
+        widget
+    }
+
+    /// Rebuild from the current progress (e.g. after a chapter was marked read), keeping the
+    /// selected day and chapter
+    pub fn refresh(
+        &mut self,
+        bible: &BibleStructure,
+        progress: &ReadingProgress,
+        today: NaiveDate,
+    ) {
+        let (selected_day, selected_item) = (self.selected_day, self.item_state.selected());
+        let plans_dir = std::mem::take(&mut self.plans_dir);
+        *self = Self::new(bible, progress, &plans_dir, today);
+        if let Some(active) = &self.active {
+            self.selected_day = selected_day.min(active.plan.days.len() - 1);
+            self.item_state.select(selected_item);
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(choices) = &self.choices {
+            Self::render_choices(
+                frame,
+                area,
+                choices,
+                &mut self.choice_state,
+                self.error_message.as_deref(),
+            );
+            return;
+        }
+        let Some(active) = &self.active else {
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+        let offset = schedule_offset(&active.completion, active.current_day);
+        let (status, status_color) = match offset {
+            days if days < 0 => (
+                format!("{} (r: catch up)", describe_offset(days)),
+                Color::Yellow,
+            ),
+            days => (describe_offset(days), Color::Green),
+        };
+        let header = Paragraph::new(Line::from(vec![
+            Span::styled(
+                active.plan.name.clone(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "  ·  Day {} of {}  ·  ",
+                active.current_day + 1,
+                active.plan.days.len()
+            )),
+            Span::styled(status, Style::default().fg(status_color)),
+        ]))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Reading Plan"));
+        frame.render_widget(header, chunks[0]);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(34), Constraint::Min(0)])
+            .split(chunks[1]);
+        Self::render_calendar(frame, columns[0], active, self.selected_day);
+
+        let date = active.start + Duration::days(self.selected_day as i64);
+        let items: Vec<ListItem> = active.plan.days[self.selected_day]
+            .iter()
+            .zip(&active.completion[self.selected_day])
+            .map(|(item, &done)| {
+                let (check, style) = if done {
+                    ("[✓] ", Style::default().fg(Color::Green))
+                } else {
+                    ("[ ] ", Style::default())
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(check, style),
+                    Span::styled(format!("{} {}", item.book, item.chapter), style),
+                ]))
+            })
+            .collect();
+        let title = if self.selected_day == active.current_day {
+            format!("Today, day {} ({})", self.selected_day + 1, date)
+        } else {
+            format!("Day {} ({})", self.selected_day + 1, date)
+        };
+        let block = Block::default().borders(Borders::ALL).title(title);
+        if items.is_empty() {
+            let rest = Paragraph::new("Nothing to read on this day.")
+                .style(Style::default().fg(Color::Gray))
+                .block(block);
+            frame.render_widget(rest, columns[1]);
+            return;
+        }
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, columns[1], &mut self.item_state);
+
+        if let Some(menu) = &self.catch_up {
+            Self::render_catch_up(frame, menu);
+        }
+    }
+
+    fn render_catch_up(frame: &mut Frame, menu: &CatchUpMenu) {
+        let popup_area = centered_rect(60, 40, frame.area());
+        frame.render_widget(Clear, popup_area);
+        let items: Vec<ListItem> = menu
+            .strategies()
+            .iter()
+            .map(|strategy| ListItem::new(strategy.description()))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title("Catch up (Enter: apply, ←/→: days to spread over, Esc: cancel)"),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(
+            list,
+            popup_area,
+            &mut ListState::default().with_selected(Some(menu.selected)),
+        );
+    }
+
+    /// The plan's days as weeks of seven, with the selected day highlighted and today
+    /// underlined, scrolled to keep the selected week in view
+    fn render_calendar(frame: &mut Frame, area: Rect, active: &ActivePlan, selected_day: usize) {
+        let weeks = active.plan.days.len().div_ceil(7);
+        let visible = area.height.saturating_sub(2) as usize;
+        let selected_week = selected_day / 7;
+        let first_week = selected_week
+            .saturating_sub(visible / 2)
+            .min(weeks.saturating_sub(visible));
+
+        let lines: Vec<Line> = (first_week..weeks.min(first_week + visible))
+            .map(|week| {
+                let week_start = active.start + Duration::days(week as i64 * 7);
+                let mut spans = vec![Span::styled(
+                    format!("{:<8}", week_start.format("%b %d")),
+                    Style::default().fg(Color::Gray),
+                )];
+                for day in week * 7..(week * 7 + 7).min(active.plan.days.len()) {
+                    let (marker, color) = active.day_marker(day);
+                    let mut style = Style::default().fg(color);
+                    if day == selected_day {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                    if day == active.current_day {
+                        style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                    }
+                    spans.push(Span::styled(format!(" {} ", marker), style));
+                }
+                Line::from(spans)
+            })
+            .collect();
+        let finished = (0..active.plan.days.len())
+            .filter(|&day| active.is_day_done(day))
+            .count();
+        let calendar = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(
+            format!("Calendar ({}/{})", finished, active.plan.days.len()),
+        ));
+        frame.render_widget(calendar, area);
+    }
+
+    fn render_choices(
+        frame: &mut Frame,
+        area: Rect,
+        choices: &[(PlanId, Result<Plan, String>)],
+        state: &mut ListState,
+        error_message: Option<&str>,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+        let items: Vec<ListItem> = choices
+            .iter()
+            .map(|(id, plan)| match (id, plan) {
+                (PlanId::BuiltIn(kind), Ok(plan)) => ListItem::new(Line::from(vec![
+                    Span::raw(format!("{} ", plan.name)),
+                    Span::styled(
+                        format!("({} days)  {}", plan.days.len(), kind.description()),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ])),
+                (PlanId::Custom { .. }, Ok(plan)) => ListItem::new(Line::from(vec![
+                    Span::raw(format!("{} ", plan.name)),
+                    Span::styled(
+                        format!("({} days)  Your plan", plan.days.len()),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ])),
+                (_, Err(e)) => ListItem::new(Span::styled(
+                    format!("✗ {}", e),
+                    Style::default().fg(Color::Red),
+                )),
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Choose a Reading Plan"),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[0], state);
+
+        let (text, color) = match error_message {
+            Some(error) => (error.to_string(), Color::Red),
+            None => (
+                "Enter: start the selected plan today (plans written with dates start on their \
+                 first date)"
+                    .to_string(),
+                Color::Gray,
+            ),
+        };
+        let message = Paragraph::new(text)
+            .style(Style::default().fg(color))
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(message, chunks[1]);
+    }
+
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        bible: &BibleStructure,
+        today: NaiveDate,
+    ) -> PlansAction {
+        if let Some(choices) = &self.choices {
+            let selected = self.choice_state.selected().unwrap_or(0);
+            return match key.code {
+                KeyCode::Up => {
+                    self.choice_state.select(Some(selected.saturating_sub(1)));
+                    PlansAction::None
+                }
+                KeyCode::Down => {
+                    self.choice_state
+                        .select(Some((selected + 1).min(choices.len().saturating_sub(1))));
+                    PlansAction::None
+                }
+                KeyCode::Enter => match choices.get(selected) {
+                    Some((id, Ok(plan))) => {
+                        PlansAction::Start(PlanState::new(id.clone(), plan.start.unwrap_or(today)))
+                    }
+                    _ => PlansAction::None,
+                },
+                KeyCode::Esc if self.active.is_some() => {
+                    self.choices = None;
+                    PlansAction::None
+                }
+                _ => PlansAction::None,
+            };
+        }
+        if let Some(menu) = self.catch_up.as_mut() {
+            match key.code {
+                KeyCode::Up => menu.selected = menu.selected.saturating_sub(1),
+                KeyCode::Down => menu.selected = (menu.selected + 1).min(2),
+                KeyCode::Left => menu.spread_days = (menu.spread_days - 1).max(1),
+                KeyCode::Right => menu.spread_days = (menu.spread_days + 1).min(30),
+                KeyCode::Enter => {
+                    let strategy = menu.strategies()[menu.selected];
+                    self.catch_up = None;
+                    return PlansAction::CatchUp(strategy);
+                }
+                KeyCode::Esc => self.catch_up = None,
+                _ => {}
+            }
+            return PlansAction::None;
+        }
+        let Some(active) = &self.active else {
+            return PlansAction::None;
+        };
+
+        let last_day = active.plan.days.len() - 1;
+        let items = &active.plan.days[self.selected_day];
+        match key.code {
+            KeyCode::Left => self.select_day(self.selected_day.saturating_sub(1)),
+            KeyCode::Right => self.select_day((self.selected_day + 1).min(last_day)),
+            KeyCode::PageUp => self.select_day(self.selected_day.saturating_sub(7)),
+            KeyCode::PageDown => self.select_day((self.selected_day + 7).min(last_day)),
+            KeyCode::Char('t') => self.select_day(active.current_day),
+            KeyCode::Up => {
+                let selected = self.item_state.selected().unwrap_or(0);
+                self.item_state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                let selected = self.item_state.selected().unwrap_or(0);
+                self.item_state
+                    .select(Some((selected + 1).min(items.len().saturating_sub(1))));
+            }
+            KeyCode::Enter => {
+                let selected = self.item_state.selected().unwrap_or(0);
+                let done = active.completion[self.selected_day].get(selected);
+                if let (Some(item), Some(false)) = (items.get(selected), done) {
+                    return PlansAction::MarkRead {
+                        book: item.book.clone(),
+                        chapter: item.chapter,
+                    };
+                }
+            }
+            KeyCode::Char('r') if schedule_offset(&active.completion, active.current_day) < 0 => {
+                self.catch_up = Some(CatchUpMenu {
+                    selected: 0,
+                    spread_days: 7,
+                });
+            }
+            KeyCode::Char('c') => {
+                self.choices = Some(available_plans(bible, &self.plans_dir));
+                self.choice_state.select(Some(0));
+            }
+            _ => {}
+        }
+        PlansAction::None
+    }
+
+    fn select_day(&mut self, day: usize) {
+        self.selected_day = day;
+        self.select_first_open_item();
+    }
+
+    /// Select the first chapter of the selected day that is still to read
+    fn select_first_open_item(&mut self) {
+        let first_open = self.active.as_ref().map(|active| {
+            active.completion[self.selected_day]
+                .iter()
+                .position(|&done| !done)
+                .unwrap_or(0)
+        });
+        self.item_state.select(first_open);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::plans::PlanKind;
+
+    #[test]
+    fn the_plan_shows_todays_chapters_and_how_far_behind_it_is() {
+        let bible = get_bible_structure();
+        let start = NaiveDate::from_ymd_opt(2024, 11, 1).unwrap();
+        let today = start + Duration::days(2);
+        let mut progress = ReadingProgress::new();
+        progress.plan = Some(PlanState::new(
+            PlanId::BuiltIn(PlanKind::NewTestament90),
+            start,
+        ));
+        for chapter in 1..=3 {
+            progress.record_reading_on("Matthew", chapter, 1, 20, 1, start);
+        }
+        progress.record_reading_on("Matthew", 7, 1, 29, 1, today);
+        let mut plans = PlansWidget::new(bible, &progress, Path::new("/no/such/dir"), today);
+
+        let mut terminal = Terminal::new(backend::TestBackend::new(100, 20)).unwrap();
+        terminal
+            .draw(|frame| plans.render(frame, frame.area()))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Day 3 of 90"));
+        assert!(screen.contains("1 day behind (r: catch up)"));
+        assert!(screen.contains("[✓] Matthew 7"));
+
+        // Day 3 is Matthew 7-9, and Matthew 7 is already read
+        assert_eq!(
+            plans.handle_key(KeyEvent::from(KeyCode::Enter), bible, today),
+            PlansAction::MarkRead {
+                book: "Matthew".to_string(),
+                chapter: 8
+            }
+        );
+        plans.handle_key(KeyEvent::from(KeyCode::Char('r')), bible, today);
+        plans.handle_key(KeyEvent::from(KeyCode::Left), bible, today);
+        assert_eq!(
+            plans.handle_key(KeyEvent::from(KeyCode::Enter), bible, today),
+            PlansAction::CatchUp(CatchUp::Spread { days: 6 })
+        );
+
+        plans.handle_key(KeyEvent::from(KeyCode::Char('c')), bible, today);
+        assert_eq!(
+            plans.handle_key(KeyEvent::from(KeyCode::Enter), bible, today),
+            PlansAction::Start(PlanState::new(PlanId::BuiltIn(PlanKind::Canonical), today))
+        );
+    }
+}
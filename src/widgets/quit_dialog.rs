@@ -0,0 +1,205 @@
+use std::path::PathBuf;
+
+use ratatui::{prelude::*, widgets::*};
+
+use crate::i18n::{tr, trf};
+use crate::widgets::popup::centered_rect;
+use crate::widgets::text_input::TextInput;
+
+/// Why quitting needs confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuitReason {
+    /// Writing the progress file failed, with the error message
+    SaveFailed(String),
+    /// The settings tab has edits that were not saved to the config file
+    UnsavedSettings,
+    /// Nothing would be lost; this shows what was recorded before quitting
+    SessionSummary(String),
+}
+
+/// A choice offered by the quit dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuitChoice {
+    Retry,
+    SaveAs,
+    SaveSettings,
+    QuitAnyway,
+    Quit,
+    CopyAndQuit,
+    Cancel,
+}
+
+impl QuitChoice {
+    fn label(&self) -> &'static str {
+        tr(match self {
+            QuitChoice::Retry => "Retry",
+            QuitChoice::SaveAs => "Save As…",
+            QuitChoice::SaveSettings => "Save settings",
+            QuitChoice::QuitAnyway => "Quit anyway",
+            QuitChoice::Quit => "Quit",
+            QuitChoice::CopyAndQuit => "Copy and quit",
+            QuitChoice::Cancel => "Cancel",
+        })
+    }
+}
+
+/// Modal shown instead of quitting when something would be lost
+pub struct QuitDialog {
+    pub reason: QuitReason,
+    selected: usize,
+    /// Path typed for "Save As", while that prompt is open
+    save_as_input: Option<TextInput>,
+}
+
+impl QuitDialog {
+    pub fn new(reason: QuitReason) -> Self {
+        Self {
+            reason,
+            selected: 0,
+            save_as_input: None,
+        }
+    }
+
+    fn choices(&self) -> &'static [QuitChoice] {
+        match self.reason {
+            QuitReason::SaveFailed(_) => &[
+                QuitChoice::Retry,
+                QuitChoice::SaveAs,
+                QuitChoice::QuitAnyway,
+                QuitChoice::Cancel,
+            ],
+            QuitReason::UnsavedSettings => &[
+                QuitChoice::SaveSettings,
+                QuitChoice::QuitAnyway,
+                QuitChoice::Cancel,
+            ],
+            QuitReason::SessionSummary(_) => &[
+                QuitChoice::Quit,
+                QuitChoice::CopyAndQuit,
+                QuitChoice::Cancel,
+            ],
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let popup_area = centered_rect(60, 30, frame.area());
+        frame.render_widget(Clear, popup_area);
+
+        let (title, message) = match &self.reason {
+            QuitReason::SaveFailed(error) => (
+                tr("Couldn't save progress"),
+                trf("Your reading progress could not be saved:\n{}", &[error]),
+            ),
+            QuitReason::UnsavedSettings => (
+                tr("Unsaved settings"),
+                tr("You changed settings that haven't been saved to the config file.").to_string(),
+            ),
+            QuitReason::SessionSummary(summary) => (tr("Session summary"), summary.clone()),
+        };
+        // Warnings stand out in red; the summary is just informative
+        let border_color = match self.reason {
+            QuitReason::SessionSummary(_) => Color::Cyan,
+            _ => Color::Red,
+        };
+
+        let mut lines: Vec<Line> = message
+            .lines()
+            .map(|line| Line::raw(line.to_string()))
+            .collect();
+        lines.push(Line::default());
+
+        if let Some(input) = &self.save_as_input {
+            let mut spans = vec![Span::styled(
+                tr("Save as: "),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )];
+            spans.extend(input.cursor_spans());
+            lines.push(Line::from(spans));
+            lines.push(Line::styled(
+                tr("Enter: save and quit | Esc: back"),
+                Style::default().fg(Color::Gray),
+            ));
+        } else {
+            let mut buttons = Vec::new();
+            for (idx, choice) in self.choices().iter().enumerate() {
+                let style = if idx == self.selected {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Black).bg(Color::Gray)
+                };
+                buttons.push(Span::styled(format!("[ {} ]", choice.label()), style));
+                buttons.push(Span::raw(" "));
+            }
+            lines.push(Line::from(buttons));
+            lines.push(Line::styled(
+                tr("←/→: choose | Enter: confirm | Esc: cancel"),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color))
+                .title(title),
+        );
+        frame.render_widget(dialog, popup_area);
+    }
+
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> QuitDialogAction {
+        if let Some(input) = self.save_as_input.as_mut() {
+            match key.code {
+                crossterm::event::KeyCode::Enter if !input.trim().is_empty() => {
+                    return QuitDialogAction::SaveAs(PathBuf::from(input.trim()));
+                }
+                crossterm::event::KeyCode::Esc => self.save_as_input = None,
+                _ => {
+                    input.handle_key(key, |c| !c.is_control());
+                }
+            }
+            return QuitDialogAction::None;
+        }
+
+        let choices = self.choices();
+        match key.code {
+            crossterm::event::KeyCode::Left | crossterm::event::KeyCode::BackTab => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            crossterm::event::KeyCode::Right | crossterm::event::KeyCode::Tab => {
+                self.selected = (self.selected + 1).min(choices.len() - 1);
+            }
+            crossterm::event::KeyCode::Esc => return QuitDialogAction::Cancel,
+            crossterm::event::KeyCode::Enter => match choices[self.selected] {
+                QuitChoice::Retry => return QuitDialogAction::Retry,
+                QuitChoice::SaveAs => self.save_as_input = Some(TextInput::new()),
+                QuitChoice::SaveSettings => return QuitDialogAction::SaveSettings,
+                QuitChoice::QuitAnyway | QuitChoice::Quit => return QuitDialogAction::QuitAnyway,
+                QuitChoice::CopyAndQuit => return QuitDialogAction::CopyAndQuit,
+                QuitChoice::Cancel => return QuitDialogAction::Cancel,
+            },
+            _ => {}
+        }
+        QuitDialogAction::None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuitDialogAction {
+    None,
+    /// Close the dialog and keep the app running
+    Cancel,
+    /// Try saving the progress file again
+    Retry,
+    /// Save the progress to another file, then quit
+    SaveAs(PathBuf),
+    /// Save the edited settings, then quit
+    SaveSettings,
+    QuitAnyway,
+    /// Copy the session summary to the clipboard, then quit
+    CopyAndQuit,
+}
@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+
+use ratatui::{prelude::*, widgets::*};
+
+use crate::i18n::{tr, trf};
+use crate::widgets::popup::centered_rect;
+
+/// A choice offered by the recovery dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecoveryChoice {
+    Restore,
+    RestoreFrom,
+    Salvage,
+    Quit,
+}
+
+impl RecoveryChoice {
+    fn label(&self) -> &'static str {
+        tr(match self {
+            RecoveryChoice::Restore => "Restore backup",
+            RecoveryChoice::RestoreFrom => "Restore from…",
+            RecoveryChoice::Salvage => "Open read-only",
+            RecoveryChoice::Quit => "Quit",
+        })
+    }
+}
+
+/// Modal shown over an empty dashboard when the progress file can't be loaded, until the user
+/// picks a way forward. Nothing is saved while it's open.
+pub struct RecoveryDialog {
+    /// Why the progress file couldn't be loaded
+    pub error: String,
+    /// The copy of the progress file from before the last session's first save, if there is
+    /// one
+    pub backup: Option<PathBuf>,
+    selected: usize,
+    /// Why the last choice didn't work, e.g. the backup is damaged too
+    pub message: Option<String>,
+}
+
+impl RecoveryDialog {
+    pub fn new(error: String, backup: Option<PathBuf>) -> Self {
+        Self {
+            error,
+            backup,
+            selected: 0,
+            message: None,
+        }
+    }
+
+    fn choices(&self) -> &'static [RecoveryChoice] {
+        match self.backup {
+            Some(_) => &[
+                RecoveryChoice::Restore,
+                RecoveryChoice::RestoreFrom,
+                RecoveryChoice::Salvage,
+                RecoveryChoice::Quit,
+            ],
+            None => &[
+                RecoveryChoice::RestoreFrom,
+                RecoveryChoice::Salvage,
+                RecoveryChoice::Quit,
+            ],
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let popup_area = centered_rect(70, 50, frame.area());
+        frame.render_widget(Clear, popup_area);
+
+        let mut lines = vec![Line::raw(tr("Your reading progress couldn't be loaded:"))];
+        lines.extend(self.error.lines().map(|line| Line::raw(line.to_string())));
+        lines.push(Line::default());
+        if let Some(backup) = &self.backup {
+            lines.push(Line::raw(trf(
+                "Restore backup: replace it with {}, kept from before your last session. The damaged file is kept as .corrupt.",
+                &[&backup.display()],
+            )));
+        }
+        lines.push(Line::raw(tr(
+            "Restore from…: browse for another copy, e.g. from a sync folder or a backup drive.",
+        )));
+        lines.push(Line::raw(tr(
+            "Open read-only: show whatever can be read, and write what was left out next to the file. Nothing is saved.",
+        )));
+        lines.push(Line::default());
+        if let Some(message) = &self.message {
+            lines.push(Line::styled(
+                message.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+            lines.push(Line::default());
+        }
+
+        let mut buttons = Vec::new();
+        for (idx, choice) in self.choices().iter().enumerate() {
+            let style = if idx == self.selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Black).bg(Color::Gray)
+            };
+            buttons.push(Span::styled(format!("[ {} ]", choice.label()), style));
+            buttons.push(Span::raw(" "));
+        }
+        lines.push(Line::from(buttons));
+        lines.push(Line::styled(
+            tr("←/→: choose | Enter: confirm"),
+            Style::default().fg(Color::Gray),
+        ));
+
+        let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(tr("Damaged progress file")),
+        );
+        frame.render_widget(dialog, popup_area);
+    }
+
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> RecoveryAction {
+        let choices = self.choices();
+        match key.code {
+            crossterm::event::KeyCode::Left | crossterm::event::KeyCode::BackTab => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            crossterm::event::KeyCode::Right | crossterm::event::KeyCode::Tab => {
+                self.selected = (self.selected + 1).min(choices.len() - 1);
+            }
+            crossterm::event::KeyCode::Enter => {
+                return match choices[self.selected] {
+                    RecoveryChoice::Restore => match &self.backup {
+                        Some(backup) => RecoveryAction::Restore(backup.clone()),
+                        None => RecoveryAction::None,
+                    },
+                    RecoveryChoice::RestoreFrom => RecoveryAction::PickCopy,
+                    RecoveryChoice::Salvage => RecoveryAction::Salvage,
+                    RecoveryChoice::Quit => RecoveryAction::Quit,
+                };
+            }
+            _ => {}
+        }
+        RecoveryAction::None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryAction {
+    None,
+    /// Replace the progress file with this copy of it and load that
+    Restore(PathBuf),
+    /// Browse for a copy to restore
+    PickCopy,
+    /// Load what can be read, without saving
+    Salvage,
+    /// Quit without touching the progress file
+    Quit,
+}
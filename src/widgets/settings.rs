@@ -0,0 +1,426 @@
+use std::path::PathBuf;
+
+use ratatui::{prelude::*, widgets::*};
+
+use crate::config::{Config, GradientConfig, GradientMode};
+use crate::i18n::{tr, trf};
+use crate::widgets::help::KeyBinding;
+
+/// Keybindings available in the settings tab, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("↑/↓", "Select a setting"),
+    ("←/→", "Change the selected setting"),
+    ("Enter", "Save changes to the config file"),
+    ("Enter", "Browse for the file or folder of a path setting"),
+    ("Ctrl+S", "Save changes to the config file"),
+    ("Esc", "Discard unsaved changes"),
+    ("?", "Show this help"),
+    ("q", "Quit"),
+];
+
+/// Named color gradients offered by the settings screen
+const GRADIENT_PRESETS: &[(&str, &[&str])] = &[
+    (
+        "Gray → blue → green → gold",
+        &["gray", "blue", "green", "#ffd700"],
+    ),
+    ("Gray → white", &["darkgray", "gray", "white"]),
+    ("Heat", &["gray", "yellow", "#ff8700", "red"]),
+    ("Ocean", &["gray", "cyan", "blue", "magenta"]),
+];
+
+/// Largest daily chapter goal offered when stepping with the arrow keys
+const MAX_DAILY_CHAPTER_GOAL: u32 = 50;
+/// Step and largest value of the daily reading time, in minutes
+const DAILY_MINUTES_STEP: u32 = 5;
+const MAX_DAILY_MINUTES: u32 = 180;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Setting {
+    DailyChapterGoal,
+    DailyMinutes,
+    GradientMode,
+    GradientColors,
+    AccessibilityMode,
+    ProgressFile,
+    PlansFolder,
+}
+
+const SETTINGS: [Setting; 7] = [
+    Setting::DailyChapterGoal,
+    Setting::DailyMinutes,
+    Setting::GradientMode,
+    Setting::GradientColors,
+    Setting::AccessibilityMode,
+    Setting::ProgressFile,
+    Setting::PlansFolder,
+];
+
+impl Setting {
+    /// Picked with the file picker rather than stepped through
+    fn is_path(self) -> bool {
+        matches!(self, Setting::ProgressFile | Setting::PlansFolder)
+    }
+}
+
+/// Editor for the settings stored in the config file
+pub struct SettingsWidget {
+    daily_chapter_goal: Option<u32>,
+    daily_minutes: Option<u32>,
+    color_gradient: GradientConfig,
+    accessibility_mode: bool,
+    progress_path: PathBuf,
+    plans_dir: PathBuf,
+    saved_goal: Option<u32>,
+    saved_minutes: Option<u32>,
+    saved_gradient: GradientConfig,
+    saved_accessibility_mode: bool,
+    saved_progress_path: PathBuf,
+    saved_plans_dir: PathBuf,
+    /// The progress file the app is using, until it restarts with another one
+    progress_path_in_use: PathBuf,
+    list_state: ListState,
+    pub message: Option<Result<String, String>>,
+}
+
+impl SettingsWidget {
+    pub fn new(config: &Config) -> Self {
+        let progress_path = config
+            .configured_progress_path
+            .clone()
+            .unwrap_or_else(|| config.progress_path.clone());
+        Self {
+            progress_path: progress_path.clone(),
+            plans_dir: config.plans_dir.clone(),
+            saved_progress_path: progress_path,
+            saved_plans_dir: config.plans_dir.clone(),
+            progress_path_in_use: config.progress_path.clone(),
+            daily_chapter_goal: config.daily_chapter_goal,
+            daily_minutes: config.daily_minutes,
+            color_gradient: config.color_gradient.clone(),
+            accessibility_mode: config.accessibility_mode,
+            saved_goal: config.daily_chapter_goal,
+            saved_minutes: config.daily_minutes,
+            saved_gradient: config.color_gradient.clone(),
+            saved_accessibility_mode: config.accessibility_mode,
+            list_state: ListState::default().with_selected(Some(0)),
+            message: None,
+        }
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.daily_chapter_goal != self.saved_goal
+            || self.daily_minutes != self.saved_minutes
+            || self.color_gradient != self.saved_gradient
+            || self.accessibility_mode != self.saved_accessibility_mode
+            || self.progress_path != self.saved_progress_path
+            || self.plans_dir != self.saved_plans_dir
+    }
+
+    /// The selected setting, if it's a path: the file or folder to start browsing from, and
+    /// whether it's a folder
+    pub fn selected_path(&self) -> Option<(PathBuf, bool)> {
+        match SETTINGS[self.list_state.selected().unwrap_or(0)] {
+            Setting::ProgressFile => Some((self.progress_path.clone(), false)),
+            Setting::PlansFolder => Some((self.plans_dir.clone(), true)),
+            _ => None,
+        }
+    }
+
+    /// Set the selected path setting to what was picked with the file picker
+    pub fn set_selected_path(&mut self, path: PathBuf) {
+        match SETTINGS[self.list_state.selected().unwrap_or(0)] {
+            Setting::ProgressFile => self.progress_path = path,
+            Setting::PlansFolder => self.plans_dir = path,
+            _ => return,
+        }
+        self.message = None;
+    }
+
+    /// Copy the edited settings into `config`, and remember them as the saved values
+    pub fn apply_to(&mut self, config: &mut Config) {
+        config.daily_chapter_goal = self.daily_chapter_goal;
+        config.daily_minutes = self.daily_minutes;
+        config.color_gradient = self.color_gradient.clone();
+        config.accessibility_mode = self.accessibility_mode;
+        // The app keeps using the progress file it loaded until it restarts
+        if self.progress_path != self.saved_progress_path {
+            config.configured_progress_path = Some(self.progress_path.clone());
+        }
+        config.plans_dir = self.plans_dir.clone();
+        self.saved_progress_path = self.progress_path.clone();
+        self.saved_plans_dir = self.plans_dir.clone();
+        self.saved_goal = self.daily_chapter_goal;
+        self.saved_minutes = self.daily_minutes;
+        self.saved_gradient = self.color_gradient.clone();
+        self.saved_accessibility_mode = self.accessibility_mode;
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+
+        let items: Vec<ListItem> = SETTINGS
+            .iter()
+            .map(|setting| {
+                let value = if setting.is_path() {
+                    self.value(*setting)
+                } else {
+                    format!("◀ {} ▶", self.value(*setting))
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<24}", self.label(*setting)),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(value),
+                ]))
+            })
+            .collect();
+
+        let title = tr(if self.has_unsaved_changes() {
+            "Settings (unsaved changes)"
+        } else {
+            "Settings"
+        });
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, chunks[0], &mut self.list_state);
+
+        let footer = match &self.message {
+            Some(Ok(message)) => {
+                Paragraph::new(message.clone()).style(Style::default().fg(Color::Green))
+            }
+            Some(Err(error)) => {
+                Paragraph::new(error.clone()).style(Style::default().fg(Color::Red))
+            }
+            None if self.selected_path().is_some() => Paragraph::new(tr(
+                "Enter: browse | Ctrl+S: save | Esc: discard changes | ?: Help",
+            ))
+            .style(Style::default().fg(Color::Gray)),
+            None => Paragraph::new(tr(
+                "←/→: change | Enter: save | Esc: discard changes | ?: Help",
+            ))
+            .style(Style::default().fg(Color::Gray)),
+        };
+        frame.render_widget(
+            footer.block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+    }
+
+    fn label(&self, setting: Setting) -> &'static str {
+        tr(match setting {
+            Setting::DailyChapterGoal => "Daily chapter goal",
+            Setting::DailyMinutes => "Daily reading time",
+            Setting::GradientMode => "Color passages by",
+            Setting::GradientColors => "Color gradient",
+            Setting::AccessibilityMode => "Accessibility mode",
+            Setting::ProgressFile => "Progress file",
+            Setting::PlansFolder => "Plans folder",
+        })
+    }
+
+    fn value(&self, setting: Setting) -> String {
+        match setting {
+            Setting::DailyChapterGoal => match self.daily_chapter_goal {
+                Some(goal) => trf("{} chapters", &[&goal]),
+                None => tr("off").to_string(),
+            },
+            Setting::DailyMinutes => match self.daily_minutes {
+                Some(minutes) => format!("{} min", minutes),
+                None => tr("off").to_string(),
+            },
+            Setting::GradientMode => match self.color_gradient.mode {
+                GradientMode::ReadCount => tr("read count").to_string(),
+                GradientMode::Recency => tr("recency").to_string(),
+            },
+            Setting::GradientColors => match self.preset_index() {
+                Some(idx) => GRADIENT_PRESETS[idx].0.to_string(),
+                None => trf("custom ({})", &[&self.color_gradient.colors.join(", ")]),
+            },
+            Setting::AccessibilityMode if self.accessibility_mode => tr("on").to_string(),
+            Setting::AccessibilityMode => tr("off").to_string(),
+            Setting::ProgressFile if self.progress_path != self.progress_path_in_use => trf(
+                "{} (used from the next start)",
+                &[&self.progress_path.display()],
+            ),
+            Setting::ProgressFile => self.progress_path.display().to_string(),
+            Setting::PlansFolder => self.plans_dir.display().to_string(),
+        }
+    }
+
+    fn preset_index(&self) -> Option<usize> {
+        GRADIENT_PRESETS
+            .iter()
+            .position(|(_, colors)| *colors == self.color_gradient.colors)
+    }
+
+    /// Step the selected setting forward (`delta` = 1) or backward (`delta` = -1)
+    fn change(&mut self, delta: i32) {
+        let selected = SETTINGS[self.list_state.selected().unwrap_or(0)];
+        match selected {
+            Setting::DailyChapterGoal => {
+                let goal = self.daily_chapter_goal.unwrap_or(0) as i32 + delta;
+                let goal = goal.clamp(0, MAX_DAILY_CHAPTER_GOAL as i32) as u32;
+                self.daily_chapter_goal = if goal == 0 { None } else { Some(goal) };
+            }
+            Setting::DailyMinutes => {
+                let minutes =
+                    self.daily_minutes.unwrap_or(0) as i32 + delta * DAILY_MINUTES_STEP as i32;
+                let minutes = minutes.clamp(0, MAX_DAILY_MINUTES as i32) as u32;
+                self.daily_minutes = if minutes == 0 { None } else { Some(minutes) };
+            }
+            Setting::GradientMode => {
+                self.color_gradient.mode = match self.color_gradient.mode {
+                    GradientMode::ReadCount => GradientMode::Recency,
+                    GradientMode::Recency => GradientMode::ReadCount,
+                };
+            }
+            Setting::GradientColors => {
+                let len = GRADIENT_PRESETS.len() as i32;
+                let next = match self.preset_index() {
+                    Some(idx) => (idx as i32 + delta).rem_euclid(len),
+                    None if delta > 0 => 0,
+                    None => len - 1,
+                };
+                self.color_gradient.colors = GRADIENT_PRESETS[next as usize]
+                    .1
+                    .iter()
+                    .map(|color| color.to_string())
+                    .collect();
+            }
+            Setting::AccessibilityMode => self.accessibility_mode = !self.accessibility_mode,
+            Setting::ProgressFile | Setting::PlansFolder => return,
+        }
+        self.message = None;
+    }
+
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> SettingsAction {
+        let selected = self.list_state.selected().unwrap_or(0);
+        match key.code {
+            crossterm::event::KeyCode::Up => {
+                self.list_state.select(Some(selected.saturating_sub(1)));
+            }
+            crossterm::event::KeyCode::Down => {
+                self.list_state
+                    .select(Some((selected + 1).min(SETTINGS.len() - 1)));
+            }
+            crossterm::event::KeyCode::Left => self.change(-1),
+            crossterm::event::KeyCode::Right => self.change(1),
+            crossterm::event::KeyCode::Char('s')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                return SettingsAction::Save;
+            }
+            crossterm::event::KeyCode::Enter => {
+                return match self.selected_path() {
+                    Some((path, false)) => SettingsAction::PickFile(path),
+                    Some((path, true)) => SettingsAction::PickFolder(path),
+                    None => SettingsAction::Save,
+                };
+            }
+            crossterm::event::KeyCode::Esc => {
+                self.daily_chapter_goal = self.saved_goal;
+                self.daily_minutes = self.saved_minutes;
+                self.color_gradient = self.saved_gradient.clone();
+                self.accessibility_mode = self.saved_accessibility_mode;
+                self.progress_path = self.saved_progress_path.clone();
+                self.plans_dir = self.saved_plans_dir.clone();
+                self.message = None;
+            }
+            _ => {}
+        }
+        SettingsAction::None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsAction {
+    None,
+    /// Write the edited settings to the config file
+    Save,
+    /// Browse for the selected path setting's file, starting from this one
+    PickFile(PathBuf),
+    /// Browse for the selected path setting's folder, starting from this one
+    PickFolder(PathBuf),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn widget() -> SettingsWidget {
+        SettingsWidget {
+            daily_chapter_goal: None,
+            daily_minutes: None,
+            color_gradient: GradientConfig::default(),
+            accessibility_mode: false,
+            progress_path: PathBuf::from("reading_progress.yaml"),
+            plans_dir: PathBuf::from("plans"),
+            saved_goal: None,
+            saved_minutes: None,
+            saved_gradient: GradientConfig::default(),
+            saved_accessibility_mode: false,
+            saved_progress_path: PathBuf::from("reading_progress.yaml"),
+            saved_plans_dir: PathBuf::from("plans"),
+            progress_path_in_use: PathBuf::from("reading_progress.yaml"),
+            list_state: ListState::default().with_selected(Some(0)),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn goal_steps_between_off_and_max() {
+        let mut settings = widget();
+        settings.change(-1);
+        assert_eq!(settings.daily_chapter_goal, None);
+        settings.change(1);
+        settings.change(1);
+        assert_eq!(settings.daily_chapter_goal, Some(2));
+        assert!(settings.has_unsaved_changes());
+    }
+
+    #[test]
+    fn gradient_presets_cycle() {
+        let mut settings = widget();
+        settings.list_state.select(Some(3));
+        assert_eq!(settings.preset_index(), Some(0));
+        settings.change(-1);
+        assert_eq!(settings.preset_index(), Some(GRADIENT_PRESETS.len() - 1));
+        settings.change(1);
+        assert_eq!(settings.preset_index(), Some(0));
+        assert!(!settings.has_unsaved_changes());
+    }
+
+    #[test]
+    fn path_settings_are_browsed_for() {
+        let mut settings = widget();
+        settings.list_state.select(Some(6));
+        assert_eq!(
+            settings.handle_key(crossterm::event::KeyEvent::from(
+                crossterm::event::KeyCode::Enter
+            )),
+            SettingsAction::PickFolder(PathBuf::from("plans"))
+        );
+        settings.set_selected_path(PathBuf::from("/srv/plans"));
+        assert!(settings.has_unsaved_changes());
+
+        settings.list_state.select(Some(5));
+        settings.set_selected_path(PathBuf::from("/srv/progress.yaml"));
+        assert!(settings
+            .value(Setting::ProgressFile)
+            .ends_with("(used from the next start)"));
+    }
+}
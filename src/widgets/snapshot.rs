@@ -0,0 +1,30 @@
+//! Renders widgets into an in-memory terminal for the widgets' snapshot tests. Snapshots are
+//! stored in `src/widgets/snapshots`; after an intended change to a layout, review and accept
+//! the new ones with `cargo insta review` (or rerun the tests with `INSTA_UPDATE=always`).
+
+use chrono::{Duration, Utc};
+use ratatui::backend::TestBackend;
+use ratatui::{Frame, Terminal};
+
+use crate::progress::ReadingProgress;
+
+/// The screen `draw` renders on a `width`×`height` terminal, one quoted line per row
+pub fn render(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(draw).unwrap();
+    terminal.backend().to_string()
+}
+
+/// Readings from today and yesterday, which render the same whatever day the tests run: all of
+/// Genesis 1-2 and Psalm 23, half of John 3, and Jude read twice
+pub fn sample_progress() -> ReadingProgress {
+    let today = Utc::now().date_naive();
+    let yesterday = today - Duration::days(1);
+    let mut progress = ReadingProgress::new();
+    progress.record_reading_on("Genesis", 1, 1, 31, 1, yesterday);
+    progress.record_reading_on("Genesis", 2, 1, 25, 1, yesterday);
+    progress.record_reading_on("Psalms", 23, 1, 6, 1, yesterday);
+    progress.record_reading_on("John", 3, 1, 18, 1, today);
+    progress.record_reading_on("Jude", 1, 1, 25, 2, today);
+    progress
+}
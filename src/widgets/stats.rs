@@ -0,0 +1,638 @@
+use chrono::{NaiveDate, Utc};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::achievements::{unlocked_on, Achievement};
+use crate::bible_structure::{BibleStructure, BookFilter, Genre};
+use crate::compare::{compare_sides, CompareSide};
+use crate::config::Config;
+use crate::coverage::{book_percent_read, percent_read};
+use crate::group::{Group, GroupProgress};
+use crate::milestones::Milestone;
+use crate::progress::ReadingProgress;
+use crate::stats::{
+    current_streak, evaluate_goals, format_minutes, neglected_books, reading_days, GoalProgress,
+    NeglectedBook,
+};
+use crate::utils::{get_all_books, truncate_with_ellipsis};
+use crate::widgets::help::KeyBinding;
+
+/// Keybindings available in the stats tab, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    (
+        "v",
+        "Switch between the summary, the book/genre charts, achievements, milestones, neglected books, your group and the comparison",
+    ),
+    ("↑/↓", "Scroll the chart, achievements, milestones or neglected books"),
+    (
+        "a/b",
+        "Change the left/right side of the comparison (you, a campaign, a year or a group member)",
+    ),
+    ("y", "Show last year in review"),
+    ("PgUp/PgDn", "Scroll a page up or down"),
+    ("Home/End", "Jump to the top/bottom"),
+    ("?", "Show this help"),
+    ("q", "Quit"),
+];
+
+/// What the stats tab shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsView {
+    Summary,
+    /// Completion of each book, as a bar chart
+    Books,
+    /// Completion of each genre, as a bar chart
+    Genres,
+    /// Every achievement, locked or unlocked
+    Achievements,
+    /// Milestones reached, newest first
+    Milestones,
+    /// Books not read in a while
+    Neglected,
+    /// Members of the configured group side by side
+    Group,
+    /// Completion of each book for two of `compare_sides`, in two columns
+    Compare,
+}
+
+impl StatsView {
+    fn next(self) -> Self {
+        match self {
+            StatsView::Summary => StatsView::Books,
+            StatsView::Books => StatsView::Genres,
+            StatsView::Genres => StatsView::Achievements,
+            StatsView::Achievements => StatsView::Milestones,
+            StatsView::Milestones => StatsView::Neglected,
+            StatsView::Neglected => StatsView::Group,
+            StatsView::Group => StatsView::Compare,
+            StatsView::Compare => StatsView::Summary,
+        }
+    }
+}
+
+/// Overall reading statistics
+pub struct StatsWidget {
+    streak: u32,
+    days_read: usize,
+    readings: usize,
+    /// Total minutes of the timed sessions, and how many there were
+    reading_time: (u32, usize),
+    percent_by_scope: Vec<(&'static str, f64)>,
+    percent_by_book: Vec<(String, f64)>,
+    percent_by_genre: Vec<(String, f64)>,
+    /// Progress on the configured goals
+    goals: Vec<GoalProgress>,
+    /// (label, value) lines added by plugins
+    plugin_stats: Vec<(String, String)>,
+    /// Each achievement with the date it was unlocked, if it has been
+    achievements: Vec<(Achievement, Option<NaiveDate>)>,
+    /// Newest first
+    milestones: Vec<Milestone>,
+    /// Books not read within `neglected_days`
+    neglected: Vec<NeglectedBook>,
+    neglected_days: u32,
+    today: NaiveDate,
+    /// The group from `group_path`, or why it couldn't be loaded; None without a group file
+    group: Option<Result<Group, String>>,
+    /// What the comparison view can show, and the indices of its left and right sides
+    compare_sides: Vec<CompareSide>,
+    compare: (usize, usize),
+    view: StatsView,
+    /// First bar shown in the chart views
+    scroll: usize,
+    /// Number of bars visible in the last render, used for paging
+    page_size: usize,
+}
+
+impl StatsWidget {
+    pub fn new(
+        bible: &'static BibleStructure,
+        progress: &ReadingProgress,
+        config: &Config,
+    ) -> Self {
+        let today = Utc::now().date_naive();
+        let percent_by_scope = [
+            BookFilter::All,
+            BookFilter::OldTestament,
+            BookFilter::NewTestament,
+        ]
+        .into_iter()
+        .map(|filter| (filter.name(), percent_read(bible, progress, filter)))
+        .collect();
+        let percent_by_book = get_all_books(bible)
+            .into_iter()
+            .map(|book| {
+                let percent = book_percent_read(bible, progress, &book);
+                (book, percent)
+            })
+            .collect();
+        let percent_by_genre = Genre::ALL
+            .iter()
+            .map(|&genre| {
+                let percent = percent_read(bible, progress, BookFilter::Genre(genre));
+                (genre.name().to_string(), percent)
+            })
+            .collect();
+        let achievements = Achievement::ALL
+            .into_iter()
+            .map(|achievement| (achievement, unlocked_on(progress, achievement)))
+            .collect();
+        let group_progress = config
+            .group_path
+            .as_ref()
+            .map(|path| GroupProgress::load(path));
+        let members = match &group_progress {
+            Some(Ok(group)) => group.readable(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            streak: current_streak(&progress.history, today),
+            days_read: reading_days(&progress.history).len(),
+            readings: progress.history.len(),
+            reading_time: (
+                progress
+                    .sessions
+                    .iter()
+                    .map(|session| session.minutes)
+                    .sum(),
+                progress.sessions.len(),
+            ),
+            percent_by_scope,
+            percent_by_book,
+            percent_by_genre,
+            goals: evaluate_goals(bible, progress, &config.goals, today),
+            plugin_stats: Vec::new(),
+            achievements,
+            milestones: progress.milestones.iter().rev().cloned().collect(),
+            neglected: neglected_books(bible, progress, today, config.neglected_days),
+            neglected_days: config.neglected_days,
+            today,
+            group: group_progress.map(|group| group.map(|group| Group::of(bible, &group, today))),
+            compare_sides: compare_sides(bible, progress, &members),
+            compare: (0, 1),
+            view: StatsView::Summary,
+            scroll: 0,
+            page_size: 1,
+        }
+    }
+
+    /// Show the plugins' lines in the summary
+    pub fn with_plugin_stats(mut self, lines: Vec<(String, String)>) -> Self {
+        self.plugin_stats = lines;
+        self
+    }
+
+    /// Replace the plugins' lines, e.g. once they arrive from the background
+    pub fn set_plugin_stats(&mut self, lines: Vec<(String, String)>) {
+        self.plugin_stats = lines;
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        match self.view {
+            StatsView::Summary => self.render_summary(frame, area),
+            StatsView::Books | StatsView::Genres => self.render_chart(frame, area),
+            StatsView::Achievements => self.render_achievements(frame, area),
+            StatsView::Milestones => self.render_milestones(frame, area),
+            StatsView::Neglected => self.render_neglected(frame, area),
+            StatsView::Group => self.render_group(frame, area),
+            StatsView::Compare => self.render_compare(frame, area),
+        }
+    }
+
+    fn render_summary(&self, frame: &mut Frame, area: Rect) {
+        let label = Style::default().fg(Color::Yellow);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Current streak: ", label),
+                Span::raw(format!("{} days", self.streak)),
+            ]),
+            Line::from(vec![
+                Span::styled("Days with a reading: ", label),
+                Span::raw(self.days_read.to_string()),
+            ]),
+            Line::from(vec![
+                Span::styled("Readings recorded: ", label),
+                Span::raw(self.readings.to_string()),
+            ]),
+        ];
+        if let (minutes, sessions @ 1..) = self.reading_time {
+            lines.push(Line::from(vec![
+                Span::styled("Time spent reading: ", label),
+                Span::raw(format!(
+                    "{} over {} timed session{}",
+                    format_minutes(minutes),
+                    sessions,
+                    if sessions == 1 { "" } else { "s" }
+                )),
+            ]));
+        }
+        lines.push(Line::default());
+        for (scope, percent) in &self.percent_by_scope {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}: ", scope), label),
+                Span::raw(format!("{:.1}% of verses read", percent)),
+            ]));
+        }
+        if !self.goals.is_empty() {
+            lines.push(Line::default());
+            for goal in &self.goals {
+                let (status, color) = match (goal.met, &goal.warning) {
+                    (true, _) => ("met".to_string(), Color::Green),
+                    (false, Some(warning)) => (format!("at risk: {}", warning), Color::Red),
+                    (false, None) => ("on track".to_string(), Color::Yellow),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled("Goal: ", label),
+                    Span::raw(format!("{} — ", goal.summary)),
+                    Span::styled(status, Style::default().fg(color)),
+                ]));
+            }
+        }
+        if !self.plugin_stats.is_empty() {
+            lines.push(Line::default());
+            for (name, value) in &self.plugin_stats {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{}: ", name), label),
+                    Span::raw(value.as_str()),
+                ]));
+            }
+        }
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            "Press v to see completion by book and by genre, your achievements, milestones, neglected books and a comparison",
+            Style::default().fg(Color::Gray),
+        ));
+
+        let stats =
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Stats"));
+        frame.render_widget(stats, area);
+    }
+
+    /// Bars of the current chart view, as (name, percent read)
+    fn chart_bars(&self) -> &[(String, f64)] {
+        match self.view {
+            StatsView::Summary
+            | StatsView::Achievements
+            | StatsView::Milestones
+            | StatsView::Neglected
+            | StatsView::Group => &[],
+            StatsView::Compare => &self.compare_sides[self.compare.0].percent_by_book,
+            StatsView::Books => &self.percent_by_book,
+            StatsView::Genres => &self.percent_by_genre,
+        }
+    }
+
+    /// Horizontal bar chart of completion percentages, one bar per row, scrolled to `self.scroll`
+    fn render_chart(&mut self, frame: &mut Frame, area: Rect) {
+        let title = match self.view {
+            StatsView::Genres => "Completion by Genre",
+            _ => "Completion by Book",
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} (↑↓: scroll, v: next view)", title));
+        let inner = block.inner(area);
+        let visible = inner.height as usize;
+        // BarChart panics if a label is wider than the chart, so labels get at most half of it
+        let label_width = inner.width as usize / 2;
+        self.page_size = visible.max(1);
+        self.scroll = self
+            .scroll
+            .min(self.chart_bars().len().saturating_sub(visible));
+
+        let bars = completion_bars(self.chart_bars(), self.scroll, visible, label_width);
+        frame.render_widget(completion_chart(block, &bars), area);
+    }
+
+    /// Two sides' completion by book in two columns, scrolled together
+    fn render_compare(&mut self, frame: &mut Frame, area: Rect) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let (left, right) = self.compare;
+        let blocks = [left, right].map(|index| {
+            let side = &self.compare_sides[index];
+            Block::default().borders(Borders::ALL).title(format!(
+                "{}: {:.1}% ({})",
+                side.name,
+                side.percent_read,
+                if index == left { "a" } else { "b" }
+            ))
+        });
+        let inner = blocks[0].inner(columns[0]);
+        let visible = inner.height as usize;
+        let label_width = inner.width as usize / 2;
+        self.page_size = visible.max(1);
+        self.scroll = self.scroll.min(self.scroll_len().saturating_sub(visible));
+
+        for ((block, index), column) in blocks.into_iter().zip([left, right]).zip(columns.iter()) {
+            let bars = completion_bars(
+                &self.compare_sides[index].percent_by_book,
+                self.scroll,
+                visible,
+                label_width,
+            );
+            frame.render_widget(completion_chart(block, &bars), *column);
+        }
+    }
+
+    /// Achievements as a list: unlocked ones highlighted with their date, locked ones dimmed
+    fn render_achievements(&mut self, frame: &mut Frame, area: Rect) {
+        let unlocked = self
+            .achievements
+            .iter()
+            .filter(|(_, date)| date.is_some())
+            .count();
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "Achievements {}/{} (↑↓: scroll, v: next view)",
+            unlocked,
+            self.achievements.len()
+        ));
+        let inner = block.inner(area);
+        // Each achievement takes a name line and a description line
+        let visible = (inner.height as usize / 2).max(1);
+        self.page_size = visible;
+        self.scroll = self
+            .scroll
+            .min(self.achievements.len().saturating_sub(visible));
+
+        let lines: Vec<Line> = self
+            .achievements
+            .iter()
+            .skip(self.scroll)
+            .take(visible)
+            .flat_map(|(achievement, date)| {
+                let (badge, status, style) = match date {
+                    Some(date) => (
+                        "★",
+                        format!("unlocked {}", date.format("%Y-%m-%d")),
+                        Style::default().fg(Color::Green),
+                    ),
+                    None => (
+                        "☆",
+                        "locked".to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                };
+                [
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{} {}", badge, achievement.name()),
+                            style.add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(format!("  ({})", status), style),
+                    ]),
+                    Line::styled(format!("  {}", achievement.description()), style),
+                ]
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    /// Milestones with the day each was reached, newest first
+    fn render_milestones(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "Milestones: {} (↑↓: scroll, v: next view)",
+            self.milestones.len()
+        ));
+        let visible = (block.inner(area).height as usize).max(1);
+        self.page_size = visible;
+        self.scroll = self
+            .scroll
+            .min(self.milestones.len().saturating_sub(visible));
+
+        let lines: Vec<Line> = if self.milestones.is_empty() {
+            vec![Line::styled(
+                "Finish a book, or read 100 chapters this year, to reach your first milestone",
+                Style::default().fg(Color::DarkGray),
+            )]
+        } else {
+            self.milestones
+                .iter()
+                .skip(self.scroll)
+                .take(visible)
+                .map(|milestone| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{}  ", milestone.date.format("%Y-%m-%d")),
+                            Style::default().fg(Color::Yellow),
+                        ),
+                        Span::raw(milestone.kind.description()),
+                    ])
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_neglected(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "Not read in {} days: {} books (↑↓: scroll, v: next view)",
+            self.neglected_days,
+            self.neglected.len()
+        ));
+        let visible = (block.inner(area).height as usize).max(1);
+        self.page_size = visible;
+        self.scroll = self
+            .scroll
+            .min(self.neglected.len().saturating_sub(visible));
+
+        let lines: Vec<Line> = if self.neglected.is_empty() {
+            vec![Line::styled(
+                format!(
+                    "Every book has been read in the last {} days",
+                    self.neglected_days
+                ),
+                Style::default().fg(Color::Green),
+            )]
+        } else {
+            self.neglected
+                .iter()
+                .skip(self.scroll)
+                .take(visible)
+                .map(|neglected| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:<18}", neglected.book),
+                            Style::default().fg(Color::Yellow),
+                        ),
+                        Span::raw(neglected.describe(self.today)),
+                    ])
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_group(&self, frame: &mut Frame, area: Rect) {
+        let group = match &self.group {
+            Some(Ok(group)) => group,
+            Some(Err(e)) => {
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .title("Group (v: next view)");
+                let error = Paragraph::new(e.as_str())
+                    .style(Style::default().fg(Color::Red))
+                    .wrap(Wrap { trim: true })
+                    .block(block);
+                frame.render_widget(error, area);
+                return;
+            }
+            None => return,
+        };
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "{}: {} chapters together this week (v: next view)",
+            group.name,
+            group.chapters_this_week()
+        ));
+
+        let header = Row::new(vec!["Member", "Bible", "OT", "NT", "Streak", "This week"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows: Vec<Row> = group
+            .ranked()
+            .into_iter()
+            .map(|(name, stats)| match stats {
+                Ok(stats) => Row::new(vec![
+                    Cell::from(name.as_str()),
+                    Cell::from(format!("{:.1}%", stats.percent_read))
+                        .style(Style::default().fg(completion_color(stats.percent_read))),
+                    Cell::from(format!("{:.1}%", stats.percent_old_testament)),
+                    Cell::from(format!("{:.1}%", stats.percent_new_testament)),
+                    Cell::from(format!("{} days", stats.streak)),
+                    Cell::from(format!("{} chapters", stats.chapters_this_week)),
+                ]),
+                Err(e) => Row::new(vec![
+                    Cell::from(name.as_str()),
+                    Cell::from(e.as_str()).style(Style::default().fg(Color::Red)),
+                ]),
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(7),
+                Constraint::Length(7),
+                Constraint::Length(7),
+                Constraint::Length(9),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header)
+        .column_spacing(2)
+        .block(block);
+        frame.render_widget(table, area);
+    }
+
+    /// Number of rows the current view scrolls through
+    fn scroll_len(&self) -> usize {
+        match self.view {
+            StatsView::Achievements => self.achievements.len(),
+            StatsView::Milestones => self.milestones.len(),
+            StatsView::Neglected => self.neglected.len(),
+            _ => self.chart_bars().len(),
+        }
+    }
+
+    /// Whether the view has something to show: a group file for the group, and two sides to
+    /// compare for the comparison
+    fn available(&self, view: StatsView) -> bool {
+        match view {
+            StatsView::Group => self.group.is_some(),
+            StatsView::Compare => self.compare_sides.len() > 1,
+            _ => true,
+        }
+    }
+
+    /// The side after `index` in `compare_sides`, skipping `other` so both columns differ
+    fn next_side(&self, index: usize, other: usize) -> usize {
+        let next = (index + 1) % self.compare_sides.len();
+        if next == other {
+            (next + 1) % self.compare_sides.len()
+        } else {
+            next
+        }
+    }
+
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) {
+        let len = self.scroll_len();
+        match key.code {
+            crossterm::event::KeyCode::Char('v') => {
+                self.view = self.view.next();
+                while !self.available(self.view) {
+                    self.view = self.view.next();
+                }
+                self.scroll = 0;
+            }
+            crossterm::event::KeyCode::Char('a') if self.view == StatsView::Compare => {
+                self.compare.0 = self.next_side(self.compare.0, self.compare.1);
+            }
+            crossterm::event::KeyCode::Char('b') if self.view == StatsView::Compare => {
+                self.compare.1 = self.next_side(self.compare.1, self.compare.0);
+            }
+            crossterm::event::KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            crossterm::event::KeyCode::Down => self.scroll += 1,
+            crossterm::event::KeyCode::PageUp => {
+                self.scroll = self.scroll.saturating_sub(self.page_size)
+            }
+            crossterm::event::KeyCode::PageDown => self.scroll += self.page_size,
+            crossterm::event::KeyCode::Home => self.scroll = 0,
+            crossterm::event::KeyCode::End => self.scroll = len,
+            _ => {}
+        }
+        // Rendering clamps the scroll to the last full page
+        self.scroll = self.scroll.min(len.saturating_sub(1));
+    }
+}
+
+/// One bar per (name, percent read) from `skip`, at most `visible` of them
+fn completion_bars(
+    percents: &[(String, f64)],
+    skip: usize,
+    visible: usize,
+    label_width: usize,
+) -> Vec<Bar<'static>> {
+    percents
+        .iter()
+        .skip(skip)
+        .take(visible)
+        .map(|(name, percent)| {
+            Bar::default()
+                .label(Line::from(truncate_with_ellipsis(name, label_width)))
+                .value(percent.round() as u64)
+                .text_value(format!("{:.0}%", percent))
+                .style(Style::default().fg(completion_color(*percent)))
+                .value_style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(completion_color(*percent)),
+                )
+        })
+        .collect()
+}
+
+/// Horizontal bar chart of completion percentages, one bar per row
+fn completion_chart<'a>(block: Block<'a>, bars: &'a [Bar<'a>]) -> BarChart<'a> {
+    BarChart::default()
+        .block(block)
+        .direction(Direction::Horizontal)
+        .data(BarGroup::default().bars(bars))
+        .bar_width(1)
+        .bar_gap(0)
+        .max(100)
+}
+
+/// Bar color: red for untouched books, yellow while in progress, green once complete
+fn completion_color(percent: f64) -> Color {
+    if percent >= 100.0 {
+        Color::Green
+    } else if percent > 0.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
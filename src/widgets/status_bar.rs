@@ -0,0 +1,110 @@
+use chrono::Utc;
+use ratatui::prelude::*;
+
+use crate::bible_structure::BibleStructure;
+use crate::i18n::{tr, trf};
+use crate::progress::ReadingProgress;
+use crate::stats::{chapters_read_on, current_streak, evaluate_goals, Goal, GoalProgress};
+use crate::widgets::dashboard::DashboardWidget;
+use crate::widgets::tree_builder::RecentReadEntry;
+
+/// Summary shown at the bottom of the dashboard: streak, today's readings and goal progress
+#[derive(Debug, Clone)]
+pub struct StatusBar {
+    pub streak: u32,
+    pub today: Vec<RecentReadEntry>,
+    pub daily_chapter_goal: Option<u32>,
+    pub goals: Vec<Goal>,
+    goal_progress: Vec<GoalProgress>,
+}
+
+impl StatusBar {
+    pub fn new(
+        bible: &'static BibleStructure,
+        progress: &ReadingProgress,
+        daily_chapter_goal: Option<u32>,
+        goals: Vec<Goal>,
+    ) -> Self {
+        let today = Utc::now().date_naive();
+        let streak = current_streak(&progress.history, today);
+        let today_entries = chapters_read_on(&progress.history, today)
+            .into_iter()
+            .map(|(book, chapter)| RecentReadEntry {
+                book,
+                chapter,
+                date: today,
+            })
+            .collect();
+
+        Self {
+            streak,
+            today: today_entries,
+            daily_chapter_goal,
+            goal_progress: evaluate_goals(bible, progress, &goals, today),
+            goals,
+        }
+    }
+
+    pub fn line(&self) -> Line<'static> {
+        let label = Style::default().fg(Color::Gray);
+        let separator = Span::styled(" | ", label);
+
+        let streak_style = if self.streak > 0 {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        let streak_text = match self.streak {
+            1 => tr("1 day").to_string(),
+            n => trf("{} days", &[&n]),
+        };
+
+        let today_text = if self.today.is_empty() {
+            tr("nothing yet").to_string()
+        } else {
+            DashboardWidget::format_entries_with_ranges(&self.today)
+        };
+
+        let mut spans = vec![
+            Span::styled(tr("Streak: "), label),
+            Span::styled(streak_text, streak_style),
+            separator.clone(),
+            Span::styled(tr("Today: "), label),
+            Span::raw(today_text),
+        ];
+
+        if let Some(goal) = self.daily_chapter_goal {
+            let done = self.today.len() as u32;
+            let goal_style = if done >= goal {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            spans.push(separator.clone());
+            spans.push(Span::styled(tr("Goal: "), label));
+            spans.push(Span::styled(
+                trf("{}/{} chapters", &[&done, &goal]),
+                goal_style,
+            ));
+        }
+
+        for goal in &self.goal_progress {
+            let (marker, style) = if goal.met {
+                ("", Style::default().fg(Color::Green))
+            } else if goal.warning.is_some() {
+                ("⚠ ", Style::default().fg(Color::Red))
+            } else {
+                ("", Style::default().fg(Color::Yellow))
+            };
+            spans.push(separator.clone());
+            spans.push(Span::styled(format!("{}{}", marker, goal.summary), style));
+        }
+
+        spans.push(separator);
+        spans.push(Span::styled(tr("?: Help"), label));
+
+        Line::from(spans)
+    }
+}
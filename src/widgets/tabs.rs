@@ -0,0 +1,105 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::i18n::tr;
+use crate::widgets::help::KeyBinding;
+
+/// Top-level screens of the app, shown as tabs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Dashboard,
+    Plans,
+    History,
+    Stats,
+    Settings,
+}
+
+/// Keybindings for switching tabs, available on every tab
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("[/]", "Switch to the previous/next tab"),
+    ("Alt+1…5", "Switch to a tab by number"),
+    ("Ctrl+P", "Search every command"),
+];
+
+impl Tab {
+    pub const ALL: [Tab; 5] = [
+        Tab::Dashboard,
+        Tab::Plans,
+        Tab::History,
+        Tab::Stats,
+        Tab::Settings,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Tab::Dashboard => "Dashboard",
+            Tab::Plans => "Plans",
+            Tab::History => "History",
+            Tab::Stats => "Stats",
+            Tab::Settings => "Settings",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Tab::ALL.iter().position(|tab| tab == self).unwrap_or(0)
+    }
+
+    /// Tab by its 1-based number, as shown in the tab bar
+    pub fn from_number(number: u32) -> Option<Tab> {
+        Tab::ALL.get((number as usize).checked_sub(1)?).copied()
+    }
+
+    pub fn next(&self) -> Tab {
+        Tab::ALL[(self.index() + 1) % Tab::ALL.len()]
+    }
+
+    pub fn previous(&self) -> Tab {
+        Tab::ALL[(self.index() + Tab::ALL.len() - 1) % Tab::ALL.len()]
+    }
+}
+
+/// Render the tab bar with `selected` highlighted
+pub fn render_tabs(frame: &mut Frame, area: Rect, selected: Tab) {
+    let titles: Vec<String> = Tab::ALL
+        .iter()
+        .enumerate()
+        .map(|(idx, tab)| format!("{} {}", idx + 1, tr(tab.title())))
+        .collect();
+    let tabs = Tabs::new(titles)
+        .select(selected.index())
+        .style(Style::default().fg(Color::Gray))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+    frame.render_widget(tabs, area);
+}
+
+/// Render a bordered message for tabs that have nothing to show yet
+pub fn render_placeholder(frame: &mut Frame, area: Rect, title: &str, message: &str) {
+    let placeholder = Paragraph::new(message)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string()),
+        );
+    frame.render_widget(placeholder, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_wrap_around() {
+        assert_eq!(Tab::Dashboard.previous(), Tab::Settings);
+        assert_eq!(Tab::Settings.next(), Tab::Dashboard);
+        assert_eq!(Tab::from_number(3), Some(Tab::History));
+        assert_eq!(Tab::from_number(0), None);
+        assert_eq!(Tab::from_number(6), None);
+    }
+}
@@ -0,0 +1,185 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use unicode_width::UnicodeWidthStr;
+
+use crate::widgets::help::KeyBinding;
+use crate::widgets::text_input::TextInput;
+
+/// Keys for moving between lines, on top of the text field keys each line supports
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("Enter", "Start a new line"),
+    ("↑/↓", "Move to the line above/below"),
+    (
+        "Backspace/Delete",
+        "At the start/end of a line, join it with the line above/below",
+    ),
+];
+
+/// Multi-line text, edited one line at a time with the same keys as a text field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextArea {
+    lines: Vec<TextInput>,
+    /// Line the cursor is on
+    row: usize,
+    /// First line shown, so the cursor's line stays in view
+    scroll: usize,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextArea {
+    pub fn new() -> Self {
+        Self {
+            lines: vec![TextInput::new()],
+            row: 0,
+            scroll: 0,
+        }
+    }
+
+    /// The text, lines joined with newlines
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(TextInput::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Apply an editing key. Returns true if the text changed.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let last_row = self.lines.len() - 1;
+        let line = &mut self.lines[self.row];
+        match key.code {
+            KeyCode::Enter => {
+                let rest = line.split_off();
+                self.row += 1;
+                self.lines.insert(self.row, TextInput::from(rest.as_str()));
+                self.lines[self.row].place_cursor(0);
+                true
+            }
+            KeyCode::Up if self.row > 0 => {
+                self.move_to_row(self.row - 1);
+                false
+            }
+            KeyCode::Down if self.row < last_row => {
+                self.move_to_row(self.row + 1);
+                false
+            }
+            KeyCode::Backspace if line.cursor() == 0 && self.row > 0 => {
+                let line = self.lines.remove(self.row);
+                self.row -= 1;
+                let previous = &mut self.lines[self.row];
+                let joint = previous.len();
+                previous.set(format!("{}{}", previous.as_str(), line.as_str()));
+                previous.place_cursor(joint);
+                true
+            }
+            KeyCode::Delete if line.cursor() == line.len() && self.row < last_row => {
+                let next = self.lines.remove(self.row + 1);
+                let line = &mut self.lines[self.row];
+                let joint = line.cursor();
+                line.set(format!("{}{}", line.as_str(), next.as_str()));
+                line.place_cursor(joint);
+                true
+            }
+            _ => line.handle_key(key, |_| true),
+        }
+    }
+
+    /// Move to another line, keeping the cursor at the same character position where possible
+    fn move_to_row(&mut self, row: usize) {
+        let line = &self.lines[self.row];
+        let column = line[..line.cursor()].chars().count();
+        self.row = row;
+        let line = &mut self.lines[row];
+        let offset = line
+            .char_indices()
+            .nth(column)
+            .map_or(line.len(), |(idx, _)| idx);
+        line.place_cursor(offset);
+    }
+
+    /// Draw the text in `block`, scrolled to the cursor's line, and show the cursor there
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, block: Block) {
+        let inner = block.inner(area);
+        let height = (inner.height as usize).max(1);
+        if self.row < self.scroll {
+            self.scroll = self.row;
+        } else if self.row >= self.scroll + height {
+            self.scroll = self.row + 1 - height;
+        }
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .map(|line| Line::raw(line.as_str()))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .scroll((self.scroll as u16, 0)),
+            area,
+        );
+        if inner.width > 0 && inner.height > 0 {
+            let line = &self.lines[self.row];
+            let offset = line[..line.cursor()].width() as u16;
+            frame.set_cursor_position(Position::new(
+                (inner.x + offset).min(inner.right() - 1),
+                inner.y + (self.row - self.scroll) as u16,
+            ));
+        }
+    }
+}
+
+impl From<&str> for TextArea {
+    /// The text with the cursor at its end
+    fn from(text: &str) -> Self {
+        let lines: Vec<TextInput> = text.split('\n').map(TextInput::from).collect();
+        Self {
+            row: lines.len() - 1,
+            lines,
+            scroll: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(area: &mut TextArea, code: KeyCode) -> bool {
+        area.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn edits_across_lines() {
+        let mut area = TextArea::from("Psalm 23\nstill waters");
+        press(&mut area, KeyCode::Home);
+        press(&mut area, KeyCode::Right);
+        press(&mut area, KeyCode::Right);
+        press(&mut area, KeyCode::Right);
+        press(&mut area, KeyCode::Right);
+        press(&mut area, KeyCode::Right);
+        assert!(press(&mut area, KeyCode::Enter));
+        assert_eq!(area.text(), "Psalm 23\nstill\n waters");
+
+        // Up keeps the column where the line is long enough
+        press(&mut area, KeyCode::End);
+        press(&mut area, KeyCode::Up);
+        assert!(press(&mut area, KeyCode::Char('!')));
+        assert_eq!(area.text(), "Psalm 23\nstill!\n waters");
+
+        press(&mut area, KeyCode::Down);
+        press(&mut area, KeyCode::Home);
+        assert!(press(&mut area, KeyCode::Backspace));
+        assert_eq!(area.text(), "Psalm 23\nstill! waters");
+        press(&mut area, KeyCode::Up);
+        press(&mut area, KeyCode::End);
+        assert!(press(&mut area, KeyCode::Delete));
+        assert_eq!(area.text(), "Psalm 23still! waters");
+        assert!(!press(&mut area, KeyCode::Up));
+    }
+}
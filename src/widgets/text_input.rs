@@ -0,0 +1,233 @@
+use std::ops::Deref;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::prelude::*;
+use unicode_width::UnicodeWidthStr;
+
+/// Editing keys every text field supports, shown in the help overlay of forms that use them
+pub const KEYBINDINGS: &[crate::widgets::help::KeyBinding] = &[
+    ("←/→", "Move the cursor (Ctrl: by word)"),
+    ("Home/End", "Move the cursor to the start/end of the field"),
+    ("Backspace/Delete", "Delete before/after the cursor"),
+    ("Ctrl+W", "Delete the word before the cursor"),
+    ("Ctrl+U", "Delete everything before the cursor"),
+];
+
+/// The text of an input field and the cursor within it. It derefs to the text, so it can be
+/// read like a `&str`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    value: String,
+    /// Byte offset of the cursor, always on a character boundary
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Replace the text, putting the cursor at the end
+    pub fn set(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+    }
+
+    /// Byte offset of the cursor
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Move the cursor to byte `offset`, or to the end if the text is shorter, backing up to a
+    /// character boundary
+    pub fn place_cursor(&mut self, offset: usize) {
+        let mut offset = offset.min(self.value.len());
+        while !self.value.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        self.cursor = offset;
+    }
+
+    /// Remove and return the text after the cursor
+    pub fn split_off(&mut self) -> String {
+        self.value.split_off(self.cursor)
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Apply an editing key. Typed characters are only inserted if `accept` allows them.
+    /// Returns true if the text changed, so callers can refresh anything derived from it.
+    pub fn handle_key(&mut self, key: KeyEvent, accept: impl Fn(char) -> bool) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Char('w') if ctrl => self.delete_to(self.previous_word()),
+            KeyCode::Char('u') if ctrl => self.delete_to(0),
+            KeyCode::Char(c) if !ctrl && !alt && accept(c) => {
+                self.insert(c);
+                true
+            }
+            KeyCode::Backspace if ctrl || alt => self.delete_to(self.previous_word()),
+            KeyCode::Backspace => self.delete_to(self.previous_char()),
+            KeyCode::Delete => self.delete_to(self.next_char()),
+            KeyCode::Left if ctrl || alt => self.move_to(self.previous_word()),
+            KeyCode::Left => self.move_to(self.previous_char()),
+            KeyCode::Right if ctrl || alt => self.move_to(self.next_word()),
+            KeyCode::Right => self.move_to(self.next_char()),
+            KeyCode::Home => self.move_to(0),
+            KeyCode::End => self.move_to(self.value.len()),
+            _ => false,
+        }
+    }
+
+    /// Show the terminal cursor in the field, drawn as a bordered block in `area`
+    pub fn set_cursor(&self, frame: &mut Frame, area: Rect) {
+        if area.width < 3 || area.height < 3 {
+            return;
+        }
+        let offset = self.value[..self.cursor].width() as u16;
+        let x = (area.x + 1 + offset).min(area.right() - 2);
+        frame.set_cursor_position(Position::new(x, area.y + 1));
+    }
+
+    /// The text with the character under the cursor reversed, for fields drawn inline in a line
+    /// of text rather than in their own block
+    pub fn cursor_spans(&self) -> Vec<Span<'static>> {
+        let next = self.next_char();
+        let under_cursor = match &self.value[self.cursor..next] {
+            "" => " ",
+            c => c,
+        };
+        vec![
+            Span::raw(self.value[..self.cursor].to_string()),
+            Span::styled(
+                under_cursor.to_string(),
+                Style::default().add_modifier(Modifier::REVERSED),
+            ),
+            Span::raw(self.value[next..].to_string()),
+        ]
+    }
+
+    fn move_to(&mut self, position: usize) -> bool {
+        self.cursor = position;
+        false
+    }
+
+    /// Delete between the cursor and `position`, which may be on either side of it
+    fn delete_to(&mut self, position: usize) -> bool {
+        let range = position.min(self.cursor)..position.max(self.cursor);
+        if range.is_empty() {
+            return false;
+        }
+        self.cursor = range.start;
+        self.value.replace_range(range, "");
+        true
+    }
+
+    fn previous_char(&self) -> usize {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(idx, _)| idx)
+    }
+
+    fn next_char(&self) -> usize {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    /// Start of the word before the cursor, skipping any spaces right before it
+    fn previous_word(&self) -> usize {
+        let before = self.value[..self.cursor].trim_end();
+        before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(idx, c)| idx + c.len_utf8())
+    }
+
+    /// End of the word after the cursor, skipping any spaces right after it
+    fn next_word(&self) -> usize {
+        let after = &self.value[self.cursor..];
+        let skipped = after.len() - after.trim_start().len();
+        after[skipped..]
+            .find(char::is_whitespace)
+            .map_or(self.value.len(), |idx| self.cursor + skipped + idx)
+    }
+}
+
+impl Deref for TextInput {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl From<&str> for TextInput {
+    fn from(value: &str) -> Self {
+        let mut input = Self::new();
+        input.set(value);
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        input.handle_key(KeyEvent::new(code, modifiers), |_| true)
+    }
+
+    #[test]
+    fn edits_at_the_cursor() {
+        let mut input = TextInput::from("John 3");
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        assert!(press(&mut input, KeyCode::Char('1'), KeyModifiers::NONE));
+        assert_eq!(input.as_str(), "John1 3");
+        assert!(press(&mut input, KeyCode::Backspace, KeyModifiers::NONE));
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        assert!(press(&mut input, KeyCode::Delete, KeyModifiers::NONE));
+        assert_eq!(input.as_str(), "ohn 3");
+        assert!(!press(&mut input, KeyCode::End, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn moves_and_deletes_by_word() {
+        let mut input = TextInput::from("1 John  3:16");
+        assert!(press(&mut input, KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!(input.as_str(), "1 John  ");
+        assert!(press(&mut input, KeyCode::Backspace, KeyModifiers::ALT));
+        assert_eq!(input.as_str(), "1 ");
+
+        let mut input = TextInput::from("Song of Songs");
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Right, KeyModifiers::CONTROL);
+        press(&mut input, KeyCode::Right, KeyModifiers::CONTROL);
+        assert!(press(&mut input, KeyCode::Char('u'), KeyModifiers::CONTROL));
+        assert_eq!(input.as_str(), " Songs");
+    }
+
+    #[test]
+    fn rejected_characters_are_not_inserted() {
+        let mut input = TextInput::new();
+        assert!(!input.handle_key(KeyEvent::from(KeyCode::Char('x')), |c| c.is_ascii_digit()));
+        assert!(input.is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{prelude::*, widgets::*};
+use unicode_width::UnicodeWidthStr;
+
+use crate::i18n::tr;
+
+/// How long a toast stays on screen
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// A short notification in the top-right corner that disappears on its own
+pub struct Toast {
+    title: String,
+    lines: Vec<String>,
+    shown_at: Instant,
+}
+
+impl Toast {
+    pub fn new(title: impl Into<String>, lines: Vec<String>) -> Self {
+        Self {
+            title: title.into(),
+            lines,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.shown_at.elapsed() >= TOAST_DURATION
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let title = tr(&self.title);
+        let content_width = self
+            .lines
+            .iter()
+            .map(|line| line.width())
+            .chain(std::iter::once(title.width()))
+            .max()
+            .unwrap_or(0) as u16;
+        let width = (content_width + 4).min(area.width);
+        let height = (self.lines.len() as u16 + 2).min(area.height);
+        // Just below the tab bar, against the right edge
+        let toast_area = Rect::new(
+            area.right().saturating_sub(width),
+            area.y + 1.min(area.height.saturating_sub(height)),
+            width,
+            height,
+        );
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .map(|line| Line::raw(line.clone()))
+            .collect();
+        frame.render_widget(Clear, toast_area);
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .padding(Padding::horizontal(1)),
+            ),
+            toast_area,
+        );
+    }
+}
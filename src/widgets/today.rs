@@ -0,0 +1,136 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::today::TodayItem;
+use crate::widgets::help::KeyBinding;
+use crate::widgets::popup::centered_rect;
+
+/// Keybindings available in the Today panel, shown in the help overlay
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    ("↑/↓", "Select a reading"),
+    ("Enter", "Mark the selected chapter as read today"),
+    (
+        "b",
+        "Switch between the daily readings and a balanced mix (Old Testament narrative, Psalms & wisdom, New Testament)",
+    ),
+    ("T/Esc", "Close the panel"),
+];
+
+/// Action requested by the Today panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TodayAction {
+    None,
+    Close,
+    /// Record the whole chapter as read today
+    MarkRead {
+        book: String,
+        chapter: u32,
+    },
+    /// Switch between the daily readings and the balanced mix
+    ToggleBalanced,
+}
+
+/// Popup listing the chapters suggested for today
+pub struct TodayPanel {
+    items: Vec<TodayItem>,
+    state: ListState,
+    /// Whether the items are the balanced mix rather than the daily readings
+    balanced: bool,
+}
+
+impl TodayPanel {
+    pub fn new(items: Vec<TodayItem>, balanced: bool) -> Self {
+        // Start on the first reading that is still to do
+        let first_open = items.iter().position(|item| !item.done).unwrap_or(0);
+        Self {
+            items,
+            state: ListState::default().with_selected(Some(first_open)),
+            balanced,
+        }
+    }
+
+    /// Replace the items (e.g. after one was marked done), keeping the selection
+    pub fn set_items(&mut self, items: Vec<TodayItem>) {
+        self.items = items;
+        let selected = self.state.selected().unwrap_or(0);
+        self.state
+            .select(Some(selected.min(self.items.len().saturating_sub(1))));
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let popup_area = centered_rect(60, 50, frame.area());
+        frame.render_widget(Clear, popup_area);
+
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| {
+                let (check, style) = if item.done {
+                    ("[✓] ", Style::default().fg(Color::Green))
+                } else {
+                    ("[ ] ", Style::default())
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(check, style),
+                    Span::styled(format!("{} {}", item.book, item.chapter), style),
+                    Span::styled(
+                        format!("  {} · ~{} min", item.source.label(), item.minutes),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let minutes_left: u32 = self
+            .items
+            .iter()
+            .filter(|item| !item.done)
+            .map(|item| item.minutes)
+            .sum();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(if self.balanced {
+                        "Today: balanced mix (Enter: mark read, b: daily readings, T/Esc: close)"
+                    } else {
+                        "Today (Enter: mark read, b: balanced mix, T/Esc: close)"
+                    })
+                    .title_bottom(format!(" ~{} min left ", minutes_left)),
+            )
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(">> ");
+        frame.render_stateful_widget(list, popup_area, &mut self.state);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> TodayAction {
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('T') | KeyCode::Esc => TodayAction::Close,
+            KeyCode::Char('b') => TodayAction::ToggleBalanced,
+            KeyCode::Up => {
+                self.state.select(Some(selected.saturating_sub(1)));
+                TodayAction::None
+            }
+            KeyCode::Down => {
+                self.state
+                    .select(Some((selected + 1).min(self.items.len().saturating_sub(1))));
+                TodayAction::None
+            }
+            KeyCode::Enter => match self.items.get(selected) {
+                Some(item) if !item.done => TodayAction::MarkRead {
+                    book: item.book.clone(),
+                    chapter: item.chapter,
+                },
+                _ => TodayAction::None,
+            },
+            _ => TodayAction::None,
+        }
+    }
+}
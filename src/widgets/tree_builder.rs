@@ -1,400 +1,541 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
 use chrono::{Duration, NaiveDate, Utc};
-use ratatui::style::Color;
-use ratatui::style::Style;
-use ratatui::text::Text;
+use indexmap::IndexMap;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Span, Text};
+use serde::{Deserialize, Serialize};
 use tui_tree_widget::TreeItem;
+use unicode_width::UnicodeWidthStr;
 
+use crate::bible_structure::BookFilter;
+use crate::book_name::BookName;
+use crate::coverage::{book_percent_read, chapter_verse_counts, percentage};
 use crate::progress::{InsideBookBibleReference, ReadingProgress, ReadingRecord};
 use crate::range_query::RangeMap;
+use crate::utils::truncate_with_ellipsis;
+use crate::widgets::gradient::ColorGradient;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TreeId {
     OldTestament,
     NewTestament,
-    Book(String),
+    Book(BookName),
     Chapter {
-        book: String,
+        book: BookName,
         chapter: u32,
     },
     Passage {
-        book: String,
+        book: BookName,
         chapter: u32,
         verse_start: u32,
         verse_end: u32,
     },
 }
 
-#[derive(Debug, Clone)]
-pub struct DashboardItem {
-    pub book: String,
-    pub chapter: u32,
-    pub verse_start: u32,
-    pub verse_end: u32,
-    pub read_count: u32,
-    pub last_read: Option<chrono::NaiveDate>,
-    pub is_read: bool,
+impl TreeId {
+    /// The reference this node stands for, e.g. "Romans 8" or "Romans 8:1-5"
+    pub fn reference(&self) -> String {
+        match self {
+            TreeId::OldTestament => "Old Testament".to_string(),
+            TreeId::NewTestament => "New Testament".to_string(),
+            TreeId::Book(book) => book.to_string(),
+            TreeId::Chapter { book, chapter } => format!("{} {}", book, chapter),
+            TreeId::Passage {
+                book,
+                chapter,
+                verse_start,
+                verse_end,
+            } if verse_start == verse_end => format!("{} {}:{}", book, chapter, verse_start),
+            TreeId::Passage {
+                book,
+                chapter,
+                verse_start,
+                verse_end,
+            } => format!("{} {}:{}-{}", book, chapter, verse_start, verse_end),
+        }
+    }
 }
 
-pub fn build_dashboard_tree_items(
-    bible: &'static crate::bible_structure::BibleStructure,
-    progress: &ReadingProgress,
-) -> Vec<TreeItem<'static, TreeId>> {
-    // First pass: calculate maximum prefix width
-    let max_prefix_width = calculate_max_prefix_width(bible, progress);
-
-    let mut tree = Vec::new();
-
-    // Old Testament - calculate min_read_count for the testament
-    let mut ot_books = Vec::new();
-    let ot_min_read_count = calculate_testament_min_read_count(&bible.ot, progress);
-    for book in bible.ot.keys() {
-        let chapters = bible.ot.get(book).unwrap();
-        let book_records = progress.books.get(book);
-        let (book_min_read_count, _, _) = calculate_book_read_stats(chapters, book_records);
-        let (book_chapters, chapter_colors) = build_chapter_items(
-            book,
-            chapters,
-            book_records,
-            book_min_read_count,
-            max_prefix_width,
-        );
-        let book_label = build_book_label(
-            book,
-            chapters,
-            book_records,
-            ot_min_read_count,
-            &chapter_colors,
-            max_prefix_width,
-        );
-        let book_id = book.clone();
-        ot_books.push(TreeItem::new(TreeId::Book(book_id), book_label, book_chapters).unwrap());
-    }
-
-    tree.push(TreeItem::new(TreeId::OldTestament, "Old Testament", ot_books).unwrap());
-
-    // New Testament - calculate min_read_count for the testament
-    let mut nt_books = Vec::new();
-    let nt_min_read_count = calculate_testament_min_read_count(&bible.nt, progress);
-    for book in bible.nt.keys() {
-        let chapters = bible.nt.get(book).unwrap();
-        let book_records = progress.books.get(book);
-        let (book_min_read_count, _, _) = calculate_book_read_stats(chapters, book_records);
-        let (book_chapters, chapter_colors) = build_chapter_items(
-            book,
-            chapters,
-            book_records,
-            book_min_read_count,
-            max_prefix_width,
-        );
-        let book_label = build_book_label(
-            book,
-            chapters,
-            book_records,
-            nt_min_read_count,
-            &chapter_colors,
-            max_prefix_width,
-        );
-        let book_id = book.clone();
-        nt_books.push(TreeItem::new(TreeId::Book(book_id), book_label, book_chapters).unwrap());
-    }
+/// Columns each tree level is indented by relative to its parent
+pub const TREE_INDENT: usize = 2;
 
-    tree.push(TreeItem::new(TreeId::NewTestament, "New Testament", nt_books).unwrap());
+/// Checkmark drawn before chapters marked for a batch action
+const MARK: &str = "✓ ";
 
-    tree
+/// Batch marks when text markers are on, where a checkmark would read as `ReadStatus::Complete`
+const TEXT_MARK: &str = "[x] ";
+const TEXT_UNMARKED: &str = "[ ] ";
+
+/// Whether every verse of a passage has been read, as conveyed by a text marker in
+/// accessibility mode instead of by color alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadStatus {
+    Unread,
+    Partial,
+    Complete,
 }
 
-/// Calculate the maximum width of the prefix portion (book/chapter name + read count)
-/// across all books and chapters, excluding the "Last read:" portion
-fn calculate_max_prefix_width(
-    bible: &'static crate::bible_structure::BibleStructure,
-    progress: &ReadingProgress,
-) -> usize {
-    let mut max_width = 0;
-
-    // Check Old Testament books
-    for book in bible.ot.keys() {
-        let chapters = bible.ot.get(book).unwrap();
-        let book_records = progress.books.get(book);
-        let (book_min_read_count, verses_read_more, total_verses_for_stats) =
-            calculate_book_read_stats(chapters, book_records);
-        let read_count_text = format_read_count_text(
-            book_min_read_count,
-            verses_read_more,
-            total_verses_for_stats,
-        );
-        let book_prefix = if !read_count_text.is_empty() {
-            format!("{} ({})", book, read_count_text)
+impl ReadStatus {
+    fn of(verse_counts: &[u32]) -> Self {
+        if verse_counts.iter().all(|&count| count > 0) {
+            ReadStatus::Complete
+        } else if verse_counts.iter().any(|&count| count > 0) {
+            ReadStatus::Partial
         } else {
-            book.clone()
-        };
-        max_width = max_width.max(book_prefix.len());
+            ReadStatus::Unread
+        }
+    }
 
-        // Check chapters in this book
-        for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
-            let chapter = (chapter_idx + 1) as u32;
-            let verse_items = compute_chapter_items(book, chapter, max_verse, book_records);
-            let total_verses: u32 = verse_items
-                .iter()
-                .map(|item| item.verse_end - item.verse_start + 1)
-                .sum();
-            let read_verses: u32 = verse_items
-                .iter()
-                .filter(|item| item.is_read)
-                .map(|item| item.verse_end - item.verse_start + 1)
-                .sum();
-
-            let (chapter_min_read_count, verses_read_more, total_verses_for_stats) =
-                calculate_chapter_read_stats(chapter, max_verse, book_records);
-            let read_count_text = format_read_count_text(
-                chapter_min_read_count,
-                verses_read_more,
-                total_verses_for_stats,
-            );
-            let read_count_display = if verses_read_more == total_verses_for_stats
-                && total_verses_for_stats > 0
-                && chapter_min_read_count > 0
-            {
-                format!(
-                    "{}x ({} verses)",
-                    chapter_min_read_count, total_verses_for_stats
-                )
-            } else {
-                read_count_text
-            };
+    pub fn marker(self) -> &'static str {
+        match self {
+            ReadStatus::Unread => "✗",
+            ReadStatus::Partial => "◐",
+            ReadStatus::Complete => "✓",
+        }
+    }
 
-            let chapter_prefix = if !read_count_display.is_empty() {
-                format!("Chapter {} ({})", chapter, read_count_display)
-            } else {
-                format!(
-                    "Chapter {} ({} / {} verses)",
-                    chapter, read_verses, total_verses
-                )
-            };
-            max_width = max_width.max(chapter_prefix.len());
+    pub fn description(self) -> &'static str {
+        match self {
+            ReadStatus::Unread => "Not read yet",
+            ReadStatus::Partial => "Partly read",
+            ReadStatus::Complete => "Every verse read at least once",
         }
     }
+}
 
-    // Check New Testament books
-    for book in bible.nt.keys() {
-        let chapters = bible.nt.get(book).unwrap();
-        let book_records = progress.books.get(book);
-        let (book_min_read_count, verses_read_more, total_verses_for_stats) =
-            calculate_book_read_stats(chapters, book_records);
-        let read_count_text = format_read_count_text(
-            book_min_read_count,
-            verses_read_more,
-            total_verses_for_stats,
-        );
-        let book_prefix = if !read_count_text.is_empty() {
-            format!("{} ({})", book, read_count_text)
-        } else {
-            book.clone()
+/// Per-book label summaries for the dashboard tree, cached so that recording a reading only
+/// recomputes the books it touched instead of walking every verse of the Bible again. Tree items
+/// are built from the summaries on demand, with chapter nodes only for expanded books.
+pub struct DashboardTree {
+    books: HashMap<String, BookSummary>,
+    /// Labels are padded to this width so that the "Last read" column lines up
+    max_prefix_width: usize,
+    /// Start labels with a `ReadStatus` marker, for when colors can't be told apart
+    pub text_markers: bool,
+}
+
+impl DashboardTree {
+    pub fn new(
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+        gradient: &ColorGradient,
+    ) -> Self {
+        let mut tree = Self {
+            books: HashMap::new(),
+            max_prefix_width: 0,
+            text_markers: false,
         };
-        max_width = max_width.max(book_prefix.len());
+        let all_books = testaments(bible)
+            .into_iter()
+            .flat_map(|(_, books)| books.keys().map(|book| BookName::new(book)))
+            .collect();
+        tree.update_books(bible, progress, gradient, &all_books);
+        tree
+    }
 
-        // Check chapters in this book
-        for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
-            let chapter = (chapter_idx + 1) as u32;
-            let verse_items = compute_chapter_items(book, chapter, max_verse, book_records);
-            let total_verses: u32 = verse_items
-                .iter()
-                .map(|item| item.verse_end - item.verse_start + 1)
-                .sum();
-            let read_verses: u32 = verse_items
-                .iter()
-                .filter(|item| item.is_read)
-                .map(|item| item.verse_end - item.verse_start + 1)
-                .sum();
-
-            let (chapter_min_read_count, verses_read_more, total_verses_for_stats) =
-                calculate_chapter_read_stats(chapter, max_verse, book_records);
-            let read_count_text = format_read_count_text(
-                chapter_min_read_count,
-                verses_read_more,
-                total_verses_for_stats,
-            );
-            let read_count_display = if verses_read_more == total_verses_for_stats
-                && total_verses_for_stats > 0
-                && chapter_min_read_count > 0
-            {
-                format!(
-                    "{}x ({} verses)",
-                    chapter_min_read_count, total_verses_for_stats
-                )
-            } else {
-                read_count_text
-            };
+    /// Recompute the given books from the current progress, reusing the other books' summaries
+    pub fn update_books(
+        &mut self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        progress: &ReadingProgress,
+        gradient: &ColorGradient,
+        books: &HashSet<BookName>,
+    ) {
+        let mut summaries = Vec::new();
+        for (_, testament_books) in testaments(bible) {
+            for (book, chapters) in testament_books {
+                if books.contains(book.as_str()) {
+                    let summary = BookSummary::compute(
+                        book,
+                        chapters,
+                        progress.books.get(book.as_str()),
+                        gradient,
+                    );
+                    summaries.push((book.clone(), summary));
+                }
+            }
+        }
+
+        let max_prefix_width = self
+            .books
+            .iter()
+            .filter(|(book, _)| !books.contains(book.as_str()))
+            .map(|(_, summary)| summary.prefix_width())
+            .chain(summaries.iter().map(|(_, summary)| summary.prefix_width()))
+            .max()
+            .unwrap_or(0);
+
+        self.max_prefix_width = max_prefix_width;
+        self.books.extend(summaries);
+    }
+
+    /// A book's or chapter's label as plain text under its full reference, e.g.
+    /// "Romans 8 (2x) | Last read: 3 days ago"
+    pub fn stats_line(&self, id: &TreeId) -> Option<String> {
+        let label = match id {
+            TreeId::Book(book) => &self.books.get(book.as_str())?.label,
+            TreeId::Chapter { book, chapter } => {
+                &self
+                    .books
+                    .get(book.as_str())?
+                    .chapters
+                    .get((*chapter as usize).checked_sub(1)?)?
+                    .label
+            }
+            _ => return None,
+        };
+        let mut line = format!("{} ({})", id.reference(), label.read_count_text);
+        if let Some(date) = label.last_read {
+            line.push_str(&format!(" | Last read: {}", format_last_read_date(date)));
+        }
+        Some(line)
+    }
 
-            let chapter_prefix = if !read_count_display.is_empty() {
-                format!("Chapter {} ({})", chapter, read_count_display)
+    /// Tree items for the books matching `filter`, grouped by testament. Only books in
+    /// `expanded_books` get their chapter nodes; the others get a placeholder child so they can
+    /// still be opened. `label_width` is the number of columns available to a book's label
+    /// (chapters get `TREE_INDENT` fewer); longer labels are truncated. Chapters in `marked`,
+    /// as (book, chapter), get a checkmark.
+    pub fn items(
+        &self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        filter: BookFilter,
+        expanded_books: &HashSet<String>,
+        marked: &BTreeSet<(String, u32)>,
+        label_width: Option<usize>,
+    ) -> Vec<TreeItem<'static, TreeId>> {
+        let mut tree = Vec::new();
+        for (testament_id, books) in testaments(bible) {
+            let in_old_testament = testament_id == TreeId::OldTestament;
+            let book_items: Vec<_> = books
+                .keys()
+                .filter(|book| filter.includes(in_old_testament, book))
+                .filter_map(|book| {
+                    let summary = self.books.get(book)?;
+                    let expanded = expanded_books.contains(book);
+                    let marked_chapters: HashSet<u32> = marked
+                        .range((book.clone(), 0)..=(book.clone(), u32::MAX))
+                        .map(|(_, chapter)| *chapter)
+                        .collect();
+                    Some(summary.tree_item(
+                        book,
+                        self.max_prefix_width,
+                        expanded,
+                        &marked_chapters,
+                        self.text_markers,
+                        label_width,
+                    ))
+                })
+                .collect();
+            if book_items.is_empty() {
+                continue;
+            }
+            let label = if in_old_testament {
+                "Old Testament"
             } else {
-                format!(
-                    "Chapter {} ({} / {} verses)",
-                    chapter, read_verses, total_verses
-                )
+                "New Testament"
             };
-            max_width = max_width.max(chapter_prefix.len());
+            tree.push(TreeItem::new(testament_id, label, book_items).unwrap());
+        }
+        tree
+    }
+
+    /// Label text, color and read status of each of `book`'s chapters, in order
+    pub fn chapter_labels(&self, book: &str) -> Vec<(String, Color, ReadStatus)> {
+        self.books.get(book).map_or_else(Vec::new, |summary| {
+            summary
+                .chapters
+                .iter()
+                .map(|chapter| {
+                    let label = &chapter.label;
+                    (label.prefix.clone(), label.color, label.status)
+                })
+                .collect()
+        })
+    }
+
+    /// List the tree path of every chapter shown by `filter` in canonical order, along with
+    /// whether it still has unread verses. Used to jump between unread chapters.
+    pub fn chapter_unread_flags(
+        &self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        filter: BookFilter,
+    ) -> Vec<(Vec<TreeId>, bool)> {
+        let mut chapters = Vec::new();
+        for (testament_id, books) in testaments(bible) {
+            let in_old_testament = testament_id == TreeId::OldTestament;
+            for book in books.keys() {
+                if !filter.includes(in_old_testament, book) {
+                    continue;
+                }
+                let Some(summary) = self.books.get(book) else {
+                    continue;
+                };
+                let book = BookName::new(book);
+                for (chapter_idx, chapter) in summary.chapters.iter().enumerate() {
+                    let path = vec![
+                        testament_id.clone(),
+                        TreeId::Book(book.clone()),
+                        TreeId::Chapter {
+                            book: book.clone(),
+                            chapter: (chapter_idx + 1) as u32,
+                        },
+                    ];
+                    chapters.push((path, chapter.has_unread));
+                }
+            }
         }
+        chapters
     }
 
-    max_width
+    /// Percentage of verses among the books shown by `filter` that have been read at least once
+    pub fn percent_read(
+        &self,
+        bible: &'static crate::bible_structure::BibleStructure,
+        filter: BookFilter,
+    ) -> f64 {
+        let (mut verses_read, mut total_verses) = (0, 0);
+        for (testament_id, books) in testaments(bible) {
+            let in_old_testament = testament_id == TreeId::OldTestament;
+            for book in books.keys() {
+                if !filter.includes(in_old_testament, book) {
+                    continue;
+                }
+                if let Some(summary) = self.books.get(book) {
+                    verses_read += summary.verses_read;
+                    total_verses += summary.total_verses;
+                }
+            }
+        }
+        percentage(verses_read, total_verses)
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ChapterColor {
-    Green,
-    Yellow,
-    White,
+fn testaments(
+    bible: &'static crate::bible_structure::BibleStructure,
+) -> [(TreeId, &'static IndexMap<String, Vec<u32>>); 2] {
+    [
+        (TreeId::OldTestament, &bible.ot),
+        (TreeId::NewTestament, &bible.nt),
+    ]
 }
 
-/// Build chapter tree items for a book
-/// Returns (chapter_items, chapter_colors) where chapter_colors indicates the color state of each chapter
-fn build_chapter_items(
-    book: &str,
-    chapters: &[u32],
-    book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
-    book_min_read_count: u32,
-    max_prefix_width: usize,
-) -> (Vec<TreeItem<'static, TreeId>>, Vec<ChapterColor>) {
-    let mut book_chapters = Vec::new();
-    let mut chapter_colors = Vec::new();
+/// A node label, split into the text before the "Last read" column and the date for that column
+#[derive(Debug, Clone)]
+struct Label {
+    prefix: String,
+    /// Read count part of the prefix, e.g. "2x + 20/30"
+    read_count_text: String,
+    last_read: Option<NaiveDate>,
+    color: Color,
+    status: ReadStatus,
+}
 
-    for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
-        let chapter = (chapter_idx + 1) as u32;
-        let verse_items = compute_chapter_items(book, chapter, max_verse, book_records);
+impl Label {
+    fn new(
+        name: String,
+        verse_counts: &[u32],
+        last_read: Option<NaiveDate>,
+        gradient: &ColorGradient,
+    ) -> Self {
+        let (min_read_count, verses_read_more) = read_count_stats(verse_counts);
+        let read_count_text =
+            format_read_count_text(min_read_count, verses_read_more, verse_counts.len() as u32);
+        Self {
+            prefix: format!("{} ({})", name, read_count_text),
+            read_count_text,
+            last_read,
+            color: gradient.color_for(min_read_count, last_read),
+            status: ReadStatus::of(verse_counts),
+        }
+    }
 
-        let total_verses: u32 = verse_items
-            .iter()
-            .map(|item| item.verse_end - item.verse_start + 1)
-            .sum();
-        let read_verses: u32 = verse_items
-            .iter()
-            .filter(|item| item.is_read)
-            .map(|item| item.verse_end - item.verse_start + 1)
-            .sum();
-
-        // Calculate read count statistics for this chapter
-        let (chapter_min_read_count, verses_read_more, total_verses_for_stats) =
-            calculate_chapter_read_stats(chapter, max_verse, book_records);
-
-        let chapter_style = if chapter_min_read_count > book_min_read_count {
-            Style::default().fg(Color::Green)
-        } else if verses_read_more > 0 {
-            Style::default().fg(Color::Yellow)
-        } else {
-            Style::default().fg(Color::White)
+    /// Render the label, padding the prefix to `prefix_width` columns so the "Last read" column
+    /// lines up. If the result is wider than `max_width`, the padding is dropped first and then
+    /// the label is cut off with an ellipsis.
+    fn render(&self, prefix_width: usize, max_width: Option<usize>) -> Text<'static> {
+        let last_read_text = match self.last_read {
+            Some(date) => format!(" | Last read: {:>15}", format_last_read_date(date)),
+            None => String::new(),
         };
+        let padding = " ".repeat(prefix_width.saturating_sub(self.prefix.width()));
+        let mut text = format!("{}{}{}", self.prefix, padding, last_read_text);
+        if let Some(max_width) = max_width {
+            if text.width() > max_width {
+                text = truncate_with_ellipsis(
+                    &format!("{}{}", self.prefix, last_read_text),
+                    max_width,
+                );
+            }
+        }
+        Text::from(text).style(Style::default().fg(self.color))
+    }
+}
 
-        // Find the most recent last_read date for this chapter
-        let last_read_date = verse_items.iter().filter_map(|item| item.last_read).max();
+/// A chapter's label, and whether it still has unread verses
+#[derive(Debug, Clone)]
+struct ChapterSummary {
+    label: Label,
+    has_unread: bool,
+}
 
-        let last_read_text = if let Some(date) = last_read_date {
-            let date_str = format_last_read_date(date);
-            format!(" | Last read: {:>15}", date_str)
-        } else {
-            String::new()
-        };
+/// Everything the dashboard shows for a book, computed in a single pass over its records
+#[derive(Debug, Clone)]
+struct BookSummary {
+    label: Label,
+    chapters: Vec<ChapterSummary>,
+    verses_read: u32,
+    total_verses: u32,
+}
 
-        let read_count_text = format_read_count_text(
-            chapter_min_read_count,
-            verses_read_more,
-            total_verses_for_stats,
-        );
+impl BookSummary {
+    fn compute(
+        book: &str,
+        chapters: &[u32],
+        book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
+        gradient: &ColorGradient,
+    ) -> Self {
+        let mut book_verse_counts = Vec::new();
+        let mut chapter_summaries = Vec::with_capacity(chapters.len());
 
-        // Special case: if all verses are read at least one more time (100%), add parenthetical with verse count
-        let read_count_display = if verses_read_more == total_verses_for_stats
-            && total_verses_for_stats > 0
-            && chapter_min_read_count > 0
-        {
-            format!(
-                "{}x ({} verses)",
-                chapter_min_read_count, total_verses_for_stats
-            )
-        } else {
-            read_count_text
-        };
+        for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
+            let chapter = (chapter_idx + 1) as u32;
+            let (verse_counts, last_read) = chapter_verse_counts(chapter, max_verse, book_records);
+            chapter_summaries.push(ChapterSummary {
+                label: Label::new(
+                    format!("Chapter {}", chapter),
+                    &verse_counts,
+                    last_read,
+                    gradient,
+                ),
+                has_unread: verse_counts.contains(&0),
+            });
+            book_verse_counts.extend(verse_counts);
+        }
+
+        // Most recent last_read date across all records of this book
+        let book_last_read = book_records
+            .and_then(|records| records.iter().map(|(_, record)| record.last_read).max());
+
+        Self {
+            label: Label::new(
+                book.to_string(),
+                &book_verse_counts,
+                book_last_read,
+                gradient,
+            ),
+            chapters: chapter_summaries,
+            verses_read: book_verse_counts.iter().filter(|&&count| count > 0).count() as u32,
+            total_verses: book_verse_counts.len() as u32,
+        }
+    }
+
+    /// Width of the widest label prefix among the book and its chapters
+    fn prefix_width(&self) -> usize {
+        self.chapters
+            .iter()
+            .map(|chapter| chapter.label.prefix.width())
+            .chain(std::iter::once(self.label.prefix.width()))
+            .max()
+            .unwrap_or(0)
+    }
 
-        let chapter_prefix = if !read_count_display.is_empty() {
-            format!("Chapter {} ({})", chapter, read_count_display)
+    /// The book's tree item. Collapsed books get a single placeholder child instead of their
+    /// chapters, which keeps the expand arrow without building a node per chapter. If any
+    /// chapters are in `marked_chapters`, every chapter label starts with a checkbox column.
+    /// With `text_markers`, labels also start with their `ReadStatus` marker.
+    fn tree_item(
+        &self,
+        book: &str,
+        prefix_width: usize,
+        expanded: bool,
+        marked_chapters: &HashSet<u32>,
+        text_markers: bool,
+        label_width: Option<usize>,
+    ) -> TreeItem<'static, TreeId> {
+        let status_marker = |label: &Label| {
+            if text_markers {
+                format!("{} ", label.status.marker())
+            } else {
+                String::new()
+            }
+        };
+        let (mark, unmarked) = if text_markers {
+            (TEXT_MARK, TEXT_UNMARKED)
         } else {
-            format!(
-                "Chapter {} ({} / {} verses)",
-                chapter, read_verses, total_verses
-            )
+            (MARK, "  ")
         };
-        let padding = " ".repeat(max_prefix_width.saturating_sub(chapter_prefix.len()));
-        let chapter_text = format!("{}{}{}", chapter_prefix, padding, last_read_text);
 
-        let chapter_color = match chapter_style.fg {
-            Some(Color::Green) => ChapterColor::Green,
-            Some(Color::Yellow) => ChapterColor::Yellow,
-            _ => ChapterColor::White,
+        let book = BookName::new(book);
+        let chapter_id = |chapter_idx: usize| TreeId::Chapter {
+            book: book.clone(),
+            chapter: (chapter_idx + 1) as u32,
         };
-        chapter_colors.push(chapter_color);
-
-        book_chapters.push(TreeItem::new_leaf(
-            TreeId::Chapter {
-                book: book.to_string(),
-                chapter,
-            },
-            Text::from(chapter_text).style(chapter_style),
-        ));
+        let chapter_items = if expanded {
+            self.chapters
+                .iter()
+                .enumerate()
+                .map(|(chapter_idx, chapter)| {
+                    let mut lead = String::new();
+                    if !marked_chapters.is_empty() {
+                        if marked_chapters.contains(&(chapter_idx as u32 + 1)) {
+                            lead.push_str(mark);
+                        } else {
+                            lead.push_str(unmarked);
+                        }
+                    }
+                    lead.push_str(&status_marker(&chapter.label));
+                    // Chapters are indented one level further than their book
+                    let width = label_width.map(|width| width.saturating_sub(TREE_INDENT));
+                    TreeItem::new_leaf(
+                        chapter_id(chapter_idx),
+                        render_with_lead(&chapter.label, lead, prefix_width, width),
+                    )
+                })
+                .collect()
+        } else {
+            vec![TreeItem::new_leaf(chapter_id(0), "…")]
+        };
+        TreeItem::new(
+            TreeId::Book(book.clone()),
+            render_with_lead(
+                &self.label,
+                status_marker(&self.label),
+                prefix_width,
+                label_width,
+            ),
+            chapter_items,
+        )
+        .unwrap()
     }
-
-    (book_chapters, chapter_colors)
 }
 
-/// Build book label text with style
-fn build_book_label(
-    book: &str,
-    chapters: &[u32],
-    book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
-    testament_min_read_count: u32,
-    chapter_colors: &[ChapterColor],
-    max_prefix_width: usize,
+/// Render `label` within `max_width` columns, preceded by `lead`
+fn render_with_lead(
+    label: &Label,
+    lead: String,
+    prefix_width: usize,
+    max_width: Option<usize>,
 ) -> Text<'static> {
-    // Calculate read count statistics for this book
-    let (book_min_read_count, verses_read_more, total_verses_for_stats) =
-        calculate_book_read_stats(chapters, book_records);
-
-    // Find the most recent last_read date across all chapters in this book
-    let book_last_read = if let Some(records) = book_records {
-        records.iter().map(|(_, record)| record.last_read).max()
-    } else {
-        None
-    };
-
-    let last_read_text = if let Some(date) = book_last_read {
-        let date_str = format_last_read_date(date);
-        format!(" | Last read: {:>15}", date_str)
-    } else {
-        String::new()
-    };
-
-    let read_count_text = format_read_count_text(
-        book_min_read_count,
-        verses_read_more,
-        total_verses_for_stats,
-    );
+    let max_width = max_width.map(|width| width.saturating_sub(lead.width()));
+    let mut text = label.render(prefix_width, max_width);
+    if !lead.is_empty() {
+        if let Some(line) = text.lines.first_mut() {
+            line.spans.insert(0, Span::raw(lead));
+        }
+    }
+    text
+}
 
-    let book_prefix = if !read_count_text.is_empty() {
-        format!("{} ({})", book, read_count_text)
-    } else {
-        book.to_string()
-    };
-    let padding = " ".repeat(max_prefix_width.saturating_sub(book_prefix.len()));
-    let book_text = format!("{}{}{}", book_prefix, padding, last_read_text);
-
-    // Determine book color based on children's colors first, then fall back to read count comparison
-    let book_style = determine_book_color_from_children(
-        book_min_read_count,
-        testament_min_read_count,
-        chapters,
-        book_records,
-        chapter_colors,
-    );
-
-    Text::from(book_text).style(book_style)
+/// Minimum read count across the verses, and how many verses were read more than that
+fn read_count_stats(verse_counts: &[u32]) -> (u32, u32) {
+    let min_read_count = verse_counts.iter().min().copied().unwrap_or(0);
+    let verses_read_more = verse_counts
+        .iter()
+        .filter(|&&count| count > min_read_count)
+        .count() as u32;
+    (min_read_count, verses_read_more)
 }
 
 /// Format read count display text: "2x" or "2x + 2%" or "2x + 20/30"
@@ -437,216 +578,6 @@ fn format_read_count_text(min_read_count: u32, verses_read_more: u32, total_vers
     }
 }
 
-/// Get the maximum read count for each verse in a chapter
-fn get_verse_read_counts(
-    chapter: u32,
-    max_verse: u32,
-    book_records: &RangeMap<InsideBookBibleReference, ReadingRecord>,
-) -> std::collections::HashMap<u32, u32> {
-    let mut verse_read_counts = std::collections::HashMap::new();
-
-    let chapter_start = InsideBookBibleReference { chapter, verse: 1 };
-    let chapter_end_exclusive = InsideBookBibleReference {
-        chapter,
-        verse: max_verse + 1,
-    };
-
-    for (range, record) in book_records.range(chapter_start..chapter_end_exclusive) {
-        if range.start.chapter == chapter && range.end.chapter == chapter {
-            for verse in range.start.verse..range.end.verse {
-                let current_max = verse_read_counts.get(&verse).copied().unwrap_or(0);
-                if record.read_count > current_max {
-                    verse_read_counts.insert(verse, record.read_count);
-                }
-            }
-        }
-    }
-
-    verse_read_counts
-}
-
-/// Calculate min read count and count of verses read at least one more time for a chapter
-/// Returns (min_read_count, verses_read_more, total_verses)
-fn calculate_chapter_read_stats(
-    chapter: u32,
-    max_verse: u32,
-    book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
-) -> (u32, u32, u32) {
-    if book_records.is_none() {
-        return (0, 0, 0);
-    }
-
-    let records = book_records.unwrap();
-    let verse_read_counts = get_verse_read_counts(chapter, max_verse, records);
-
-    // Find minimum read count across all verses in this chapter
-    // Include verses that haven't been read (read_count = 0)
-    let mut min_read_count = u32::MAX;
-    for verse in 1..=max_verse {
-        let verse_read_count = verse_read_counts.get(&verse).copied().unwrap_or(0);
-        if verse_read_count < min_read_count {
-            min_read_count = verse_read_count;
-        }
-    }
-
-    // If no verses have been read, min_read_count will be MAX, so set it to 0
-    if min_read_count == u32::MAX {
-        return (0, 0, 0);
-    }
-
-    // Count verses that have been read at least one more time than the minimum
-    let mut verses_read_more = 0u32;
-    for verse in 1..=max_verse {
-        let verse_read_count = verse_read_counts.get(&verse).copied().unwrap_or(0);
-        if verse_read_count > min_read_count {
-            verses_read_more += 1;
-        }
-    }
-
-    (min_read_count, verses_read_more, max_verse)
-}
-
-/// Calculate min read count and count of verses read at least one more time for a book
-/// Returns (min_read_count, verses_read_more, total_verses)
-fn calculate_book_read_stats(
-    chapters: &[u32],
-    book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
-) -> (u32, u32, u32) {
-    if book_records.is_none() {
-        return (0, 0, 0);
-    }
-
-    let records = book_records.unwrap();
-    let mut all_verse_read_counts = Vec::new();
-
-    // Collect read counts for all verses in the book
-    for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
-        let chapter = (chapter_idx + 1) as u32;
-        let verse_read_counts = get_verse_read_counts(chapter, max_verse, records);
-
-        for verse in 1..=max_verse {
-            let read_count = verse_read_counts.get(&verse).copied().unwrap_or(0);
-            all_verse_read_counts.push(read_count);
-        }
-    }
-
-    if all_verse_read_counts.is_empty() {
-        return (0, 0, 0);
-    }
-
-    // Find minimum read count across all verses in the book
-    // This will be 0 if any verse hasn't been read
-    let min_read_count = all_verse_read_counts.iter().min().copied().unwrap_or(0);
-
-    // Count verses that have been read at least one more time than the minimum
-    let verses_read_more = all_verse_read_counts
-        .iter()
-        .filter(|&&count| count > min_read_count)
-        .count() as u32;
-
-    let total_verses = all_verse_read_counts.len() as u32;
-
-    (min_read_count, verses_read_more, total_verses)
-}
-
-/// Calculate min read count for a testament (across all books in the testament)
-fn calculate_testament_min_read_count(
-    testament_books: &indexmap::IndexMap<String, Vec<u32>>,
-    progress: &ReadingProgress,
-) -> u32 {
-    let mut all_verse_read_counts = Vec::new();
-
-    for (book, chapters) in testament_books.iter() {
-        let book_records = progress.books.get(book);
-        if let Some(records) = book_records {
-            for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
-                let chapter = (chapter_idx + 1) as u32;
-                let verse_read_counts = get_verse_read_counts(chapter, max_verse, records);
-
-                for verse in 1..=max_verse {
-                    let read_count = verse_read_counts.get(&verse).copied().unwrap_or(0);
-                    all_verse_read_counts.push(read_count);
-                }
-            }
-        }
-    }
-
-    if all_verse_read_counts.is_empty() {
-        return 0;
-    }
-
-    all_verse_read_counts.iter().min().copied().unwrap_or(0)
-}
-
-/// Determine book color based on children's colors first, then fall back to read count comparison
-/// - Green if all children are green
-/// - Yellow if any child is yellow (partially read) or some (but not all) children are green
-/// - Otherwise, use read count comparison logic
-fn determine_book_color_from_children(
-    book_min_read_count: u32,
-    testament_min_read_count: u32,
-    chapters: &[u32],
-    book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
-    chapter_colors: &[ChapterColor],
-) -> Style {
-    let total_chapters = chapter_colors.len();
-
-    // If any chapters exist and we have color information
-    if total_chapters > 0 {
-        // Count chapters by color
-        let green_count = chapter_colors
-            .iter()
-            .filter(|&&c| c == ChapterColor::Green)
-            .count();
-        let yellow_count = chapter_colors
-            .iter()
-            .filter(|&&c| c == ChapterColor::Yellow)
-            .count();
-
-        if green_count == total_chapters {
-            // All children are green - green
-            return Style::default().fg(Color::Green);
-        } else if yellow_count > 0 || green_count > 0 {
-            // Any child is yellow (partially read) or some (but not all) children are green - yellow
-            return Style::default().fg(Color::Yellow);
-        }
-    }
-
-    // Fall back to read count comparison if no children are green
-    // If book's min_read_count equals testament's min_read_count, use white
-    if book_min_read_count == testament_min_read_count {
-        return Style::default().fg(Color::White);
-    }
-
-    // Get all verse read counts for this book
-    let mut all_verse_read_counts = Vec::new();
-    if let Some(records) = book_records {
-        for (chapter_idx, &max_verse) in chapters.iter().enumerate() {
-            let chapter = (chapter_idx + 1) as u32;
-            let verse_read_counts = get_verse_read_counts(chapter, max_verse, records);
-
-            for verse in 1..=max_verse {
-                let read_count = verse_read_counts.get(&verse).copied().unwrap_or(0);
-                all_verse_read_counts.push(read_count);
-            }
-        }
-    }
-
-    // Check if at least one verse has read_count >= testament_min_read_count + 1
-    let has_verse_one_more = all_verse_read_counts
-        .iter()
-        .any(|&count| count > testament_min_read_count);
-
-    if has_verse_one_more {
-        // At least one verse is one or more times greater - green
-        Style::default().fg(Color::Green)
-    } else {
-        // Some verses are greater than testament_min but none are one more - yellow
-        // (This happens when book_min > testament_min but no verse reaches testament_min + 1)
-        Style::default().fg(Color::Yellow)
-    }
-}
-
 /// Format a date in natural language (e.g., "today", "yesterday", "last week")
 fn format_last_read_date(date: NaiveDate) -> String {
     let today = Utc::now().date_naive();
@@ -677,106 +608,24 @@ fn format_last_read_date(date: NaiveDate) -> String {
     }
 }
 
-fn compute_chapter_items(
+/// How far through `book` the reader is, e.g. "75% · last read 3 days ago", or "not started"
+pub fn book_progress_summary(
+    bible: &'static crate::bible_structure::BibleStructure,
+    progress: &ReadingProgress,
     book: &str,
-    chapter: u32,
-    max_verse: u32,
-    book_records: Option<&RangeMap<InsideBookBibleReference, ReadingRecord>>,
-) -> Vec<DashboardItem> {
-    let mut items = Vec::new();
-
-    if let Some(records) = book_records {
-        // Get all read ranges for this chapter
-        let chapter_start = InsideBookBibleReference { chapter, verse: 1 };
-        let chapter_end_exclusive = InsideBookBibleReference {
-            chapter,
-            verse: max_verse + 1,
-        };
-        let read_ranges: Vec<_> = records
-            .range(chapter_start..chapter_end_exclusive)
-            .map(|(range, record)| {
-                (
-                    *range.start,
-                    *range.end,
-                    record.read_count,
-                    record.last_read,
-                )
-            })
-            .collect();
-
-        // Find missing verses - collect read verses first
-        let mut read_verses = std::collections::BTreeSet::new();
-        for (start_ref, end_ref, _, _) in &read_ranges {
-            if start_ref.chapter == chapter && end_ref.chapter == chapter {
-                for verse in start_ref.verse..end_ref.verse {
-                    read_verses.insert(verse);
-                }
-            }
-        }
-
-        // Create items for read verses
-        for (start_ref, end_ref, read_count, last_read) in &read_ranges {
-            if start_ref.chapter == chapter && end_ref.chapter == chapter {
-                let verse_end = end_ref.verse.saturating_sub(1);
-                if verse_end >= start_ref.verse {
-                    items.push(DashboardItem {
-                        book: book.to_string(),
-                        chapter,
-                        verse_start: start_ref.verse,
-                        verse_end,
-                        read_count: *read_count,
-                        last_read: Some(*last_read),
-                        is_read: true,
-                    });
-                }
-            }
-        }
-
-        // Add missing verse ranges
-        let mut current_start = None;
-        for verse in 1..=max_verse {
-            if !read_verses.contains(&verse) {
-                if current_start.is_none() {
-                    current_start = Some(verse);
-                }
-            } else if let Some(start) = current_start {
-                items.push(DashboardItem {
-                    book: book.to_string(),
-                    chapter,
-                    verse_start: start,
-                    verse_end: verse - 1,
-                    read_count: 0,
-                    last_read: None,
-                    is_read: false,
-                });
-                current_start = None;
-            }
-        }
-        if let Some(start) = current_start {
-            items.push(DashboardItem {
-                book: book.to_string(),
-                chapter,
-                verse_start: start,
-                verse_end: max_verse,
-                read_count: 0,
-                last_read: None,
-                is_read: false,
-            });
-        }
-    } else {
-        // No records for this book, all verses are unread
-        items.push(DashboardItem {
-            book: book.to_string(),
-            chapter,
-            verse_start: 1,
-            verse_end: max_verse,
-            read_count: 0,
-            last_read: None,
-            is_read: false,
-        });
+) -> String {
+    let last_read = progress
+        .books
+        .get(book)
+        .and_then(|records| records.iter().map(|(_, record)| record.last_read).max());
+    match last_read {
+        None => "not started".to_string(),
+        Some(date) => format!(
+            "{:.0}% · last read {}",
+            book_percent_read(bible, progress, book),
+            format_last_read_date(date)
+        ),
     }
-
-    items
 }
 
 /// Represents a recent reading entry for display
@@ -797,7 +646,7 @@ pub fn collect_recent_reads(progress: &ReadingProgress) -> Vec<(NaiveDate, Vec<R
         for (range, record) in records.iter() {
             // Use the chapter from the start of the range
             let chapter = range.start.chapter;
-            all_entries.push((record.last_read, book.clone(), chapter));
+            all_entries.push((record.last_read, book.to_string(), chapter));
         }
     }
 
@@ -869,3 +718,185 @@ pub fn collect_recent_reads(progress: &ReadingProgress) -> Vec<(NaiveDate, Vec<R
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::coverage::percent_read;
+
+    #[test]
+    fn updating_changed_books_matches_a_full_rebuild() {
+        let bible = get_bible_structure();
+        let gradient = ColorGradient::default();
+        let mut progress = ReadingProgress::new();
+        let mut tree = DashboardTree::new(bible, &progress, &gradient);
+
+        progress.record_reading("Jude", 1, 1, 25);
+        progress.record_reading("Genesis", 1, 1, 10);
+        let changed = progress.take_changed_books();
+        tree.update_books(bible, &progress, &gradient, &changed);
+
+        let fresh = DashboardTree::new(bible, &progress, &gradient);
+        assert_eq!(tree.max_prefix_width, fresh.max_prefix_width);
+        assert_eq!(
+            tree.chapter_unread_flags(bible, BookFilter::All),
+            fresh.chapter_unread_flags(bible, BookFilter::All)
+        );
+        assert_eq!(
+            tree.percent_read(bible, BookFilter::All),
+            fresh.percent_read(bible, BookFilter::All)
+        );
+        assert_eq!(
+            tree.percent_read(bible, BookFilter::All),
+            percent_read(bible, &progress, BookFilter::All)
+        );
+    }
+
+    #[test]
+    fn book_summary_shows_percent_and_last_read() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Ruth", 1, 1, 22);
+        assert_eq!(
+            book_progress_summary(bible, &progress, "Ruth"),
+            "26% · last read today"
+        );
+        assert_eq!(
+            book_progress_summary(bible, &progress, "Genesis"),
+            "not started"
+        );
+    }
+
+    #[test]
+    fn filtered_items_skip_empty_testaments() {
+        let bible = get_bible_structure();
+        let tree = DashboardTree::new(bible, &ReadingProgress::new(), &ColorGradient::default());
+        let items = tree.items(
+            bible,
+            BookFilter::NewTestament,
+            &HashSet::new(),
+            &BTreeSet::new(),
+            None,
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].identifier(), &TreeId::NewTestament);
+        assert_eq!(items[0].children().len(), bible.nt.len());
+    }
+
+    #[test]
+    fn labels_are_padded_and_truncated_by_display_width() {
+        let label = Label {
+            prefix: "創世記 (0%)".to_string(),
+            read_count_text: "0%".to_string(),
+            last_read: None,
+            color: Color::Gray,
+            status: ReadStatus::Unread,
+        };
+        // The prefix is 11 columns wide but 15 bytes long
+        assert_eq!(label.render(14, None).width(), 14);
+        assert_eq!(label.render(14, Some(8)).to_string(), "創世記 …");
+    }
+
+    #[test]
+    fn only_expanded_books_get_chapter_nodes() {
+        let bible = get_bible_structure();
+        let tree = DashboardTree::new(bible, &ReadingProgress::new(), &ColorGradient::default());
+        let expanded = HashSet::from(["Romans".to_string()]);
+        let items = tree.items(
+            bible,
+            BookFilter::NewTestament,
+            &expanded,
+            &BTreeSet::new(),
+            None,
+        );
+        let book = |name: &str| {
+            items[0]
+                .children()
+                .iter()
+                .find(|item| item.identifier() == &TreeId::Book(name.into()))
+                .unwrap()
+        };
+        assert_eq!(book("Romans").children().len(), 16);
+        assert_eq!(book("Hebrews").children().len(), 1);
+    }
+
+    #[test]
+    fn marked_chapters_get_a_checkmark() {
+        let bible = get_bible_structure();
+        let tree = DashboardTree::new(bible, &ReadingProgress::new(), &ColorGradient::default());
+        let expanded = HashSet::from(["Jude".to_string(), "Titus".to_string()]);
+        let marked = BTreeSet::from([("Titus".to_string(), 2)]);
+        let items = tree.items(bible, BookFilter::NewTestament, &expanded, &marked, None);
+        let chapter_label = |book: &str, chapter: usize| {
+            let book = items[0]
+                .children()
+                .iter()
+                .find(|item| item.identifier() == &TreeId::Book(book.into()))
+                .unwrap();
+            format!("{:?}", book.children()[chapter - 1])
+        };
+        assert!(chapter_label("Titus", 2).contains(MARK));
+        assert!(!chapter_label("Titus", 1).contains(MARK));
+        assert!(!chapter_label("Jude", 1).contains(MARK));
+    }
+
+    #[test]
+    fn text_markers_show_read_status() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Titus", 1, 1, 16);
+        progress.record_reading("Titus", 2, 1, 3);
+        let mut tree = DashboardTree::new(bible, &progress, &ColorGradient::default());
+        tree.text_markers = true;
+        let expanded = HashSet::from(["Titus".to_string()]);
+        let items = tree.items(
+            bible,
+            BookFilter::NewTestament,
+            &expanded,
+            &BTreeSet::new(),
+            None,
+        );
+        let titus = items[0]
+            .children()
+            .iter()
+            .find(|item| item.identifier() == &TreeId::Book("Titus".into()))
+            .unwrap();
+        let labels: Vec<String> = titus
+            .children()
+            .iter()
+            .map(|chapter| format!("{:?}", chapter))
+            .collect();
+        assert!(labels[0].contains("\"✓ \""));
+        assert!(labels[1].contains("\"◐ \""));
+        assert!(labels[2].contains("\"✗ \""));
+    }
+
+    #[test]
+    fn stats_lines_use_the_full_reference() {
+        let bible = get_bible_structure();
+        let mut progress = ReadingProgress::new();
+        progress.record_reading("Titus", 1, 1, 16);
+        let tree = DashboardTree::new(bible, &progress, &ColorGradient::default());
+        let chapter = TreeId::Chapter {
+            book: "Titus".into(),
+            chapter: 1,
+        };
+        assert_eq!(
+            tree.stats_line(&chapter).as_deref(),
+            Some("Titus 1 (1x) | Last read: today")
+        );
+        assert_eq!(
+            tree.stats_line(&TreeId::Book("Jude".into())).as_deref(),
+            Some("Jude (0%)")
+        );
+        assert_eq!(tree.stats_line(&TreeId::NewTestament), None);
+        let passage = TreeId::Passage {
+            book: "Titus".into(),
+            chapter: 1,
+            verse_start: 5,
+            verse_end: 9,
+        };
+        assert_eq!(passage.reference(), "Titus 1:5-9");
+    }
+}
@@ -0,0 +1,122 @@
+use std::hash::Hash;
+
+use tui_tree_widget::{TreeItem, TreeState};
+
+/// Bulk expand/collapse operations on a `TreeState`
+pub trait TreeStateExt<Identifier> {
+    /// Open every node that has children
+    fn expand_all(&mut self, items: &[TreeItem<'_, Identifier>]);
+
+    /// Close every node, keeping the selection on its top-level ancestor
+    fn collapse_all(&mut self);
+
+    /// Open nodes so that exactly `depth` levels of the tree are visible
+    /// (1 = top level only, 2 = top level and its children, ...)
+    fn expand_to_depth(&mut self, items: &[TreeItem<'_, Identifier>], depth: usize);
+}
+
+impl<Identifier> TreeStateExt<Identifier> for TreeState<Identifier>
+where
+    Identifier: Clone + PartialEq + Eq + Hash,
+{
+    fn expand_all(&mut self, items: &[TreeItem<'_, Identifier>]) {
+        self.expand_to_depth(items, usize::MAX);
+    }
+
+    fn collapse_all(&mut self) {
+        self.expand_to_depth(&[], 1);
+    }
+
+    fn expand_to_depth(&mut self, items: &[TreeItem<'_, Identifier>], depth: usize) {
+        self.close_all();
+        let mut path = Vec::new();
+        open_to_depth(self, items, &mut path, depth.saturating_sub(1));
+
+        // Keep the selection visible by moving it up to its deepest visible ancestor
+        let selected = self.selected();
+        if selected.len() > depth.max(1) {
+            let ancestor = selected[..depth.max(1)].to_vec();
+            self.select(ancestor);
+        }
+    }
+}
+
+fn open_to_depth<Identifier>(
+    state: &mut TreeState<Identifier>,
+    items: &[TreeItem<'_, Identifier>],
+    path: &mut Vec<Identifier>,
+    remaining: usize,
+) where
+    Identifier: Clone + PartialEq + Eq + Hash,
+{
+    if remaining == 0 {
+        return;
+    }
+    for item in items {
+        if item.children().is_empty() {
+            continue;
+        }
+        path.push(item.identifier().clone());
+        state.open(path.clone());
+        open_to_depth(state, item.children(), path, remaining - 1);
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tree() -> Vec<TreeItem<'static, u32>> {
+        vec![
+            TreeItem::new(
+                1,
+                "1",
+                vec![
+                    TreeItem::new(11, "11", vec![TreeItem::new_leaf(111, "111")]).unwrap(),
+                    TreeItem::new_leaf(12, "12"),
+                ],
+            )
+            .unwrap(),
+            TreeItem::new(2, "2", vec![TreeItem::new_leaf(21, "21")]).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn expand_all_opens_every_parent() {
+        let items = sample_tree();
+        let mut state = TreeState::default();
+        state.expand_all(&items);
+        assert_eq!(state.opened().len(), 3);
+        assert!(state.opened().contains(&vec![1, 11]));
+        assert!(!state.opened().contains(&vec![1, 12]));
+    }
+
+    #[test]
+    fn expand_to_depth_limits_levels() {
+        let items = sample_tree();
+        let mut state = TreeState::default();
+        state.expand_to_depth(&items, 2);
+        assert_eq!(state.opened().len(), 2);
+        assert!(state.opened().contains(&vec![1]));
+        assert!(state.opened().contains(&vec![2]));
+
+        state.expand_to_depth(&items, 1);
+        assert!(state.opened().is_empty());
+    }
+
+    #[test]
+    fn collapsing_moves_selection_to_visible_ancestor() {
+        let items = sample_tree();
+        let mut state = TreeState::default();
+        state.expand_all(&items);
+        state.select(vec![1, 11, 111]);
+
+        state.expand_to_depth(&items, 2);
+        assert_eq!(state.selected(), &[1, 11]);
+
+        state.collapse_all();
+        assert_eq!(state.selected(), &[1]);
+        assert!(state.opened().is_empty());
+    }
+}
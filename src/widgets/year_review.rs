@@ -0,0 +1,29 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::widgets::popup::centered_rect;
+use crate::year_review::YearReview;
+
+/// Popup with the highlights of a year of reading; any key closes it
+pub fn render_year_review(frame: &mut Frame, review: &YearReview) {
+    let popup_area = centered_rect(60, 40, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let label = Style::default().fg(Color::Yellow);
+    let lines: Vec<Line> = review
+        .highlights()
+        .into_iter()
+        .map(|(name, value)| {
+            Line::from(vec![
+                Span::styled(format!("{}: ", name), label),
+                Span::raw(value),
+            ])
+        })
+        .collect();
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!("{} (press any key to close)", review.title())),
+    );
+    frame.render_widget(paragraph, popup_area);
+}
@@ -0,0 +1,188 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::bible_structure::BibleStructure;
+use crate::progress::ReadingEvent;
+use crate::stats::longest_streak;
+
+/// Highlights of one calendar year of reading
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearReview {
+    pub year: i32,
+    /// Chapters read, counting a chapter once per day it was read
+    pub chapters: usize,
+    /// Chapters read the year before, counted the same way
+    pub chapters_previous_year: usize,
+    pub days_read: usize,
+    /// The book with the most chapters read, and how many
+    pub most_read_book: Option<(String, usize)>,
+    pub longest_streak: u32,
+    /// Percentage of the Bible's verses read at the end of the year and of the year before
+    pub percent_read: f64,
+    pub percent_read_previous_year: f64,
+}
+
+impl YearReview {
+    pub fn new(bible: &BibleStructure, history: &[ReadingEvent], year: i32) -> Self {
+        let in_year = |year: i32| {
+            history
+                .iter()
+                .filter(move |event| event.date.year() == year)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let events = in_year(year);
+
+        let mut by_book: HashMap<&str, usize> = HashMap::new();
+        for (_, book, _) in chapter_days(&events) {
+            *by_book.entry(book).or_default() += 1;
+        }
+        let most_read_book = by_book
+            .into_iter()
+            // Break ties alphabetically, so the result doesn't depend on hashing
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+            .map(|(book, chapters)| (book.to_string(), chapters));
+
+        Self {
+            year,
+            chapters: chapter_days(&events).len(),
+            chapters_previous_year: chapter_days(&in_year(year - 1)).len(),
+            days_read: events
+                .iter()
+                .map(|event| event.date)
+                .collect::<HashSet<_>>()
+                .len(),
+            most_read_book,
+            longest_streak: longest_streak(&events),
+            percent_read: percent_read_by(bible, history, year),
+            percent_read_previous_year: percent_read_by(bible, history, year - 1),
+        }
+    }
+
+    /// Each highlight as (label, value), as shown in the app and in the Markdown report
+    pub fn highlights(&self) -> Vec<(&'static str, String)> {
+        let change = self.chapters as i64 - self.chapters_previous_year as i64;
+        let mut highlights = vec![
+            (
+                "Chapters read",
+                format!(
+                    "{} ({}{} on {})",
+                    self.chapters,
+                    if change >= 0 { "+" } else { "" },
+                    change,
+                    self.year - 1
+                ),
+            ),
+            ("Days with a reading", self.days_read.to_string()),
+            ("Longest streak", format!("{} days", self.longest_streak)),
+        ];
+        if let Some((book, chapters)) = &self.most_read_book {
+            highlights.push((
+                "Most-read book",
+                format!("{} ({} chapters)", book, chapters),
+            ));
+        }
+        highlights.push((
+            "Bible read",
+            format!(
+                "{:.1}% (+{:.1} points since the end of {})",
+                self.percent_read,
+                self.percent_read - self.percent_read_previous_year,
+                self.year - 1
+            ),
+        ));
+        highlights
+    }
+
+    pub fn title(&self) -> String {
+        format!("{} in review", self.year)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n\n", self.title());
+        for (label, value) in self.highlights() {
+            markdown.push_str(&format!("- **{}**: {}\n", label, value));
+        }
+        markdown
+    }
+}
+
+/// Distinct (date, book, chapter) readings
+fn chapter_days(events: &[ReadingEvent]) -> HashSet<(NaiveDate, &str, u32)> {
+    events
+        .iter()
+        .map(|event| (event.date, event.book.as_str(), event.chapter))
+        .collect()
+}
+
+/// Percentage of the Bible's verses read by the end of `year`, according to the history
+fn percent_read_by(bible: &BibleStructure, history: &[ReadingEvent], year: i32) -> f64 {
+    let verses: HashSet<(&str, u32, u32)> = history
+        .iter()
+        .filter(|event| event.date.year() <= year)
+        .flat_map(|event| {
+            (event.verse_start..=event.verse_end)
+                .map(move |verse| (event.book.as_str(), event.chapter, verse))
+        })
+        .collect();
+    let total: u32 = bible.ot.values().chain(bible.nt.values()).flatten().sum();
+    if total == 0 {
+        0.0
+    } else {
+        verses.len() as f64 * 100.0 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bible_structure::get_bible_structure;
+    use crate::progress::ReadingSource;
+
+    fn event(year: i32, month: u32, day: u32, book: &str, chapter: u32) -> ReadingEvent {
+        ReadingEvent {
+            date: NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+            book: book.into(),
+            chapter,
+            verse_start: 1,
+            verse_end: 10,
+            read_count: 1,
+            note: None,
+            tags: Vec::new(),
+            source: ReadingSource::Read,
+            translation: None,
+            campaign: None,
+            overwrite: false,
+        }
+    }
+
+    #[test]
+    fn summarizes_a_year() {
+        let bible = get_bible_structure();
+        let history = vec![
+            event(2024, 12, 31, "Ruth", 1),
+            event(2025, 1, 1, "Ruth", 1),
+            event(2025, 1, 2, "Ruth", 2),
+            event(2025, 1, 2, "John", 1),
+            event(2025, 1, 3, "Ruth", 3),
+            event(2025, 3, 1, "Ruth", 1),
+        ];
+
+        let review = YearReview::new(bible, &history, 2025);
+        assert_eq!(review.chapters, 5);
+        assert_eq!(review.chapters_previous_year, 1);
+        assert_eq!(review.days_read, 4);
+        assert_eq!(review.longest_streak, 3);
+        assert_eq!(review.most_read_book, Some(("Ruth".to_string(), 4)));
+        assert_eq!(
+            review.to_markdown(),
+            "# 2025 in review\n\n\
+             - **Chapters read**: 5 (+4 on 2024)\n\
+             - **Days with a reading**: 4\n\
+             - **Longest streak**: 3 days\n\
+             - **Most-read book**: Ruth (4 chapters)\n\
+             - **Bible read**: 0.1% (+0.1 points since the end of 2024)\n"
+        );
+    }
+}